@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::battle::status_condition::StatusCondition;
+use super::inventory::{Inventory, ItemId};
+use super::pokemon::Pokemon;
+
+/// What happens when an item it's used on a Pokémon
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemEffect {
+    // Restores the given amount of HP
+    HealHp(u32),
+    // Cures the given status condition
+    CureStatus(StatusCondition),
+    // Brings back a fainted Pokémon with half of his HP
+    Revive,
+    // Restores the given PP to every move of the Pokémon
+    RestorePp(u32)
+}
+
+impl ItemEffect {
+    /// Retrieves the effect of the item with the given id, if it's a known one
+    pub fn from_item_id(item_id: &str) -> Option<ItemEffect> {
+        match item_id {
+            "potion" => Some(ItemEffect::HealHp(20)),
+            "super_potion" => Some(ItemEffect::HealHp(50)),
+            "hyper_potion" => Some(ItemEffect::HealHp(200)),
+            "antidote" => Some(ItemEffect::CureStatus(StatusCondition::Poisoned)),
+            "burn_heal" => Some(ItemEffect::CureStatus(StatusCondition::Burned)),
+            "paralyze_heal" => Some(ItemEffect::CureStatus(StatusCondition::Paralyzed)),
+            "revive" => Some(ItemEffect::Revive),
+            "elixir" => Some(ItemEffect::RestorePp(10)),
+            "max_elixir" => Some(ItemEffect::RestorePp(u32::MAX)),
+            "oran_berry" => Some(ItemEffect::HealHp(10)),
+            "pecha_berry" => Some(ItemEffect::CureStatus(StatusCondition::Poisoned)),
+            "cheri_berry" => Some(ItemEffect::CureStatus(StatusCondition::Paralyzed)),
+            _ => None
+        }
+    }
+}
+
+/// The pockets of the bag. Every item belongs to just one of them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemCategory {
+    Items,
+    Medicine,
+    Pokeballs,
+    KeyItems
+}
+
+impl Default for ItemCategory {
+    fn default() -> Self { ItemCategory::Items }
+}
+
+impl ItemCategory {
+    /// The order of the pockets on the bag screen
+    pub const ALL: [ItemCategory; 4] = [
+        ItemCategory::Items, ItemCategory::Medicine, ItemCategory::Pokeballs, ItemCategory::KeyItems
+    ];
+
+    pub fn from_item_id(item_id: &str) -> ItemCategory {
+        match item_id {
+            "poke_ball" | "great_ball" | "ultra_ball" | "master_ball" => ItemCategory::Pokeballs,
+            "bicycle" | "town_map" | RUNNING_SHOES | SHINY_CHARM | EXP_SHARE | OLD_ROD | GOOD_ROD | SUPER_ROD =>
+                ItemCategory::KeyItems,
+            _ if ItemEffect::from_item_id(item_id).is_some() => ItemCategory::Medicine,
+            _ => ItemCategory::Items
+        }
+    }
+
+    pub fn from_string(category: &str) -> Option<ItemCategory> {
+        match category {
+            "Items" => Some(ItemCategory::Items),
+            "Medicine" => Some(ItemCategory::Medicine),
+            "Pokeballs" => Some(ItemCategory::Pokeballs),
+            "KeyItems" => Some(ItemCategory::KeyItems),
+            _ => None
+        }
+    }
+
+    pub fn to_category_str(&self) -> &'static str {
+        match self {
+            ItemCategory::Items => "Items",
+            ItemCategory::Medicine => "Medicine",
+            ItemCategory::Pokeballs => "Pokeballs",
+            ItemCategory::KeyItems => "KeyItems"
+        }
+    }
+
+    /// The name of the pocket showed to the player
+    pub fn to_label_str(&self) -> &'static str {
+        match self {
+            ItemCategory::Items => "Objetos",
+            ItemCategory::Medicine => "Botiquín",
+            ItemCategory::Pokeballs => "Poké Balls",
+            ItemCategory::KeyItems => "Objetos clave"
+        }
+    }
+
+    /// Only the medicine and the Poké Balls can be used while battling
+    pub fn is_usable_in_battle(&self) -> bool {
+        matches!(self, ItemCategory::Medicine | ItemCategory::Pokeballs)
+    }
+
+    /// Key items can't be tossed nor given to a Pokémon
+    pub fn can_be_tossed(&self) -> bool {
+        *self != ItemCategory::KeyItems
+    }
+}
+
+// The key item that lets the player run
+pub const RUNNING_SHOES: &str = "running_shoes";
+// The key item that makes the shiny Pokémon easier to find
+pub const SHINY_CHARM: &str = "shiny_charm";
+// The key item that gives experience to the Pokémon of the party that didn't fight
+pub const EXP_SHARE: &str = "exp_share";
+// The fishing rods. The better ones get more bites, and reach other Pokémon
+pub const OLD_ROD: &str = "old_rod";
+pub const GOOD_ROD: &str = "good_rod";
+pub const SUPER_ROD: &str = "super_rod";
+// The repels, that keep the wild Pokémon away for some steps
+pub const REPEL: &str = "repel";
+pub const SUPER_REPEL: &str = "super_repel";
+pub const MAX_REPEL: &str = "max_repel";
+
+/// The steps that the wild Pokémon stay away after using the given item, if it's a repel
+pub fn get_repel_steps(item_id: &str) -> Option<u32> {
+    match item_id {
+        REPEL => Some(100),
+        SUPER_REPEL => Some(200),
+        MAX_REPEL => Some(250),
+        _ => None
+    }
+}
+
+/// The repel that the player used last, and the steps that it keeps working
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveRepel {
+    pub item_id: String,
+    pub steps_left: u32,
+}
+
+impl ActiveRepel {
+    /// Walks one step with the repel on. Returns true if the repel just stopped working with this step
+    pub fn take_step(&mut self) -> bool {
+        self.steps_left = self.steps_left.saturating_sub(1);
+        self.steps_left == 0
+    }
+}
+
+/// The name of the item showed to the player
+pub fn get_item_name(item_id: &str) -> &str {
+    match item_id {
+        "potion" => "Poción",
+        "super_potion" => "Superpoción",
+        "hyper_potion" => "Hiperpoción",
+        "antidote" => "Antídoto",
+        "burn_heal" => "Antiquemar",
+        "paralyze_heal" => "Antiparalizador",
+        "revive" => "Revivir",
+        "elixir" => "Elixir",
+        "max_elixir" => "Elixir máximo",
+        "oran_berry" => "Baya Aranja",
+        "pecha_berry" => "Baya Meloc",
+        "cheri_berry" => "Baya Zreza",
+        "poke_ball" => "Poké Ball",
+        "great_ball" => "Super Ball",
+        "ultra_ball" => "Ultra Ball",
+        "master_ball" => "Master Ball",
+        "bicycle" => "Bici",
+        "town_map" => "Mapa",
+        RUNNING_SHOES => "Deportivas",
+        SHINY_CHARM => "Amuleto iris",
+        EXP_SHARE => "Repartir exp.",
+        OLD_ROD => "Caña vieja",
+        GOOD_ROD => "Caña buena",
+        SUPER_ROD => "Supercaña",
+        REPEL => "Repelente",
+        SUPER_REPEL => "Superrepelente",
+        MAX_REPEL => "Máximo repelente",
+        _ => item_id
+    }
+}
+
+/// The reasons why an item can't be used
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemError {
+    UnknownItem,
+    NotInBag,
+    NoPokemon,
+    // Key items can't be tossed nor given
+    KeyItem,
+    // The item wouldn't do anything, like a potion on a Pokémon with all his HP
+    NoEffect,
+    // The player already carries all the units of the item that fit on the bag
+    BagFull
+}
+
+impl ItemError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            ItemError::UnknownItem => "¡No se puede usar este objeto!",
+            ItemError::NotInBag => "No te quedan más.",
+            ItemError::NoPokemon => "No hay ningún Pokémon ahí.",
+            ItemError::KeyItem => "¡No puedes deshacerte de eso!",
+            ItemError::NoEffect => "No tendría ningún efecto.",
+            ItemError::BagFull => "No te caben más en la mochila."
+        }
+    }
+}
+
+/// The items that the player carries, by id and the quantity of each one
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bag {
+    items: Inventory,
+}
+
+impl Bag {
+    pub fn new() -> Self {
+        Self { items: Inventory::new() }
+    }
+
+    /// Puts some units of an item on the bag, never past the maximum stack of the item.
+    ///
+    /// Returns the units that really got in, the rest don't fit
+    pub fn add_item(&mut self, item_id: &str, quantity: u32) -> u32 {
+        self.items.add_item(item_id, quantity)
+    }
+
+    /// How many units more of the item fit on the bag
+    pub fn get_free_space(&self, item_id: &str) -> u32 {
+        self.items.free_space(item_id)
+    }
+
+    /// Takes out of the bag one unit of the item. The entry it's removed when there's no more units left
+    pub fn remove_item(&mut self, item_id: &str) -> bool {
+        self.items.remove_item(item_id, 1)
+    }
+
+    pub fn get_quantity(&self, item_id: &str) -> u32 {
+        self.items.count(item_id)
+    }
+
+    pub fn has_item(&self, item_id: &str) -> bool {
+        self.items.has(item_id)
+    }
+
+    /// Takes out of the bag the given units of an item. Unlike tossing, it fails without taking anything if the
+    /// player doesn't carry that many
+    pub fn remove_items(&mut self, item_id: &str, quantity: u32) -> Result<(), ItemError> {
+        if self.items.remove_item(item_id, quantity) { Ok(()) } else { Err(ItemError::NotInBag) }
+    }
+
+    pub fn get_items(&self) -> &HashMap<ItemId, u32> {
+        self.items.get_items()
+    }
+
+    /// The items of a pocket of the bag with their quantities, sorted by their id so the list never changes its order
+    pub fn get_items_by_category(&self, category: ItemCategory) -> Vec<(&str, u32)> {
+        let mut items: Vec<(&str, u32)> = self.items.get_items().iter()
+            .filter(|(item_id, _)| ItemCategory::from_item_id(item_id) == category)
+            .map(|(item_id, quantity)| (item_id.as_str(), *quantity))
+            .collect();
+        items.sort_by(|first, second| first.0.cmp(second.0));
+        items
+    }
+
+    /// Throws away the given units of an item. Tossing more units than the carried ones just empties the entry
+    pub fn toss_item(&mut self, item_id: &str, quantity: u32) -> Result<(), ItemError> {
+        if !ItemCategory::from_item_id(item_id).can_be_tossed() {
+            return Err(ItemError::KeyItem);
+        }
+        let carried = self.get_quantity(item_id);
+        if carried == 0 {
+            return Err(ItemError::NotInBag);
+        }
+
+        self.items.remove_item(item_id, quantity.min(carried));
+        Ok(())
+    }
+
+    /// Uses an item on a Pokémon. The item only gets spent if it really has any effect
+    pub fn use_item(&mut self, item_id: &str, pokemon: &mut Pokemon) -> Result<(), ItemError> {
+        let effect = ItemEffect::from_item_id(item_id).ok_or(ItemError::UnknownItem)?;
+        if self.get_quantity(item_id) == 0 {
+            return Err(ItemError::NotInBag);
+        }
+
+        match effect {
+            ItemEffect::HealHp(hp) => {
+                if pokemon.is_fainted() || pokemon.get_current_hp() >= pokemon.get_max_hp() {
+                    return Err(ItemError::NoEffect);
+                }
+                pokemon.set_current_hp(pokemon.get_current_hp() + hp);
+            },
+            ItemEffect::CureStatus(status) => {
+                if pokemon.get_status() != status {
+                    return Err(ItemError::NoEffect);
+                }
+                pokemon.cure_status();
+            },
+            ItemEffect::Revive => {
+                if !pokemon.is_fainted() {
+                    return Err(ItemError::NoEffect);
+                }
+                pokemon.set_current_hp((pokemon.get_max_hp() / 2).max(1));
+            },
+            ItemEffect::RestorePp(pp) => {
+                if !pokemon.restore_pp(pp) {
+                    return Err(ItemError::NoEffect);
+                }
+            }
+        }
+
+        self.remove_item(item_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::pokemon::PokemonMove;
+
+    fn pokemon() -> Pokemon {
+        let moves = vec![PokemonMove::new("Placaje", "Normal", 40, 35), PokemonMove::new("Gruñido", "Normal", 0, 40)];
+        Pokemon::for_tests(19, "Rattata", moves)
+    }
+
+    #[test]
+    fn the_elixir_restores_the_pp_of_every_move() {
+        let mut bag = Bag::new();
+        bag.add_item("elixir", 1);
+        let mut pokemon = pokemon();
+        for _ in 0..30 {
+            pokemon.use_move(0);
+        }
+        pokemon.use_move(1);
+
+        assert_eq!(bag.use_item("elixir", &mut pokemon), Ok(()));
+        assert_eq!(pokemon.get_moves()[0].pp, 15);
+        // Never over the max PP
+        assert_eq!(pokemon.get_moves()[1].pp, 40);
+        assert!(!bag.has_item("elixir"));
+    }
+
+    #[test]
+    fn the_elixir_isnt_spent_with_every_pp_full() {
+        let mut bag = Bag::new();
+        bag.add_item("elixir", 1);
+        let mut pokemon = pokemon();
+
+        assert_eq!(bag.use_item("elixir", &mut pokemon), Err(ItemError::NoEffect));
+        assert_eq!(bag.get_quantity("elixir"), 1);
+    }
+
+    #[test]
+    fn the_max_elixir_fills_every_move() {
+        let mut bag = Bag::new();
+        bag.add_item("max_elixir", 1);
+        let mut pokemon = pokemon();
+        for _ in 0..35 {
+            pokemon.use_move(0);
+        }
+
+        assert_eq!(bag.use_item("max_elixir", &mut pokemon), Ok(()));
+        assert_eq!(pokemon.get_moves()[0].pp, 35);
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::terrain::Terrain;
+use crate::utils::consts::in_game_constant;
+
+/// The gameplay tuning values, loaded from the balance JSON config when the game starts, so they can be
+/// changed without compiling the game again.
+///
+/// Every value missing on the config takes the compiled-in default, the same one of the `in_game_constant` module
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BalanceConfig {
+    // Tiles per second that the characters move at every speed
+    walk_speed: f64,
+    run_speed: f64,
+    bike_speed: f64,
+    jump_speed: f64,
+    // Multiply the walking and running speeds over every terrain
+    tall_grass_speed_multiplier: f64,
+    sand_speed_multiplier: f64,
+    ice_speed_multiplier: f64,
+    // Chance of find a wild Pokémon per step, for the zones that doesn't provide his own rate
+    encounter_rate: f64,
+    // Multiplies the time between one character of the Dialogue Box and the next one
+    text_speed_multiplier: f64,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self {
+            walk_speed: in_game_constant::WALK_SPEED,
+            run_speed: in_game_constant::RUN_SPEED,
+            bike_speed: in_game_constant::BIKE_SPEED,
+            jump_speed: in_game_constant::JUMP_SPEED,
+            tall_grass_speed_multiplier: in_game_constant::TALL_GRASS_SPEED_MULTIPLIER,
+            sand_speed_multiplier: in_game_constant::SAND_SPEED_MULTIPLIER,
+            ice_speed_multiplier: in_game_constant::ICE_SPEED_MULTIPLIER,
+            encounter_rate: in_game_constant::DEFAULT_ENCOUNTER_RATE,
+            text_speed_multiplier: 1.0,
+        }
+    }
+}
+
+impl BalanceConfig {
+    /// Puts back the default of every value that's out of his valid range, so a typo on the config can't
+    /// break the game. Returns the names of the values that were wrong
+    pub fn validate(&mut self) -> Vec<&'static str> {
+        let defaults = BalanceConfig::default();
+        let mut invalid_values = Vec::new();
+
+        let ranges: [(&'static str, &mut f64, f64, f64, f64); 9] = [
+            ("walk_speed", &mut self.walk_speed, 0.5, 20.0, defaults.walk_speed),
+            ("run_speed", &mut self.run_speed, 0.5, 20.0, defaults.run_speed),
+            ("bike_speed", &mut self.bike_speed, 0.5, 20.0, defaults.bike_speed),
+            ("jump_speed", &mut self.jump_speed, 0.5, 20.0, defaults.jump_speed),
+            ("tall_grass_speed_multiplier", &mut self.tall_grass_speed_multiplier, 0.1, 3.0, defaults.tall_grass_speed_multiplier),
+            ("sand_speed_multiplier", &mut self.sand_speed_multiplier, 0.1, 3.0, defaults.sand_speed_multiplier),
+            ("ice_speed_multiplier", &mut self.ice_speed_multiplier, 0.1, 3.0, defaults.ice_speed_multiplier),
+            ("encounter_rate", &mut self.encounter_rate, 0.0, 1.0, defaults.encounter_rate),
+            ("text_speed_multiplier", &mut self.text_speed_multiplier, 0.1, 5.0, defaults.text_speed_multiplier),
+        ];
+        for (name, value, min, max, default) in ranges {
+            if !(min..=max).contains(&*value) {
+                *value = default;
+                invalid_values.push(name);
+            }
+        }
+        invalid_values
+    }
+
+    pub fn get_walk_speed(&self) -> f64 { self.walk_speed }
+    pub fn get_run_speed(&self) -> f64 { self.run_speed }
+    pub fn get_bike_speed(&self) -> f64 { self.bike_speed }
+    pub fn get_jump_speed(&self) -> f64 { self.jump_speed }
+    pub fn get_encounter_rate(&self) -> f64 { self.encounter_rate }
+    pub fn get_text_speed_multiplier(&self) -> f64 { self.text_speed_multiplier }
+
+    /// The plain ground keeps the speeds as they are
+    pub fn get_terrain_speed_multiplier(&self, terrain: Terrain) -> f64 {
+        match terrain {
+            Terrain::Ground => 1.0,
+            Terrain::TallGrass => self.tall_grass_speed_multiplier,
+            Terrain::Sand => self.sand_speed_multiplier,
+            Terrain::Ice => self.ice_speed_multiplier
+        }
+    }
+}
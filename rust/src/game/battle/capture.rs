@@ -0,0 +1,109 @@
+use crate::game::code_abstractions::random::GameRng;
+
+// Catch modifiers of the different Poké Balls
+pub const POKE_BALL: f64 = 1.0;
+pub const GREAT_BALL: f64 = 1.5;
+pub const ULTRA_BALL: f64 = 2.0;
+pub const MASTER_BALL: f64 = 255.0;
+
+/// How many times the Poké Ball shakes before the wild Pokémon breaks free, and if finally gets caught
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatchResult {
+    pub shakes: u8,
+    pub caught: bool,
+}
+
+/// Throws a Poké Ball to a wild Pokémon, using the standard capture formula.
+///
+/// The lower the HP of the wild Pokémon, the easier it's to catch it, and a status condition (the `status_bonus`)
+/// makes it easier too. The Poké Ball needs to pass four shake checks in a row to catch the Pokémon.
+pub fn attempt_catch<R: GameRng>(rng: &mut R, current_hp: u32, max_hp: u32, catch_rate: u32, ball_modifier: f64,
+    status_bonus: f64) -> CatchResult
+{
+    let catch_value = catch_value(current_hp, max_hp, catch_rate, ball_modifier, status_bonus);
+    if catch_value >= 255.0 {
+        return CatchResult { shakes: 4, caught: true };
+    }
+
+    let shake_probability = shake_probability(catch_value);
+
+    let mut shakes: u8 = 0;
+    while shakes < 4 && rng.next_in_range(0, 65536) < shake_probability {
+        shakes += 1;
+    }
+
+    CatchResult { shakes, caught: shakes == 4 }
+}
+
+/// The modified catch rate of the wild Pokémon. When reaches 255, the capture it's guaranteed
+pub fn catch_value(current_hp: u32, max_hp: u32, catch_rate: u32, ball_modifier: f64, status_bonus: f64) -> f64 {
+    let max_hp = max_hp.max(1) as f64;
+    let current_hp = (current_hp as f64).min(max_hp);
+
+    (3.0 * max_hp - 2.0 * current_hp) * catch_rate as f64 * ball_modifier / (3.0 * max_hp) * status_bonus
+}
+
+/// The chance (over 65536) that the Poké Ball passes every one of the shake checks
+pub fn shake_probability(catch_value: f64) -> u32 {
+    if catch_value <= 0.0 {
+        return 0;
+    }
+    (1048560.0 / (16711680.0 / catch_value).sqrt().sqrt()) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::code_abstractions::random::SeededRng;
+
+    // How many Poké Balls get thrown on every test, with the same seed
+    const THROWS: u32 = 10_000;
+    const LEGENDARY_CATCH_RATE: u32 = 3;
+
+    fn catches(seed: u64, current_hp: u32, catch_rate: u32, ball_modifier: f64, status_bonus: f64) -> u32 {
+        let mut rng = SeededRng::from_seed(seed);
+        (0..THROWS)
+            .filter(|_| attempt_catch(&mut rng, current_hp, 100, catch_rate, ball_modifier, status_bonus).caught)
+            .count() as u32
+    }
+
+    #[test]
+    fn the_master_ball_always_catches() {
+        let mut rng = SeededRng::from_seed(1);
+        for _ in 0..THROWS {
+            assert_eq!(attempt_catch(&mut rng, 100, 100, LEGENDARY_CATCH_RATE, MASTER_BALL, 1.0),
+                CatchResult { shakes: 4, caught: true });
+        }
+    }
+
+    #[test]
+    fn a_full_hp_legendary_mostly_escapes_a_poke_ball() {
+        assert!(catches(2, 100, LEGENDARY_CATCH_RATE, POKE_BALL, 1.0) < THROWS / 20);
+    }
+
+    #[test]
+    fn a_status_condition_raises_the_chance() {
+        assert!(catches(3, 100, 45, POKE_BALL, 1.5) > catches(3, 100, 45, POKE_BALL, 1.0));
+        assert!(catch_value(100, 100, 45, POKE_BALL, 1.5) > catch_value(100, 100, 45, POKE_BALL, 1.0));
+    }
+
+    #[test]
+    fn a_weakened_pokemon_is_easier_to_catch() {
+        // At 1 HP the catch value it's almost three times the one at full HP
+        assert_eq!(catch_value(100, 100, 45, POKE_BALL, 1.0), 15.0);
+        assert!(catch_value(1, 100, 45, POKE_BALL, 1.0) > 44.0);
+        assert!(catches(4, 1, 45, POKE_BALL, 1.0) > catches(4, 100, 45, POKE_BALL, 1.0));
+    }
+
+    #[test]
+    fn a_catch_value_of_zero_never_shakes() {
+        assert_eq!(shake_probability(0.0), 0);
+        let mut rng = SeededRng::from_seed(5);
+        assert_eq!(attempt_catch(&mut rng, 100, 100, 0, POKE_BALL, 1.0), CatchResult { shakes: 0, caught: false });
+    }
+
+    #[test]
+    fn the_same_seed_throws_the_same_balls() {
+        assert_eq!(catches(6, 50, 45, GREAT_BALL, 1.0), catches(6, 50, 45, GREAT_BALL, 1.0));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::game::code_abstractions::random::GameRng;
+
+/// The chance (over 256) that the player runs away from a wild battle, using the standard escape formula.
+///
+/// The Pokémon of the player always escapes when it's as fast as the wild one. A slower one gets better odds
+/// on every attempt of the same battle (`attempts` counts this one too)
+pub fn escape_odds(player_speed: u32, wild_speed: u32, attempts: u32) -> u32 {
+    if player_speed >= wild_speed {
+        return 256;
+    }
+    player_speed.saturating_mul(128) / wild_speed.max(1) + 30u32.saturating_mul(attempts)
+}
+
+/// Tries to run away from a wild battle. Returns true if the player got away safely
+pub fn attempt_escape<R: GameRng>(rng: &mut R, player_speed: u32, wild_speed: u32, attempts: u32) -> bool {
+    let odds = escape_odds(player_speed, wild_speed, attempts);
+    odds >= 256 || rng.next_in_range(0, 256) < odds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::code_abstractions::random::SeededRng;
+
+    const ATTEMPTS: u32 = 1_000;
+
+    fn escapes(player_speed: u32, wild_speed: u32, attempts: u32) -> u32 {
+        let mut rng = SeededRng::from_seed(336);
+        (0..ATTEMPTS).filter(|_| attempt_escape(&mut rng, player_speed, wild_speed, attempts)).count() as u32
+    }
+
+    #[test]
+    fn a_pokemon_as_fast_as_the_wild_one_always_escapes() {
+        assert_eq!(escape_odds(50, 50, 1), 256);
+        assert_eq!(escapes(50, 50, 1), ATTEMPTS);
+        assert_eq!(escapes(120, 30, 1), ATTEMPTS);
+    }
+
+    #[test]
+    fn every_attempt_makes_the_escape_easier() {
+        assert_eq!(escape_odds(50, 100, 1), 94);
+        assert_eq!(escape_odds(50, 100, 2), 124);
+        assert_eq!(escape_odds(50, 100, 3), 154);
+        assert!(escapes(50, 100, 1) < escapes(50, 100, 3));
+        // Enough attempts always get away
+        assert_eq!(escapes(1, 255, 9), ATTEMPTS);
+    }
+
+    #[test]
+    fn a_pokemon_without_speed_can_still_escape() {
+        assert_eq!(escape_odds(0, 100, 1), 30);
+        assert_eq!(escape_odds(0, 0, 1), 256);
+    }
+}
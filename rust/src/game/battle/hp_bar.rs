@@ -0,0 +1,137 @@
+use gdnative::prelude::*;
+use gdnative::api::Control;
+
+// Below these ratios of HP the bar turns yellow, and then red
+const YELLOW_HP_RATIO: f32 = 0.5;
+const RED_HP_RATIO: f32 = 0.2;
+
+/// The color of the bar for the given ratio of HP left, like on the main games
+pub fn hp_bar_color(ratio: f32) -> Color {
+    if ratio > YELLOW_HP_RATIO {
+        Color::rgb(0.2, 0.8, 0.3)
+    } else if ratio > RED_HP_RATIO {
+        Color::rgb(0.95, 0.8, 0.1)
+    } else {
+        Color::rgb(0.9, 0.2, 0.15)
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Control)]
+#[derive(Debug)]
+/// The health bar of a Pokémon on the battle scene.
+///
+/// Doesn't know anything about the battle: it listens to the `hp_changed` signal of the Game, and drains (or fills)
+/// the bar smoothly up to the new HP
+pub struct HpBar {
+    // The side of the battle whose Pokémon shows this bar: Player or Opponent
+    #[property]
+    side: String,
+    // Seconds that the bar takes to reach the new HP
+    #[property(default = 0.6)]
+    drain_duration: f64,
+
+    // The ratio of HP drawn right now, and the one where the drain started and ends
+    displayed_ratio: f32,
+    start_ratio: f32,
+    target_ratio: f32,
+    elapsed: f64,
+}
+
+#[gdnative::methods]
+impl HpBar {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            side: "Player".to_string(),
+            drain_duration: 0.6,
+            displayed_ratio: 1.0,
+            start_ratio: 1.0,
+            target_ratio: 1.0,
+            elapsed: 0.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<Control>) {
+        owner.set_process(false);
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            let game = unsafe { game.assume_safe() };
+            if !game.is_connected("hp_changed", owner, "_on_hp_changed") {
+                game.connect("hp_changed", owner, "_on_hp_changed", VariantArray::new_shared(), 0).unwrap();
+            }
+        }
+        // The Game could be busy adding the battle scene to the tree, so it gets asked on the next idle frame
+        unsafe { owner.call_deferred("_sync_with_game", &[]) };
+    }
+
+    #[export]
+    /// Starts with the HP that the Pokémon has when the battle begins, without any animation
+    fn _sync_with_game(&mut self, owner: &Control) {
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            let battler_hp = unsafe { game.assume_safe().call("get_battler_hp", &[self.side.to_variant()]) }.to_dictionary();
+            let ratio = hp_ratio(battler_hp.get("current_hp").to_i64(), battler_hp.get("max_hp").to_i64());
+            self.displayed_ratio = ratio;
+            self.start_ratio = ratio;
+            self.target_ratio = ratio;
+            owner.update();
+        }
+    }
+
+    #[export]
+    fn _on_hp_changed(&mut self, owner: &Control, side: String, current_hp: i64, max_hp: i64) {
+        if side != self.side {
+            return;
+        }
+        self.start_ratio = self.displayed_ratio;
+        self.target_ratio = hp_ratio(current_hp, max_hp);
+        self.elapsed = 0.0;
+        owner.set_process(true);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &Control, delta: f64) {
+        self.elapsed += delta;
+        let progress = if self.drain_duration > 0.0 { (self.elapsed / self.drain_duration).min(1.0) } else { 1.0 };
+        self.displayed_ratio = self.start_ratio + (self.target_ratio - self.start_ratio) * progress as f32;
+
+        if progress >= 1.0 {
+            owner.set_process(false);
+        }
+        owner.update();
+    }
+
+    #[export]
+    fn _draw(&self, owner: &Control) {
+        let size = owner.size();
+        owner.draw_rect(Rect2::new(Point2::new(0.0, 0.0), size.to_size()),
+            Color::rgba(0.15, 0.15, 0.15, 1.0), true, 1.0, false);
+        owner.draw_rect(Rect2::new(Point2::new(0.0, 0.0), Size2::new(size.x * self.displayed_ratio, size.y)),
+            hp_bar_color(self.displayed_ratio), true, 1.0, false);
+    }
+}
+
+fn hp_ratio(current_hp: i64, max_hp: i64) -> f32 {
+    if max_hp <= 0 { 0.0 } else { (current_hp as f32 / max_hp as f32).max(0.0).min(1.0) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_ratio_stays_between_empty_and_full() {
+        assert_eq!(hp_ratio(10, 20), 0.5);
+        assert_eq!(hp_ratio(30, 20), 1.0);
+        assert_eq!(hp_ratio(-5, 20), 0.0);
+        assert_eq!(hp_ratio(10, 0), 0.0);
+    }
+
+    #[test]
+    fn the_bar_changes_color_with_the_hp_left() {
+        let (green, yellow, red) = (hp_bar_color(1.0), hp_bar_color(0.5), hp_bar_color(0.2));
+        assert_eq!(hp_bar_color(0.51), green);
+        assert_eq!(hp_bar_color(0.21), yellow);
+        assert_eq!(hp_bar_color(0.0), red);
+        assert!(green != yellow && yellow != red);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::game::pokemon::Pokemon;
+
+/// The two sides of a battle. The UI of every side listens only to the HP changes of his own Pokémon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BattleSide {
+    // The Pokémon of the player's party that it's fighting
+    Player,
+    // The wild Pokémon (or the one of the trainer)
+    Opponent
+}
+
+impl BattleSide {
+    pub fn from_string(side: &str) -> Option<BattleSide> {
+        match side {
+            "Player" => Some(BattleSide::Player),
+            "Opponent" => Some(BattleSide::Opponent),
+            _ => None
+        }
+    }
+
+    pub fn to_side_str(&self) -> &'static str {
+        match self {
+            BattleSide::Player => "Player",
+            BattleSide::Opponent => "Opponent"
+        }
+    }
+}
+
+/// What happened to the HP of a Pokémon after a hit, ready to be sent to the UI with the `hp_changed` signal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HpChange {
+    pub side: BattleSide,
+    pub current_hp: u32,
+    pub max_hp: u32,
+    // The hit knocked out the Pokémon
+    pub fainted: bool,
+}
+
+/// Takes the damage from the HP of the Pokémon. The HP never goes under zero, and a Pokémon that was
+/// already knocked out doesn't faint again
+pub fn apply_damage(pokemon: &mut Pokemon, side: BattleSide, damage: u32) -> HpChange {
+    let was_fainted = pokemon.is_fainted();
+    pokemon.set_current_hp(pokemon.get_current_hp().saturating_sub(damage));
+
+    HpChange {
+        side,
+        current_hp: pokemon.get_current_hp(),
+        max_hp: pokemon.get_max_hp(),
+        fainted: !was_fainted && pokemon.is_fainted(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemon() -> Pokemon {
+        Pokemon::for_tests(25, "Pikachu", Vec::new())
+    }
+
+    #[test]
+    fn a_hit_sends_the_new_hp_of_the_pokemon() {
+        let mut pokemon = pokemon();
+        assert_eq!(apply_damage(&mut pokemon, BattleSide::Opponent, 8),
+            HpChange { side: BattleSide::Opponent, current_hp: 12, max_hp: 20, fainted: false });
+    }
+
+    #[test]
+    fn the_hit_that_knocks_out_faints_the_pokemon_once() {
+        let mut pokemon = pokemon();
+        assert_eq!(apply_damage(&mut pokemon, BattleSide::Player, 50),
+            HpChange { side: BattleSide::Player, current_hp: 0, max_hp: 20, fainted: true });
+        assert!(!apply_damage(&mut pokemon, BattleSide::Player, 5).fainted);
+    }
+
+    #[test]
+    fn the_sides_round_trip_as_strings() {
+        for side in &[BattleSide::Player, BattleSide::Opponent] {
+            assert_eq!(BattleSide::from_string(side.to_side_str()), Some(*side));
+        }
+        assert_eq!(BattleSide::from_string("Rival"), None);
+    }
+}
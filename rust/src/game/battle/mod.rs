@@ -0,0 +1,9 @@
+pub mod type_chart;
+pub mod capture;
+pub mod escape;
+pub mod moves;
+pub mod rewards;
+pub mod status_condition;
+pub mod hp_events;
+pub mod hp_bar;
+pub mod turn;
@@ -0,0 +1,116 @@
+use crate::game::pokemon::{Pokemon, PokemonMove};
+use super::type_chart::{self, Type};
+
+/// The move that a Pokémon uses when none of his moves has PP left
+pub const STRUGGLE: &str = "Forcejeo";
+const STRUGGLE_POWER: u32 = 50;
+// The Pokémon that struggles loses a quarter of his max HP on every hit
+const STRUGGLE_RECOIL_DIVISOR: u32 = 4;
+
+/// What the Pokémon really does when the player picks a move on the fight menu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveChoice {
+    // The move of the given slot, that spends one PP
+    Move(usize),
+    // Every move it's out of PP, so the Pokémon can only struggle
+    Struggle
+}
+
+/// The reasons why a move can't be selected on the fight menu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveError {
+    NoMove,
+    NoPpLeft
+}
+
+impl MoveError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            MoveError::NoMove => "No hay ningún movimiento ahí.",
+            MoveError::NoPpLeft => "¡No quedan PP para este movimiento!"
+        }
+    }
+}
+
+/// Decides what the Pokémon does with the move of the given slot. A Pokémon without PP on any move
+/// always struggles, whatever the slot picked
+pub fn choose_move(pokemon: &Pokemon, move_slot: usize) -> Result<MoveChoice, MoveError> {
+    if !pokemon.has_usable_moves() {
+        return Ok(MoveChoice::Struggle);
+    }
+    match pokemon.get_moves().get(move_slot) {
+        Some(pokemon_move) if pokemon_move.pp == 0 => Err(MoveError::NoPpLeft),
+        Some(_) => Ok(MoveChoice::Move(move_slot)),
+        None => Err(MoveError::NoMove)
+    }
+}
+
+/// Struggle it's typeless, so it hits every Pokémon (even the Ghost ones) without any type advantage
+pub fn struggle() -> PokemonMove {
+    PokemonMove::new(STRUGGLE, "", STRUGGLE_POWER, 0)
+}
+
+/// The damage that the Pokémon that struggles takes himself. Always at least 1 HP
+pub fn struggle_recoil(max_hp: u32) -> u32 {
+    (max_hp / STRUGGLE_RECOIL_DIVISOR).max(1)
+}
+
+/// The type multiplier of the move against the defender types. The moves without type (like Struggle)
+/// ignore the type chart, immunities included
+pub fn move_effectiveness(pokemon_move: &PokemonMove, defender: &[Type]) -> f32 {
+    match Type::from_string(&pokemon_move.move_type) {
+        Some(move_type) => type_chart::effectiveness(move_type, defender),
+        None => 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pokemon(moves: Vec<PokemonMove>) -> Pokemon {
+        Pokemon::for_tests(19, "Rattata", moves)
+    }
+
+    #[test]
+    fn a_pokemon_without_pp_struggles() {
+        let mut tackle = PokemonMove::new("Placaje", "Normal", 40, 35);
+        tackle.pp = 0;
+        let pokemon = pokemon(vec![tackle]);
+
+        assert_eq!(choose_move(&pokemon, 0), Ok(MoveChoice::Struggle));
+        // Whatever the slot picked
+        assert_eq!(choose_move(&pokemon, 3), Ok(MoveChoice::Struggle));
+        assert_eq!(choose_move(&self::pokemon(Vec::new()), 0), Ok(MoveChoice::Struggle));
+    }
+
+    #[test]
+    fn a_move_without_pp_cant_be_selected_while_others_have_it() {
+        let mut tackle = PokemonMove::new("Placaje", "Normal", 40, 35);
+        tackle.pp = 0;
+        let pokemon = pokemon(vec![tackle, PokemonMove::new("Ataque Rápido", "Normal", 40, 30)]);
+
+        assert_eq!(choose_move(&pokemon, 0), Err(MoveError::NoPpLeft));
+        assert_eq!(choose_move(&pokemon, 1), Ok(MoveChoice::Move(1)));
+        assert_eq!(choose_move(&pokemon, 2), Err(MoveError::NoMove));
+    }
+
+    #[test]
+    fn using_a_move_spends_his_pp_until_struggling() {
+        let mut pokemon = pokemon(vec![PokemonMove::new("Placaje", "Normal", 40, 2)]);
+        assert!(pokemon.use_move(0));
+        assert!(pokemon.use_move(0));
+        assert!(!pokemon.use_move(0));
+        assert_eq!(pokemon.get_moves()[0].pp, 0);
+        assert_eq!(choose_move(&pokemon, 0), Ok(MoveChoice::Struggle));
+    }
+
+    #[test]
+    fn struggle_ignores_the_type_chart() {
+        assert_eq!(move_effectiveness(&struggle(), &[Type::Ghost]), 1.0);
+        assert_eq!(move_effectiveness(&PokemonMove::new("Placaje", "Normal", 40, 35), &[Type::Ghost]), 0.0);
+        assert_eq!(struggle_recoil(80), 20);
+        assert_eq!(struggle_recoil(3), 1);
+    }
+}
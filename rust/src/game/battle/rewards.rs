@@ -0,0 +1,85 @@
+// The Pokémon of a trainer give half as much experience more than the wild ones
+const TRAINER_EXPERIENCE_PERCENT: u32 = 150;
+
+/// The money that a defeated trainer pays: his base payout for every level of his strongest Pokémon
+pub fn prize_money(base_payout: u32, highest_level: u8) -> u32 {
+    base_payout.saturating_mul(highest_level as u32)
+}
+
+/// The experience that gives a defeated Pokémon, before splitting it between the Pokémon of the player
+pub fn defeated_experience(base_experience: u32, level: u8, trainer_battle: bool) -> u32 {
+    let experience = base_experience * level as u32 / 7;
+    if trainer_battle { experience * TRAINER_EXPERIENCE_PERCENT / 100 } else { experience }
+}
+
+/// Splits the experience of a defeated Pokémon between the party slots that can still fight (`standing`).
+///
+/// The Pokémon that were sent out to the battle (`participants`) share the experience in equal parts. With
+/// the Exp. Share, every other Pokémon that it's still standing gets half of the whole experience too.
+///
+/// Returns the experience of every party slot that earns any
+pub fn split_experience(experience: u32, participants: &[usize], standing: &[usize], exp_share: bool) -> Vec<(usize, u32)> {
+    let sharing: Vec<usize> = participants.iter()
+        .filter(|party_slot| standing.contains(party_slot))
+        .copied()
+        .collect();
+
+    let mut experience_split: Vec<(usize, u32)> = Vec::new();
+    if !sharing.is_empty() {
+        let share = (experience / sharing.len() as u32).max(1);
+        experience_split.extend(sharing.iter().map(|party_slot| (*party_slot, share)));
+    }
+    if exp_share {
+        experience_split.extend(standing.iter()
+            .filter(|party_slot| !sharing.contains(party_slot))
+            .map(|party_slot| (*party_slot, (experience / 2).max(1))));
+    }
+    experience_split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::trainer::TrainerData;
+
+    #[test]
+    fn the_participants_share_the_experience() {
+        assert_eq!(split_experience(90, &[0], &[0, 1, 2], false), vec![(0, 90)]);
+        assert_eq!(split_experience(90, &[0, 2], &[0, 1, 2], false), vec![(0, 45), (2, 45)]);
+        // A participant that fainted earns nothing, and the rest don't get his part
+        assert_eq!(split_experience(90, &[0, 2], &[1, 2], false), vec![(2, 45)]);
+        assert!(split_experience(90, &[0], &[1], false).is_empty());
+    }
+
+    #[test]
+    fn the_exp_share_gives_half_to_the_rest_of_the_party() {
+        assert_eq!(split_experience(90, &[0], &[0, 1, 2], true), vec![(0, 90), (1, 45), (2, 45)]);
+        assert_eq!(split_experience(90, &[0, 1], &[0, 1, 3], true), vec![(0, 45), (1, 45), (3, 45)]);
+        // Even when every participant fainted
+        assert_eq!(split_experience(90, &[0], &[1], true), vec![(1, 45)]);
+    }
+
+    #[test]
+    fn every_pokemon_earns_at_least_one_point() {
+        assert_eq!(split_experience(1, &[0, 1, 2], &[0, 1, 2, 3], true), vec![(0, 1), (1, 1), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn the_prize_follows_the_highest_level() {
+        let trainer: TrainerData = serde_json::from_str(r#"{ "base_payout": 20, "team": [
+            { "species_id": 16, "level": 9 }, { "species_id": 19, "level": 12 }, { "species_id": 10, "level": 7 }
+        ] }"#).unwrap();
+        assert_eq!(trainer.highest_level(), 12);
+        assert_eq!(prize_money(trainer.base_payout, trainer.highest_level()), 240);
+        // A trainer without team pays nothing
+        assert_eq!(prize_money(20, TrainerData::default().highest_level()), 0);
+        assert_eq!(prize_money(20, 1), 20);
+        assert_eq!(prize_money(u32::MAX, 100), u32::MAX);
+    }
+
+    #[test]
+    fn the_trainer_pokemon_give_more_experience() {
+        assert_eq!(defeated_experience(64, 7, false), 64);
+        assert_eq!(defeated_experience(64, 7, true), 96);
+    }
+}
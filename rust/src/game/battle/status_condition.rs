@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::code_abstractions::random::GameRng;
+
+/// Every how many steps the poisoned Pokémon of the party lose HP while walking on the overworld
+pub const OVERWORLD_POISON_STEPS: u32 = 4;
+/// The chance that a paralyzed Pokémon can't move on his turn
+pub const PARALYSIS_SKIP_CHANCE: f64 = 0.25;
+
+/// The non-volatile status conditions. They remain after the battle, until something heals them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatusCondition {
+    Healthy,
+    Poisoned,
+    Burned,
+    Paralyzed
+}
+
+impl Default for StatusCondition {
+    fn default() -> Self { StatusCondition::Healthy }
+}
+
+impl StatusCondition {
+    /// The HP that the Pokémon loses at the end of every battle turn
+    pub fn end_of_turn_damage(&self, max_hp: u32) -> u32 {
+        match self {
+            // Poison takes 1/8 of the max HP, and burn 1/16. At least 1 HP anyway
+            StatusCondition::Poisoned => (max_hp / 8).max(1),
+            StatusCondition::Burned => (max_hp / 16).max(1),
+            StatusCondition::Healthy | StatusCondition::Paralyzed => 0
+        }
+    }
+
+    /// Burned Pokémon deal half of the damage with physical moves
+    pub fn physical_damage_modifier(&self) -> f64 {
+        if *self == StatusCondition::Burned { 0.5 } else { 1.0 }
+    }
+
+    /// Multiplier of the catch rate of a wild Pokémon. Any status condition makes it easier to catch
+    pub fn catch_bonus(&self) -> f64 {
+        if *self == StatusCondition::Healthy { 1.0 } else { 1.5 }
+    }
+
+    /// Paralyzed Pokémon move at half of their speed
+    pub fn speed_modifier(&self) -> f64 {
+        if *self == StatusCondition::Paralyzed { 0.5 } else { 1.0 }
+    }
+
+    /// Checks if the Pokémon loses his turn because of the paralysis
+    pub fn skips_turn<R: GameRng>(&self, rng: &mut R) -> bool {
+        *self == StatusCondition::Paralyzed && rng.chance(PARALYSIS_SKIP_CHANCE)
+    }
+
+    /// The abbreviation showed on the battle UI and on the party menu
+    pub fn to_abbreviation_str(&self) -> &'static str {
+        match self {
+            StatusCondition::Healthy => "",
+            StatusCondition::Poisoned => "ENV",
+            StatusCondition::Burned => "QUE",
+            StatusCondition::Paralyzed => "PAR"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedRng(f64);
+
+    impl GameRng for FixedRng {
+        fn next_f64(&mut self) -> f64 { self.0 }
+        fn next_in_range(&mut self, low: u32, _high: u32) -> u32 { low }
+    }
+
+    #[test]
+    fn only_the_paralysis_skips_turns() {
+        assert!(StatusCondition::Paralyzed.skips_turn(&mut FixedRng(0.1)));
+        assert!(!StatusCondition::Paralyzed.skips_turn(&mut FixedRng(PARALYSIS_SKIP_CHANCE)));
+        for status in &[StatusCondition::Healthy, StatusCondition::Poisoned, StatusCondition::Burned] {
+            assert!(!status.skips_turn(&mut FixedRng(0.0)));
+        }
+    }
+
+    #[test]
+    fn the_paralysis_halves_the_speed() {
+        assert_eq!(StatusCondition::Paralyzed.speed_modifier(), 0.5);
+        assert_eq!(StatusCondition::Burned.speed_modifier(), 1.0);
+        assert_eq!(StatusCondition::Healthy.speed_modifier(), 1.0);
+    }
+
+    #[test]
+    fn the_burn_halves_the_physical_damage() {
+        assert_eq!(StatusCondition::Burned.physical_damage_modifier(), 0.5);
+        assert_eq!(StatusCondition::Paralyzed.physical_damage_modifier(), 1.0);
+        assert_eq!(StatusCondition::Healthy.physical_damage_modifier(), 1.0);
+    }
+
+    #[test]
+    fn poison_and_burn_hurt_at_the_end_of_the_turn() {
+        assert_eq!(StatusCondition::Poisoned.end_of_turn_damage(80), 10);
+        assert_eq!(StatusCondition::Burned.end_of_turn_damage(80), 5);
+        // Never less than 1 HP
+        assert_eq!(StatusCondition::Poisoned.end_of_turn_damage(5), 1);
+        assert_eq!(StatusCondition::Burned.end_of_turn_damage(10), 1);
+        assert_eq!(StatusCondition::Paralyzed.end_of_turn_damage(80), 0);
+        assert_eq!(StatusCondition::Healthy.end_of_turn_damage(80), 0);
+    }
+
+    #[test]
+    fn any_status_makes_the_catch_easier() {
+        assert_eq!(StatusCondition::Healthy.catch_bonus(), 1.0);
+        for status in &[StatusCondition::Poisoned, StatusCondition::Burned, StatusCondition::Paralyzed] {
+            assert_eq!(status.catch_bonus(), 1.5);
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use std::collections::VecDeque;
+
+use crate::game::code_abstractions::random::GameRng;
+use crate::game::pokemon::{Pokemon, PokemonMove};
+use super::escape;
+use super::hp_events::{self, BattleSide, HpChange};
+use super::moves::{self, MoveChoice, MoveError};
+use super::type_chart::Type;
+
+// The moves of the same type of the Pokémon that uses them hit a half harder
+const SAME_TYPE_BONUS: f32 = 1.5;
+
+/// The steps of a battle. The battle goes from the selection of the actions to the execution of the turn, and
+/// waits there until the UI finishes showing what happened, before asking again for the next actions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BattleState {
+    // Both sides have to choose what they do on this turn
+    SelectingAction,
+    // The actions of the turn are being resolved, one at a time
+    ExecutingTurn,
+    // The turn is over, and the UI is still showing it
+    WaitingForInput,
+    Victory,
+    Defeat,
+    // The player ran away from the wild Pokémon
+    Fled
+}
+
+impl BattleState {
+    /// The battle ended, and no action can change it anymore
+    pub fn is_over(&self) -> bool {
+        matches!(self, BattleState::Victory | BattleState::Defeat | BattleState::Fled)
+    }
+
+    pub fn to_state_str(&self) -> &'static str {
+        match self {
+            BattleState::SelectingAction => "SelectingAction",
+            BattleState::ExecutingTurn => "ExecutingTurn",
+            BattleState::WaitingForInput => "WaitingForInput",
+            BattleState::Victory => "Victory",
+            BattleState::Defeat => "Defeat",
+            BattleState::Fled => "Fled"
+        }
+    }
+}
+
+/// What a side does on his turn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BattleAction {
+    // Uses the move of the given slot
+    Fight(usize),
+    // Only the player can run away, and only from the wild Pokémon
+    Run
+}
+
+impl BattleAction {
+    /// The action chosen on the battle menu: `Fight`, with the slot of the move, or `Run`
+    pub fn from_string(action: &str, move_slot: usize) -> Option<BattleAction> {
+        match action {
+            "Fight" => Some(BattleAction::Fight(move_slot)),
+            "Run" => Some(BattleAction::Run),
+            _ => None
+        }
+    }
+}
+
+/// Something that happened while resolving a turn, that the battle scene has to animate. The ones that the Game
+/// already notifies keep the name of his signal
+#[derive(Debug, Clone, PartialEq)]
+pub enum BattleEvent {
+    MoveUsed { side: BattleSide, move_name: String, effectiveness: f32 },
+    // The paralysis didn't let the Pokémon move
+    TurnSkipped(BattleSide),
+    HpChanged(HpChange),
+    PokemonFainted(BattleSide),
+    // The opponent fainted, and the trainer sends out the next Pokémon of his team
+    OpponentSentOut,
+    EscapedFromBattle,
+    EscapeFailed,
+    StateChanged(BattleState)
+}
+
+impl BattleEvent {
+    /// The name of the signal that notifies this event to the Godot side
+    pub fn to_signal_str(&self) -> &'static str {
+        match self {
+            BattleEvent::MoveUsed { .. } => "move_used",
+            BattleEvent::TurnSkipped(_) => "turn_skipped",
+            BattleEvent::HpChanged(_) => "hp_changed",
+            BattleEvent::PokemonFainted(_) => "pokemon_fainted",
+            BattleEvent::OpponentSentOut => "opponent_sent_out",
+            BattleEvent::EscapedFromBattle => "escaped_from_battle",
+            BattleEvent::EscapeFailed => "escape_failed",
+            BattleEvent::StateChanged(_) => "battle_state_changed"
+        }
+    }
+}
+
+/// The reasons why the actions of a turn can't be taken
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BattleError {
+    // The battle isn't waiting for that right now
+    WrongState(BattleState),
+    InvalidMove(MoveError),
+    // The opponent can't run away, and nobody runs away from a trainer
+    CantRun,
+    // There's no battle going on
+    NotInBattle
+}
+
+impl BattleError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            BattleError::WrongState(_) | BattleError::NotInBattle => "Ahora no se puede hacer eso.",
+            BattleError::InvalidMove(move_error) => move_error.to_message_str(),
+            BattleError::CantRun => "¡No se puede huir!"
+        }
+    }
+}
+
+/// A battle between the Pokémon of the player and a wild one (or the team of a trainer), turn after turn, until one
+/// of the sides has no Pokémon left or the player runs away.
+///
+/// It only keeps the progress of the battle. The Pokémon stay where they are (the party and the opponent of the
+/// Game), and every step borrows them, so the HP and PP that they spend are already stored when the battle ends.
+/// It doesn't know anything about Godot: the Game feeds it with the actions chosen on the battle scene, and emits
+/// the events that every action produces
+#[derive(Debug, Clone)]
+pub struct Battle {
+    state: BattleState,
+    // The actions of the current turn that are still waiting to be resolved, the fastest first
+    pending_actions: VecDeque<(BattleSide, BattleAction)>,
+    escape_attempts: u32,
+    // The player got away from the battle
+    fled: bool,
+    turn: u32,
+    // The Pokémon that the trainer keeps waiting to go out. None on the wild battles, where running away is allowed
+    trainer_reserve: Option<usize>,
+}
+
+impl Default for Battle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Battle {
+    pub fn new() -> Self {
+        Self {
+            state: BattleState::SelectingAction,
+            pending_actions: VecDeque::new(),
+            escape_attempts: 0,
+            fled: false,
+            turn: 0,
+            trainer_reserve: None,
+        }
+    }
+
+    /// A battle against a trainer, that sends out the given number of Pokémon after the first one
+    pub fn against_trainer(reserve: usize) -> Self {
+        Self { trainer_reserve: Some(reserve), ..Self::new() }
+    }
+
+    pub fn get_state(&self) -> BattleState {
+        self.state
+    }
+
+    pub fn get_turn(&self) -> u32 {
+        self.turn
+    }
+
+    /// Starts a new turn with the actions of both sides. Running away always goes first, and the moves are sorted by
+    /// the speed of every Pokémon (the player wins the ties)
+    pub fn select_actions(&mut self, player: &Pokemon, opponent: &Pokemon, player_action: BattleAction,
+        opponent_action: BattleAction) -> Result<Vec<BattleEvent>, BattleError>
+    {
+        if self.state != BattleState::SelectingAction {
+            return Err(BattleError::WrongState(self.state));
+        }
+        if opponent_action == BattleAction::Run
+            || (player_action == BattleAction::Run && self.trainer_reserve.is_some())
+        {
+            return Err(BattleError::CantRun);
+        }
+        if let BattleAction::Fight(move_slot) = player_action {
+            moves::choose_move(player, move_slot).map_err(BattleError::InvalidMove)?;
+        }
+
+        let player_first = player_action == BattleAction::Run
+            || player.get_effective_speed() >= opponent.get_effective_speed();
+        let mut actions = vec![(BattleSide::Player, player_action), (BattleSide::Opponent, opponent_action)];
+        if !player_first {
+            actions.reverse();
+        }
+        self.pending_actions = actions.into_iter().collect();
+        self.turn += 1;
+        Ok(vec![self.change_state(BattleState::ExecutingTurn)])
+    }
+
+    /// Resolves the next action of the turn. Once the last one it's done, the status conditions hurt the Pokémon
+    /// and the battle waits for the UI. When the opponent of a trainer faints the turn ends there, and the trainer
+    /// sends out his next Pokémon (the Game has to place it as the new opponent on the `OpponentSentOut` event).
+    ///
+    /// Returns what happened, on the order that the battle scene must show it
+    pub fn resolve_next_action<R: GameRng>(&mut self, rng: &mut R, player: &mut Pokemon, opponent: &mut Pokemon)
+        -> Result<Vec<BattleEvent>, BattleError>
+    {
+        if self.state != BattleState::ExecutingTurn {
+            return Err(BattleError::WrongState(self.state));
+        }
+
+        let mut events = Vec::new();
+        match self.pending_actions.pop_front() {
+            Some((side, BattleAction::Fight(move_slot))) =>
+                resolve_move(rng, side, move_slot, player, opponent, &mut events),
+            Some((_, BattleAction::Run)) => self.resolve_escape(rng, player, opponent, &mut events),
+            None => ()
+        }
+
+        if let Some(result) = self.check_result(player, opponent) {
+            self.pending_actions.clear();
+            events.push(self.change_state(result));
+            return Ok(events);
+        }
+        // Nobody can hit the fainted Pokémon, so the trainer replaces it right after this turn
+        if opponent.is_fainted() {
+            self.pending_actions.clear();
+        }
+        if self.pending_actions.is_empty() {
+            resolve_end_of_turn(player, opponent, &mut events);
+            match self.check_result(player, opponent) {
+                Some(result) => events.push(self.change_state(result)),
+                None => {
+                    if opponent.is_fainted() {
+                        self.trainer_reserve = self.trainer_reserve.map(|reserve| reserve - 1);
+                        events.push(BattleEvent::OpponentSentOut);
+                    }
+                    events.push(self.change_state(BattleState::WaitingForInput));
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// The UI finished showing the turn, so the battle asks for the next actions
+    pub fn acknowledge_turn(&mut self) -> Result<Vec<BattleEvent>, BattleError> {
+        if self.state != BattleState::WaitingForInput {
+            return Err(BattleError::WrongState(self.state));
+        }
+        Ok(vec![self.change_state(BattleState::SelectingAction)])
+    }
+
+    fn change_state(&mut self, state: BattleState) -> BattleEvent {
+        self.state = state;
+        BattleEvent::StateChanged(state)
+    }
+
+    fn resolve_escape<R: GameRng>(&mut self, rng: &mut R, player: &Pokemon, opponent: &Pokemon,
+        events: &mut Vec<BattleEvent>)
+    {
+        self.escape_attempts += 1;
+        if escape::attempt_escape(rng, player.get_effective_speed(), opponent.get_effective_speed(),
+            self.escape_attempts)
+        {
+            self.fled = true;
+            events.push(BattleEvent::EscapedFromBattle);
+        } else {
+            events.push(BattleEvent::EscapeFailed);
+        }
+    }
+
+    /// How the battle ended, if it did. When both Pokémon faint at the same time the player loses. A trainer only
+    /// loses when the last Pokémon of his team faints
+    fn check_result(&self, player: &Pokemon, opponent: &Pokemon) -> Option<BattleState> {
+        if player.is_fainted() {
+            Some(BattleState::Defeat)
+        } else if opponent.is_fainted() && self.trainer_reserve.unwrap_or(0) == 0 {
+            Some(BattleState::Victory)
+        } else if self.fled {
+            Some(BattleState::Fled)
+        } else {
+            None
+        }
+    }
+}
+
+/// The wild Pokémon just uses any of his moves with PP left. Without PP on every move, it struggles
+pub fn choose_opponent_action<R: GameRng>(rng: &mut R, opponent: &Pokemon) -> BattleAction {
+    let usable_slots: Vec<usize> = opponent.get_moves().iter().enumerate()
+        .filter(|(_, pokemon_move)| pokemon_move.pp > 0)
+        .map(|(move_slot, _)| move_slot)
+        .collect();
+    if usable_slots.is_empty() {
+        return BattleAction::Fight(0);
+    }
+    BattleAction::Fight(usable_slots[rng.next_in_range(0, usable_slots.len() as u32) as usize])
+}
+
+/// The Pokémon of the side uses a move on the other one. A fainted Pokémon doesn't get to move
+fn resolve_move<R: GameRng>(rng: &mut R, side: BattleSide, move_slot: usize, player: &mut Pokemon,
+    opponent: &mut Pokemon, events: &mut Vec<BattleEvent>)
+{
+    let (attacker, defender) = match side {
+        BattleSide::Player => (player, opponent),
+        BattleSide::Opponent => (opponent, player)
+    };
+    if attacker.is_fainted() {
+        return;
+    }
+    if attacker.get_status().skips_turn(rng) {
+        events.push(BattleEvent::TurnSkipped(side));
+        return;
+    }
+
+    let (pokemon_move, recoil) = match moves::choose_move(attacker, move_slot) {
+        Ok(MoveChoice::Move(move_slot)) => {
+            attacker.use_move(move_slot);
+            (attacker.get_moves()[move_slot].clone(), 0)
+        },
+        // The opponent could pick a move without PP. It just struggles, like when every move it's out of PP
+        _ => (moves::struggle(), moves::struggle_recoil(attacker.get_max_hp()))
+    };
+
+    let effectiveness = moves::move_effectiveness(&pokemon_move, &pokemon_types(defender));
+    let damage = calculate_damage(attacker, defender, &pokemon_move, effectiveness);
+    events.push(BattleEvent::MoveUsed { side, move_name: pokemon_move.name.to_owned(), effectiveness });
+
+    hurt(defender, opposite_side(side), damage, events);
+    if recoil > 0 {
+        hurt(attacker, side, recoil, events);
+    }
+}
+
+/// Poison and burn take their HP from every Pokémon still standing
+fn resolve_end_of_turn(player: &mut Pokemon, opponent: &mut Pokemon, events: &mut Vec<BattleEvent>) {
+    apply_end_of_turn_status(player, BattleSide::Player, events);
+    apply_end_of_turn_status(opponent, BattleSide::Opponent, events);
+}
+
+fn apply_end_of_turn_status(pokemon: &mut Pokemon, side: BattleSide, events: &mut Vec<BattleEvent>) {
+    if pokemon.is_fainted() || pokemon.apply_end_of_turn_status() == 0 {
+        return;
+    }
+    let hp_change = HpChange {
+        side,
+        current_hp: pokemon.get_current_hp(),
+        max_hp: pokemon.get_max_hp(),
+        fainted: pokemon.is_fainted(),
+    };
+    events.push(BattleEvent::HpChanged(hp_change));
+    if hp_change.fainted {
+        events.push(BattleEvent::PokemonFainted(side));
+    }
+}
+
+fn hurt(pokemon: &mut Pokemon, side: BattleSide, damage: u32, events: &mut Vec<BattleEvent>) {
+    let hp_change = hp_events::apply_damage(pokemon, side, damage);
+    events.push(BattleEvent::HpChanged(hp_change));
+    if hp_change.fainted {
+        events.push(BattleEvent::PokemonFainted(side));
+    }
+}
+
+fn opposite_side(side: BattleSide) -> BattleSide {
+    match side {
+        BattleSide::Player => BattleSide::Opponent,
+        BattleSide::Opponent => BattleSide::Player
+    }
+}
+
+/// The one or two types of the Pokémon, as the type chart knows them
+fn pokemon_types(pokemon: &Pokemon) -> Vec<Type> {
+    [pokemon.type1.as_str(), pokemon.type2.as_str()].iter()
+        .filter_map(|pokemon_type| Type::from_string(pokemon_type))
+        .collect()
+}
+
+/// The damage of a move, with the formula of the main games: it grows with the level of the attacker and the power
+/// of the move, and depends on how the attack of the one compares to the defense of the other. The same type
+/// bonus and the type chart change it afterwards. A move that hits always deals at least 1 HP.
+///
+/// The moves don't have a category yet, so every move it's treated as physical: the attack against the defense,
+/// halved when the attacker it's burned
+pub fn calculate_damage(attacker: &Pokemon, defender: &Pokemon, pokemon_move: &PokemonMove, effectiveness: f32) -> u32 {
+    if effectiveness == 0.0 || pokemon_move.power == 0 {
+        return 0;
+    }
+    let level_factor = 2 * attacker.get_level() as u32 / 5 + 2;
+    let attack = attacker.get_stats().attack.max(1);
+    let defense = defender.get_stats().defense.max(1);
+    let base_damage = level_factor * pokemon_move.power * attack / defense / 50 + 2;
+
+    let same_type = !pokemon_move.move_type.is_empty()
+        && (pokemon_move.move_type == attacker.type1 || pokemon_move.move_type == attacker.type2);
+    let same_type_bonus = if same_type { SAME_TYPE_BONUS } else { 1.0 };
+    let burn_modifier = attacker.get_status().physical_damage_modifier() as f32;
+    let damage = base_damage as f32 * same_type_bonus * effectiveness * burn_modifier;
+    (damage as u32).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::battle::status_condition::StatusCondition;
+    use crate::game::code_abstractions::random::SeededRng;
+    use crate::game::pokemon::PokemonStats;
+
+    const SEED: u64 = 7;
+
+    fn tackle() -> PokemonMove {
+        PokemonMove::new("Placaje", "Normal", 40, 35)
+    }
+
+    fn growl() -> PokemonMove {
+        PokemonMove::new("Gruñido", "Normal", 0, 40)
+    }
+
+    /// A level 50 Pokémon without types, so the type chart and the same type bonus don't change the damage
+    fn pokemon(attack: u32, speed: u32, max_hp: u32, moves: Vec<PokemonMove>) -> Pokemon {
+        Pokemon::new_pokemon(1, "Pokémon".to_string(), "".to_string(), "".to_string(), 1.0, 40.0, 50, max_hp,
+            PokemonStats::new(attack, 50, 50, 50, speed), moves)
+    }
+
+    fn first_move_side(events: &[BattleEvent]) -> Option<BattleSide> {
+        events.iter().find_map(|event| match event {
+            BattleEvent::MoveUsed { side, .. } => Some(*side),
+            _ => None
+        })
+    }
+
+    #[test]
+    fn the_player_wins_the_speed_ties() {
+        let mut rng = SeededRng::from_seed(SEED);
+        let (mut player, mut opponent) = (pokemon(50, 60, 100, vec![tackle()]), pokemon(50, 60, 100, vec![tackle()]));
+        let mut battle = Battle::new();
+        battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert_eq!(first_move_side(&events), Some(BattleSide::Player));
+    }
+
+    #[test]
+    fn the_faster_pokemon_moves_first() {
+        let mut rng = SeededRng::from_seed(SEED);
+        let (mut player, mut opponent) = (pokemon(50, 30, 100, vec![tackle()]), pokemon(50, 90, 100, vec![tackle()]));
+        let mut battle = Battle::new();
+        battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert_eq!(first_move_side(&events), Some(BattleSide::Opponent));
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert_eq!(first_move_side(&events), Some(BattleSide::Player));
+    }
+
+    #[test]
+    fn the_actions_are_refused_out_of_their_step() {
+        let mut rng = SeededRng::from_seed(SEED);
+        let (mut player, mut opponent) = (pokemon(50, 60, 100, vec![tackle()]), pokemon(50, 30, 100, vec![tackle()]));
+        let mut battle = Battle::new();
+
+        assert_eq!(battle.resolve_next_action(&mut rng, &mut player, &mut opponent),
+            Err(BattleError::WrongState(BattleState::SelectingAction)));
+        assert_eq!(battle.acknowledge_turn(), Err(BattleError::WrongState(BattleState::SelectingAction)));
+
+        battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+        assert_eq!(battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)),
+            Err(BattleError::WrongState(BattleState::ExecutingTurn)));
+    }
+
+    #[test]
+    fn the_opponent_cant_run_and_the_moves_must_exist() {
+        let (player, opponent) = (pokemon(50, 60, 100, vec![tackle()]), pokemon(50, 30, 100, vec![tackle()]));
+        let mut battle = Battle::new();
+
+        assert_eq!(battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Run),
+            Err(BattleError::CantRun));
+        assert_eq!(battle.select_actions(&player, &opponent, BattleAction::Fight(3), BattleAction::Fight(0)),
+            Err(BattleError::InvalidMove(MoveError::NoMove)));
+        // Nothing got started by the refused actions
+        assert_eq!(battle.get_state(), BattleState::SelectingAction);
+        assert_eq!(battle.get_turn(), 0);
+    }
+
+    #[test]
+    fn a_two_turn_battle_ends_on_victory() {
+        let mut rng = SeededRng::from_seed(SEED);
+        // Every tackle of the player takes 19 HP, so the opponent needs two of them
+        let mut player = pokemon(50, 60, 100, vec![tackle()]);
+        let mut opponent = pokemon(10, 30, 30, vec![tackle()]);
+        let mut battle = Battle::new();
+
+        battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+        battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert_eq!(opponent.get_current_hp(), 11);
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert_eq!(events.last(), Some(&BattleEvent::StateChanged(BattleState::WaitingForInput)));
+        battle.acknowledge_turn().unwrap();
+
+        battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert!(events.contains(&BattleEvent::PokemonFainted(BattleSide::Opponent)));
+        assert_eq!(events.last(), Some(&BattleEvent::StateChanged(BattleState::Victory)));
+        assert_eq!(battle.get_state(), BattleState::Victory);
+        assert_eq!(battle.get_turn(), 2);
+        // The fainted opponent doesn't get his move
+        assert_eq!(opponent.get_moves()[0].pp, 34);
+        assert!(battle.resolve_next_action(&mut rng, &mut player, &mut opponent).is_err());
+    }
+
+    #[test]
+    fn fainting_both_at_once_is_a_defeat() {
+        let mut rng = SeededRng::from_seed(SEED);
+        let mut player = pokemon(50, 60, 80, vec![growl()]);
+        let mut opponent = pokemon(50, 30, 80, vec![growl()]);
+        for pokemon in [&mut player, &mut opponent].iter_mut() {
+            pokemon.set_current_hp(1);
+            pokemon.set_status(StatusCondition::Poisoned);
+        }
+        let mut battle = Battle::new();
+
+        battle.select_actions(&player, &opponent, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+        battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+
+        assert!(events.contains(&BattleEvent::PokemonFainted(BattleSide::Player)));
+        assert!(events.contains(&BattleEvent::PokemonFainted(BattleSide::Opponent)));
+        assert_eq!(battle.get_state(), BattleState::Defeat);
+    }
+
+    #[test]
+    fn running_goes_first_and_flees() {
+        let mut rng = SeededRng::from_seed(SEED);
+        let (mut player, mut opponent) = (pokemon(50, 60, 100, vec![tackle()]), pokemon(50, 30, 100, vec![tackle()]));
+        let mut battle = Battle::new();
+        battle.select_actions(&player, &opponent, BattleAction::Run, BattleAction::Fight(0)).unwrap();
+
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut opponent).unwrap();
+        assert_eq!(events, vec![BattleEvent::EscapedFromBattle, BattleEvent::StateChanged(BattleState::Fled)]);
+        assert_eq!(player.get_current_hp(), 100);
+    }
+
+    #[test]
+    fn nobody_runs_away_from_a_trainer() {
+        let (player, opponent) = (pokemon(50, 60, 100, vec![tackle()]), pokemon(50, 30, 100, vec![tackle()]));
+        let mut battle = Battle::against_trainer(0);
+
+        assert_eq!(battle.select_actions(&player, &opponent, BattleAction::Run, BattleAction::Fight(0)),
+            Err(BattleError::CantRun));
+        assert_eq!(battle.get_state(), BattleState::SelectingAction);
+    }
+
+    #[test]
+    fn the_trainer_sends_out_his_next_pokemon_before_losing() {
+        let mut rng = SeededRng::from_seed(SEED);
+        // Every tackle of the player takes 19 HP, so every Pokémon of the trainer faints on the first hit
+        let mut player = pokemon(50, 60, 100, vec![tackle()]);
+        let mut first = pokemon(10, 30, 15, vec![tackle()]);
+        let mut battle = Battle::against_trainer(1);
+
+        battle.select_actions(&player, &first, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut first).unwrap();
+        assert!(events.contains(&BattleEvent::PokemonFainted(BattleSide::Opponent)));
+        assert_eq!(&events[events.len() - 2..],
+            &[BattleEvent::OpponentSentOut, BattleEvent::StateChanged(BattleState::WaitingForInput)]);
+        // The turn ended with the faint, so the fainted Pokémon never got his move
+        assert_eq!(first.get_moves()[0].pp, 35);
+        assert_eq!(player.get_current_hp(), 100);
+        battle.acknowledge_turn().unwrap();
+
+        let mut second = pokemon(10, 30, 15, vec![tackle()]);
+        battle.select_actions(&player, &second, BattleAction::Fight(0), BattleAction::Fight(0)).unwrap();
+        let events = battle.resolve_next_action(&mut rng, &mut player, &mut second).unwrap();
+        assert!(!events.contains(&BattleEvent::OpponentSentOut));
+        assert_eq!(events.last(), Some(&BattleEvent::StateChanged(BattleState::Victory)));
+    }
+
+    #[test]
+    fn the_burn_halves_the_damage() {
+        let mut burned = pokemon(65, 60, 100, vec![tackle()]);
+        burned.set_status(StatusCondition::Burned);
+        let (healthy, defender) = (pokemon(65, 60, 100, vec![tackle()]), pokemon(50, 60, 100, vec![tackle()]));
+
+        assert_eq!(calculate_damage(&healthy, &defender, &tackle(), 1.0), 24);
+        assert_eq!(calculate_damage(&burned, &defender, &tackle(), 1.0), 12);
+        assert_eq!(calculate_damage(&burned, &defender, &tackle(), 0.0), 0);
+        // Even a burned Pokémon hits for at least 1 HP
+        let mut weak = pokemon(1, 60, 100, vec![tackle()]);
+        weak.set_status(StatusCondition::Burned);
+        assert_eq!(calculate_damage(&weak, &defender, &tackle(), 0.25), 1);
+    }
+
+    #[test]
+    fn the_opponent_only_picks_moves_with_pp() {
+        let mut rng = SeededRng::from_seed(SEED);
+        let mut no_pp = tackle();
+        no_pp.pp = 0;
+        let opponent = pokemon(50, 60, 100, vec![no_pp, growl()]);
+        for _ in 0..20 {
+            assert_eq!(choose_opponent_action(&mut rng, &opponent), BattleAction::Fight(1));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+/// The eighteen Pokémon types
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Type {
+    Normal,
+    Fire,
+    Water,
+    Electric,
+    Grass,
+    Ice,
+    Fighting,
+    Poison,
+    Ground,
+    Flying,
+    Psychic,
+    Bug,
+    Rock,
+    Ghost,
+    Dragon,
+    Dark,
+    Steel,
+    Fairy,
+}
+
+impl Type {
+    // Returns a Vec<Type> with all the Variants, in the same order that the rows and columns of the type chart
+    pub fn values() -> Vec<Type> {
+        vec![Self::Normal, Self::Fire, Self::Water, Self::Electric, Self::Grass, Self::Ice, Self::Fighting, Self::Poison, Self::Ground, Self::Flying, Self::Psychic, Self::Bug, Self::Rock, Self::Ghost, Self::Dragon, Self::Dark, Self::Steel, Self::Fairy]
+    }
+
+    // Associated fn that converts the spanish name of a type (as they are stored on the species data) to his Type counterpart
+    pub fn from_string(string: &str) -> Option<Type> {
+        match string {
+            "Normal" => Some(Self::Normal),
+            "Fuego" => Some(Self::Fire),
+            "Agua" => Some(Self::Water),
+            "Eléctrico" => Some(Self::Electric),
+            "Planta" => Some(Self::Grass),
+            "Hielo" => Some(Self::Ice),
+            "Lucha" => Some(Self::Fighting),
+            "Veneno" => Some(Self::Poison),
+            "Tierra" => Some(Self::Ground),
+            "Volador" => Some(Self::Flying),
+            "Psíquico" => Some(Self::Psychic),
+            "Bicho" => Some(Self::Bug),
+            "Roca" => Some(Self::Rock),
+            "Fantasma" => Some(Self::Ghost),
+            "Dragón" => Some(Self::Dragon),
+            "Siniestro" => Some(Self::Dark),
+            "Acero" => Some(Self::Steel),
+            "Hada" => Some(Self::Fairy),
+            _ => None
+        }
+    }
+
+    // Returns the name of the type translated into spanish
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "Normal",
+            Self::Fire => "Fuego",
+            Self::Water => "Agua",
+            Self::Electric => "Eléctrico",
+            Self::Grass => "Planta",
+            Self::Ice => "Hielo",
+            Self::Fighting => "Lucha",
+            Self::Poison => "Veneno",
+            Self::Ground => "Tierra",
+            Self::Flying => "Volador",
+            Self::Psychic => "Psíquico",
+            Self::Bug => "Bicho",
+            Self::Rock => "Roca",
+            Self::Ghost => "Fantasma",
+            Self::Dragon => "Dragón",
+            Self::Dark => "Siniestro",
+            Self::Steel => "Acero",
+            Self::Fairy => "Hada",
+        }
+    }
+}
+
+// Shorthands to keep the type chart readable
+const __: f32 = 1.0; // Neutral
+const HF: f32 = 0.5; // Not very effective
+const SE: f32 = 2.0; // Super effective
+const NO: f32 = 0.0; // Doesn't affect
+
+/// The damage multiplier of every attacking type (rows) against every defending type (columns)
+const TYPE_CHART: [[f32; 18]; 18] = [
+    //              Nor Fir Wat Ele Gra Ice Fig Poi Gro Fly Psy Bug Roc Gho Dra Dar Ste Fai
+    /* Normal   */ [__, __, __, __, __, __, __, __, __, __, __, __, HF, NO, __, __, HF, __],
+    /* Fire     */ [__, HF, HF, __, SE, SE, __, __, __, __, __, SE, HF, __, HF, __, SE, __],
+    /* Water    */ [__, SE, HF, __, HF, __, __, __, SE, __, __, __, SE, __, HF, __, __, __],
+    /* Electric */ [__, __, SE, HF, HF, __, __, __, NO, SE, __, __, __, __, HF, __, __, __],
+    /* Grass    */ [__, HF, SE, __, HF, __, __, HF, SE, HF, __, HF, SE, __, HF, __, HF, __],
+    /* Ice      */ [__, HF, HF, __, SE, HF, __, __, SE, SE, __, __, __, __, SE, __, HF, __],
+    /* Fighting */ [SE, __, __, __, __, SE, __, HF, __, HF, HF, HF, SE, NO, __, SE, SE, HF],
+    /* Poison   */ [__, __, __, __, SE, __, __, HF, HF, __, __, __, HF, HF, __, __, NO, SE],
+    /* Ground   */ [__, SE, __, SE, HF, __, __, SE, __, NO, __, HF, SE, __, __, __, SE, __],
+    /* Flying   */ [__, __, __, HF, SE, __, SE, __, __, __, __, SE, HF, __, __, __, HF, __],
+    /* Psychic  */ [__, __, __, __, __, __, SE, SE, __, __, HF, __, __, __, __, NO, HF, __],
+    /* Bug      */ [__, HF, __, __, SE, __, HF, HF, __, HF, SE, __, __, HF, __, SE, HF, HF],
+    /* Rock     */ [__, SE, __, __, __, SE, HF, __, HF, SE, __, SE, __, __, __, __, HF, __],
+    /* Ghost    */ [NO, __, __, __, __, __, __, __, __, __, SE, __, __, SE, __, HF, __, __],
+    /* Dragon   */ [__, __, __, __, __, __, __, __, __, __, __, __, __, __, SE, __, HF, NO],
+    /* Dark     */ [__, __, __, __, __, __, HF, __, __, __, SE, __, __, SE, __, HF, __, HF],
+    /* Steel    */ [__, HF, HF, HF, __, SE, __, __, __, __, __, __, SE, __, __, __, HF, SE],
+    /* Fairy    */ [__, HF, __, __, __, __, SE, HF, __, __, __, __, __, __, SE, SE, HF, __],
+];
+
+/// Returns the damage multiplier of an attack of the given type over a Pokémon with the `defender` types.
+///
+/// The multipliers of every defending type are combined, so the result could be 0, 0.25, 0.5, 1, 2 or 4
+pub fn effectiveness(attack: Type, defender: &[Type]) -> f32 {
+    defender.iter()
+        .map(|defending_type| TYPE_CHART[attack as usize][*defending_type as usize])
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_type_multipliers() {
+        assert_eq!(effectiveness(Type::Water, &[Type::Fire]), 2.0);
+        assert_eq!(effectiveness(Type::Fire, &[Type::Water]), 0.5);
+        assert_eq!(effectiveness(Type::Normal, &[Type::Ghost]), 0.0);
+        assert_eq!(effectiveness(Type::Normal, &[Type::Normal]), 1.0);
+    }
+
+    #[test]
+    fn dual_type_multipliers_combine() {
+        assert_eq!(effectiveness(Type::Ice, &[Type::Dragon, Type::Flying]), 4.0);
+        assert_eq!(effectiveness(Type::Fire, &[Type::Water, Type::Rock]), 0.25);
+        assert_eq!(effectiveness(Type::Water, &[Type::Fire, Type::Water]), 1.0);
+        // An immunity cancels any weakness
+        assert_eq!(effectiveness(Type::Ground, &[Type::Fire, Type::Flying]), 0.0);
+    }
+
+    #[test]
+    fn without_defending_types_the_attack_is_neutral() {
+        assert_eq!(effectiveness(Type::Dragon, &[]), 1.0);
+    }
+
+    #[test]
+    fn the_spanish_names_go_both_ways() {
+        for pokemon_type in Type::values() {
+            assert_eq!(Type::from_string(pokemon_type.to_spanish_str()), Some(pokemon_type));
+        }
+    }
+
+    #[test]
+    fn unknown_type_names_are_none() {
+        assert_eq!(Type::from_string(""), None);
+        assert_eq!(Type::from_string("Luz"), None);
+        // Only the spanish names, with his accents
+        assert_eq!(Type::from_string("Fire"), None);
+        assert_eq!(Type::from_string("fuego"), None);
+        assert_eq!(Type::from_string("Dragon"), None);
+    }
+}
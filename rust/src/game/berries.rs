@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The in-game hours that a berry spends on every stage, before growing into the next one
+pub const BERRY_STAGE_HOURS: f64 = 6.0;
+
+/// The berries that the player can plant on the berry spots, in the order that they're offered
+pub const PLANTABLE_BERRIES: [&str; 3] = ["oran_berry", "pecha_berry", "cheri_berry"];
+
+/// How grown it's a planted berry
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GrowthStage {
+    Planted,
+    Sprouted,
+    Growing,
+    Flowering,
+    // The plant has new berries, ready to be harvested
+    Ready
+}
+
+impl GrowthStage {
+    pub const ALL: [GrowthStage; 5] = [
+        GrowthStage::Planted, GrowthStage::Sprouted, GrowthStage::Growing, GrowthStage::Flowering, GrowthStage::Ready
+    ];
+
+    /// The stage of a berry planted the given in-game hours ago
+    pub fn from_hours_planted(hours_planted: f64) -> GrowthStage {
+        let stage = (hours_planted.max(0.0) / BERRY_STAGE_HOURS) as usize;
+        GrowthStage::ALL[stage.min(GrowthStage::ALL.len() - 1)]
+    }
+
+    pub fn to_stage_str(&self) -> &'static str {
+        match self {
+            GrowthStage::Planted => "Planted",
+            GrowthStage::Sprouted => "Sprouted",
+            GrowthStage::Growing => "Growing",
+            GrowthStage::Flowering => "Flowering",
+            GrowthStage::Ready => "Ready"
+        }
+    }
+
+    /// What the player sees when he checks the plant
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            GrowthStage::Planted => "Hay una baya plantada aquí.",
+            GrowthStage::Sprouted => "¡Ha brotado la baya!",
+            GrowthStage::Growing => "La planta está creciendo.",
+            GrowthStage::Flowering => "¡La planta está en flor!",
+            GrowthStage::Ready => "¡La planta tiene bayas!"
+        }
+    }
+}
+
+/// If the item it's a berry that can be planted
+pub fn is_plantable(item_id: &str) -> bool {
+    PLANTABLE_BERRIES.contains(&item_id)
+}
+
+/// A berry planted on a berry spot.
+///
+/// Only the moment when it was planted it's stored, measured as the in-game hours since the game started, so the
+/// plant keeps growing with the time that passes while it's far from the player, or saved on the file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BerryPlant {
+    berry_id: String,
+    planted_at: f64,
+}
+
+impl BerryPlant {
+    pub fn new(berry_id: &str, planted_at: f64) -> Self {
+        Self { berry_id: berry_id.to_string(), planted_at }
+    }
+
+    pub fn get_berry_id(&self) -> &str {
+        &self.berry_id
+    }
+
+    /// The stage of the plant at the given in-game hours since the game started
+    pub fn stage(&self, elapsed_hours: f64) -> GrowthStage {
+        GrowthStage::from_hours_planted(elapsed_hours - self.planted_at)
+    }
+}
+
+/// The reasons why a berry can't be planted on a spot
+#[derive(Debug, Clone, PartialEq)]
+pub enum BerryError {
+    NotPlantable,
+    NotInBag,
+    // There's already a berry growing on the spot
+    SpotTaken,
+    // There's nothing to harvest on the spot yet
+    NotReady,
+    // The harvested berries wouldn't fit on the bag
+    BagFull
+}
+
+impl BerryError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            BerryError::NotPlantable => "Eso no se puede plantar.",
+            BerryError::NotInBag => "No tienes ninguna baya para plantar.",
+            BerryError::SpotTaken => "Ya hay algo plantado aquí.",
+            BerryError::NotReady => "Todavía no hay nada que recoger.",
+            BerryError::BagFull => "No te caben más bayas en la mochila."
+        }
+    }
+}
+
+/// The berries planted by the player, by the id of their berry spot
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct BerryGarden {
+    plants: HashMap<String, BerryPlant>,
+}
+
+impl BerryGarden {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_plant(&self, spot_id: &str) -> Option<&BerryPlant> {
+        self.plants.get(spot_id)
+    }
+
+    /// Plants the berry on the given spot, that must be empty
+    pub fn plant(&mut self, spot_id: &str, berry_id: &str, elapsed_hours: f64) -> Result<(), BerryError> {
+        if !is_plantable(berry_id) {
+            return Err(BerryError::NotPlantable);
+        }
+        if self.plants.contains_key(spot_id) {
+            return Err(BerryError::SpotTaken);
+        }
+        self.plants.insert(spot_id.to_string(), BerryPlant::new(berry_id, elapsed_hours));
+        Ok(())
+    }
+
+    /// Takes out the plant of the spot, if it's ready to be harvested. The spot gets empty again
+    pub fn harvest(&mut self, spot_id: &str, elapsed_hours: f64) -> Option<BerryPlant> {
+        match self.plants.get(spot_id) {
+            Some(plant) if plant.stage(elapsed_hours) == GrowthStage::Ready => self.plants.remove(spot_id),
+            _ => None
+        }
+    }
+}
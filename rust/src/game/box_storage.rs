@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+
+use super::party::Party;
+use super::pokemon::Pokemon;
+
+/// How many boxes has the PC of the player
+pub const BOX_COUNT: usize = 8;
+/// How many Pokémon fit inside a single box
+pub const BOX_CAPACITY: usize = 30;
+
+/// Where a Pokémon received by the player ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PokemonDestination {
+    Party,
+    // The index of the box of the PC
+    Box(usize)
+}
+
+/// A single box of the PC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PokemonBox {
+    pub name: String,
+    pokemon: Vec<Pokemon>,
+}
+
+impl PokemonBox {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), pokemon: Vec::new() }
+    }
+
+    pub fn get_pokemon(&self) -> &Vec<Pokemon> {
+        &self.pokemon
+    }
+
+    pub fn len(&self) -> usize {
+        self.pokemon.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pokemon.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.pokemon.len() >= BOX_CAPACITY
+    }
+}
+
+/// The PC storage system, where goes every Pokémon that the player can't carry with him
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoxStorage {
+    boxes: Vec<PokemonBox>,
+}
+
+impl Default for BoxStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoxStorage {
+    pub fn new() -> Self {
+        Self {
+            boxes: (1..=BOX_COUNT)
+                .map(|number| PokemonBox::new(&format!("Caja {}", number)))
+                .collect()
+        }
+    }
+
+    /// Stores the Pokémon on the first box with free space, starting from the given box and moving
+    /// to the next ones when it's full.
+    ///
+    /// Returns the index of the box where the Pokémon was stored. If every box it's full, the Pokémon it's given back
+    pub fn deposit(&mut self, pokemon: Pokemon, starting_box: usize) -> Result<usize, Pokemon> {
+        let box_count = self.boxes.len();
+        for offset in 0..box_count {
+            let box_index = (starting_box + offset) % box_count;
+            if !self.boxes[box_index].is_full() {
+                self.boxes[box_index].pokemon.push(pokemon);
+                return Ok(box_index);
+            }
+        }
+        Err(pokemon)
+    }
+
+    /// The last Pokémon stored on the given box, like the one that just got deposited
+    pub fn get_last_pokemon_mut(&mut self, box_index: usize) -> Option<&mut Pokemon> {
+        self.boxes.get_mut(box_index)?.pokemon.last_mut()
+    }
+
+    /// Takes out of the storage the Pokémon placed at the given position of a box, if exists
+    pub fn withdraw(&mut self, box_index: usize, slot: usize) -> Option<Pokemon> {
+        let pokemon_box = self.boxes.get_mut(box_index)?;
+        if slot < pokemon_box.pokemon.len() {
+            Some(pokemon_box.pokemon.remove(slot))
+        } else {
+            None
+        }
+    }
+
+    /// Moves a Pokémon from one box to another. Returns false (and nothing moves) if the Pokémon doesn't
+    /// exist or the destination box it's full
+    pub fn move_between_boxes(&mut self, from_box: usize, slot: usize, to_box: usize) -> bool {
+        match self.boxes.get(to_box) {
+            Some(destination) if !destination.is_full() => (),
+            _ => return false
+        }
+
+        match self.withdraw(from_box, slot) {
+            Some(pokemon) => {
+                self.boxes[to_box].pokemon.push(pokemon);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Sends a Pokémon of the party to the given box.
+    ///
+    /// The party must keep at least one Pokémon, and the box must have free space
+    pub fn deposit_from_party(&mut self, party: &mut Party, party_slot: usize, box_index: usize) -> bool {
+        if party.len() <= 1 || party_slot >= party.len() {
+            return false;
+        }
+        match self.boxes.get(box_index) {
+            Some(destination) if !destination.is_full() => (),
+            _ => return false
+        }
+
+        if let Some(pokemon) = party.remove_member(party_slot) {
+            self.boxes[box_index].pokemon.push(pokemon);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Brings a Pokémon of a box to the party. If the party it's full, the Pokémon just stays on his box
+    pub fn withdraw_to_party(&mut self, party: &mut Party, box_index: usize, slot: usize) -> bool {
+        if party.is_full() {
+            return false;
+        }
+
+        match self.withdraw(box_index, slot) {
+            Some(pokemon) => party.add_member(pokemon).is_ok(),
+            None => false
+        }
+    }
+
+    pub fn get_box(&self, box_index: usize) -> Option<&PokemonBox> {
+        self.boxes.get(box_index)
+    }
+
+    pub fn get_boxes(&self) -> &Vec<PokemonBox> {
+        &self.boxes
+    }
+
+    /// If there's no room for one more Pokémon on any box
+    pub fn is_full(&self) -> bool {
+        self.boxes.iter().all(PokemonBox::is_full)
+    }
+
+    /// The total of Pokémon stored on the PC
+    pub fn total_pokemon(&self) -> usize {
+        self.boxes.iter().map(|pokemon_box| pokemon_box.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::party::MAX_PARTY_SIZE;
+    use crate::game::player::PlayerData;
+
+    fn pokemon(id: i32) -> Pokemon {
+        Pokemon::for_tests(id, &format!("Pokémon {}", id), Vec::new())
+    }
+
+    fn full_party() -> Party {
+        let mut party = Party::new();
+        for id in 1..=MAX_PARTY_SIZE as i32 {
+            party.add_member(pokemon(id)).unwrap();
+        }
+        party
+    }
+
+    #[test]
+    fn a_full_party_sends_the_new_pokemon_to_the_box() {
+        let mut player_data = PlayerData::new();
+        for id in 1..=MAX_PARTY_SIZE as i32 {
+            assert_eq!(player_data.receive_pokemon(pokemon(id)).unwrap(), PokemonDestination::Party);
+        }
+
+        assert_eq!(player_data.receive_pokemon(pokemon(7)).unwrap(), PokemonDestination::Box(0));
+        assert_eq!(player_data.get_party().len(), MAX_PARTY_SIZE);
+        assert_eq!(player_data.get_received_pokemon(PokemonDestination::Box(0)).unwrap().id, 7);
+    }
+
+    #[test]
+    fn a_full_box_overflows_to_the_next_one() {
+        let mut box_storage = BoxStorage::new();
+        for id in 0..BOX_CAPACITY as i32 {
+            assert_eq!(box_storage.deposit(pokemon(id), 0).unwrap(), 0);
+        }
+        assert_eq!(box_storage.deposit(pokemon(100), 0).unwrap(), 1);
+        // The last box wraps to the first one
+        assert_eq!(box_storage.deposit(pokemon(101), BOX_COUNT - 1).unwrap(), BOX_COUNT - 1);
+        assert_eq!(box_storage.total_pokemon(), BOX_CAPACITY + 2);
+    }
+
+    #[test]
+    fn the_pokemon_is_given_back_when_every_box_is_full() {
+        let mut box_storage = BoxStorage::new();
+        for id in 0..(BOX_COUNT * BOX_CAPACITY) as i32 {
+            assert!(!box_storage.is_full());
+            box_storage.deposit(pokemon(id), 0).unwrap();
+        }
+        assert!(box_storage.is_full());
+        assert_eq!(box_storage.deposit(pokemon(-1), 3).unwrap_err().id, -1);
+    }
+
+    #[test]
+    fn the_player_has_room_until_the_party_and_every_box_are_full() {
+        let mut player_data = PlayerData::new();
+        for id in 0..(MAX_PARTY_SIZE + BOX_COUNT * BOX_CAPACITY) as i32 {
+            assert!(player_data.has_room_for_pokemon());
+            player_data.receive_pokemon(pokemon(id)).unwrap();
+        }
+        assert!(!player_data.has_room_for_pokemon());
+
+        player_data.get_box_storage_mut().withdraw(BOX_COUNT - 1, 0).unwrap();
+        assert!(player_data.has_room_for_pokemon());
+    }
+
+    #[test]
+    fn withdrawing_into_a_full_party_keeps_the_pokemon_on_his_box() {
+        let mut party = full_party();
+        let mut box_storage = BoxStorage::new();
+        box_storage.deposit(pokemon(7), 2).unwrap();
+
+        assert!(!box_storage.withdraw_to_party(&mut party, 2, 0));
+        assert_eq!(box_storage.get_box(2).unwrap().len(), 1);
+        assert_eq!(party.len(), MAX_PARTY_SIZE);
+
+        party.remove_member(0);
+        assert!(box_storage.withdraw_to_party(&mut party, 2, 0));
+        assert!(box_storage.get_box(2).unwrap().is_empty());
+        assert_eq!(party.get_members().last().unwrap().id, 7);
+    }
+
+    #[test]
+    fn the_party_always_keeps_a_pokemon() {
+        let mut party = Party::new();
+        party.add_member(pokemon(1)).unwrap();
+        let mut box_storage = BoxStorage::new();
+
+        assert!(!box_storage.deposit_from_party(&mut party, 0, 0));
+        party.add_member(pokemon(2)).unwrap();
+        assert!(box_storage.deposit_from_party(&mut party, 0, 0));
+        assert_eq!(party.get_members()[0].id, 2);
+        assert_eq!(box_storage.get_box(0).unwrap().get_pokemon()[0].id, 1);
+    }
+
+    #[test]
+    fn moving_to_a_full_box_moves_nothing() {
+        let mut box_storage = BoxStorage::new();
+        for id in 0..BOX_CAPACITY as i32 {
+            box_storage.deposit(pokemon(id), 1).unwrap();
+        }
+        box_storage.deposit(pokemon(100), 0).unwrap();
+
+        assert!(!box_storage.move_between_boxes(0, 0, 1));
+        assert!(!box_storage.move_between_boxes(0, 5, 2));
+        assert!(box_storage.move_between_boxes(0, 0, 2));
+        assert_eq!(box_storage.get_box(2).unwrap().get_pokemon()[0].id, 100);
+    }
+}
@@ -3,6 +3,8 @@
 /// The most basic abstraction of the game core, a character.
 /// Character must represent any Kinematic2D Body that it's suppossed to be a human representation.
 pub mod character {
+    use gdnative::prelude::*;
+
     pub trait CharacterTileMovement<O, I> {
         fn process_player_input(&mut self, owner: &O, input: &I);
 
@@ -17,6 +19,61 @@ pub mod character {
 
         fn landing_dust_effect(&mut self, owner: &O);
     }
+
+    /// What's left of a motion that hit a wall, moving along the wall instead of into it.
+    ///
+    /// The part that goes against the wall (the one on the direction of his normal) it's removed, so a diagonal
+    /// motion against a wall that only blocks one axis keeps going on the other one
+    pub fn slide_along_wall(motion: Vector2, wall_normal: Vector2) -> Vector2 {
+        motion - wall_normal * motion.dot(wall_normal)
+    }
+
+    /// Moves a body that walks freely (not tile by tile) with `move_and_collide`. When it hits a wall, the rest of
+    /// the motion it's tried again along the wall, so the body slides on it instead of stopping dead.
+    ///
+    /// The tile based movement never needs it: the raycasts stop every step (and the corners of the diagonal
+    /// ones) before any collision. Returns the motion that the body really did
+    pub fn move_sliding_on_walls(body: &KinematicBody2D, motion: Vector2) -> Vector2 {
+        let collision = match body.move_and_collide(motion, true, true, false) {
+            Some(collision) => unsafe { collision.assume_safe() },
+            None => return motion
+        };
+        let travel = collision.travel();
+        let slide = slide_along_wall(collision.remainder(), collision.normal());
+        if slide == Vector2::zero() {
+            return travel;
+        }
+
+        // Only one retry: a second wall means that the body it's stuck on a corner
+        match body.move_and_collide(slide, true, true, false) {
+            Some(second_collision) => travel + unsafe { second_collision.assume_safe() }.travel(),
+            None => travel + slide
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn a_diagonal_motion_slides_along_the_wall() {
+            // A wall on the right of the body, so only the horizontal part of the motion gets blocked
+            let wall_normal = Vector2::new(-1.0, 0.0);
+            assert_eq!(slide_along_wall(Vector2::new(2.0, 3.0), wall_normal), Vector2::new(0.0, 3.0));
+            // A floor under the body
+            assert_eq!(slide_along_wall(Vector2::new(2.0, 3.0), Vector2::new(0.0, -1.0)), Vector2::new(2.0, 0.0));
+        }
+
+        #[test]
+        fn a_motion_straight_into_the_wall_stops() {
+            assert_eq!(slide_along_wall(Vector2::new(4.0, 0.0), Vector2::new(-1.0, 0.0)), Vector2::zero());
+        }
+
+        #[test]
+        fn a_motion_along_the_wall_keeps_going() {
+            assert_eq!(slide_along_wall(Vector2::new(0.0, -5.0), Vector2::new(-1.0, 0.0)), Vector2::new(0.0, -5.0));
+        }
+    }
 }
 
 
@@ -38,6 +95,135 @@ pub mod signals {
         /// Registers a signal on `Godot`directly from the Rust code.
         fn register_signal(_builder: &ClassBuilder<T>) -> ();
     }
+
+    /// Connects a signal that only needs to be received once, using the `CONNECT_ONESHOT` flag of Godot.
+    ///
+    /// Godot removes the connection by itself after the first emission. If the connection it's still alive (the signal
+    /// wasn't emitted yet), nothing it's connected again, so the method never gets called twice for the same emission
+    pub fn connect_oneshot<T>(owner: &Object, signal: &str, target: TRef<T>, method: &str) -> Result<(), GodotError>
+        where T: GodotObject + SubClass<Object>
+    {
+        let target = target.upcast::<Object>();
+        if owner.is_connected(signal, target, method) {
+            return Ok(());
+        }
+        owner.connect(signal, target, method, VariantArray::new_shared(), Object::CONNECT_ONESHOT)
+    }
+
+    /// The Rust types that can travel as the argument of a signal, bound to the `VariantType` that Godot expects for them.
+    ///
+    /// This way, the type of the argument it's checked by the compiler when the signal gets registered, instead of
+    /// finding a mismatched `Variant` when the signal it's emitted
+    pub trait SignalArgumentType: ToVariant {
+        const VARIANT_TYPE: VariantType;
+    }
+
+    impl SignalArgumentType for bool {
+        const VARIANT_TYPE: VariantType = VariantType::Bool;
+    }
+
+    impl SignalArgumentType for i64 {
+        const VARIANT_TYPE: VariantType = VariantType::I64;
+    }
+
+    impl SignalArgumentType for f64 {
+        const VARIANT_TYPE: VariantType = VariantType::F64;
+    }
+
+    impl SignalArgumentType for GodotString {
+        const VARIANT_TYPE: VariantType = VariantType::GodotString;
+    }
+
+    impl SignalArgumentType for Vector2 {
+        const VARIANT_TYPE: VariantType = VariantType::Vector2;
+    }
+
+    /// Registers signals with a typed argument. Implemented for every `ClassBuilder`, so it can be used
+    /// directly inside any `register_signal`
+    pub trait AddTypedSignal {
+        /// Registers a signal with a single argument, whose Godot type it's inferred from the Rust type of the default value
+        fn add_typed_signal<A: SignalArgumentType>(&self, name: &str, arg_name: &str, default: A);
+    }
+
+    impl<T: NativeClass> AddTypedSignal for ClassBuilder<T> {
+        fn add_typed_signal<A: SignalArgumentType>(&self, name: &str, arg_name: &str, default: A) {
+            self.add_signal( Signal {
+                name,
+                args: &[ SignalArgument {
+                    name: arg_name,
+                    default: default.to_variant(),
+                    export_info: ExportInfo::new(A::VARIANT_TYPE),
+                    usage: PropertyUsage::DEFAULT,
+                }],
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// The Godot type that `add_typed_signal` registers for the given default value
+        fn registered_type<A: SignalArgumentType>(_default: &A) -> VariantType {
+            A::VARIANT_TYPE
+        }
+
+        #[test]
+        fn every_argument_type_maps_to_his_godot_type() {
+            assert_eq!(bool::VARIANT_TYPE, VariantType::Bool);
+            assert_eq!(i64::VARIANT_TYPE, VariantType::I64);
+            assert_eq!(f64::VARIANT_TYPE, VariantType::F64);
+            assert_eq!(GodotString::VARIANT_TYPE, VariantType::GodotString);
+            assert_eq!(Vector2::VARIANT_TYPE, VariantType::Vector2);
+        }
+
+        #[test]
+        fn the_type_is_inferred_from_the_default_value() {
+            // Like the `animate` signal of the NPCs
+            assert_eq!(registered_type(&Vector2::new(0.0, 0.0)), VariantType::Vector2);
+            assert_eq!(registered_type(&0i64), VariantType::I64);
+            assert_eq!(registered_type(&false), VariantType::Bool);
+        }
+    }
+}
+
+pub mod interactions {
+    use gdnative::prelude::*;
+
+    /// The method that every interactable object exports, and the one that the player calls when interacts with it
+    pub const INTERACT_METHOD: &str = "interact";
+    /// The child node that marks the objects that hold data for the player, but handle nothing by themselves
+    pub const INTERACT_NODE: &str = "Interact";
+    // How many levels above the collider the owner of the interaction it's looked for. The big objects (statues,
+    // counters...) have a collision shape for every tile, sometimes grouped under another node below the owner
+    const MAX_INTERACTION_DEPTH: usize = 3;
+
+    /// Any object of the world that does something when the player interacts with it (signs, items, doors, NPCs...)
+    ///
+    /// Implementors should export an `interact` method that just calls `on_interact`, so the player can dispatch the
+    /// interaction without knowing what kind of object it's in front of him.
+    pub trait Interactable<T> {
+        /// What happens when the player interacts with the object
+        fn on_interact(&mut self, owner: TRef<T>);
+    }
+
+    /// Looks for the interactable object behind the body that the player it's colliding with. Usually the
+    /// collider it's a body child of the node that has the script, but the objects that span several tiles can
+    /// have it a few levels below, so the ancestors are also checked. Any tile of a big object leads to the same owner
+    pub fn find_interactable(collider: TRef<Node>) -> Option<TRef<Node>> {
+        collider_and_ancestors(collider).find(|node| node.has_method(INTERACT_METHOD))
+    }
+
+    /// The same as `find_interactable`, but for the objects marked with an `Interact` child node
+    pub fn find_interact_node_owner(collider: TRef<Node>) -> Option<TRef<Node>> {
+        collider_and_ancestors(collider).find(|node| node.has_node(INTERACT_NODE))
+    }
+
+    /// The collider itself, followed by his parents up to `MAX_INTERACTION_DEPTH` levels above
+    fn collider_and_ancestors(collider: TRef<Node>) -> impl Iterator<Item = TRef<Node>> {
+        std::iter::successors(Some(collider), |node| node.get_parent().map(|parent| unsafe { parent.assume_safe() }))
+            .take(MAX_INTERACTION_DEPTH + 1)
+    }
 }
 
 pub mod node_operations {
@@ -127,4 +313,92 @@ pub mod dialogue_connections {
             };   
         }
     }
-}
\ No newline at end of file
+}
+pub mod random {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// The source of every random thing of the game: wild encounters, Poké Ball shakes, shiny checks, paralysis,
+    /// wandering NPCs...
+    ///
+    /// The game logic always receives it as a parameter instead of calling a global one, so the same seed
+    /// always gives the same outcomes
+    pub trait GameRng {
+        /// A random number between 0.0 (included) and 1.0 (excluded)
+        fn next_f64(&mut self) -> f64;
+
+        /// A random number between `low` (included) and `high` (excluded). An empty range always gives `low`
+        fn next_in_range(&mut self, low: u32, high: u32) -> u32;
+
+        /// true with the given probability, from 0.0 (never) to 1.0 (always)
+        fn chance(&mut self, probability: f64) -> bool {
+            self.next_f64() < probability
+        }
+    }
+
+    /// The RNG used by the game. Seeded with the current time, unless a fixed seed it's given
+    #[derive(Debug, Clone)]
+    pub struct SeededRng {
+        rng: StdRng,
+    }
+
+    impl SeededRng {
+        pub fn from_seed(seed: u64) -> Self {
+            Self { rng: StdRng::seed_from_u64(seed) }
+        }
+
+        pub fn from_time() -> Self {
+            let seed = SystemTime::now().duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or_default();
+            Self::from_seed(seed)
+        }
+    }
+
+    impl Default for SeededRng {
+        fn default() -> Self {
+            Self::from_time()
+        }
+    }
+
+    impl GameRng for SeededRng {
+        fn next_f64(&mut self) -> f64 {
+            self.rng.gen::<f64>()
+        }
+
+        fn next_in_range(&mut self, low: u32, high: u32) -> u32 {
+            if low >= high { low } else { self.rng.gen_range(low..high) }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn the_same_seed_gives_the_same_rolls() {
+            let mut first = SeededRng::from_seed(42);
+            let mut second = SeededRng::from_seed(42);
+            for _ in 0..100 {
+                assert_eq!(first.next_f64(), second.next_f64());
+                assert_eq!(first.next_in_range(0, 256), second.next_in_range(0, 256));
+                assert_eq!(first.chance(0.5), second.chance(0.5));
+            }
+        }
+
+        #[test]
+        fn the_rolls_stay_inside_their_ranges() {
+            let mut rng = SeededRng::from_seed(7);
+            for _ in 0..1000 {
+                let roll = rng.next_f64();
+                assert!((0.0..1.0).contains(&roll));
+                assert!((10..20).contains(&rng.next_in_range(10, 20)));
+            }
+            assert_eq!(rng.next_in_range(5, 5), 5);
+            assert_eq!(rng.next_in_range(9, 3), 9);
+            assert!(!rng.chance(0.0));
+            assert!(rng.chance(1.0));
+        }
+    }
+}
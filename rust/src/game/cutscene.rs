@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use gdnative::prelude::*;
+use gdnative::api::Area2D;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::code_abstractions::signals::connect_oneshot;
+use crate::game::event_flags::{flag_key, namespaces};
+use crate::game::quests::quest_events;
+use crate::utils::{consts::in_game_constant, utils};
+
+const PLAYER_PATH: &str = "/root/Game/Player";
+const DIALOGUE_BOX_PATH: &str = "/root/Game/Player/Camera2D/CanvasLayer/DialogueBox";
+
+/// One step of a cutscene. The cutscene waits until the step it's done before going to the next one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CutsceneStep {
+    // Walks the actor (`Player`, or the path of a node of the scene) the given tiles
+    Move { actor: String, tiles: (i32, i32) },
+    Wait { seconds: f64 },
+    // Shows a dialogue of the dialogues config, and waits until the player closes it
+    Dialogue { dialogue_id: String },
+    SetFlag { flag: String, value: bool },
+}
+
+/// The steps of every cutscene, by cutscene id, loaded from the cutscenes JSON config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CutsceneTable {
+    #[serde(default)]
+    cutscenes: HashMap<String, Vec<CutsceneStep>>,
+}
+
+impl CutsceneTable {
+    pub fn get_steps(&self, cutscene_id: &str) -> Vec<CutsceneStep> {
+        self.cutscenes.get(cutscene_id).cloned().unwrap_or_default()
+    }
+}
+
+/// An actor walking from one tile to another
+#[derive(Debug)]
+struct ActorMove {
+    actor: Ref<Node2D>,
+    from: Vector2,
+    to: Vector2,
+    // From 0.0 (on the first tile) to 1.0 (on the last one)
+    progress: f64,
+}
+
+#[derive(NativeClass)]
+#[inherit(Area2D)]
+#[derive(Debug)]
+/// A scripted sequence of the game (moving characters, dialogues...) that plays the first time that the player
+/// walks into his area. The player can't move while it's playing.
+///
+/// Pressing `Exit` skips what's left of the cutscene, leaving everything as it would be at the end of it
+pub struct Cutscene {
+    // The id of the steps on the cutscenes config. It's also remembered on the saved game, so it only plays once
+    #[property]
+    cutscene_id: String,
+
+    steps: Vec<CutsceneStep>,
+    current_step: usize,
+    playing: bool,
+    // What the current step it's waiting for
+    wait_time_left: f64,
+    actor_move: Option<ActorMove>,
+    waiting_dialogue: bool,
+    // Tiles per second, from the balance config
+    walk_speed: f64,
+}
+
+#[gdnative::methods]
+impl Cutscene {
+    fn new(_owner: &Area2D) -> Self {
+        Self {
+            cutscene_id: "".to_string(),
+            steps: Vec::new(),
+            current_step: 0,
+            playing: false,
+            wait_time_left: 0.0,
+            actor_move: None,
+            waiting_dialogue: false,
+            walk_speed: in_game_constant::WALK_SPEED,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Area2D) {
+        self.steps = utils::retrieve_cutscene_table().get_steps(&self.cutscene_id);
+        self.walk_speed = utils::retrieve_balance_config().get_walk_speed();
+        owner.set_process(false);
+    }
+
+    #[export]
+    /// Receives a signal when a body enteres the Cutscene area (connected on the Godot GUI)
+    fn _on_area2d_body_entered(&mut self, owner: TRef<Area2D>, body: Ref<Node>) {
+        if unsafe { body.assume_safe() }.name().to_string() == "Player" {
+            self.play(owner);
+        }
+    }
+
+    #[export]
+    /// Starts the cutscene, unless it's already playing or the player already saw it
+    fn play(&mut self, owner: TRef<Area2D>) {
+        if self.playing || self.steps.is_empty() || self.is_already_seen(owner) {
+            return;
+        }
+        self.playing = true;
+        self.current_step = 0;
+        self.lock_player(owner, true);
+        owner.set_process(true);
+        self.start_step(owner);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: TRef<Area2D>, delta: f64) {
+        if !self.playing {
+            return;
+        }
+        // A dialogue on the screen owns the keys until it gets closed
+        if !self.waiting_dialogue && Input::is_action_just_pressed(Input::godot_singleton(), "Exit") {
+            self.skip(owner);
+            return;
+        }
+
+        if let Some(actor_move) = self.actor_move.as_mut() {
+            let tiles = (actor_move.to - actor_move.from).length() / in_game_constant::TILE_SIZE;
+            actor_move.progress = if tiles > 0.0 {
+                (actor_move.progress + self.walk_speed * delta / tiles as f64).min(1.0)
+            } else {
+                1.0
+            };
+            let actor = unsafe { actor_move.actor.assume_safe() };
+            actor.set_global_position(actor_move.from.lerp(actor_move.to, actor_move.progress as f32));
+            if actor_move.progress >= 1.0 {
+                self.finish_move();
+                self.next_step(owner);
+            }
+        } else if self.wait_time_left > 0.0 {
+            self.wait_time_left -= delta;
+            if self.wait_time_left <= 0.0 {
+                self.next_step(owner);
+            }
+        }
+    }
+
+    #[export]
+    /// The player closed the dialogue of the current step. Closing it releases the player, so he gets locked again
+    fn _on_dialogue_finished(&mut self, owner: TRef<Area2D>, _signal_info: Variant) {
+        if self.waiting_dialogue {
+            self.waiting_dialogue = false;
+            self.lock_player(owner, true);
+            self.next_step(owner);
+        }
+    }
+
+    fn start_step(&mut self, owner: TRef<Area2D>) {
+        let step = match self.steps.get(self.current_step) {
+            Some(step) => step.clone(),
+            None => return self.finish(owner)
+        };
+
+        match step {
+            CutsceneStep::Move { actor, tiles } => match self.get_actor(owner, &actor) {
+                Some(actor) => {
+                    let from = unsafe { actor.assume_safe() }.global_position();
+                    let to = from + Vector2::new(tiles.0 as f32, tiles.1 as f32) * in_game_constant::TILE_SIZE;
+                    self.actor_move = Some(ActorMove { actor, from, to, progress: 0.0 });
+                },
+                None => {
+                    godot_print!("The actor {} of the cutscene {} doesn't exist", actor, self.cutscene_id);
+                    self.next_step(owner);
+                }
+            },
+            CutsceneStep::Wait { seconds } => {
+                self.wait_time_left = seconds.max(0.0);
+                if self.wait_time_left == 0.0 {
+                    self.next_step(owner);
+                }
+            },
+            CutsceneStep::Dialogue { dialogue_id } => {
+                if !self.show_dialogue(owner, &dialogue_id) {
+                    self.next_step(owner);
+                }
+            },
+            CutsceneStep::SetFlag { flag, value } => {
+                self.set_flag(owner, &flag, value);
+                self.next_step(owner);
+            }
+        }
+    }
+
+    fn next_step(&mut self, owner: TRef<Area2D>) {
+        self.current_step += 1;
+        self.start_step(owner);
+    }
+
+    /// Jumps to the end of the cutscene: every actor gets placed where it would end, and every flag gets set.
+    /// The waits and the dialogues that are left are just ignored
+    fn skip(&mut self, owner: TRef<Area2D>) {
+        if let Some(actor_move) = self.actor_move.as_mut() {
+            actor_move.progress = 1.0;
+            unsafe { actor_move.actor.assume_safe() }.set_global_position(actor_move.to);
+            self.finish_move();
+            self.current_step += 1;
+        }
+
+        for step in self.steps[self.current_step.min(self.steps.len())..].to_vec() {
+            match step {
+                CutsceneStep::Move { actor, tiles } => {
+                    if let Some(actor) = self.get_actor(owner, &actor) {
+                        let actor = unsafe { actor.assume_safe() };
+                        let to = actor.global_position() + Vector2::new(tiles.0 as f32, tiles.1 as f32) * in_game_constant::TILE_SIZE;
+                        actor.set_global_position(to);
+                        place_actor(actor, to);
+                    }
+                },
+                CutsceneStep::SetFlag { flag, value } => self.set_flag(owner, &flag, value),
+                CutsceneStep::Wait { .. } | CutsceneStep::Dialogue { .. } => ()
+            }
+        }
+        self.current_step = self.steps.len();
+        self.finish(owner);
+    }
+
+    fn finish(&mut self, owner: TRef<Area2D>) {
+        self.playing = false;
+        self.wait_time_left = 0.0;
+        self.actor_move = None;
+        owner.set_process(false);
+        self.set_flag(owner, &flag_key(namespaces::CUTSCENE_SEEN, &self.cutscene_id), true);
+        self.lock_player(owner, false);
+    }
+
+    /// The actor arrived. The ones that walk by tiles (like the player) are told where they are now
+    fn finish_move(&mut self) {
+        if let Some(actor_move) = self.actor_move.take() {
+            place_actor(unsafe { actor_move.actor.assume_safe() }, actor_move.to);
+        }
+    }
+
+    /// Prints the dialogue on the Dialogue Box. Returns false if there's nothing to print
+    fn show_dialogue(&mut self, owner: TRef<Area2D>, dialogue_id: &str) -> bool {
+        let game = unsafe { owner.get_node("/root/Game") }.map(|game| unsafe { game.assume_safe() });
+        let lines: Vec<String> = match game {
+            Some(game) => unsafe { game.call("get_dialogue", &[dialogue_id.to_variant()]) }
+                .to_array()
+                .iter()
+                .map(|line| line.to_string())
+                .collect(),
+            None => Vec::new()
+        };
+        let dialogue_box = match unsafe { owner.get_node(DIALOGUE_BOX_PATH) } {
+            Some(dialogue_box) => unsafe { dialogue_box.assume_safe() },
+            None => return false
+        };
+        if lines.is_empty() {
+            return false;
+        }
+
+        if let Err(err) = connect_oneshot(&dialogue_box, "dialogue_box_inactive", owner, "_on_dialogue_finished") {
+            godot_error!("Can't wait for the dialogue of the cutscene {}: {:?}", self.cutscene_id, err);
+            return false;
+        }
+        self.waiting_dialogue = true;
+        let dialogue_data = (0, Vec::<String>::new(), lines);
+        unsafe { dialogue_box.call("_print_dialogue", &[dialogue_data.to_variant()]) };
+
+        // The quests that ask the player to talk with someone wait for his dialogue
+        if let Some(game) = game {
+            unsafe { game.call("record_quest_event", &[quest_events::TALK.to_variant(), dialogue_id.to_variant()]) };
+        }
+        true
+    }
+
+    fn get_actor(&self, owner: TRef<Area2D>, actor: &str) -> Option<Ref<Node2D>> {
+        let actor_node = if actor == "Player" {
+            unsafe { owner.get_node(PLAYER_PATH) }
+        } else {
+            // The rest of the actors are placed on the same scene than the cutscene
+            owner.get_parent().and_then(|scene| unsafe { scene.assume_safe() }.get_node(actor))
+        };
+        actor_node.and_then(|actor| unsafe { actor.assume_safe() }.cast::<Node2D>()).map(|actor| actor.claim())
+    }
+
+    fn is_already_seen(&self, owner: TRef<Area2D>) -> bool {
+        match unsafe { owner.get_node("/root/Game") } {
+            Some(game) => unsafe { game.assume_safe().call("get_flag",
+                &[flag_key(namespaces::CUTSCENE_SEEN, &self.cutscene_id).to_variant()]) }.to_bool(),
+            None => false
+        }
+    }
+
+    fn set_flag(&self, owner: TRef<Area2D>, flag: &str, value: bool) {
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            unsafe { game.assume_safe().call("set_flag", &[flag.to_variant(), value.to_variant()]) };
+        }
+    }
+
+    fn lock_player(&self, owner: TRef<Area2D>, locked: bool) {
+        let signal_info = if locked { "on_cutscene" } else { "end_interaction" };
+        if let Some(player) = unsafe { owner.get_node(PLAYER_PATH) } {
+            unsafe { player.assume_safe().call_deferred("handle_interaction", &[Variant::from_str(signal_info)]) };
+        }
+    }
+}
+
+/// Places the actor on his new tile. The ones that walk by tiles (like the player) have to know it too
+fn place_actor(actor: TRef<Node2D>, position: Vector2) {
+    if actor.has_method("teleport") {
+        unsafe { actor.call("teleport", &[position.to_variant()]) };
+    }
+}
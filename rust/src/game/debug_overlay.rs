@@ -0,0 +1,101 @@
+use gdnative::prelude::*;
+use gdnative::api::{Engine, Label, OS};
+
+const GAME_PATH: &str = "/root/Game";
+const PLAYER_PATH: &str = "/root/Game/Player";
+// Seconds between every refresh of the text. Reading it on every frame would only make it unreadable
+const REFRESH_SECONDS: f64 = 0.25;
+
+#[derive(NativeClass)]
+#[inherit(Label)]
+#[derive(Debug)]
+/// A text on a corner of the screen with the live state of the player and the game, for development only.
+///
+/// It's hidden until the toggle key it's pressed, and it only exists on the debug builds, so the exported game
+/// never shows it. While it's visible, the text gets refreshed a few times per second, and not on every frame.
+///
+/// It also listens for the rest of the debug commands, like reloading the dialogues
+pub struct DebugOverlay {
+    // The input action that shows and hides the overlay
+    #[property(default = "Debug_Overlay")]
+    toggle_action: String,
+    // The input action that reads again every dialogues file, to try the texts while editing them
+    #[property(default = "Debug_Reload_Dialogues")]
+    reload_dialogues_action: String,
+
+    // Seconds until the next refresh of the text
+    refresh_in: f64,
+}
+
+#[gdnative::methods]
+impl DebugOverlay {
+    fn new(_owner: &Label) -> Self {
+        Self {
+            toggle_action: "Debug_Overlay".to_string(),
+            reload_dialogues_action: "Debug_Reload_Dialogues".to_string(),
+            refresh_in: 0.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Label) {
+        owner.set_visible(false);
+        // The release builds don't even listen for the toggle key
+        owner.set_process(OS::godot_singleton().is_debug_build());
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &Label, delta: f64) {
+        if Input::is_action_just_pressed(Input::godot_singleton(), self.toggle_action.as_str()) {
+            let visible = !owner.is_visible();
+            owner.set_visible(visible);
+            self.refresh_in = 0.0;
+        }
+        if Input::is_action_just_pressed(Input::godot_singleton(), self.reload_dialogues_action.as_str()) {
+            if let Some(game) = unsafe { owner.get_node(GAME_PATH) } {
+                unsafe { game.assume_safe().call("reload_dialogues", &[]) };
+            }
+        }
+        if !owner.is_visible() {
+            return;
+        }
+
+        self.refresh_in -= delta;
+        if self.refresh_in <= 0.0 {
+            self.refresh_in = REFRESH_SECONDS;
+            owner.set_text(self.get_debug_text(owner));
+        }
+    }
+
+    /// Reads everything straight from the player and the Game nodes. Anything that it's missing on the scene
+    /// shows as a dash
+    fn get_debug_text(&self, owner: &Label) -> String {
+        let player = unsafe { owner.get_node_as::<Node2D>(PLAYER_PATH) };
+        let game = unsafe { owner.get_node(GAME_PATH) }.map(|game| unsafe { game.assume_safe() });
+
+        let position = player
+            .map(|player| {
+                let position = player.global_position();
+                format!("({:.1}, {:.1})", position.x, position.y)
+            })
+            .unwrap_or_else(|| "-".to_string());
+        let player_status = player
+            .map(|player| unsafe { player.call("get_player_status", &[]) }.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let facing = player
+            .map(|player| unsafe { player.call("get_facing_direction", &[]) }.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let scene = game
+            .and_then(|game| unsafe { game.call("get_world_scene", &[]) }.try_to_object::<Node>())
+            .map(|scene| unsafe { scene.assume_safe() }.name().to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let game_state = game
+            .map(|game| unsafe { game.call("get_game_state", &[]) }.to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        format!(
+            "FPS: {}\nEscena: {}\nEstado: {}\nPosición: {}\nJugador: {}\nDirección: {}",
+            Engine::godot_singleton().get_frames_per_second(), scene, game_state, position, player_status, facing
+        )
+    }
+}
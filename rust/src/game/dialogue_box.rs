@@ -2,12 +2,19 @@ use crate::game::code_abstractions::{
     signals::RegisterSignal,
     node_operations::NodeReferences
 };
+use crate::game::menu::menu_cursor::MenuCursor;
 
 use gdnative::api::AnimatedSprite;
 use gdnative::prelude::*;
 use gdnative::{api::RichTextLabel, api::NinePatchRect};
 
 const DIALOGUE_SPEED: f64 = 0.05;
+// Seconds that `Interact` has to be held before it fast-forwards the text, so a tap it's never taken as a hold
+const HOLD_THRESHOLD: f64 = 0.3;
+// How many times faster the text gets printed while `Interact` it's held
+const FAST_FORWARD_MULTIPLIER: f64 = 3.0;
+// Seconds between the pages that get skipped by holding `Interact`, so the player can still glance at them
+const FAST_ADVANCE_DELAY: f64 = 0.25;
 
 /// Enum that represents the posible states of the Dialogue Box.
 ///
@@ -44,6 +51,10 @@ pub struct DialogueBox {
     decision_selected: i32,
     number_of_decisions: i32,
     selection_enabled: bool,
+    // Moves through the elections, repeating while the key it's held like on the menus
+    election_cursor: MenuCursor,
+    // The option chosen by the player on the last dialogue (1 for the first one), or 0 if it had no elections
+    last_decision: i32,
 
     current_char: i32,
     current_line: i32,
@@ -54,6 +65,14 @@ pub struct DialogueBox {
     player_ref: Option<Ref<Node>>,
     dialogue_box_status: DialogueBoxStatus,
     times_pressed_interact: i32,
+    // `Interact` was tapped since the last print tick
+    interact_tapped: bool,
+    // Seconds that `Interact` has been held down without releasing it
+    interact_held_time: f64,
+    // The key that opened the dialogue could still be down, so nothing it's read until it gets released
+    waiting_interact_release: bool,
+    // Seconds until holding `Interact` can skip another page
+    fast_advance_cooldown: f64,
     // Gets an input singleton to point to the input events
     input: &'static Input
 }
@@ -102,6 +121,8 @@ impl DialogueBox {
             decision_selected: 1,
             number_of_decisions: 0,
             selection_enabled: false,
+            election_cursor: MenuCursor::vertical(),
+            last_decision: 0,
 
             current_char: 0,
             current_line: 1,
@@ -112,6 +133,10 @@ impl DialogueBox {
             player_ref: None,
             dialogue_box_status: DialogueBoxStatus::Inactive,
             times_pressed_interact: 0,
+            interact_tapped: false,
+            interact_held_time: 0.0,
+            waiting_interact_release: false,
+            fast_advance_cooldown: 0.0,
             input: Input::godot_singleton(),
         } 
     }
@@ -149,14 +174,16 @@ impl DialogueBox {
         // If the `printing` flag is true means that the `_print_dialogue` method was triggered by a signal binding
         if self.printing {
             self.timer += _delta; // Uses a timer as a "time handler", using delta to set it's value
+            self.read_interact_input(_delta);
 
             // Checks if there are elections in the current NPC dialogue
             if self.dialogue_election.as_ref().unwrap().get_number_of_decisions() > 0 && self.selection_enabled {
-                self.enable_elections_on_interactive_dialogue(self.selection_enabled);
+                self.enable_elections_on_interactive_dialogue(self.selection_enabled, _delta);
             }
             
-            // Constant there acts algo as a barrier to trigger the print event
-            if self.timer > DIALOGUE_SPEED {
+            // Constant there acts algo as a barrier to trigger the print event. Holding `Interact` lowers it
+            let dialogue_speed = if self.is_fast_forwarding() { DIALOGUE_SPEED / FAST_FORWARD_MULTIPLIER } else { DIALOGUE_SPEED };
+            if self.timer > dialogue_speed {
                 // Communicates to the potencial receivers that the dialogue box is currently visible on the screen
                 _owner.emit_signal("dialogue_box_active", &[Variant::from_godot_string(
                     &GodotString::from_str("on_dialogue"))]);
@@ -176,20 +203,23 @@ impl DialogueBox {
                 if self.current_char < self.text_to_print.len() as i32 {
 
                     self.selection_enabled = false;
-                    self.enable_elections_on_interactive_dialogue(self.selection_enabled);
+                    self.enable_elections_on_interactive_dialogue(self.selection_enabled, 0.0);
 
                     if self.current_line < self.current_line_bound {
                         self.printer(&dialogue_text_label);
                     } else {
-                        self.play_arrow_animation(_owner, &dialogue_text_label);
+                        let advance = self.take_advance(true);
+                        self.play_arrow_animation(_owner, &dialogue_text_label, advance);
                     }
 
                 } else if self.current_char == self.text_to_print.len() as i32 {
                     if self.number_of_decisions > 0 {
                         self.selection_enabled = true;
                     }    
-                    self.play_arrow_animation(_owner, &dialogue_text_label);
-                    if Input::is_action_pressed(&self.input, "Interact") {
+                    // An election it's only chosen with a real press, never by holding the key through the text
+                    let advance = self.take_advance(self.number_of_decisions == 0);
+                    self.play_arrow_animation(_owner, &dialogue_text_label, advance);
+                    if advance {
                         self.current_char += 1;
                     }
                 // but if all characters are printed, wait for the player that with one more interaction button press,
@@ -210,6 +240,7 @@ impl DialogueBox {
                             // ! Index 2: Negative response / response that maps the selection nº 2
                             // ! Index 3 and so forth...: Next response / response that maps the selection nº 3 and so forth...
                             self.current_text_container_position = self.decision_selected;
+                            self.last_decision = self.decision_selected;
                                 
                             self.text_to_print = dialogue_election.get_text_to_print()[self.current_text_container_position as usize].to_owned();
 
@@ -217,23 +248,63 @@ impl DialogueBox {
                             self.printer(&dialogue_text_label);
 
                         } else {
-                            self.play_arrow_animation(_owner, &dialogue_text_label);
-                            self.finish_dialogue(_owner, &dialogue_text_label);
+                            let advance = self.take_advance(true);
+                            self.play_arrow_animation(_owner, &dialogue_text_label, advance);
+                            self.finish_dialogue(_owner, &dialogue_text_label, advance);
                         }
                     }
                 }
+                // A tap only counts for the tick that follows it, so it can't be kept until an election shows up
+                self.interact_tapped = false;
             }
         }
     }
 
-    fn enable_elections_on_interactive_dialogue(&mut self, visible: bool) {
+    /// Tells apart the taps of `Interact` from holding it down. The taps are kept until the next print tick
+    fn read_interact_input(&mut self, delta: f64) {
+        let pressed = Input::is_action_pressed(&self.input, "Interact");
+        if self.waiting_interact_release {
+            if pressed {
+                return;
+            }
+            self.waiting_interact_release = false;
+        }
+
+        if Input::is_action_just_pressed(&self.input, "Interact") {
+            self.interact_tapped = true;
+        }
+        self.interact_held_time = if pressed { self.interact_held_time + delta } else { 0.0 };
+        self.fast_advance_cooldown = (self.fast_advance_cooldown - delta).max(0.0);
+    }
+
+    fn is_fast_forwarding(&self) -> bool {
+        self.interact_held_time >= HOLD_THRESHOLD
+    }
+
+    /// If the dialogue should go on: the player tapped `Interact`, or he's holding it and it's allowed to skip
+    /// another page already
+    fn take_advance(&mut self, allow_hold: bool) -> bool {
+        let advance = if self.interact_tapped {
+            true
+        } else {
+            allow_hold && self.is_fast_forwarding() && self.fast_advance_cooldown <= 0.0
+        };
+        if advance {
+            self.interact_tapped = false;
+            self.fast_advance_cooldown = FAST_ADVANCE_DELAY;
+        }
+        advance
+    }
+
+    fn enable_elections_on_interactive_dialogue(&mut self, visible: bool, delta: f64) {
         // Pop up election menu
         let election_menu = self.election_menu.unwrap();
         election_menu.set_visible(visible);
         let menu_selector_arrow = self.menu_selector_arrow.unwrap();
         let n_av_decisions = self.dialogue_election.as_ref().unwrap().get_availiable_decisions().len() as f32;
+        let cursor_move = self.election_cursor.poll(self.input, delta);
 
-        if Input::is_action_just_pressed(&self.input, "Menu_Up") && self.current_char == self.text_to_print.len() as i32 {
+        if cursor_move < 0 && self.current_char == self.text_to_print.len() as i32 {
             if self.decision_selected == 1 {
                 self.decision_selected = n_av_decisions as i32;
                 menu_selector_arrow.set_position(
@@ -251,7 +322,7 @@ impl DialogueBox {
             }
         }
 
-        if Input::is_action_just_pressed(&self.input, "Menu_Down") && self.current_char == self.text_to_print.len() as i32 {
+        if cursor_move > 0 && self.current_char == self.text_to_print.len() as i32 {
             
             if self.decision_selected == n_av_decisions as i32{
                 self.decision_selected = 1;
@@ -272,7 +343,7 @@ impl DialogueBox {
 
     }
 
-    fn play_arrow_animation(&mut self, owner: &NinePatchRect, dialogue_text_label: &TRef<RichTextLabel>) {
+    fn play_arrow_animation(&mut self, owner: &NinePatchRect, dialogue_text_label: &TRef<RichTextLabel>, advance: bool) {
 
         let arrow_sprite = unsafe { owner.get_node("Cursor/Arrow")
             .unwrap().assume_safe().cast::<AnimatedSprite>().unwrap() };
@@ -280,7 +351,7 @@ impl DialogueBox {
         arrow_sprite.set_visible(true);
         arrow_sprite.play("", false);
 
-        if Input::is_action_pressed(&self.input, "Interact") {
+        if advance {
             dialogue_text_label.scroll_to_line(self.current_line as i64 - 1);
             self.current_line_bound += 1;
             arrow_sprite.stop();
@@ -305,11 +376,11 @@ impl DialogueBox {
     }
 
     // Method for end the dialogue when there's no more text to print
-    fn finish_dialogue(&mut self, owner: &NinePatchRect, dialogue_text_label: &TRef<RichTextLabel>) {
+    fn finish_dialogue(&mut self, owner: &NinePatchRect, dialogue_text_label: &TRef<RichTextLabel>, advance: bool) {
                 
         // self.printing = false;
 
-        if Input::is_action_pressed(&self.input, "Interact") {
+        if advance {
             self.times_pressed_interact += 1;
             
             // Just checks if the player pressed the interact button **when all the characters are already printed**.
@@ -320,7 +391,7 @@ impl DialogueBox {
                 self.set_empty_dialogue_box(&dialogue_text_label);
                 // Notifies all listeners the status of the DialogueBox
                 owner.emit_signal("dialogue_box_inactive", &[Variant::from_godot_string(
-                    &GodotString::from_str(""))]);
+                    &GodotString::from_str("end_interaction"))]);
                 // Restart the interact when all char printed to zero for the next time
                 self.times_pressed_interact = 0;
                 // Saves the current status of the DialogueBox for data management
@@ -378,8 +449,22 @@ impl DialogueBox {
 
             // Counter
             self.number_of_decisions = dialogue_election.get_number_of_decisions();
+            self.last_decision = 0;
+
+            // The press that opened the dialogue (or closed the previous one) mustn't skip it
+            self.interact_tapped = false;
+            self.interact_held_time = 0.0;
+            self.fast_advance_cooldown = 0.0;
+            self.waiting_interact_release = true;
         };
     }
+
+    #[export]
+    /// The option that the player chose on the last dialogue with elections, so the caller can react to it
+    /// once the dialogue box gets closed. 1 for the first option, or 0 when the dialogue had no elections
+    fn get_last_decision(&self, _owner: &NinePatchRect) -> i32 {
+        self.last_decision
+    }
 }
 
 
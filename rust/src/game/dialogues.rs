@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// A dialogues JSON file. The entries are kept raw until every one of them it's checked, so a wrong one doesn't
+/// spoil the rest of the file
+#[derive(Debug, Default, Deserialize)]
+struct DialogueFile {
+    #[serde(default)]
+    dialogues: HashMap<String, Value>,
+}
+
+/// The texts of the world (signs, NPCs...) by dialogue id, read from every dialogues JSON file once, when the
+/// game starts, so showing a dialogue never touches the disk.
+///
+/// Every dialogue it's a list of lines, and the Dialogue Box shows them one after the other
+#[derive(Debug, Clone, Default)]
+pub struct DialogueRegistry {
+    dialogues: HashMap<String, Vec<String>>,
+}
+
+impl DialogueRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the dialogues of a JSON file. A dialogue with an id already loaded replaces the older one.
+    ///
+    /// Returns what was wrong, as `file` or `file/dialogue id`. Anything wrong it's skipped, but the rest of the
+    /// dialogues of the file are still added
+    pub fn load_json(&mut self, file_name: &str, json: &str) -> Vec<String> {
+        let dialogue_file: DialogueFile = match serde_json::from_str(json) {
+            Ok(dialogue_file) => dialogue_file,
+            Err(err) => return vec![format!("{}: {}", file_name, err)]
+        };
+
+        let mut errors = Vec::new();
+        for (dialogue_id, lines) in dialogue_file.dialogues {
+            match serde_json::from_value::<Vec<String>>(lines) {
+                Ok(lines) => { self.dialogues.insert(dialogue_id, lines); },
+                Err(err) => errors.push(format!("{}/{}: {}", file_name, dialogue_id, err))
+            }
+        }
+        errors
+    }
+
+    pub fn get_dialogue(&self, dialogue_id: &str) -> Option<&[String]> {
+        self.dialogues.get(dialogue_id).map(Vec::as_slice)
+    }
+
+    /// The lines of the dialogue, or no lines at all when there's no dialogue with that id
+    pub fn get_dialogue_or_empty(&self, dialogue_id: &str) -> Vec<String> {
+        self.get_dialogue(dialogue_id).map(<[String]>::to_vec).unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.dialogues.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dialogues.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNS: &str = r#"{ "dialogues": {
+        "sign_pueblo_de_teo": ["Pueblo de Teo", "Un lugar tranquilo."],
+        "sign_ruta_1": ["Ruta 1"]
+    } }"#;
+
+    #[test]
+    fn a_dialogue_is_found_by_his_id() {
+        let mut dialogue_registry = DialogueRegistry::new();
+        assert!(dialogue_registry.load_json("signs.json", SIGNS).is_empty());
+
+        assert_eq!(dialogue_registry.len(), 2);
+        assert_eq!(dialogue_registry.get_dialogue("sign_pueblo_de_teo").unwrap(),
+            &["Pueblo de Teo".to_string(), "Un lugar tranquilo.".to_string()]);
+        assert_eq!(dialogue_registry.get_dialogue_or_empty("sign_ruta_1"), vec!["Ruta 1".to_string()]);
+    }
+
+    #[test]
+    fn an_unknown_dialogue_has_no_lines() {
+        let mut dialogue_registry = DialogueRegistry::new();
+        dialogue_registry.load_json("signs.json", SIGNS);
+        assert_eq!(dialogue_registry.get_dialogue("sign_ruta_2"), None);
+        assert!(dialogue_registry.get_dialogue_or_empty("sign_ruta_2").is_empty());
+    }
+
+    #[test]
+    fn a_wrong_dialogue_doesnt_spoil_the_rest_of_the_file() {
+        let mut dialogue_registry = DialogueRegistry::new();
+        let errors = dialogue_registry.load_json("npcs.json", r#"{ "dialogues": {
+            "npc_teo": ["Hola!"],
+            "npc_broken": "Not a list of lines"
+        } }"#);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("npcs.json/npc_broken: "), "{}", errors[0]);
+        assert!(dialogue_registry.get_dialogue("npc_teo").is_some());
+        assert!(dialogue_registry.get_dialogue("npc_broken").is_none());
+
+        let errors = dialogue_registry.load_json("garbage.json", "{ dialogues");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("garbage.json: "), "{}", errors[0]);
+    }
+
+    #[test]
+    fn a_later_file_replaces_the_repeated_ids() {
+        let mut dialogue_registry = DialogueRegistry::new();
+        dialogue_registry.load_json("signs.json", SIGNS);
+        dialogue_registry.load_json("patch.json", r#"{ "dialogues": { "sign_ruta_1": ["Ruta 1 - Norte"] } }"#);
+        assert_eq!(dialogue_registry.len(), 2);
+        assert_eq!(dialogue_registry.get_dialogue_or_empty("sign_ruta_1"), vec!["Ruta 1 - Norte".to_string()]);
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::code_abstractions::random::GameRng;
+use crate::utils::consts::in_game_constant;
+
+use super::time_of_day::DayPeriod;
+use super::weather::FieldWeather;
+
+/// A single Pokémon that can appear on an encounter zone, with his relative chance to appear
+/// and the range of levels that the wild Pokémon could have.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EncounterEntry {
+    pub species_id: i32,
+    pub weight: u32,
+    pub min_level: u8,
+    pub max_level: u8,
+    // The periods of the day when the Pokémon can appear. An empty list means that it appears at any time
+    #[serde(default)]
+    pub periods: Vec<DayPeriod>,
+}
+
+impl EncounterEntry {
+    pub fn appears_during(&self, period: DayPeriod) -> bool {
+        self.periods.is_empty() || self.periods.contains(&period)
+    }
+}
+
+/// Represents a zone of the map (a bunch of tall grass tiles, a cave...) and the wild Pokémon that lives there
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EncounterZone {
+    // Chance (0.0 - 1.0) of an encounter for every step that the player gives over the zone. It's not read from
+    // the JSON: it comes from the rates by area of `in_game_constant`, or from the balance config
+    #[serde(skip)]
+    pub encounter_rate: Option<f64>,
+    // Some zones (like the Cycling Road) allows wild battles while the player it's riding the bike
+    #[serde(default)]
+    pub allowed_on_bike: bool,
+    // Zones of water (surfing, fishing...), where the weather changes how many Pokémon appears
+    #[serde(default)]
+    pub water: bool,
+    pub entries: Vec<EncounterEntry>,
+}
+
+impl EncounterZone {
+    pub fn get_encounter_rate(&self) -> f64 {
+        self.encounter_rate.unwrap_or(in_game_constant::DEFAULT_ENCOUNTER_RATE)
+    }
+
+    /// Rolls the encounter chance for one step of the player over this zone.
+    ///
+    /// Only the Pokémon that appear during the given period of the day can be found, and the weather
+    /// of the zone could make the encounters more frequent.
+    ///
+    /// Returns Some(WildEncounter) with the wild Pokémon that appeared, or None when the player was lucky.
+    pub fn roll_encounter<R: GameRng>(&self, rng: &mut R, on_bike: bool, period: DayPeriod, weather: FieldWeather)
+        -> Option<WildEncounter>
+    {
+        if on_bike && !self.allowed_on_bike {
+            return None;
+        }
+        if !rng.chance(self.get_encounter_rate() * weather.encounter_rate_multiplier(self.water)) {
+            return None;
+        }
+
+        self.pick_wild_pokemon(rng, period)
+    }
+
+    /// A Pokémon got hooked while fishing on this zone, so there's always an encounter (unless the zone it's empty)
+    pub fn roll_fishing_encounter<R: GameRng>(&self, rng: &mut R, period: DayPeriod) -> Option<WildEncounter> {
+        self.pick_wild_pokemon(rng, period)
+    }
+
+    /// Chooses one of the entries of the zone that appear during the given period, respecting the weight of every one
+    fn pick_wild_pokemon<R: GameRng>(&self, rng: &mut R, period: DayPeriod) -> Option<WildEncounter> {
+        let available_entries: Vec<&EncounterEntry> = self.entries.iter()
+            .filter(|entry| entry.appears_during(period))
+            .collect();
+        let total_weight: u32 = available_entries.iter().map(|entry| entry.weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut roll = rng.next_in_range(0, total_weight);
+
+        for entry in available_entries {
+            if roll < entry.weight {
+                let level = if entry.min_level >= entry.max_level {
+                    entry.min_level
+                } else {
+                    rng.next_in_range(entry.min_level as u32, entry.max_level as u32 + 1) as u8
+                };
+                return Some(WildEncounter::new(entry.species_id, level));
+            }
+            roll -= entry.weight;
+        }
+        None
+    }
+}
+
+/// Holds all the encounter zones of a concrete map, identified by the zone id
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EncounterTable {
+    pub zones: HashMap<String, EncounterZone>,
+}
+
+impl EncounterTable {
+    pub fn get_zone(&self, zone_id: &str) -> Option<&EncounterZone> {
+        self.zones.get(zone_id)
+    }
+
+    /// Gives to every zone the rate tuned for his area, or the rate of the balance config for the areas without one
+    pub fn with_default_encounter_rate(mut self, encounter_rate: f64) -> Self {
+        for (zone_id, zone) in self.zones.iter_mut() {
+            zone.encounter_rate = Some(area_encounter_rate(zone_id).unwrap_or(encounter_rate));
+        }
+        self
+    }
+}
+
+/// The encounter rate tuned for the given area, if it has one
+pub fn area_encounter_rate(area_id: &str) -> Option<f64> {
+    in_game_constant::AREA_ENCOUNTER_RATES.iter()
+        .find(|(area, _)| *area == area_id)
+        .map(|(_, encounter_rate)| *encounter_rate)
+}
+
+/// The wild Pokémon that the player just found
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WildEncounter {
+    pub species_id: i32,
+    pub level: u8,
+}
+
+impl WildEncounter {
+    pub fn new(species_id: i32, level: u8) -> Self { Self { species_id, level } }
+}
+
+/// Decides if a new wild Pokémon it's shiny. One of every `odds` Pokémon is, so odds of 0 or 1 make all of them shiny
+pub fn roll_shiny<R: GameRng>(rng: &mut R, odds: u32) -> bool {
+    odds <= 1 || rng.next_in_range(0, odds) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::bag::SHINY_CHARM;
+    use crate::game::code_abstractions::random::SeededRng;
+    use crate::game::player::PlayerData;
+    use crate::game::weather::RAIN_WATER_ENCOUNTER_BOOST;
+
+    /// Always rolls the same number, so the chances either always pass or always fail
+    struct FixedRng(f64);
+
+    impl GameRng for FixedRng {
+        fn next_f64(&mut self) -> f64 { self.0 }
+        fn next_in_range(&mut self, low: u32, _high: u32) -> u32 { low }
+    }
+
+    fn table(json: &str) -> EncounterTable {
+        serde_json::from_str(json).unwrap()
+    }
+
+    const ZONES: &str = r#"{ "zones": {
+        "CampoDePruebas": { "encounter_rate": 0.9, "entries": [
+            { "species_id": 1, "weight": 1, "min_level": 3, "max_level": 3 } ] },
+        "Unknown": { "entries": [
+            { "species_id": 4, "weight": 1, "min_level": 5, "max_level": 5 } ] }
+    } }"#;
+
+    #[test]
+    fn the_rates_come_from_the_area_table_or_the_balance_config() {
+        let encounter_table = table(ZONES).with_default_encounter_rate(0.3);
+        // The JSON can't override the rate tuned for the area
+        assert_eq!(encounter_table.get_zone("CampoDePruebas").unwrap().get_encounter_rate(), 0.25);
+        assert_eq!(encounter_table.get_zone("Unknown").unwrap().get_encounter_rate(), 0.3);
+    }
+
+    #[test]
+    fn a_step_rolls_against_the_rate_of_the_zone() {
+        let zone = table(ZONES).with_default_encounter_rate(0.3).zones.remove("CampoDePruebas").unwrap();
+        assert_eq!(zone.roll_encounter(&mut FixedRng(0.2), false, DayPeriod::Day, FieldWeather::Clear),
+            Some(WildEncounter::new(1, 3)));
+        assert_eq!(zone.roll_encounter(&mut FixedRng(0.3), false, DayPeriod::Day, FieldWeather::Clear), None);
+    }
+
+    #[test]
+    fn the_bike_keeps_the_wild_pokemon_away() {
+        let zone = table(ZONES).with_default_encounter_rate(0.3).zones.remove("CampoDePruebas").unwrap();
+        assert_eq!(zone.roll_encounter(&mut FixedRng(0.0), true, DayPeriod::Day, FieldWeather::Clear), None);
+    }
+
+    #[test]
+    fn the_rain_only_boosts_the_water_zones() {
+        assert_eq!(FieldWeather::Rain.encounter_rate_multiplier(true), RAIN_WATER_ENCOUNTER_BOOST);
+        assert_eq!(FieldWeather::Rain.encounter_rate_multiplier(false), 1.0);
+        for weather in &[FieldWeather::Clear, FieldWeather::Sandstorm, FieldWeather::Snow] {
+            assert_eq!(weather.encounter_rate_multiplier(true), 1.0);
+            assert_eq!(weather.encounter_rate_multiplier(false), 1.0);
+        }
+    }
+
+    #[test]
+    fn the_rain_brings_more_pokemon_to_the_water() {
+        let mut zones = table(r#"{ "zones": {
+            "Lago": { "water": true, "entries": [ { "species_id": 129, "weight": 1, "min_level": 5, "max_level": 5 } ] },
+            "Prado": { "entries": [ { "species_id": 16, "weight": 1, "min_level": 5, "max_level": 5 } ] }
+        } }"#).with_default_encounter_rate(0.3).zones;
+        let (lake, meadow) = (zones.remove("Lago").unwrap(), zones.remove("Prado").unwrap());
+
+        // A roll over the base rate, but under the boosted one
+        let roll = 0.3 * RAIN_WATER_ENCOUNTER_BOOST - 0.05;
+        assert_eq!(lake.roll_encounter(&mut FixedRng(roll), false, DayPeriod::Day, FieldWeather::Clear), None);
+        assert_eq!(lake.roll_encounter(&mut FixedRng(roll), false, DayPeriod::Day, FieldWeather::Rain),
+            Some(WildEncounter::new(129, 5)));
+        assert_eq!(meadow.roll_encounter(&mut FixedRng(roll), false, DayPeriod::Day, FieldWeather::Rain), None);
+        assert_eq!(lake.roll_encounter(&mut FixedRng(roll), false, DayPeriod::Day, FieldWeather::Snow), None);
+    }
+
+    #[test]
+    fn the_shiny_charm_makes_the_shiny_pokemon_more_common() {
+        let mut player_data = PlayerData::new();
+        assert_eq!(player_data.get_shiny_odds(), in_game_constant::SHINY_ODDS);
+        player_data.get_bag_mut().add_item(SHINY_CHARM, 1);
+        assert_eq!(player_data.get_shiny_odds(), in_game_constant::SHINY_CHARM_ODDS);
+
+        const ROLLS: u32 = 200_000;
+        let mut rng = SeededRng::from_seed(333);
+        let mut shinies = |odds: u32| (0..ROLLS).filter(|_| roll_shiny(&mut rng, odds)).count() as u32;
+        let without_charm = shinies(in_game_constant::SHINY_ODDS);
+        let with_charm = shinies(in_game_constant::SHINY_CHARM_ODDS);
+
+        // Around 49 and 147 of them
+        assert!((20..80).contains(&without_charm), "{}", without_charm);
+        assert!((100..195).contains(&with_charm), "{}", with_charm);
+    }
+
+    #[test]
+    fn odds_of_one_make_every_pokemon_shiny() {
+        let mut rng = SeededRng::from_seed(1);
+        assert!((0..100).all(|_| roll_shiny(&mut rng, 1)));
+        assert!((0..100).all(|_| roll_shiny(&mut rng, 0)));
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Namespaces of the flag keys. Every key it's built as `namespace:id`, like `trainer_defeated:Youngster1`,
+/// so two different kinds of events never collide even if they share the same id
+pub mod namespaces {
+    pub const TRAINER_DEFEATED: &str = "trainer_defeated";
+    pub const ITEM_COLLECTED: &str = "item_collected";
+    pub const DOOR_OPENED: &str = "door_opened";
+    pub const QUEST: &str = "quest";
+    pub const CUTSCENE_SEEN: &str = "cutscene_seen";
+}
+
+/// Builds the key of a flag inside the given namespace
+pub fn flag_key(namespace: &str, id: &str) -> String {
+    format!("{}:{}", namespace, id)
+}
+
+/// The persistent booleans that remembers the one-time events of the game (defeated trainers, collected items,
+/// opened doors, quest steps...). A flag that was never set it's just false
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventFlags {
+    flags: HashMap<String, bool>,
+}
+
+impl EventFlags {
+    pub fn new() -> Self {
+        Self { flags: HashMap::new() }
+    }
+
+    pub fn get_flag(&self, key: &str) -> bool {
+        *self.flags.get(key).unwrap_or(&false)
+    }
+
+    pub fn set_flag(&mut self, key: &str, value: bool) {
+        self.flags.insert(key.to_string(), value);
+    }
+
+    pub fn get_namespaced_flag(&self, namespace: &str, id: &str) -> bool {
+        self.get_flag(&flag_key(namespace, id))
+    }
+
+    pub fn set_namespaced_flag(&mut self, namespace: &str, id: &str, value: bool) {
+        self.set_flag(&flag_key(namespace, id), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::PlayerData;
+
+    #[test]
+    fn a_flag_never_set_is_false() {
+        let event_flags = EventFlags::new();
+        assert!(!event_flags.get_flag("unknown"));
+        assert!(!event_flags.get_namespaced_flag(namespaces::TRAINER_DEFEATED, "Youngster1"));
+    }
+
+    #[test]
+    fn the_namespaces_keep_the_same_id_apart() {
+        let mut event_flags = EventFlags::new();
+        event_flags.set_namespaced_flag(namespaces::TRAINER_DEFEATED, "Ruta1", true);
+
+        assert!(event_flags.get_namespaced_flag(namespaces::TRAINER_DEFEATED, "Ruta1"));
+        assert!(event_flags.get_flag("trainer_defeated:Ruta1"));
+        assert!(!event_flags.get_namespaced_flag(namespaces::ITEM_COLLECTED, "Ruta1"));
+        assert!(!event_flags.get_flag("Ruta1"));
+
+        event_flags.set_namespaced_flag(namespaces::TRAINER_DEFEATED, "Ruta1", false);
+        assert!(!event_flags.get_namespaced_flag(namespaces::TRAINER_DEFEATED, "Ruta1"));
+    }
+
+    #[test]
+    fn the_flags_survive_a_save_and_load() {
+        let mut player_data = PlayerData::new();
+        player_data.set_trainer_defeated("Youngster1");
+        player_data.set_item_ball_collected("Ruta1Pocion");
+        player_data.set_flag(&flag_key(namespaces::DOOR_OPENED, "Laboratorio"), true);
+
+        let save = serde_json::to_string(&player_data).unwrap();
+        let loaded: PlayerData = serde_json::from_str(&save).unwrap();
+
+        assert!(loaded.is_trainer_defeated("Youngster1"));
+        assert!(!loaded.is_trainer_defeated("Youngster2"));
+        assert!(loaded.is_item_ball_collected("Ruta1Pocion"));
+        assert!(loaded.get_flag("door_opened:Laboratorio"));
+    }
+}
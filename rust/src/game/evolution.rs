@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use super::pokemon::Pokemon;
+
+/// What a Pokémon needs to evolve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EvolutionCondition {
+    // Reaching the given level
+    Level { level: u8 },
+    // Leveling up while holding the given item
+    HeldItem { item_id: String },
+}
+
+impl EvolutionCondition {
+    pub fn is_met_by(&self, pokemon: &Pokemon) -> bool {
+        match self {
+            EvolutionCondition::Level { level } => pokemon.get_level() >= *level,
+            EvolutionCondition::HeldItem { item_id } => pokemon.get_held_item() == Some(item_id.as_str())
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::game::code_abstractions::random::GameRng;
+
+use super::bag::{GOOD_ROD, OLD_ROD, SUPER_ROD};
+
+/// The shortest and the longest wait (in seconds) until a Pokémon bites the hook
+pub const MIN_BITE_WAIT: f64 = 1.0;
+pub const MAX_BITE_WAIT: f64 = 4.0;
+/// The time (in seconds) that the player has to press `Interact` once a Pokémon bites, before it escapes
+pub const BITE_WINDOW: f64 = 0.6;
+
+/// The fishing rods, from the worst to the best one. Better rods get more bites, and reach other Pokémon
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rod {
+    Old,
+    Good,
+    Super
+}
+
+impl Rod {
+    pub const ALL: [Rod; 3] = [Rod::Old, Rod::Good, Rod::Super];
+
+    pub fn from_item_id(item_id: &str) -> Option<Rod> {
+        Rod::ALL.iter().copied().find(|rod| rod.to_item_id() == item_id)
+    }
+
+    pub fn to_item_id(&self) -> &'static str {
+        match self {
+            Rod::Old => OLD_ROD,
+            Rod::Good => GOOD_ROD,
+            Rod::Super => SUPER_ROD
+        }
+    }
+
+    /// The chance (0.0 - 1.0) that something bites the hook every time the player fishes
+    pub fn bite_chance(&self) -> f64 {
+        match self {
+            Rod::Old => 0.5,
+            Rod::Good => 0.7,
+            Rod::Super => 0.9
+        }
+    }
+
+    /// Every rod has his own pool of Pokémon on every fishing zone, stored on the encounter table
+    /// as `<zone id>/<rod item id>`
+    pub fn fishing_zone_id(&self, zone_id: &str) -> String {
+        format!("{}/{}", zone_id, self.to_item_id())
+    }
+}
+
+/// Throws the hook. Returns the seconds until a Pokémon bites, or None when nothing is going to bite
+pub fn roll_bite<R: GameRng>(rng: &mut R, rod: Rod) -> Option<f64> {
+    if rng.chance(rod.bite_chance()) {
+        Some(MIN_BITE_WAIT + rng.next_f64() * (MAX_BITE_WAIT - MIN_BITE_WAIT))
+    } else {
+        None
+    }
+}
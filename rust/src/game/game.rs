@@ -1,21 +1,66 @@
+use std::ffi::OsStr;
 use std::fmt::Display;
+use std::path::Path;
 
 use gdnative::{api::CanvasModulate, prelude::*};
-use gdnative::api::{AnimationPlayer, Particles2D};
+use gdnative::api::{OS, Particles2D};
 use gdnative::api::{HTTPClient, HTTPRequest};
 
 use serde::{Deserialize, Serialize};
 
-use crate::utils::{consts::game_consts, networking, utils};
-use crate::game::player::{PlayerData, PlayerDirection};
+use crate::utils::{consts::{game_consts, in_game_constant, scenes}, networking, utils};
+use crate::game::player::{PlayerData, PlayerDirection, PlayerStatus, RespawnPoint};
+use crate::game::encounter::{self, EncounterTable, WildEncounter};
+use crate::game::fishing::{self, Rod};
+use crate::game::pokemon::{validate_nickname, LearnMoveResult, OverworldPoisonResult, Pokemon, PokemonMove, StatSpread};
+use crate::game::battle::{capture, rewards};
+use crate::game::battle::hp_events::BattleSide;
+use crate::game::battle::turn::{self, Battle, BattleAction, BattleError, BattleEvent, BattleState};
+use crate::game::box_storage::PokemonDestination;
+use crate::game::bag::{get_item_name, ItemCategory, EXP_SHARE, RUNNING_SHOES};
+use crate::game::battle::status_condition::OVERWORLD_POISON_STEPS;
+use crate::game::species::SpeciesTable;
+use crate::game::berries;
+use crate::game::shop::{self, ShopConfig, ShopError};
+use crate::game::trainer::TrainerTable;
+use crate::game::quests::{self, quest_events, QuestState, QuestTable};
+use crate::game::dialogues::DialogueRegistry;
+use crate::game::balance::BalanceConfig;
+use crate::game::time_of_day::{DayPeriod, TimeOfDay};
+use crate::game::game_state::GameState;
+use crate::game::weather::FieldWeather;
+use crate::game::battle::type_chart::Type;
+use crate::game_client::session;
 
 use chrono::{Duration, NaiveTime};
 
 use super::code_abstractions::database::Database;
+use super::code_abstractions::random::SeededRng;
+use super::code_abstractions::signals::{connect_oneshot, RegisterSignal};
 use super::city::{GameCity, City, CityWeather};
 
+// The `_process` calls counter wraps to zero after this many frames, so it never overflows in a long session.
+// It has to be a multiple of every throttling interval, so none of them skips a beat when the counter wraps
+const PROCESS_COUNTER_WRAP: i32 = 1000;
+// Every how many frames the "still waiting" messages are printed while the external data it's loading
+const WAITING_LOG_INTERVAL: i32 = 10;
+// Every how many frames the Game checks if it's time to ask the backend for the weather again
+const WEATHER_CHECK_INTERVAL: i32 = 100;
+
+// Where the Dialogue Box lives, relative to the Game node
+const DIALOGUE_BOX_PATH: &str = "Player/Camera2D/CanvasLayer/DialogueBox";
+const PLAYER_ANIMATION_PATH: &str = "Player/PlayerAnimation";
+// The sound effect of the MusicManager played when the poison hurts the party while walking
+const POISON_STEP_SOUND: &str = "poison_step";
+
+/// The next value of the `_process` calls counter, back to zero once it reaches `PROCESS_COUNTER_WRAP`
+fn next_process_count(number_of_process: i32) -> i32 {
+    (number_of_process + 1) % PROCESS_COUNTER_WRAP
+}
+
 #[derive(NativeClass)]
 #[inherit(Node2D)]
+#[register_with(Self::register_signal)]
 #[derive(Debug, Serialize, Deserialize)]
 #[derive(Clone)]
 pub struct Game {
@@ -40,9 +85,20 @@ pub struct Game {
     received_signals: i32,
     total_registered_signals: i32,
     number_of_process: i32,
+    // Seconds of play since the last autosave, and if the save in course it's an autosave
+    #[serde(skip)]
+    autosave_elapsed: f64,
+    #[serde(skip)]
+    autosaving: bool,
 
     // Current Scene Type
     current_scene_type: CurrentSceneType,
+    // What the whole game it's doing right now. Only on the overworld the player can move
+    #[serde(skip)]
+    game_state: GameState,
+    // The account whose progress it's being played. None when the game runs without logging in
+    #[serde(skip)]
+    logged_in_username: Option<String>,
     // CurrentScenePath
     current_scene_path: String,
 
@@ -61,15 +117,95 @@ pub struct Game {
     #[serde(skip)]
     database: Option<TRef<'static, Node>>,
 
+    // The screenshot taken on the last save, so the load screen can show a preview of the saved game
+    #[serde(default)]
+    save_thumbnail: String,
+
     // The current real time in GTM + 1. When game it's saved, stores the time when game has succesfully saved.
     current_time: NaiveTime,
     // Tracks the current weather in the place that the player is
     #[serde(skip)]
     current_weather: Weather,
+    // The weather with gameplay effects of the zone where the player is. Persisted, unlike the real one
+    #[serde(default)]
+    field_weather: FieldWeather,
+    // The in-game clock, that drives the tint of the world and the wild Pokémon that can be found
+    #[serde(default)]
+    time_of_day: TimeOfDay,
+    // Real seconds that lasts an hour of the in-game clock
+    #[serde(skip)]
+    #[property(default = 60.0)]
+    seconds_per_game_hour: f64,
     
     //Flag for control when all external data are fully loaded into `game_external_data: GameExternalData` object
     #[serde(skip)]
     full_data_retrieved: bool,
+
+    // The wild Pokémon zones of the current map
+    #[serde(skip)]
+    encounter_table: EncounterTable,
+    // The Pokémon that the player it's fighting: the wild one, or the one that the trainer sent out
+    #[serde(skip)]
+    opponent_pokemon: Option<Pokemon>,
+    // The trainer that it's challenging the player, and the Pokémon of his team still waiting to go out
+    #[serde(skip)]
+    trainer_battle: Option<String>,
+    #[serde(skip)]
+    trainer_team: Vec<Pokemon>,
+    // Counts the steps of the player, so the poison can hurt the party every few steps
+    #[serde(skip)]
+    steps_walked: u32,
+    // Every random roll of the game logic goes through it, seeded with the time when the game starts
+    #[serde(skip)]
+    rng: SeededRng,
+    // The battle scene, placed over the world while the battle lasts
+    #[serde(skip)]
+    battle_scene: Option<Ref<Node>>,
+    // The party slot of the Pokémon that it's fighting
+    #[serde(skip)]
+    battle_party_slot: usize,
+    // The turns of the current battle, against a wild Pokémon or a trainer
+    #[serde(skip)]
+    battle: Option<Battle>,
+    // The party slots of the Pokémon that were sent out on the current battle, the ones that share the experience
+    #[serde(skip)]
+    battle_participants: Vec<usize>,
+    // Where the Pokémon caught on the current battle went, while the player it's choosing his nickname
+    #[serde(skip)]
+    caught_pokemon: Option<PokemonDestination>,
+    // The repel that just stopped working, while the player it's asked if he wants to use another one
+    #[serde(skip)]
+    worn_off_repel: Option<String>,
+    // The base stats, the learnset and the evolutions of every Pokémon specie
+    #[serde(skip)]
+    species_table: SpeciesTable,
+    // The party slots of the Pokémon that leveled up on the last battle and the evolution that it's currently
+    // being played (party slot and the specie that it evolves into)
+    #[serde(skip)]
+    leveled_up_slots: Vec<usize>,
+    #[serde(skip)]
+    current_evolution: Option<(usize, i32)>,
+    // The moves reached by leveling up on the last battle that the Pokémon still have to learn, and the one
+    // that it's waiting for the player to choose the move to forget (party slot and the new move)
+    #[serde(skip)]
+    pending_moves: Vec<(usize, PokemonMove)>,
+    #[serde(skip)]
+    current_move_learning: Option<(usize, PokemonMove)>,
+    // The items that sells every shop
+    #[serde(skip)]
+    shop_config: ShopConfig,
+    // The team and the prize of every trainer
+    #[serde(skip)]
+    trainer_table: TrainerTable,
+    // The texts of the signs and the rest of the dialogues, by id
+    #[serde(skip)]
+    dialogue_registry: DialogueRegistry,
+    // Every quest of the game, with his objectives
+    #[serde(skip)]
+    quest_table: QuestTable,
+    // The gameplay tuning values
+    #[serde(skip)]
+    balance_config: BalanceConfig,
     
     // Binding to the Input singleton
     #[serde(skip)]
@@ -80,10 +216,368 @@ pub struct Game {
 // Impl of database will use the "default implementation of the trait methods"
 impl Database for Game {}
 
-#[gdnative::methods]
-impl Game {
-    
-    fn new(_owner: &Node2D) -> Self {
+impl RegisterSignal<Self> for Game {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // Notifies every shake of the Poké Ball, and if finally the wild Pokémon gets caught or breaks free
+        builder.add_signal( Signal {
+            name: "pokeball_shake",
+            args: &[ SignalArgument {
+                name: "shake",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        builder.add_signal( Signal {
+            name: "pokemon_caught",
+            args: &[],
+        });
+        builder.add_signal( Signal {
+            name: "pokemon_broke_free",
+            args: &[],
+        });
+        // The player closed the nickname screen of the caught Pokémon, with the name that the Pokémon ended having
+        builder.add_signal( Signal {
+            name: "caught_pokemon_named",
+            args: &[ SignalArgument {
+                name: "name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The money of the player changed (shopping, trainer prizes, blackouts...), with the new amount
+        builder.add_signal( Signal {
+            name: "money_changed",
+            args: &[ SignalArgument {
+                name: "money",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The prize paid by a defeated trainer, and the experience that every Pokémon of the party earned
+        builder.add_signal( Signal {
+            name: "prize_money_awarded",
+            args: &[ SignalArgument {
+                name: "money",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        builder.add_signal( Signal {
+            name: "experience_gained",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "experience",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The whole party fainted, so the player goes back to the last Pokémon Center, losing some money
+        builder.add_signal( Signal {
+            name: "blacked_out",
+            args: &[ SignalArgument {
+                name: "money_lost",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // Notifies if the player got away safely from the battle, or if he couldn't (trainers never let him go)
+        builder.add_signal( Signal {
+            name: "escaped_from_battle",
+            args: &[],
+        });
+        builder.add_signal( Signal {
+            name: "escape_failed",
+            args: &[ SignalArgument {
+                name: "trainer_battle",
+                default: Variant::from_bool(false),
+                export_info: ExportInfo::new(VariantType::Bool),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The game got saved by itself, so the UI can show that it's saving
+        builder.add_signal( Signal {
+            name: "autosaved",
+            args: &[],
+        });
+
+        // A new period of the in-game day begins (Morning, Day, Evening or Night), so NPCs, shops... can react to it
+        builder.add_signal( Signal {
+            name: "day_period_changed",
+            args: &[ SignalArgument {
+                name: "period",
+                default: Variant::from_str("Day"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The game went from one state to another (Overworld, Dialogue, Menu, Battle, Cutscene or Transition)
+        builder.add_signal( Signal {
+            name: "game_state_changed",
+            args: &[ SignalArgument {
+                name: "previous",
+                default: Variant::from_str(GameState::default().to_state_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "current",
+                default: Variant::from_str(GameState::default().to_state_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The HP of a Pokémon on the battle changed, so his health bar can drain up to the new value
+        builder.add_signal( Signal {
+            name: "hp_changed",
+            args: &[ SignalArgument {
+                name: "side",
+                default: Variant::from_str(BattleSide::Player.to_side_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "current_hp",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "max_hp",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        builder.add_signal( Signal {
+            name: "pokemon_fainted",
+            args: &[ SignalArgument {
+                name: "side",
+                default: Variant::from_str(BattleSide::Player.to_side_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // A Pokémon of the battle used a move, with how effective it was against the other one
+        builder.add_signal( Signal {
+            name: "move_used",
+            args: &[ SignalArgument {
+                name: "side",
+                default: Variant::from_str(BattleSide::Player.to_side_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "move_name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "effectiveness",
+                default: Variant::from_f64(1.0),
+                export_info: ExportInfo::new(VariantType::F64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        // The paralysis didn't let the Pokémon of the given side move on this turn
+        builder.add_signal( Signal {
+            name: "turn_skipped",
+            args: &[ SignalArgument {
+                name: "side",
+                default: Variant::from_str(BattleSide::Player.to_side_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        // The fainted Pokémon of the trainer got replaced by the next one of his team
+        builder.add_signal( Signal {
+            name: "opponent_sent_out",
+            args: &[ SignalArgument {
+                name: "species_id",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "level",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        // The battle went to another step (SelectingAction, ExecutingTurn, WaitingForInput, Victory, Defeat or Fled)
+        builder.add_signal( Signal {
+            name: "battle_state_changed",
+            args: &[ SignalArgument {
+                name: "state",
+                default: Variant::from_str("SelectingAction"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The weather of the zone where the player is changed, so the overlay can draw the new one
+        builder.add_signal( Signal {
+            name: "weather_changed",
+            args: &[ SignalArgument {
+                name: "weather",
+                default: Variant::from_str("Clear"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // Some poisoned Pokémon of the party got hurt while walking
+        builder.add_signal( Signal {
+            name: "party_poisoned",
+            args: &[],
+        });
+        // The poison left a Pokémon of the party with 1 HP while walking, where it would have fainted, and wore off
+        builder.add_signal( Signal {
+            name: "poison_survived",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "pokemon_name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // The repel stopped working, so the wild Pokémon can appear again
+        builder.add_signal( Signal {
+            name: "repel_worn_off",
+            args: &[],
+        });
+
+        // Every objective of a quest got done
+        builder.add_signal( Signal {
+            name: "quest_completed",
+            args: &[ SignalArgument {
+                name: "quest_id",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // A Pokémon of the party starts to evolve, so the UI can play the animation (and let the player cancel it)
+        builder.add_signal( Signal {
+            name: "evolution_started",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "evolves_to",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        builder.add_signal( Signal {
+            name: "evolution_finished",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "cancelled",
+                default: Variant::from_bool(false),
+                export_info: ExportInfo::new(VariantType::Bool),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // A Pokémon of the party learned a new move by leveling up
+        builder.add_signal( Signal {
+            name: "move_learned",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "move_name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        // A Pokémon that already knows four moves wants to learn another one, so the player has to choose the move to
+        // forget, or cancel it. The forgotten move it's empty when the Pokémon kept his moves
+        builder.add_signal( Signal {
+            name: "move_learning_started",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "move_name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+        builder.add_signal( Signal {
+            name: "move_learning_finished",
+            args: &[ SignalArgument {
+                name: "party_slot",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "move_name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "forgotten_move",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // Notifies that a wild Pokémon appeared, and the battle it's about to start
+        builder.add_signal( Signal {
+            name: "encounter_started",
+            args: &[ SignalArgument {
+                name: "species_id",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "level",
+                default: Variant::from_i64(0),
+                export_info: ExportInfo::new(VariantType::I64),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
         Self {
             // Development or production flag
             in_development: true,
@@ -98,11 +592,15 @@ impl Game {
             next_api_call: NaiveTime::from_hms(0, 0, 0),
             // Counters that sync arriving times of different signals
             received_signals: 0,
-            total_registered_signals: 2,
+            total_registered_signals: 3,
             // TTimes that the process function is called
             number_of_process: 0,
+            autosave_elapsed: 0.0,
+            autosaving: false,
             // User define enum to represent in which type of world scene the player is
             current_scene_type: Default::default(),
+            game_state: GameState::default(),
+            logged_in_username: None,
             // Default path of the game
             current_scene_path: "res://godot/Game/Map.tscn".to_string(),
             // Core nodes to track
@@ -111,18 +609,55 @@ impl Game {
             current_scene: None,
             // Game data of non game elements
             game_external_data: GameExternalData::new(),
+            // Preview of the last save
+            save_thumbnail: String::new(),
             // Current time
             current_time: NaiveTime::from_hms(0, 0, 0),
             // Current Weather
             current_weather: Weather::Sun,
+            field_weather: FieldWeather::Clear,
+            // In-game clock
+            time_of_day: TimeOfDay::default(),
+            seconds_per_game_hour: in_game_constant::DEFAULT_SECONDS_PER_GAME_HOUR,
             // Flag to control when the data it's fully loaded into the game
             full_data_retrieved: false,
+            // Wild Pokémon
+            encounter_table: EncounterTable::default(),
+            opponent_pokemon: None,
+            trainer_battle: None,
+            trainer_team: Vec::new(),
+            steps_walked: 0,
+            rng: SeededRng::from_time(),
+            species_table: SpeciesTable::default(),
+            leveled_up_slots: Vec::new(),
+            pending_moves: Vec::new(),
+            current_move_learning: None,
+            current_evolution: None,
+            shop_config: ShopConfig::default(),
+            trainer_table: TrainerTable::default(),
+            dialogue_registry: DialogueRegistry::default(),
+            quest_table: QuestTable::default(),
+            balance_config: BalanceConfig::default(),
+            battle_scene: None,
+            battle_party_slot: 0,
+            battle: None,
+            battle_participants: Vec::new(),
+            caught_pokemon: None,
+            worn_off_repel: None,
             // Input 
             input: Some(Input::godot_singleton()),
             // Database
             database: Some(Game::get_database_as_resource())
         }
     }
+}
+
+#[gdnative::methods]
+impl Game {
+    
+    fn new(_owner: &Node2D) -> Self {
+        Self::default()
+    }
 
     #[export]
     fn _ready(&mut self, owner: &Node2D) {
@@ -139,6 +674,21 @@ impl Game {
         self.game_node = owner.get_node(".");
         self.world_map_node = owner.get_node("Map");
 
+        // Restores the persisted player data (like his Pokémon party) and the in-game time from the last saved game
+        // of the account that logged in
+        self.logged_in_username = session::get_logged_in_username();
+        let game_data = utils::retrieve_game_data();
+        self.player_data = game_data.player_data;
+        self.time_of_day = game_data.time_of_day;
+        self.field_weather = game_data.field_weather;
+        self.save_thumbnail = game_data.save_thumbnail;
+        self.species_table = utils::retrieve_species_table();
+        self.shop_config = utils::retrieve_shop_config();
+        self.trainer_table = utils::retrieve_trainer_table();
+        self.dialogue_registry = utils::retrieve_dialogue_registry();
+        self.quest_table = utils::retrieve_quest_table();
+        self.balance_config = utils::retrieve_balance_config();
+
         // While the new values are coming, load the most recent saved (last one stored), avoiding nulling data
         let todays_date = utils::get_todays_date();
         self.game_external_data.todays_day_of_the_week = todays_date.0;
@@ -160,22 +710,15 @@ impl Game {
     }
 
     #[export]
-    fn _process(&mut self, owner: &Node2D, _delta: f64) {
+    fn _process(&mut self, owner: &Node2D, delta: f64) {
         // godot_print!("CURRENT SCENE TYPE FROM PROCESS: {:?}", &self.current_scene_type);
         // Updates the counter that help to reduce the amount of times that a function gets triggered by this _process callback
-        self.number_of_process += 1;
-        // Resets the counter when designed with an arbitrary value
-        if self.number_of_process > 1000 {
-            self.number_of_process = 0
-        }
+        self.number_of_process = next_process_count(self.number_of_process);
         
-        // 1º -> Notifies all the node that had info to persist that it's time to save that data
-        if Input::is_action_just_pressed(&self.input.unwrap(), "Menu") {
-            self.call_save_game_data_group(owner);
-        }
-        // 2º -> When all signals are safetly stored in the class attributes, just call the data persistence method
+        // The save it's requested from the menu, that notifies all the nodes that had info to persist.
+        // When all signals are safetly stored in the class attributes, just call the data persistence method
         if self.received_signals == self.total_registered_signals {
-            self.save_game();
+            self.save_game(owner);
         }
         
         if !self.full_data_retrieved {
@@ -188,7 +731,7 @@ impl Game {
                 self.game_external_data.todays_sunset_time = "21:32:50".to_string(); // IDEM
                 self.game_external_data.cities_weather_loaded = true;
             } else {
-                if self.number_of_process % 10 == 0 {
+                if self.number_of_process % WAITING_LOG_INTERVAL == 0 {
                     godot_print!("Esperando la respuesta del servidor...");
                 }
             }
@@ -209,14 +752,18 @@ impl Game {
                 self.weather_control(owner);
                 self.next_api_call = NaiveTime::from(utils::get_current_time().overflowing_add_signed(Duration::minutes(15)).0);
             } else {
-                if self.number_of_process % 10 == 0 {
+                if self.number_of_process % WAITING_LOG_INTERVAL == 0 {
                     godot_print!("Aún no se han recuperado todos los datos...");
                 }  
             }
         } else {
+            // The clock runs and the tint follows it every frame, so the color changes smoothly
+            self.advance_time_of_day(owner, delta);
+            self.control_day_phases(owner);
+            self.update_autosave(owner, delta);
+
             // Reduces the nº of interactions, instead of every frame, every % of x
-            if self.number_of_process % 100 == 0 {
-                self.control_day_phases(owner);
+            if self.number_of_process % WEATHER_CHECK_INTERVAL == 0 {
                 if utils::get_current_time() > self.next_api_call {
                     self.weather_control(owner)
                 }
@@ -225,39 +772,57 @@ impl Game {
     } 
 
     #[export]
+    /// Tints the world with the color of the current period of the in-game day. While it's raining, the day gets greyer
     fn control_day_phases(&mut self, owner: &Node2D) {
         if unsafe { self.world_map_node.unwrap().assume_safe().is_inside_tree() } {
             // Get's a reference to the CanvasModulate Day-Night simulator
             let day_night_node: TRef<CanvasModulate> = unsafe { owner.get_node_as::<CanvasModulate>("./Map/DayNight").unwrap() };
 
-            // Current time
-            let ctime: NaiveTime = utils::get_current_time();
-            // godot_print!("CT from control_day_phases: {:?}", &ctime);
-            // godot_print!("DayNightCycle: {:?}", &self.game_external_data.current_dn_cycle);
-
-            // Sets the DayNightCycle to a concrete variant by comparing current time with another one...
-            if ctime > NaiveTime::from_num_seconds_from_midnight(0, 0) && 
-                !utils::time_comparator(ctime, &self.game_external_data.todays_sunrise_time) {
-                    self.game_external_data.current_dn_cycle = DayNightCycle::Night;
-            } else if utils::time_comparator(ctime, &self.game_external_data.todays_sunrise_time) && 
-                !utils::time_comparator(ctime, &self.game_external_data.todays_sunset_time) {
-                    self.game_external_data.current_dn_cycle = DayNightCycle::Day;
-            } else if utils::time_comparator(ctime, &self.game_external_data.todays_sunset_time) &&
-                ctime > NaiveTime::from_num_seconds_from_midnight(0, 0) {
-                    self.game_external_data.current_dn_cycle = DayNightCycle::Night;
+            self.game_external_data.current_dn_cycle = match self.time_of_day.get_period() {
+                DayPeriod::Night => DayNightCycle::Night,
+                _ => DayNightCycle::Day
+            };
+
+            let (mut r, mut g, mut b) = self.time_of_day.current_tint();
+            if self.current_weather == Weather::Rain || self.field_weather == FieldWeather::Rain {
+                r = r.min(0.6);
+                g = g.min(0.6);
+                b = b.min(0.6);
             }
+            day_night_node.set_deferred("color", Color { r, g, b, a: 1.0 });
+        }
+    }
 
-            
-            match self.game_external_data.current_dn_cycle {
-                DayNightCycle::Day => { if self.current_weather == Weather::Rain {
-                        day_night_node.set_deferred("color",Color { r: 0.6, g: 0.6, b: 0.6, a: 1.0 })
-                    } else { // Setting when current weather is Weather::Sun-
-                        day_night_node.set_deferred("color",Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 })
-                    }    
-                },
-                DayNightCycle::Night => day_night_node.set_deferred("color",Color { r: 0.2, g: 0.2, b: 0.3, a: 1.0 }),
-                DayNightCycle::NoData => ()
-            }  
+    /// Moves the in-game clock forward, and notifies when a new period of the day begins
+    fn advance_time_of_day(&mut self, owner: &Node2D, delta: f64) {
+        if let Some(period) = self.time_of_day.advance(delta, self.seconds_per_game_hour) {
+            owner.emit_signal("day_period_changed", &[period.to_period_str().to_variant()]);
+        }
+    }
+
+    #[export]
+    /// The current period of the in-game day: Morning, Day, Evening or Night
+    fn get_day_period(&self, _owner: &Node2D) -> String {
+        self.time_of_day.get_period().to_period_str().to_string()
+    }
+
+    #[export]
+    /// The in-game time formatted as `HH:MM`
+    fn get_game_time(&self, _owner: &Node2D) -> String {
+        let (hour, minute) = self.time_of_day.get_hour_and_minute();
+        format!("{:02}:{:02}", hour, minute)
+    }
+
+    #[export]
+    /// Moves the in-game clock to the given hour (like when the player sleeps). The listeners get notified
+    /// if the period of the day changes
+    fn set_game_hour(&mut self, owner: &Node2D, hour: f64) {
+        let previous_period = self.time_of_day.get_period();
+        self.time_of_day.set_hour(hour);
+
+        let current_period = self.time_of_day.get_period();
+        if current_period != previous_period {
+            owner.emit_signal("day_period_changed", &[current_period.to_period_str().to_variant()]);
         }
     }
 
@@ -283,6 +848,58 @@ impl Game {
         self.received_signals += 1;
     }
 
+    #[export]
+    fn _save_player_status(&mut self, _owner: &Node2D, player_status: String) {
+        self.player_data.set_player_status(PlayerStatus::from_status_str(&player_status));
+        self.received_signals += 1;
+    }
+
+    #[export]
+    /// Keeps the direction of the player up to date every time that he turns. It isn't part of the save process,
+    /// so it doesn't count as one of the received signals
+    fn _update_player_direction(&mut self, _owner: &Node2D, direction: String) {
+        self.player_data.set_player_direction(&PlayerDirection::from_string(&direction));
+    }
+
+    #[export]
+    /// Starts the save process. Called from the menu when the player chooses to save the game
+    fn save_game_data(&mut self, owner: &Node2D) {
+        // The screenshot it's taken before anything changes on the screen. The one of the overwritten save gets removed
+        // Every slot of every account has his own save file, so his thumbnails are named after it
+        self.save_thumbnail = utils::capture_save_thumbnail(owner, &utils::save_file_name(), &self.save_thumbnail);
+        self.call_save_game_data_group(owner);
+    }
+
+    #[export]
+    /// Changes to the given save slot and loads his game, starting the Game scene again. The PlayerCharacter
+    /// reads his position from the new slot on his `_ready`. An empty slot starts a new game.
+    ///
+    /// Returns false, staying on the current game, if the slot doesn't exist
+    fn load_save_slot(&mut self, owner: &Node2D, slot: i64) -> bool {
+        if slot < 1 || slot > session::SAVE_SLOTS as i64 || !session::select_save_slot(slot as u8) {
+            return false;
+        }
+        utils::change_scene(owner, scenes::GAME.to_string()).is_ok()
+    }
+
+    #[export]
+    /// The save slot that the game it's saving to
+    fn get_save_slot(&self, _owner: &Node2D) -> i64 {
+        session::get_save_slot() as i64
+    }
+
+    #[export]
+    /// If nothing was saved yet on the given slot, so the save-select menu can show it as a new game
+    fn is_save_slot_empty(&self, _owner: &Node2D, slot: i64) -> bool {
+        slot < 1 || slot > session::SAVE_SLOTS as i64 || utils::is_save_slot_empty(slot as u8)
+    }
+
+    #[export]
+    /// The image that the load screen shows as the preview of the saved game
+    fn get_save_thumbnail(&self, _owner: &Node2D) -> String {
+        utils::get_save_thumbnail_or_default(&self.save_thumbnail)
+    }
+
     /// Method that calls the save game data group. After the call all the nodes attached to the group will send 
     /// the information that should be persisted
     fn call_save_game_data_group(&self, owner: &Node2D) {
@@ -292,15 +909,47 @@ impl Game {
 
     /// ### Method that persist the data stores in the class attributes
     ///
-    fn save_game(&mut self) {
+    fn save_game(&mut self, owner: &Node2D) {
         //! Calls the function who takes care about all IO operations to persist the retrieved data.
         utils::save_game_data(self);
         // Resets the counter that acts as a "all data syncronized and ready to be stored"
         self.received_signals = 0;
+        // Any save counts, so saving from the menu delays the next autosave
+        self.autosave_elapsed = 0.0;
+        if self.autosaving {
+            self.autosaving = false;
+            owner.emit_signal("autosaved", &[]);
+        }
+    }
+
+    /// Counts the time of play, and saves the game by itself once the autosave interval it's over. While the
+    /// player it's interacting (talking, on a battle...) the autosave waits until he finishes, so it never stores
+    /// the game in the middle of something
+    fn update_autosave(&mut self, owner: &Node2D, delta: f64) {
+        self.autosave_elapsed += delta;
+        // A save already in course (from the menu or the last autosave) can't be started again
+        if self.autosave_elapsed < in_game_constant::AUTOSAVE_INTERVAL_SECONDS || self.autosaving || self.received_signals > 0 {
+            return;
+        }
+
+        let player_interacting = unsafe { owner.get_node("Player") }
+            .map(|player| unsafe { player.assume_safe().call("get_player_status", &[]) }.to_string())
+            .map_or(false, |player_status| PlayerStatus::from_status_str(&player_status) == PlayerStatus::Interacting);
+        if player_interacting {
+            return;
+        }
+
+        // Only the save group it's called, without the screenshot of `save_game_data`, so the thumbnail of the last
+        // save from the menu it's kept
+        self.autosaving = true;
+        self.call_save_game_data_group(owner);
     }
 
     /// Method for load the correct scene, based on last saved player Scene
     fn load_initial_scene(&mut self, owner: &Node2D, path: String) {
+        self.load_encounter_table(&path);
+        utils::notify_music_manager(owner, "on_scene_changed", &[path.to_variant()]);
+
         if !path.ends_with("Map.tscn") {
             self.current_scene_type = CurrentSceneType::Indoors;
 
@@ -325,34 +974,37 @@ impl Game {
     #[export]
     /// This method it's the receiver of the signal that notifies that the game detected the player on an area designed to switch him
     /// from the outside world to a building interior, and VICEVERSA
+    ///
+    /// The scenes gets swapped when the screen it's completly black
     fn change_world_scene(&mut self, owner: &Node2D, path: Variant) {
-        // Gets a TRef to the Node that makes the transition between scenes animation
-        let scene_transition_animation = unsafe { owner.get_node_as::<CanvasLayer>("SceneTransition")
-            .unwrap().get_node("AnimationPlayer").unwrap().assume_safe().cast::<AnimationPlayer>().unwrap()
-        };
+        // A wrong path keeps the player on the current scene instead of crashing when the scene gets loaded
+        if let Err(err) = utils::validate_scene_path(&path.to_string()) {
+            godot_error!("{}", err);
+            return;
+        }
+        self.transition_and_call(owner, "_swap_world_scene", &[path], true);
+    }
 
+    #[export]
+    /// Replaces the world scene where the player is (the Map or a building interior) with the given one
+    fn _swap_world_scene(&mut self, owner: &Node2D, path: Variant) {
         // Stores a path to a scene provided by a signal triggered for a collision between an area and a player
         self.current_scene_path = path.to_string();
+        utils::notify_music_manager(owner, "on_scene_changed", &[path.to_owned()]);
+        self.load_encounter_table(&path.to_string());
 
         // Going from indoors to outdoors...
         if self.current_scene_path.ends_with("Map.tscn") {
             self.current_scene_type = CurrentSceneType::Outdoors;
 
-            scene_transition_animation.play("FadeToBlack", -1.0, 0.5, false);
-
             unsafe { owner.call_deferred("remove_child", &[self.current_scene.unwrap().to_variant()]) };
             unsafe { owner.call_deferred("add_child", &[self.world_map_node.unwrap().to_variant()]) };
-            
-            scene_transition_animation.play("FadeToNormal", -1.0, 1.0, false);
         
         // Changing to an inside scene...
         } else {
             //Sets what type of scene it's being player now
             self.current_scene_type = CurrentSceneType::Indoors;
             
-            // Plays the fade anim
-            scene_transition_animation.play("FadeToBlack", -1.0, 0.5, false);
-            
             // Now let's gonna remove the Map from the SceneTree
             unsafe { owner.call_deferred("remove_child", &[self.world_map_node.unwrap().to_variant()]) };
 
@@ -367,9 +1019,6 @@ impl Game {
             // Finally we insert our new Node, setting Game as it's parent
             unsafe { owner.call_deferred("add_child", &[self.current_scene.unwrap().to_variant()]) };
             
-            // Gets back the screen without fades
-            scene_transition_animation.play("FadeToNormal", -1.0, 1.0, false);
-            
             // ! Normalize node position, relative to player. Here, for every new indoors scene that it's being player, 
             // automatically moves it (set it's position) taking the player position as reference and then modifing with certain offset.
             // Indoors scenes in Pokémon usually starts on a red carpet, spawing the player there, so basically we are moving the scene to fit that condition.
@@ -381,137 +1030,1608 @@ impl Game {
         }
     }
 
+    #[export]
+    /// Takes the player to the given position of another scene (or the same one), through a door, a cave entrance...
+    ///
+    /// The target position it's relative to the origin of the target scene, so it doesn't matter where the
+    /// indoors scene gets placed
+    fn warp_player(&mut self, owner: &Node2D, target_scene: String, target_position: Vector2) {
+        if let Err(err) = utils::validate_scene_path(&target_scene) {
+            godot_error!("The warp can't take the player there. {}", err);
+            return;
+        }
+        self.transition_and_call(owner, "_warp_player_on_black",
+            &[target_scene.to_variant(), target_position.to_variant()], true);
+    }
 
-    // <--------------------------- HTTP ZONE CONTROL --------------------------------------->    
+    #[export]
+    /// The scene where the player is walking right now: the world map, or the indoors scene that replaced it
+    fn get_world_scene(&self, _owner: &Node2D) -> Option<Ref<Node>> {
+        if self.current_scene_type == CurrentSceneType::Indoors {
+            self.current_scene
+        } else {
+            self.world_map_node
+        }
+    }
 
-    /// Creates a new HTTP Godot Node and insert it into a tree. When a url is specified, performs an HTTP request, and if `connect_to`
-    /// parameter is specified, connect a signal to this class, that it's where the HTTP response comes.
-    fn new_http_node(&self, owner: &Node2D, url: &str, connect_to: &str) -> Result<(), GodotError> {
-        let http_request: Ref<HTTPRequest, Unique> = HTTPRequest::new();
-        let http_request_as_node = unsafe { http_request.assume_safe_unchecked().assume_shared().assume_safe() };
-        
-        owner.add_child(http_request_as_node, true);
-        
-        http_request_as_node.connect("request_completed", self.game_node.unwrap(), connect_to,
-            VariantArray::new_shared(), 0).unwrap();
+    #[export]
+    fn _warp_player_on_black(&mut self, owner: &Node2D, target_scene: String, target_position: Vector2) {
+        if target_scene != self.current_scene_path {
+            self._swap_world_scene(owner, target_scene.to_variant());
+        }
 
-        // Performs an http request, returning Result<(), GodotError>
-        http_request.request(url, TypedArray::new(),
-            true, HTTPClient::METHOD_GET, "")
+        let spawn_position = self.get_scene_origin() + target_position;
+        unsafe { owner.get_node("Player").unwrap().assume_safe()
+            .call("teleport", &[spawn_position.to_variant()]) };
+        // The new position gets persisted, so loading the game takes the player to the right place
+        self.player_data.set_player_position(spawn_position.x as f64, spawn_position.y as f64);
     }
 
-    /// Retrieves all the external game data, like city's weather and game's sunrise and sunset hours
-    /// from our Java Spring backend server
-    fn get_external_game_data(&self, owner: &Node2D) {
-        let url: &'static str;
-        
-        if self.in_development { url = self.development_url } else { url = self.production_url }
-        
-        match self.new_http_node(owner, url, "_get_java_spring_backend_response")
-        {
-            Ok(response) => response,
-            Err(err) => godot_print!("Err => {:?}", err)
+    /// The player lost the battle with every Pokémon fainted. Happens behind the fade that closes the battle, so the
+    /// player just appears on his respawn point, or stays where he was if he never visited a Pokémon Center
+    fn black_out(&mut self, owner: &Node2D) {
+        let money_lost = self.player_data.black_out();
+        owner.emit_signal("blacked_out", &[(money_lost as i64).to_variant()]);
+        self.notify_money_changed(owner);
+
+        if let Some(respawn_point) = self.player_data.get_respawn_point().cloned() {
+            self._warp_player_on_black(owner, respawn_point.scene_path,
+                Vector2::new(respawn_point.x as f32, respawn_point.y as f32));
         }
     }
 
+    /// Where the scene that the player it's exploring it's placed. The positions of the warps are relative to it
+    fn get_scene_origin(&self) -> Vector2 {
+        if self.current_scene_type == CurrentSceneType::Indoors {
+            self.current_scene
+        } else {
+            self.world_map_node
+        }.map(|scene| unsafe { scene.assume_safe().cast::<Node2D>().unwrap().position() })
+            .unwrap_or(Vector2::new(0.0, 0.0))
+    }
+
     #[export]
-    /// The method that receives and Http Response with all the external game data
-    fn _get_java_spring_backend_response(&mut self, _owner: &Node2D, _result: Variant, _response_code: i64, _headers: Variant, body: ByteArray) {
-        godot_print!("Spring backend response code: {:?}", _response_code);
-        if _response_code == 200 {
-            self.game_external_data.spring_backend_response_code = _response_code;
+    /// Fully heals the party on a Pokémon Center. The place where the player it's standing becomes the one where
+    /// he comes back after a whiteout
+    fn heal_party_at_pokemon_center(&mut self, owner: &Node2D) {
+        self.player_data.get_party_mut().heal_all();
+
+        if let Some(player) = unsafe { owner.get_node_as::<Node2D>("Player") } {
+            let position = player.global_position() - self.get_scene_origin();
+            self.player_data.set_respawn_point(RespawnPoint {
+                scene_path: self.current_scene_path.to_owned(),
+                x: position.x as f64,
+                y: position.y as f64,
+            });
+        }
+    }
 
-            // ! Here comes the http response, parsed as a Variant<Dictionary>
-            let response = networking::http_body_to_string(body);
+    /// Loads the wild Pokémon zones of the scene that the player it's currently exploring
+    fn load_encounter_table(&mut self, scene_path: &str) {
+        if let Some(map_name) = Path::new(scene_path).file_stem().and_then(OsStr::to_str) {
+            self.encounter_table = utils::retrieve_encounter_table(map_name)
+                .with_default_encounter_rate(self.balance_config.get_encounter_rate());
+        }
+    }
 
-            self.set_sunrise_sunset_hours(&response);
-            self.set_cities_weather(&response);
-           
-        } else {
-            self.game_external_data.spring_backend_response_code = _response_code;
-            godot_print!("FAILED to get backend information. Response code: {:?}", _response_code);
+    #[export]
+    /// The username of the account that's playing, or an empty string when nobody logged in
+    fn get_logged_in_username(&self, _owner: &Node2D) -> String {
+        self.logged_in_username.clone().unwrap_or_default()
+    }
+
+    #[export]
+    fn get_game_state(&self, _owner: &Node2D) -> String {
+        self.game_state.to_state_str().to_string()
+    }
+
+    #[export]
+    /// Moves the game to the given state. Returns false, keeping the current one, if the state it's unknown
+    /// or the game can't go there from the current state
+    fn set_game_state(&mut self, owner: &Node2D, state: String) -> bool {
+        match GameState::from_state_str(&state) {
+            Some(next) => self.change_game_state(owner, next),
+            None => {
+                godot_warn!("Unknown game state: {}", state);
+                false
+            }
         }
     }
 
-    // Encapsulates the process of sets the Sunrise/Sunset hours from the Http Response
-    fn set_sunrise_sunset_hours(&mut self, response: &Dictionary) {
-        let sunrise_hour = response.get("sunriseHour").to_string().parse::<i32>().unwrap();
-        self.game_external_data.todays_sunrise_time = utils::convert_from_unix_timestamp(
-            sunrise_hour + game_consts::UNIX_TIMESTAMP_OFFSET);
+    /// Validates the transition to the next state and announces it. Staying on the same state it's not a change
+    fn change_game_state(&mut self, owner: &Node2D, next: GameState) -> bool {
+        if !self.game_state.can_transition_to(next) {
+            godot_warn!("The game can't go from {} to {}", self.game_state.to_state_str(), next.to_state_str());
+            return false;
+        }
+        if self.game_state != next {
+            let previous = std::mem::replace(&mut self.game_state, next);
+            owner.emit_signal("game_state_changed",
+                &[Variant::from_str(previous.to_state_str()), Variant::from_str(next.to_state_str())]);
+        }
+        true
+    }
 
-        let sunset_hour = response.get("sunsetHour").to_string().parse::<i32>().unwrap();
-        self.game_external_data.todays_sunset_time = utils::convert_from_unix_timestamp(
-            sunset_hour + game_consts::UNIX_TIMESTAMP_OFFSET);
+    #[export]
+    /// Every few steps of the player, the poisoned Pokémon of the party lose HP. Every step wears the repel out too
+    fn _on_player_step_completed(&mut self, owner: &Node2D) {
+        self.steps_walked = self.steps_walked.wrapping_add(1);
+        if self.steps_walked % OVERWORLD_POISON_STEPS == 0 {
+            self.apply_overworld_poison(owner);
+        }
+        if let Some(repel_id) = self.player_data.take_repel_step() {
+            owner.emit_signal("repel_worn_off", &[]);
+            self.ask_for_another_repel(owner, repel_id);
+        }
     }
 
-    // Encapsulates the process of sets the weather of all the cities of the game
-    fn set_cities_weather(&mut self, response: &Dictionary) {
-        let current_weather = response.get("gameCities").to_array();
+    /// Hurts the poisoned Pokémon of the party, flashing the player sprite with the poison sound when any of them
+    /// loses HP. The ones that would have fainted keep 1 HP and get cured, and the UI gets told about them
+    fn apply_overworld_poison(&mut self, owner: &Node2D) {
+        let poisoned = self.player_data.get_party_mut().apply_overworld_poison();
+        if poisoned.is_empty() {
+            return;
+        }
 
-        godot_print!("\nWEATHER: {:?}\n", &current_weather);
+        owner.emit_signal("party_poisoned", &[]);
+        if let Some(player_animation) = unsafe { owner.get_node(PLAYER_ANIMATION_PATH) } {
+            unsafe { player_animation.assume_safe().call("flash_damage", &[]) };
+        }
+        utils::notify_music_manager(owner, "play_sound_effect", &[Variant::from_str(POISON_STEP_SOUND)]);
 
-        // Iterate all over the game cities / towns
-        let mut idx: i32 = 0;
-        // ! IMPORTANT: Our REST API always send the cities ordered by ID. The `self.game_cities` attribute stores cities created
-        // in base the order that the cities are hardcoded in the vector returned by the `GameCity::values()` associated fn.
-        // That order maps the ID of the cities on the JSON.
-        for location in self.game_cities.iter_mut() {
-            let current_idx_data = current_weather.get(idx).to_dictionary();
-            if location.get_name() == current_idx_data.get("name").to_string() {
-            
-                let external_weather_data = current_idx_data
-                        .get("weather")
-                        .to_dictionary();
-                
-                let location_weather_instance = CityWeather::new(
-                    external_weather_data.get("weatherIDCode").to_i64() as i32,
-                    external_weather_data.get("mainCode").to_string(),
-                    external_weather_data.get("description").to_string(),
-                    external_weather_data.get("icon").to_string()
-                );
+        for (party_slot, _) in poisoned.into_iter().filter(|(_, result)| *result == OverworldPoisonResult::Survived) {
+            let name = self.player_data.get_party().get_members().get(party_slot)
+                .map(|pokemon| pokemon.get_display_name().to_string())
+                .unwrap_or_default();
+            owner.emit_signal("poison_survived", &[party_slot.to_variant(), name.to_variant()]);
+        }
+    }
 
-                location.set_weather(location_weather_instance);
-                idx += 1;
-                self.game_external_data.cities_weather_loaded = true;
-            } else {
-                godot_print!("Something went wrong retriving data and matching it with the correct city at a given index");
-            }
+    #[export]
+    /// Receives every step of the player over the tall grass tiles of the given zone, and rolls the chance of find a
+    /// wild Pokémon with the rate of the zone and the current weather
+    fn _on_tall_grass_step(&mut self, owner: &Node2D, zone_id: String) {
+        // There's already a wild Pokémon waiting for the player, or a repel keeps them away
+        if self.opponent_pokemon.is_some() || self.player_data.is_repel_active() {
+            return;
+        }
+
+        let wild_encounter = match self.encounter_table.get_zone(&zone_id) {
+            Some(zone) => zone.roll_encounter(&mut self.rng, self.player_data.is_on_bike(),
+                self.time_of_day.get_period(), self.field_weather),
+            None => None
+        };
+
+        if let Some(wild_encounter) = wild_encounter {
+            self.start_wild_encounter(owner, wild_encounter);
         }
-        godot_print!("\n************\nGAME CITIES and it's data: {:?}", &self.game_cities);
     }
 
-    // <------------------------- WEATHER CONTROL ----------------------->
     #[export]
-    fn weather_control(&mut self, owner: &Node2D) {
-        if self.current_scene_type == CurrentSceneType::Outdoors {
-            for location in self.game_cities.iter_mut() {
-                godot_print!("City name as node path {:?}", &location.get_as_node_path());
-                
-                let mut node_path: String = "Map/".to_owned() + location.get_as_node_path().as_str() + "/Weather";
-                godot_print!("Full final node path {:?}", &node_path);
-                match location.get_weather().as_ref().unwrap().get_main_code_as_weather_variant() {
-                    Weather::Thunderstorm => node_path.push_str("/Thunderstorm"),
-                    Weather::Drizzle => node_path.push_str("/Drizzle"),
-                    Weather::Rain => node_path.push_str("/Rain"),
-                    Weather::Snow => node_path.push_str("/Snow"),
-                    Weather::Sun => node_path.push_str("/Sun"),
-                    Weather::Clouds => node_path.push_str("/Clouds"),
-                };
-                
-                if let Some(weather_node) = unsafe { owner.get_node_as::<Particles2D>(node_path.as_str()) } {
-                    weather_node.set_emitting(true);
-                }
-            }
-        }   
+    /// Uses a repel of the bag. Returns the message to show if it can't be used, or an empty string
+    fn use_repel(&mut self, _owner: &Node2D, item_id: String) -> String {
+        match self.player_data.use_repel(&item_id) {
+            Ok(_) => "".to_string(),
+            Err(item_error) => item_error.to_message_str().to_string()
+        }
     }
 
-}
+    #[export]
+    fn get_repel_steps(&self, _owner: &Node2D) -> i64 {
+        self.player_data.get_repel_steps() as i64
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[derive(Clone)]
-pub struct GameExternalData {
-    todays_date: String,
-    todays_day_of_the_week: String,
+    /// Tells the player that the repel stopped working, asking him to use another one of the same kind if he has it
+    fn ask_for_another_repel(&mut self, owner: &Node2D, repel_id: String) {
+        let dialogue_box = match unsafe { owner.get_node(DIALOGUE_BOX_PATH) } {
+            Some(dialogue_box) => unsafe { dialogue_box.assume_safe() },
+            None => return
+        };
+        let has_another = self.player_data.get_bag().get_quantity(&repel_id) > 0;
+        let dialogue_data = if has_another {
+            (1, vec!["Sí".to_string(), "No".to_string()],
+                vec![format!("Se acabó el efecto del {}.\n¿Quieres usar otro?", get_item_name(&repel_id))])
+        } else {
+            (0, Vec::new(), vec![format!("Se acabó el efecto del {}.", get_item_name(&repel_id))])
+        };
+
+        if has_another {
+            let callback_target = unsafe { owner.get_node(".").unwrap().assume_safe() };
+            if let Err(err) = connect_oneshot(&dialogue_box, "dialogue_box_inactive", callback_target, "_on_repel_answered") {
+                godot_error!("Can't wait for the answer about the repel: {:?}", err);
+                return;
+            }
+            self.worn_off_repel = Some(repel_id);
+        }
+        if let Some(player) = unsafe { owner.get_node("Player") } {
+            unsafe { player.assume_safe().call("handle_interaction", &[Variant::from_str("on_dialogue")]) };
+        }
+        unsafe { dialogue_box.call("_print_dialogue", &[dialogue_data.to_variant()]) };
+    }
+
+    #[export]
+    /// The player answered if he wants to use another repel. Only the first option (yes) uses it
+    fn _on_repel_answered(&mut self, owner: &Node2D, _signal_info: Variant) {
+        let repel_id = match self.worn_off_repel.take() {
+            Some(repel_id) => repel_id,
+            None => return
+        };
+        let decision = unsafe { owner.get_node(DIALOGUE_BOX_PATH) }
+            .map(|dialogue_box| unsafe { dialogue_box.assume_safe().call("get_last_decision", &[]) }.to_i64())
+            .unwrap_or(0);
+        if decision == 1 {
+            if let Err(item_error) = self.player_data.use_repel(&repel_id) {
+                godot_warn!("The repel couldn't be used again: {}", item_error.to_message_str());
+            }
+        }
+    }
+
+    #[export]
+    /// The best fishing rod that the player carries, or an empty string if he doesn't have any
+    fn get_fishing_rod(&self, _owner: &Node2D) -> String {
+        Rod::ALL.iter().rev()
+            .find(|rod| self.player_data.get_bag().get_quantity(rod.to_item_id()) > 0)
+            .map(|rod| rod.to_item_id().to_string())
+            .unwrap_or_default()
+    }
+
+    #[export]
+    /// Throws the hook of the given rod. Returns the seconds until a Pokémon bites, or a negative number if nothing bites
+    fn roll_fishing_bite(&mut self, _owner: &Node2D, rod_id: String) -> f64 {
+        Rod::from_item_id(&rod_id)
+            .and_then(|rod| fishing::roll_bite(&mut self.rng, rod))
+            .unwrap_or(-1.0)
+    }
+
+    #[export]
+    /// The player hooked a Pokémon on the given fishing zone. The pool of Pokémon depends on the rod.
+    ///
+    /// Returns false if there's no Pokémon for that rod on the zone, so nothing starts
+    fn start_fishing_encounter(&mut self, owner: &Node2D, zone_id: String, rod_id: String) -> bool {
+        if self.opponent_pokemon.is_some() {
+            return false;
+        }
+        let fishing_zone_id = match Rod::from_item_id(&rod_id) {
+            Some(rod) => rod.fishing_zone_id(&zone_id),
+            None => return false
+        };
+
+        let wild_encounter = match self.encounter_table.get_zone(&fishing_zone_id) {
+            Some(zone) => zone.roll_fishing_encounter(&mut self.rng, self.time_of_day.get_period()),
+            None => None
+        };
+        match wild_encounter {
+            Some(wild_encounter) => self.start_wild_encounter(owner, wild_encounter),
+            None => false
+        }
+    }
+
+    /// A wild Pokémon appeared! Locks the player, notifies the listeners and goes to the battle scene.
+    ///
+    /// Returns false if the specie of the encounter isn't known, so nothing starts and the player gets released
+    fn start_wild_encounter(&mut self, owner: &Node2D, wild_encounter: WildEncounter) -> bool {
+        // The wild Pokémon know the last moves that they would have learned by leveling up
+        let rng = &mut self.rng;
+        let wild_pokemon = self.species_table.get_species(wild_encounter.species_id).map(|specie| {
+            let mut pokemon = Pokemon::from_specie(specie, wild_encounter.level, specie.get_starting_moves(wild_encounter.level));
+            pokemon.randomize_ivs_and_nature(rng, specie);
+            pokemon
+        });
+        let mut wild_pokemon = match wild_pokemon {
+            Some(wild_pokemon) => wild_pokemon,
+            None => {
+                godot_error!("The wild Pokémon {} of the encounter isn't on the species table", wild_encounter.species_id);
+                self.notify_player(owner, "end_interaction");
+                return false;
+            }
+        };
+        self.notify_player(owner, "on_encounter");
+
+        owner.emit_signal("encounter_started", &[
+            wild_encounter.species_id.to_variant(), wild_encounter.level.to_variant()
+        ]);
+        wild_pokemon.set_shiny(encounter::roll_shiny(&mut self.rng, self.player_data.get_shiny_odds()));
+        self.player_data.get_pokedex_mut().mark_seen(wild_encounter.species_id);
+        self.opponent_pokemon = Some(wild_pokemon);
+
+        self.start_battle_transition(owner);
+        true
+    }
+
+    #[export]
+    /// Receives the signal of a trainer that it's just in front of the player, ready to fight. The trainer gets his
+    /// team ready, and his first Pokémon goes out
+    fn _on_trainer_battle_started(&mut self, owner: &Node2D, trainer_id: String) {
+        let trainer = self.trainer_table.get_trainer(&trainer_id).cloned().unwrap_or_default();
+        let species_table = &self.species_table;
+        let rng = &mut self.rng;
+        let mut trainer_team: Vec<Pokemon> = trainer.team.iter()
+            .filter_map(|trainer_pokemon| species_table.get_species(trainer_pokemon.species_id).map(|specie| {
+                let mut pokemon = Pokemon::from_specie(specie, trainer_pokemon.level,
+                    specie.get_starting_moves(trainer_pokemon.level));
+                pokemon.randomize_ivs_and_nature(rng, specie);
+                pokemon
+            }))
+            .collect();
+        if trainer_team.is_empty() {
+            godot_error!("The trainer {} has no Pokémon to fight with", trainer_id);
+            self.notify_player(owner, "end_interaction");
+            return;
+        }
+
+        let first_pokemon = trainer_team.remove(0);
+        self.player_data.get_pokedex_mut().mark_seen(first_pokemon.id);
+        self.opponent_pokemon = Some(first_pokemon);
+        self.trainer_team = trainer_team;
+        self.trainer_battle = Some(trainer_id);
+        self.start_battle_transition(owner);
+    }
+
+    #[export]
+    fn is_trainer_defeated(&self, _owner: &Node2D, trainer_id: String) -> bool {
+        self.player_data.is_trainer_defeated(&trainer_id)
+    }
+
+    #[export]
+    /// Remembers that the player defeated the given trainer, so he will never challenge the player again
+    fn set_trainer_defeated(&mut self, _owner: &Node2D, trainer_id: String) {
+        self.player_data.set_trainer_defeated(&trainer_id);
+    }
+
+    /// Fades the screen to black, and loads the battle scene when the fade finishes. The player keeps locked
+    /// until the battle ends
+    fn start_battle_transition(&mut self, owner: &Node2D) {
+        self.change_game_state(owner, GameState::Transition);
+        self.transition_and_call(owner, "_show_battle_scene", &[], false);
+    }
+
+    #[export]
+    /// Places the battle scene over the world. The world keeps loaded (but hidden) so the player comes back
+    /// exactly to the same place when the battle ends
+    fn _show_battle_scene(&mut self, owner: &Node2D) {
+        let battle_scene = unsafe { ResourceLoader::godot_singleton()
+            .load(scenes::BATTLE, "", false)
+            .and_then(|scene| scene.assume_safe().cast::<PackedScene>()?.instance(0)) };
+        let battle_scene = match battle_scene {
+            Some(battle_scene) => battle_scene,
+            None => {
+                // Without the scene there's nothing to fight on, so the player goes back to the world
+                godot_error!("The battle scene {} can't be loaded", scenes::BATTLE);
+                self.opponent_pokemon = None;
+                self.trainer_battle = None;
+                self.trainer_team.clear();
+                self.change_game_state(owner, GameState::Overworld);
+                self.notify_player(owner, "end_interaction");
+                return;
+            }
+        };
+        self.battle_scene = Some(battle_scene);
+
+        // The first Pokémon of the party that can fight goes out
+        self.battle_party_slot = self.player_data.get_party().get_members().iter()
+            .position(|pokemon| !pokemon.is_fainted())
+            .unwrap_or(0);
+        self.battle_participants = vec![self.battle_party_slot];
+        self.battle = match (&self.opponent_pokemon, &self.trainer_battle) {
+            (Some(_), Some(_)) => Some(Battle::against_trainer(self.trainer_team.len())),
+            (Some(_), None) => Some(Battle::new()),
+            (None, _) => None
+        };
+
+        self.set_world_visible(owner, false);
+        self.current_scene_type = CurrentSceneType::Battle;
+        self.change_game_state(owner, GameState::Battle);
+        utils::notify_music_manager(owner, "push_battle_track", &[]);
+        owner.add_child(battle_scene, true);
+    }
+
+    #[export]
+    /// Called by the battle scene when the battle it's over. The battle scene gets removed behind the fade
+    fn end_battle(&mut self, owner: &Node2D) {
+        self.change_game_state(owner, GameState::Transition);
+        self.transition_and_call(owner, "_close_battle_scene", &[], false);
+    }
+
+    #[export]
+    /// Removes the battle scene and gives back the control to the player
+    fn _close_battle_scene(&mut self, owner: &Node2D) {
+        if let Some(battle_scene) = self.battle_scene.take() {
+            owner.remove_child(battle_scene);
+            unsafe { battle_scene.assume_safe().queue_free() };
+        }
+        self.opponent_pokemon = None;
+        self.trainer_battle = None;
+        self.trainer_team.clear();
+        self.battle = None;
+        self.battle_participants.clear();
+
+        self.current_scene_type = if self.current_scene_path.ends_with("Map.tscn") {
+            CurrentSceneType::Outdoors
+        } else {
+            CurrentSceneType::Indoors
+        };
+        self.set_world_visible(owner, true);
+        utils::notify_music_manager(owner, "pop_battle_track", &[]);
+
+        if self.player_data.get_party().is_wiped_out() {
+            self.black_out(owner);
+        }
+
+        self.notify_player(owner, "end_interaction");
+
+        // The Pokémon that leveled up during the battle learn their new moves, and then they could evolve
+        self.continue_level_up(owner);
+    }
+
+    pub fn get_player_data(&self) -> &PlayerData {
+        &self.player_data
+    }
+
+    /// Gives experience to a Pokémon of the party, remembering it if levels up so it can learn the moves of the new
+    /// levels and evolve when the battle ends.
+    ///
+    /// Returns the levels reached by the Pokémon
+    pub fn award_battle_experience(&mut self, party_slot: usize, experience: u32) -> Vec<u8> {
+        let levels_gained = match self.player_data.get_party_mut().get_member_mut(party_slot) {
+            Some(pokemon) => match self.species_table.get_species(pokemon.id) {
+                Some(specie) => {
+                    let levels_gained = pokemon.award_experience(experience, specie);
+                    for level in levels_gained.iter() {
+                        self.pending_moves.extend(specie.get_moves_learned_at(*level).into_iter()
+                            .map(|new_move| (party_slot, new_move)));
+                    }
+                    levels_gained
+                },
+                None => Vec::new()
+            },
+            None => Vec::new()
+        };
+
+        if !levels_gained.is_empty() && !self.leveled_up_slots.contains(&party_slot) {
+            self.leveled_up_slots.push(party_slot);
+        }
+        levels_gained
+    }
+
+    #[export]
+    /// Called by the battle scene when the last Pokémon of the trainer faints. The trainer pays his prize, and never
+    /// challenges the player again. The experience of his team was already earned, as every Pokémon fainted.
+    ///
+    /// Returns the prize money
+    fn win_trainer_battle(&mut self, owner: &Node2D) -> i64 {
+        let trainer_id = match self.trainer_battle.to_owned() {
+            Some(trainer_id) => trainer_id,
+            None => return 0
+        };
+        let trainer = self.trainer_table.get_trainer(&trainer_id).cloned().unwrap_or_default();
+
+        let prize = rewards::prize_money(trainer.base_payout, trainer.highest_level());
+        self.player_data.earn(prize);
+        owner.emit_signal("prize_money_awarded", &[(prize as i64).to_variant()]);
+        self.notify_money_changed(owner);
+
+        self.player_data.set_trainer_defeated(&trainer_id);
+        prize as i64
+    }
+
+    /// The Pokémon that fought against the defeated one share his experience (the rest of the party gets half of it
+    /// with the Exp. Share). The Pokémon of the trainers give more experience than the wild ones
+    fn share_defeated_experience(&mut self, owner: &Node2D, defeated_specie_id: i32, defeated_level: u8) {
+        let standing: Vec<usize> = self.player_data.get_party().get_members().iter().enumerate()
+            .filter(|(_, pokemon)| !pokemon.is_fainted())
+            .map(|(party_slot, _)| party_slot)
+            .collect();
+        let exp_share = self.player_data.get_bag().get_quantity(EXP_SHARE) > 0;
+        let base_experience = self.species_table.get_species(defeated_specie_id)
+            .map_or(0, |specie| specie.get_base_experience());
+        let experience = rewards::defeated_experience(base_experience, defeated_level, self.trainer_battle.is_some());
+
+        for (party_slot, share) in rewards::split_experience(experience, &self.battle_participants, &standing, exp_share) {
+            self.award_battle_experience(party_slot, share);
+            owner.emit_signal("experience_gained", &[party_slot.to_variant(), (share as i64).to_variant()]);
+        }
+    }
+
+    /// Gives to a Pokémon of the party the effort values of the defeated specie, never going over the EV caps.
+    ///
+    /// Returns the EVs that the Pokémon really earned
+    pub fn award_battle_evs(&mut self, party_slot: usize, defeated_specie_id: i32) -> StatSpread {
+        let ev_yield = match self.species_table.get_species(defeated_specie_id) {
+            Some(defeated_specie) => defeated_specie.get_ev_yield().clone(),
+            None => return StatSpread::default()
+        };
+        match self.player_data.get_party_mut().get_member_mut(party_slot) {
+            Some(pokemon) => match self.species_table.get_species(pokemon.id) {
+                Some(specie) => pokemon.award_evs(&ev_yield, specie),
+                None => StatSpread::default()
+            },
+            None => StatSpread::default()
+        }
+    }
+
+    /// Goes on with what the Pokémon that leveled up still have to do: learning their new moves first, and evolving then
+    fn continue_level_up(&mut self, owner: &Node2D) {
+        if !self.start_next_move_learning(owner) {
+            self.start_next_evolution(owner);
+        }
+    }
+
+    /// Teaches the pending moves to the Pokémon that have room for them, until one of them already knows four moves.
+    /// Then the player is asked for the move to forget, and the rest of the moves wait for the answer.
+    ///
+    /// Returns if the game it's waiting for the player to choose
+    fn start_next_move_learning(&mut self, owner: &Node2D) -> bool {
+        while !self.pending_moves.is_empty() {
+            let (party_slot, new_move) = self.pending_moves.remove(0);
+            let result = match self.player_data.get_party_mut().get_member_mut(party_slot) {
+                Some(pokemon) => pokemon.learn_move(new_move.to_owned()),
+                None => continue
+            };
+
+            match result {
+                LearnMoveResult::Learned => owner.emit_signal("move_learned",
+                    &[party_slot.to_variant(), new_move.name.to_variant()]),
+                LearnMoveResult::AlreadyKnown => (),
+                LearnMoveResult::NoFreeSlot => {
+                    // The player can't move while he chooses, and gets released when he's done
+                    self.lock_player_until(owner, "on_move_learning", "move_learning_finished",
+                        "_on_move_learning_finished");
+
+                    owner.emit_signal("move_learning_started", &[party_slot.to_variant(), new_move.name.to_variant()]);
+                    self.current_move_learning = Some((party_slot, new_move));
+                    self.open_move_learn_menu(owner);
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Asks the player for the move that the Pokémon will forget to learn the new one
+    fn open_move_learn_menu(&mut self, owner: &Node2D) {
+        let move_learn_menu = unsafe { ResourceLoader::godot_singleton()
+            .load(scenes::MOVE_LEARN_MENU, "", false)
+            .and_then(|scene| scene.assume_safe().cast::<PackedScene>()?.instance(0))
+            .map(|node| node.assume_safe()) };
+        match move_learn_menu {
+            Some(move_learn_menu) => {
+                unsafe { move_learn_menu.call("set_move_learning_info", &[self.get_move_learning_info().to_variant()]) };
+                owner.add_child(move_learn_menu, false);
+            },
+            // Without the menu there's no way to choose, so the Pokémon keeps his moves
+            None => self.cancel_move_learning(owner)
+        }
+    }
+
+    /// The Pokémon that it's trying to learn a new move, the new move, and the four moves that it already knows
+    fn get_move_learning_info(&self) -> Dictionary {
+        let move_learning_info = Dictionary::new();
+        if let Some((party_slot, new_move)) = &self.current_move_learning {
+            if let Some(pokemon) = self.player_data.get_party().get_members().get(*party_slot) {
+                let known_moves = VariantArray::new();
+                for known_move in pokemon.get_moves() {
+                    known_moves.push(known_move.name.to_owned());
+                }
+                move_learning_info.insert("pokemon_name", pokemon.get_display_name().to_string());
+                move_learning_info.insert("new_move", new_move.name.to_owned());
+                move_learning_info.insert("known_moves", known_moves.into_shared());
+            }
+        }
+        move_learning_info.into_shared()
+    }
+
+    #[export]
+    /// The player chose the move to forget, and the Pokémon learns the new one on his place. A slot without a move
+    /// keeps the moves as they were
+    fn forget_move(&mut self, owner: &Node2D, move_slot: i64) {
+        if let Some((party_slot, new_move)) = self.current_move_learning.take() {
+            let move_name = new_move.name.to_owned();
+            let forgotten_move = self.player_data.get_party_mut().get_member_mut(party_slot)
+                .and_then(|pokemon| pokemon.replace_move(move_slot as usize, new_move))
+                .map(|forgotten_move| forgotten_move.name)
+                .unwrap_or_default();
+            owner.emit_signal("move_learning_finished",
+                &[party_slot.to_variant(), move_name.to_variant(), forgotten_move.to_variant()]);
+            self.continue_level_up(owner);
+        }
+    }
+
+    #[export]
+    /// The player gave up on the new move, so the Pokémon keeps the ones that it already knows
+    fn cancel_move_learning(&mut self, owner: &Node2D) {
+        if let Some((party_slot, new_move)) = self.current_move_learning.take() {
+            owner.emit_signal("move_learning_finished",
+                &[party_slot.to_variant(), new_move.name.to_variant(), Variant::from_str("")]);
+            self.continue_level_up(owner);
+        }
+    }
+
+    /// Looks for the next Pokémon that leveled up and meets the conditions to evolve, and notifies the UI to start the evolution
+    fn start_next_evolution(&mut self, owner: &Node2D) {
+        while !self.leveled_up_slots.is_empty() {
+            let party_slot = self.leveled_up_slots.remove(0);
+            let evolves_to = self.player_data.get_party().get_members().get(party_slot)
+                .and_then(|pokemon| self.species_table.check_evolution(pokemon));
+
+            if let Some(evolves_to) = evolves_to {
+                self.current_evolution = Some((party_slot, evolves_to));
+                // The player can't move while the evolution lasts, and gets released when it finishes
+                self.lock_player_until(owner, "on_evolution", "evolution_finished", "_on_evolution_finished");
+
+                owner.emit_signal("evolution_started", &[party_slot.to_variant(), evolves_to.to_variant()]);
+                return;
+            }
+        }
+    }
+
+    #[export]
+    /// Called by the UI when the evolution animation ends. The Pokémon becomes his new specie
+    fn finish_evolution(&mut self, owner: &Node2D) {
+        if let Some((party_slot, evolves_to)) = self.current_evolution.take() {
+            if let (Some(pokemon), Some(specie)) = (self.player_data.get_party_mut().get_member_mut(party_slot),
+                self.species_table.get_species(evolves_to)) {
+                    pokemon.evolve_into(specie);
+                    self.player_data.get_pokedex_mut().mark_caught(specie.id);
+            }
+            owner.emit_signal("evolution_finished", &[party_slot.to_variant(), false.to_variant()]);
+            self.start_next_evolution(owner);
+        }
+    }
+
+    #[export]
+    /// The player pressed B during the evolution, so the Pokémon stays as it is
+    fn cancel_evolution(&mut self, owner: &Node2D) {
+        if let Some((party_slot, _)) = self.current_evolution.take() {
+            owner.emit_signal("evolution_finished", &[party_slot.to_variant(), true.to_variant()]);
+            self.start_next_evolution(owner);
+        }
+    }
+
+    #[export]
+    /// The current and max HP of the Pokémon of the given side of the battle (Player or Opponent)
+    fn get_battler_hp(&self, _owner: &Node2D, side: String) -> Dictionary {
+        let battler_hp = Dictionary::new();
+        let battler = match BattleSide::from_string(&side) {
+            Some(BattleSide::Player) => self.player_data.get_party().get_members().get(self.battle_party_slot),
+            Some(BattleSide::Opponent) => self.opponent_pokemon.as_ref(),
+            None => None
+        };
+        if let Some(pokemon) = battler {
+            battler_hp.insert("current_hp", pokemon.get_current_hp() as i64);
+            battler_hp.insert("max_hp", pokemon.get_max_hp() as i64);
+        }
+        battler_hp.into_shared()
+    }
+
+    #[export]
+    /// The moves of the Pokémon of the player that it's fighting, for the fight menu. The ones without PP
+    /// can't be selected
+    fn get_battle_moves(&self, _owner: &Node2D) -> VariantArray {
+        let battle_moves = VariantArray::new();
+        if let Some(pokemon) = self.player_data.get_party().get_members().get(self.battle_party_slot) {
+            for pokemon_move in pokemon.get_moves() {
+                let move_data = Dictionary::new();
+                move_data.insert("name", pokemon_move.name.as_str());
+                move_data.insert("type", pokemon_move.move_type.as_str());
+                move_data.insert("pp", pokemon_move.pp as i64);
+                move_data.insert("max_pp", pokemon_move.max_pp as i64);
+                move_data.insert("usable", pokemon_move.pp > 0);
+                battle_moves.push(move_data.into_shared());
+            }
+        }
+        battle_moves.into_shared()
+    }
+
+    #[export]
+    /// The player chose what to do on this turn of the battle: `Fight` with the move of the given slot, or `Run`
+    /// (only from the wild Pokémon). The opponent picks his move too, and the turn starts.
+    ///
+    /// Returns the message to show if the action can't be taken, or an empty string
+    fn select_battle_action(&mut self, owner: &Node2D, action: String, move_slot: i64) -> String {
+        let player_action = match BattleAction::from_string(&action, move_slot.max(0) as usize) {
+            Some(player_action) => player_action,
+            None => {
+                godot_print!("Unknown battle action: {}", action);
+                return BattleError::WrongState(BattleState::SelectingAction).to_message_str().to_string();
+            }
+        };
+        let result = match (self.battle.as_mut(),
+            self.player_data.get_party().get_members().get(self.battle_party_slot), self.opponent_pokemon.as_ref())
+        {
+            (Some(battle), Some(player), Some(opponent)) => {
+                let opponent_action = turn::choose_opponent_action(&mut self.rng, opponent);
+                battle.select_actions(player, opponent, player_action, opponent_action)
+            },
+            _ => Err(BattleError::NotInBattle)
+        };
+        // Trainers never let the player go
+        if result == Err(BattleError::CantRun) && player_action == BattleAction::Run {
+            owner.emit_signal("escape_failed", &[true.to_variant()]);
+        }
+        self.emit_battle_result(owner, result)
+    }
+
+    #[export]
+    /// Resolves the next action of the current turn, emitting a signal for everything that happened, so the
+    /// battle scene can animate it. It must be called again until the battle waits for the input of the player.
+    ///
+    /// Returns the message to show if there's nothing to resolve now, or an empty string
+    fn resolve_battle_action(&mut self, owner: &Node2D) -> String {
+        let result = match (self.battle.as_mut(),
+            self.player_data.get_party_mut().get_member_mut(self.battle_party_slot), self.opponent_pokemon.as_mut())
+        {
+            (Some(battle), Some(player), Some(opponent)) => battle.resolve_next_action(&mut self.rng, player, opponent),
+            _ => Err(BattleError::NotInBattle)
+        };
+        self.emit_battle_result(owner, result)
+    }
+
+    #[export]
+    /// Called by the battle scene once it finished showing the turn, so the player can choose the next action
+    fn acknowledge_battle_turn(&mut self, owner: &Node2D) -> String {
+        let result = match self.battle.as_mut() {
+            Some(battle) => battle.acknowledge_turn(),
+            None => Err(BattleError::NotInBattle)
+        };
+        self.emit_battle_result(owner, result)
+    }
+
+    /// Sends every event of the battle to the battle scene, or gives back the message of the error
+    fn emit_battle_result(&mut self, owner: &Node2D, result: Result<Vec<BattleEvent>, BattleError>) -> String {
+        match result {
+            Ok(events) => {
+                events.into_iter().for_each(|event| self.emit_battle_event(owner, event));
+                String::new()
+            },
+            Err(battle_error) => battle_error.to_message_str().to_string()
+        }
+    }
+
+    fn emit_battle_event(&mut self, owner: &Node2D, event: BattleEvent) {
+        let signal = event.to_signal_str();
+        match event {
+            BattleEvent::MoveUsed { side, move_name, effectiveness } => {
+                owner.emit_signal(signal,
+                    &[side.to_side_str().to_variant(), move_name.to_variant(), (effectiveness as f64).to_variant()]);
+            },
+            BattleEvent::TurnSkipped(side) => {
+                owner.emit_signal(signal, &[side.to_side_str().to_variant()]);
+            },
+            BattleEvent::HpChanged(hp_change) => {
+                owner.emit_signal(signal, &[hp_change.side.to_side_str().to_variant(),
+                    (hp_change.current_hp as i64).to_variant(), (hp_change.max_hp as i64).to_variant()]);
+            },
+            BattleEvent::PokemonFainted(side) => {
+                owner.emit_signal(signal, &[side.to_side_str().to_variant()]);
+                // The Pokémon of the player that defeated the opponent earns his effort values, and the ones that
+                // fought share his experience
+                if side == BattleSide::Opponent {
+                    let defeated = self.opponent_pokemon.as_ref().map(|pokemon| (pokemon.id, pokemon.get_level()));
+                    if let Some((defeated_id, defeated_level)) = defeated {
+                        self.award_battle_evs(self.battle_party_slot, defeated_id);
+                        self.share_defeated_experience(owner, defeated_id, defeated_level);
+                    }
+                }
+            },
+            BattleEvent::OpponentSentOut => {
+                // The trainer replaces his fainted Pokémon with the next one of his team
+                if !self.trainer_team.is_empty() {
+                    let next_pokemon = self.trainer_team.remove(0);
+                    self.player_data.get_pokedex_mut().mark_seen(next_pokemon.id);
+                    owner.emit_signal(signal, &[next_pokemon.id.to_variant(), next_pokemon.get_level().to_variant()]);
+                    self.opponent_pokemon = Some(next_pokemon);
+                }
+            },
+            BattleEvent::EscapedFromBattle => {
+                owner.emit_signal(signal, &[]);
+            },
+            BattleEvent::EscapeFailed => {
+                owner.emit_signal(signal, &[false.to_variant()]);
+            },
+            BattleEvent::StateChanged(state) => {
+                owner.emit_signal(signal, &[state.to_state_str().to_variant()]);
+                // The player that got away goes straight back to the world
+                if state == BattleState::Fled {
+                    self.end_battle(owner);
+                }
+            }
+        }
+    }
+
+    #[export]
+    /// Seeds again the random rolls of the game (encounters, catches...), so the same actions replay the same outcomes
+    fn set_random_seed(&mut self, _owner: &Node2D, seed: i64) {
+        self.rng = SeededRng::from_seed(seed as u64);
+    }
+
+    #[export]
+    /// If the wild Pokémon of the current battle it's shiny, so the battle sprite uses the alternate palette
+    fn is_wild_pokemon_shiny(&self, _owner: &Node2D) -> bool {
+        self.opponent_pokemon.as_ref().map_or(false, Pokemon::is_shiny)
+    }
+
+    #[export]
+    /// Throws a Poké Ball to the wild Pokémon. Every shake of the ball gets notified, so the battle scene can animate it.
+    ///
+    /// A caught Pokémon joins to the player's party, or goes to the PC if the party it's full. When there's no room
+    /// for it anywhere the ball isn't thrown at all.
+    ///
+    /// Returns the message to show if the ball can't be thrown, or an empty string
+    fn throw_pokeball(&mut self, owner: &Node2D, ball_modifier: f64) -> String {
+        // Trainers doesn't like that the player steals his Pokémon
+        if self.trainer_battle.is_some() {
+            return "¡No puedes robar el Pokémon de otro entrenador!".to_string();
+        }
+        if !self.player_data.has_room_for_pokemon() {
+            return "¡No queda sitio para más Pokémon en el PC!".to_string();
+        }
+
+        if let Some(wild_pokemon) = self.opponent_pokemon.to_owned() {
+            let catch_rate = self.species_table.get_species(wild_pokemon.id)
+                .map(|specie| specie.get_catch_rate())
+                .unwrap_or(255);
+
+            let catch_result = capture::attempt_catch(&mut self.rng, wild_pokemon.get_current_hp(),
+                wild_pokemon.get_max_hp(), catch_rate, ball_modifier, wild_pokemon.get_status().catch_bonus());
+
+            for shake in 1..=catch_result.shakes.min(3) {
+                owner.emit_signal("pokeball_shake", &[shake.to_variant()]);
+            }
+
+            if catch_result.caught {
+                let caught_id = wild_pokemon.id;
+                // The Pokémon only counts as caught once it's stored, so it's never lost on the way
+                match self.player_data.receive_pokemon(wild_pokemon) {
+                    Ok(destination) => {
+                        owner.emit_signal("pokemon_caught", &[]);
+                        self.player_data.get_pokedex_mut().mark_caught(caught_id);
+                        self.record_quest_event(owner, quest_events::CATCH.to_string(), caught_id.to_string());
+                        if let PokemonDestination::Box(box_index) = destination {
+                            godot_print!("Pokémon sent to the PC box {}", box_index + 1);
+                        }
+                        self.opponent_pokemon = None;
+                        self.caught_pokemon = Some(destination);
+                        self.open_nickname_menu(owner);
+                    },
+                    Err(pokemon) => {
+                        godot_error!("There's no room for {} on the PC, so it keeps fighting", pokemon.get_display_name());
+                        owner.emit_signal("pokemon_broke_free", &[]);
+                    }
+                }
+            } else {
+                owner.emit_signal("pokemon_broke_free", &[]);
+            }
+        }
+        "".to_string()
+    }
+
+    /// Asks the player for a nickname for the Pokémon that was just caught, over the battle scene
+    fn open_nickname_menu(&self, owner: &Node2D) {
+        let nickname_menu = unsafe { ResourceLoader::godot_singleton()
+            .load(scenes::NICKNAME_MENU, "", false)
+            .and_then(|scene| scene.assume_safe().cast::<PackedScene>()?.instance(0))
+            .map(|node| node.assume_safe()) };
+        let nickname_menu = match nickname_menu {
+            Some(nickname_menu) => nickname_menu,
+            None => {
+                owner.emit_signal("caught_pokemon_named", &[self.get_caught_pokemon_name(owner).to_variant()]);
+                return;
+            }
+        };
+
+        if let Err(err) = nickname_menu.connect("nickname_menu_closed", unsafe { owner.assume_shared() },
+            "_on_nickname_menu_closed", VariantArray::new_shared(), 0)
+        {
+            // The battle would wait forever for the menu, so the Pokémon keeps the specie name
+            godot_error!("Can't know when the nickname menu gets closed: {:?}", err);
+            nickname_menu.queue_free();
+            owner.emit_signal("caught_pokemon_named", &[self.get_caught_pokemon_name(owner).to_variant()]);
+            return;
+        }
+        match self.battle_scene {
+            Some(battle_scene) => unsafe { battle_scene.assume_safe() }.add_child(nickname_menu, false),
+            None => owner.add_child(nickname_menu, false)
+        }
+    }
+
+    #[export]
+    /// The specie name of the Pokémon that was just caught, the name that it keeps if the player doesn't give him a nickname
+    fn get_caught_pokemon_name(&self, _owner: &Node2D) -> String {
+        self.caught_pokemon
+            .and_then(|destination| self.player_data.get_received_pokemon(destination))
+            .map(|pokemon| pokemon.name.to_owned())
+            .unwrap_or_default()
+    }
+
+    #[export]
+    /// Gives the nickname to the Pokémon that was just caught. A blank nickname keeps the specie name.
+    ///
+    /// Returns the message to show if the nickname isn't valid, or an empty string
+    fn set_caught_pokemon_nickname(&mut self, _owner: &Node2D, nickname: String) -> String {
+        let nickname = match validate_nickname(&nickname) {
+            Ok(nickname) => nickname,
+            Err(nickname_error) => return nickname_error.to_message_str()
+        };
+        if let Some(destination) = self.caught_pokemon {
+            if let Some(pokemon) = self.player_data.get_received_pokemon_mut(destination) {
+                pokemon.set_nickname(&nickname);
+            }
+        }
+        "".to_string()
+    }
+
+    #[export]
+    /// The nickname screen got closed (with a new nickname, or cancelled). The battle scene can go on then
+    fn _on_nickname_menu_closed(&mut self, owner: &Node2D) {
+        let name = self.caught_pokemon.take()
+            .and_then(|destination| self.player_data.get_received_pokemon(destination))
+            .map(|pokemon| pokemon.get_display_name().to_string())
+            .unwrap_or_default();
+        owner.emit_signal("caught_pokemon_named", &[name.to_variant()]);
+    }
+
+    #[export]
+    /// Uses an item of the bag on a Pokémon of the party. Called both from the overworld menu and from the battle.
+    ///
+    /// Returns the message that the UI should show when the item can't be used, or an empty string if everything went fine
+    fn use_item(&mut self, _owner: &Node2D, item_id: String, party_slot: i64) -> String {
+        match self.player_data.use_item(&item_id, party_slot as usize) {
+            Ok(()) => "".to_string(),
+            Err(item_error) => item_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Gives an item of the bag to a Pokémon of the party. Returns the message to show if it can't be given, or an empty string
+    fn give_item(&mut self, _owner: &Node2D, item_id: String, party_slot: i64) -> String {
+        match self.player_data.give_item(&item_id, party_slot as usize) {
+            Ok(()) => "".to_string(),
+            Err(item_error) => item_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Throws away some units of an item. Returns the message to show if it can't be tossed, or an empty string
+    fn toss_item(&mut self, _owner: &Node2D, item_id: String, quantity: i64) -> String {
+        match self.player_data.get_bag_mut().toss_item(&item_id, quantity.max(0) as u32) {
+            Ok(()) => "".to_string(),
+            Err(item_error) => item_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// The items of a pocket of the bag, in the order that the bag screen shows them
+    fn get_bag_category(&self, _owner: &Node2D, category: String) -> VariantArray {
+        let category_items = VariantArray::new();
+        if let Some(category) = ItemCategory::from_string(&category) {
+            for (item_id, quantity) in self.player_data.get_bag().get_items_by_category(category) {
+                let item = Dictionary::new();
+                item.insert("item_id", item_id);
+                item.insert("name", get_item_name(item_id));
+                item.insert("quantity", quantity as i64);
+                category_items.push(item.into_shared());
+            }
+        }
+        category_items.into_shared()
+    }
+
+    #[export]
+    /// Puts new items on the bag, like the ones that the player finds on the ground.
+    ///
+    /// Returns the units that really got in. The rest didn't fit on the bag
+    fn add_item_to_bag(&mut self, _owner: &Node2D, item_id: String, quantity: i64) -> i64 {
+        self.player_data.get_bag_mut().add_item(&item_id, quantity.max(0) as u32) as i64
+    }
+
+    #[export]
+    /// If the given units of an item fit on the bag
+    fn fits_in_bag(&self, _owner: &Node2D, item_id: String, quantity: i64) -> bool {
+        self.player_data.get_bag().get_free_space(&item_id) >= quantity.max(0) as u32
+    }
+
+    #[export]
+    fn get_money(&self, _owner: &Node2D) -> i64 {
+        self.player_data.get_money() as i64
+    }
+
+    #[export]
+    /// Gives money to the player, like the prize of a defeated trainer. Returns false if some of it didn't fit
+    fn award_money(&mut self, owner: &Node2D, amount: i64) -> bool {
+        let fits = self.player_data.earn(amount.max(0) as u32);
+        self.notify_money_changed(owner);
+        fits
+    }
+
+    #[export]
+    /// Charges the player for anything that isn't an item of a shop, like the fee of a service. Fails, without
+    /// charging anything, if he doesn't have enough money
+    fn spend_money(&mut self, owner: &Node2D, amount: i64) -> bool {
+        if amount < 0 || !self.player_data.try_spend(amount as u32) {
+            return false;
+        }
+        self.notify_money_changed(owner);
+        true
+    }
+
+    fn notify_money_changed(&self, owner: &Node2D) {
+        owner.emit_signal("money_changed", &[(self.player_data.get_money() as i64).to_variant()]);
+    }
+
+    #[export]
+    /// The items that sells the given shop, with their prices, in the order of the shop config
+    fn get_shop_inventory(&self, _owner: &Node2D, shop_id: String) -> VariantArray {
+        let shop_items = VariantArray::new();
+        for item_id in self.shop_config.get_inventory(&shop_id) {
+            if let Some(price) = shop::get_item_price(item_id) {
+                let item = Dictionary::new();
+                item.insert("item_id", item_id.as_str());
+                item.insert("name", get_item_name(item_id));
+                item.insert("price", price as i64);
+                shop_items.push(item.into_shared());
+            }
+        }
+        shop_items.into_shared()
+    }
+
+    #[export]
+    /// The items of the bag that the shops would buy, with the quantity carried and the price that the shop pays for them
+    fn get_sellable_items(&self, _owner: &Node2D) -> VariantArray {
+        let sellable_items = VariantArray::new();
+        for category in ItemCategory::ALL.iter() {
+            for (item_id, quantity) in self.player_data.get_bag().get_items_by_category(*category) {
+                if let Some(price) = shop::get_sell_price(item_id) {
+                    let item = Dictionary::new();
+                    item.insert("item_id", item_id);
+                    item.insert("name", get_item_name(item_id));
+                    item.insert("quantity", quantity as i64);
+                    item.insert("price", price as i64);
+                    sellable_items.push(item.into_shared());
+                }
+            }
+        }
+        sellable_items.into_shared()
+    }
+
+    #[export]
+    /// Buys some units of an item of the given shop. Returns the message to show if it can't be bought, or an empty string
+    fn buy_item(&mut self, owner: &Node2D, shop_id: String, item_id: String, quantity: i64) -> String {
+        if !self.shop_config.sells(&shop_id, &item_id) {
+            return ShopError::NotOnSale.to_message_str().to_string();
+        }
+        match self.player_data.buy_item(&item_id, quantity.max(1) as u32) {
+            Ok(_) => {
+                self.notify_money_changed(owner);
+                "".to_string()
+            },
+            Err(shop_error) => shop_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Sells some units of an item of the bag. Returns the message to show if it can't be sold, or an empty string
+    fn sell_item(&mut self, owner: &Node2D, item_id: String, quantity: i64) -> String {
+        match self.player_data.sell_item(&item_id, quantity.max(1) as u32) {
+            Ok(_) => {
+                self.notify_money_changed(owner);
+                "".to_string()
+            },
+            Err(shop_error) => shop_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Reads an event flag, so any node (or GDScript) can check if a one-time event already happened
+    fn get_flag(&self, _owner: &Node2D, key: String) -> bool {
+        self.player_data.get_flag(&key)
+    }
+
+    #[export]
+    fn set_flag(&mut self, _owner: &Node2D, key: String, value: bool) {
+        self.player_data.set_flag(&key, value);
+    }
+
+    #[export]
+    fn is_item_ball_collected(&self, _owner: &Node2D, item_ball_id: String) -> bool {
+        self.player_data.is_item_ball_collected(&item_ball_id)
+    }
+
+    #[export]
+    /// Gives the item of an item ball to the player, and remembers that the ball it's already collected.
+    ///
+    /// Returns false if the ball was already collected, so nothing gets added twice, or if the items don't fit on
+    /// the bag. The ball stays on the ground then, until the player makes room for them
+    fn collect_item_ball(&mut self, _owner: &Node2D, item_ball_id: String, item_id: String, quantity: i64) -> bool {
+        let quantity = quantity.max(0) as u32;
+        if self.player_data.is_item_ball_collected(&item_ball_id)
+            || self.player_data.get_bag().get_free_space(&item_id) < quantity {
+            return false;
+        }
+        self.player_data.set_item_ball_collected(&item_ball_id);
+        self.player_data.get_bag_mut().add_item(&item_id, quantity);
+        true
+    }
+
+    #[export]
+    /// The first berry of the bag that can be planted, or an empty string if the player has none
+    fn get_plantable_berry(&self, _owner: &Node2D) -> String {
+        berries::PLANTABLE_BERRIES.iter()
+            .find(|berry_id| self.player_data.get_bag().get_quantity(berry_id) > 0)
+            .map(|berry_id| berry_id.to_string())
+            .unwrap_or_default()
+    }
+
+    #[export]
+    /// The berry planted on the given spot and how grown it is, measured with the in-game time that passed
+    /// since it was planted (even the time of the previous sessions). An empty spot gives an empty dictionary
+    fn get_berry_spot(&self, _owner: &Node2D, spot_id: String) -> Dictionary {
+        let berry_spot = Dictionary::new();
+        if let Some(plant) = self.player_data.get_berry_plant(&spot_id) {
+            let stage = plant.stage(self.time_of_day.get_elapsed_hours());
+            berry_spot.insert("berry_id", plant.get_berry_id());
+            berry_spot.insert("name", get_item_name(plant.get_berry_id()));
+            berry_spot.insert("stage", stage.to_stage_str());
+            berry_spot.insert("message", stage.to_message_str());
+        }
+        berry_spot.into_shared()
+    }
+
+    #[export]
+    /// Plants a berry of the bag on the given spot. Returns the message to show if it can't be planted, or an empty string
+    fn plant_berry(&mut self, _owner: &Node2D, spot_id: String, berry_id: String) -> String {
+        match self.player_data.plant_berry(&spot_id, &berry_id, self.time_of_day.get_elapsed_hours()) {
+            Ok(_) => "".to_string(),
+            Err(berry_error) => berry_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Picks the berries of the given spot, putting the given amount of them on the bag.
+    ///
+    /// Returns the message to show if they can't be harvested (not ready, or the bag it's full), or an empty string
+    fn harvest_berries(&mut self, _owner: &Node2D, spot_id: String, amount: i64) -> String {
+        match self.player_data.harvest_berries(&spot_id, amount.max(1) as u32, self.time_of_day.get_elapsed_hours()) {
+            Ok(_) => "".to_string(),
+            Err(berry_error) => berry_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// The lines of the dialogue with the given id. A dialogue that doesn't exist has no lines, so the signs and the
+    /// cutscenes can fall back to their own texts
+    fn get_dialogue(&self, _owner: &Node2D, dialogue_id: String) -> Vec<String> {
+        if self.dialogue_registry.get_dialogue(&dialogue_id).is_none() {
+            godot_warn!("There's no dialogue with the id {}", dialogue_id);
+        }
+        self.dialogue_registry.get_dialogue_or_empty(&dialogue_id)
+    }
+
+    #[export]
+    /// Reads again every dialogues file, so the texts can be edited without restarting the game. Debug builds only
+    fn reload_dialogues(&mut self, _owner: &Node2D) {
+        if !OS::godot_singleton().is_debug_build() {
+            return;
+        }
+        self.dialogue_registry = utils::retrieve_dialogue_registry();
+        godot_print!("Reloaded {} dialogues", self.dialogue_registry.len());
+    }
+
+    #[export]
+    /// Starts the given quest. Returns the message to show if it can't be started, or an empty string
+    fn start_quest(&mut self, _owner: &Node2D, quest_id: String) -> String {
+        match self.player_data.start_quest(&self.quest_table, &quest_id) {
+            Ok(_) => "".to_string(),
+            Err(quest_error) => quest_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Counts the given times on an objective of an active quest. Returns the message to show if it can't be
+    /// counted, or an empty string
+    fn advance_quest_objective(&mut self, owner: &Node2D, quest_id: String, objective_id: String, amount: i64) -> String {
+        match self.player_data.advance_quest_objective(&self.quest_table, &quest_id, &objective_id, amount.max(0) as u32) {
+            Ok(completed) => {
+                if completed {
+                    owner.emit_signal("quest_completed", &[quest_id.to_variant()]);
+                }
+                "".to_string()
+            },
+            Err(quest_error) => quest_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// Finishes an active quest, even with objectives still missing. Returns the message to show if it isn't
+    /// active, or an empty string
+    fn complete_quest(&mut self, owner: &Node2D, quest_id: String) -> String {
+        match self.player_data.complete_quest(&quest_id) {
+            Ok(_) => {
+                owner.emit_signal("quest_completed", &[quest_id.to_variant()]);
+                "".to_string()
+            },
+            Err(quest_error) => quest_error.to_message_str().to_string()
+        }
+    }
+
+    #[export]
+    /// NotStarted, Active or Completed
+    fn get_quest_state(&self, _owner: &Node2D, quest_id: String) -> String {
+        self.player_data.get_quest_state(&quest_id).to_state_str().to_string()
+    }
+
+    #[export]
+    /// Something happened on the world (the player talked with someone, caught a Pokémon...). Every objective of
+    /// the active quests that waits for it moves forward
+    fn record_quest_event(&mut self, owner: &Node2D, kind: String, target: String) {
+        let event = quests::quest_event_key(&kind, &target);
+        for quest_id in self.player_data.record_quest_event(&self.quest_table, &event, 1) {
+            owner.emit_signal("quest_completed", &[quest_id.to_variant()]);
+        }
+    }
+
+    #[export]
+    /// The quests on the given state (Active or Completed), with the progress of every objective, for the quests menu
+    fn get_quests(&self, _owner: &Node2D, state: String) -> VariantArray {
+        let state = match state.as_str() {
+            "Completed" => QuestState::Completed,
+            _ => QuestState::Active
+        };
+        let quest_list = VariantArray::new();
+        for quest_id in self.player_data.get_quest_ids(state) {
+            let definition = match self.quest_table.get_quest(&quest_id) {
+                Some(definition) => definition,
+                None => continue
+            };
+            let objectives = VariantArray::new();
+            for objective in definition.objectives.iter() {
+                let progress = self.player_data.get_quest_progress(&quest_id)
+                    .map(|progress| progress.get_objective_progress(&objective.id))
+                    .unwrap_or(0);
+                let objective_info = Dictionary::new();
+                objective_info.insert("description", objective.description.to_owned());
+                objective_info.insert("progress", progress);
+                objective_info.insert("goal", objective.goal);
+                objectives.push(objective_info.into_shared());
+            }
+
+            let quest_info = Dictionary::new();
+            quest_info.insert("quest_id", quest_id.to_owned());
+            quest_info.insert("name", definition.name.to_owned());
+            quest_info.insert("description", definition.description.to_owned());
+            quest_info.insert("objectives", objectives.into_shared());
+            quest_list.push(quest_info.into_shared());
+        }
+        quest_list.into_shared()
+    }
+
+    #[export]
+    /// If the player can run, having the running shoes on his bag
+    fn has_running_shoes(&self, _owner: &Node2D) -> bool {
+        self.player_data.has_running_shoes()
+    }
+
+    #[export]
+    /// If the player it's riding his bike
+    fn is_on_bike(&self, _owner: &Node2D) -> bool {
+        self.player_data.is_on_bike()
+    }
+
+    #[export]
+    /// Gives the running shoes to the player, called from the NPC or the event that gifts them.
+    /// Returns false if he already had them, so nobody gifts them twice
+    fn give_running_shoes(&mut self, _owner: &Node2D) -> bool {
+        if self.player_data.has_running_shoes() {
+            return false;
+        }
+        self.player_data.get_bag_mut().add_item(RUNNING_SHOES, 1);
+        true
+    }
+
+    #[export]
+    /// How many species the player has seen and caught, and the percentage of every specie of the game caught
+    fn get_pokedex_counts(&self, _owner: &Node2D) -> Dictionary {
+        let pokedex = self.player_data.get_pokedex();
+        let pokedex_counts = Dictionary::new();
+        pokedex_counts.insert("seen", pokedex.seen_count() as i64);
+        pokedex_counts.insert("caught", pokedex.caught_count() as i64);
+        pokedex_counts.insert("completion", pokedex.completion_percentage(self.species_table.len()));
+        pokedex_counts.into_shared()
+    }
+
+    #[export]
+    /// If the player has seen (`Seen`), caught (`Caught`) or never found (`Unknown`) the given specie
+    fn get_pokedex_status(&self, _owner: &Node2D, specie_id: i32) -> String {
+        self.player_data.get_pokedex().get_status(specie_id).to_status_str().to_string()
+    }
+
+    #[export]
+    /// The data of every Pokémon of the party, in the order that the party menu shows them
+    fn get_party_summary(&self, _owner: &Node2D) -> VariantArray {
+        let party_summary = VariantArray::new();
+        for pokemon in self.player_data.get_party().get_members() {
+            let stats = pokemon.get_stats();
+            let pokemon_summary = Dictionary::new();
+            pokemon_summary.insert("name", pokemon.get_display_name());
+            pokemon_summary.insert("level", pokemon.get_level() as i64);
+            pokemon_summary.insert("current_hp", pokemon.get_current_hp() as i64);
+            pokemon_summary.insert("max_hp", pokemon.get_max_hp() as i64);
+            pokemon_summary.insert("status", pokemon.get_status().to_abbreviation_str());
+            pokemon_summary.insert("held_item", pokemon.get_held_item().map(get_item_name).unwrap_or(""));
+            pokemon_summary.insert("shiny", pokemon.is_shiny());
+            pokemon_summary.insert("attack", stats.attack as i64);
+            pokemon_summary.insert("defense", stats.defense as i64);
+            pokemon_summary.insert("special_attack", stats.special_attack as i64);
+            pokemon_summary.insert("special_defense", stats.special_defense as i64);
+            pokemon_summary.insert("speed", stats.speed as i64);
+            party_summary.push(pokemon_summary.into_shared());
+        }
+        party_summary.into_shared()
+    }
+
+    #[export]
+    /// Everything that the summary screen shows about a Pokémon of the party: his stats, his nature and his moves.
+    /// Empty if there's no Pokémon on the given slot
+    fn get_pokemon_summary(&self, _owner: &Node2D, party_slot: i64) -> Dictionary {
+        let pokemon_summary = Dictionary::new();
+        let pokemon = match self.player_data.get_party().get_members().get(party_slot.max(0) as usize) {
+            Some(pokemon) => pokemon,
+            None => return pokemon_summary.into_shared()
+        };
+
+        let stats = pokemon.get_stats();
+        pokemon_summary.insert("name", pokemon.get_display_name());
+        pokemon_summary.insert("specie", pokemon.name.as_str());
+        pokemon_summary.insert("type1", pokemon.type1.as_str());
+        pokemon_summary.insert("type2", pokemon.type2.as_str());
+        pokemon_summary.insert("level", pokemon.get_level() as i64);
+        pokemon_summary.insert("nature", pokemon.get_nature().to_spanish_str());
+        pokemon_summary.insert("shiny", pokemon.is_shiny());
+        pokemon_summary.insert("status", pokemon.get_status().to_abbreviation_str());
+        pokemon_summary.insert("current_hp", pokemon.get_current_hp() as i64);
+        pokemon_summary.insert("max_hp", pokemon.get_max_hp() as i64);
+        pokemon_summary.insert("attack", stats.attack as i64);
+        pokemon_summary.insert("defense", stats.defense as i64);
+        pokemon_summary.insert("special_attack", stats.special_attack as i64);
+        pokemon_summary.insert("special_defense", stats.special_defense as i64);
+        pokemon_summary.insert("speed", stats.speed as i64);
+
+        let moves = VariantArray::new();
+        for pokemon_move in pokemon.get_moves() {
+            let move_summary = Dictionary::new();
+            move_summary.insert("name", pokemon_move.name.as_str());
+            move_summary.insert("type", pokemon_move.move_type.as_str());
+            move_summary.insert("pp", pokemon_move.pp as i64);
+            move_summary.insert("max_pp", pokemon_move.max_pp as i64);
+            moves.push(move_summary.into_shared());
+        }
+        pokemon_summary.insert("moves", moves.into_shared());
+        pokemon_summary.into_shared()
+    }
+
+    #[export]
+    /// Reorders the party, swapping the Pokémon of the two given slots
+    fn swap_party_members(&mut self, _owner: &Node2D, first_slot: i64, second_slot: i64) -> bool {
+        self.player_data.get_party_mut().swap_members(first_slot as usize, second_slot as usize)
+    }
+
+    #[export]
+    /// The items of the bag and how many units of each one the player has
+    fn get_bag_contents(&self, _owner: &Node2D) -> Dictionary {
+        let bag_contents = Dictionary::new();
+        for (item_id, quantity) in self.player_data.get_bag().get_items() {
+            bag_contents.insert(item_id, *quantity);
+        }
+        bag_contents.into_shared()
+    }
+
+    /// Sends an interaction signal to the player, like the ones that lock him while something happens and release him
+    /// after it
+    fn notify_player(&self, owner: &Node2D, interaction: &str) {
+        if let Some(player) = utils::try_get_node_as::<Node>(owner, "Player") {
+            unsafe { player.call("handle_interaction", &[Variant::from_str(interaction)]) };
+        }
+    }
+
+    /// Locks the player with the given interaction until the Game emits the signal that releases him. If the release
+    /// can't be connected the player doesn't get locked at all, so he never gets stuck
+    fn lock_player_until(&self, owner: &Node2D, interaction: &str, release_signal: &str, release_method: &str) {
+        let player = match utils::try_get_node_as::<Node>(owner, "Player") {
+            Some(player) => player,
+            None => return
+        };
+        if let Err(err) = connect_oneshot(owner, release_signal, player, release_method) {
+            godot_error!("Can't release the player on {}, so he doesn't get locked: {:?}", release_signal, err);
+            return;
+        }
+        unsafe { player.call("handle_interaction", &[Variant::from_str(interaction)]) };
+    }
+
+    /// Shows or hides the world where the player is (the Map or the interior of a building) and the player himself
+    fn set_world_visible(&self, owner: &Node2D, visible: bool) {
+        let world = if self.current_scene_type == CurrentSceneType::Indoors {
+            self.current_scene
+        } else {
+            self.world_map_node
+        };
+
+        if let Some(world) = world {
+            unsafe { world.assume_safe().cast::<Node2D>().unwrap().set_visible(visible) };
+        }
+        unsafe { owner.get_node_as::<Node2D>("Player").unwrap().set_visible(visible) };
+    }
+
+    /// Fades the screen to black through the TransitionOverlay, and calls the given method of the Game when the
+    /// screen it's completly black. Without the overlay, the method just gets called on the next frame
+    fn transition_and_call(&self, owner: &Node2D, method: &str, args: &[Variant], release_player: bool) {
+        let method_args = VariantArray::new();
+        args.iter().for_each(|arg| method_args.push(arg.clone()));
+
+        match unsafe { owner.get_node("/root/TransitionOverlay") } {
+            Some(transition_overlay) => unsafe { transition_overlay.assume_safe().call("transition_and_call", &[
+                self.game_node.unwrap().to_variant(), method.to_variant(),
+                method_args.into_shared().to_variant(), release_player.to_variant()
+            ]) },
+            None => unsafe { owner.call_deferred(method, args) }
+        };
+    }
+
+
+    // <--------------------------- HTTP ZONE CONTROL --------------------------------------->    
+
+    /// Creates a new HTTP Godot Node and insert it into a tree. When a url is specified, performs an HTTP request, and if `connect_to`
+    /// parameter is specified, connect a signal to this class, that it's where the HTTP response comes.
+    fn new_http_node(&self, owner: &Node2D, url: &str, connect_to: &str) -> Result<(), GodotError> {
+        let http_request: Ref<HTTPRequest, Unique> = HTTPRequest::new();
+        let http_request_as_node = unsafe { http_request.assume_safe_unchecked().assume_shared().assume_safe() };
+        
+        owner.add_child(http_request_as_node, true);
+        
+        http_request_as_node.connect("request_completed", self.game_node.unwrap(), connect_to,
+            VariantArray::new_shared(), 0).unwrap();
+
+        // Performs an http request, returning Result<(), GodotError>
+        http_request.request(url, TypedArray::new(),
+            true, HTTPClient::METHOD_GET, "")
+    }
+
+    /// Retrieves all the external game data, like city's weather and game's sunrise and sunset hours
+    /// from our Java Spring backend server
+    fn get_external_game_data(&self, owner: &Node2D) {
+        let url: &'static str;
+        
+        if self.in_development { url = self.development_url } else { url = self.production_url }
+        
+        match self.new_http_node(owner, url, "_get_java_spring_backend_response")
+        {
+            Ok(response) => response,
+            Err(err) => godot_print!("Err => {:?}", err)
+        }
+    }
+
+    #[export]
+    /// The method that receives and Http Response with all the external game data
+    fn _get_java_spring_backend_response(&mut self, _owner: &Node2D, _result: Variant, _response_code: i64, _headers: Variant, body: ByteArray) {
+        godot_print!("Spring backend response code: {:?}", _response_code);
+        if _response_code == 200 {
+            self.game_external_data.spring_backend_response_code = _response_code;
+
+            // ! Here comes the http response, parsed as a Variant<Dictionary>
+            let response = networking::http_body_to_string(body);
+
+            self.set_sunrise_sunset_hours(&response);
+            self.set_cities_weather(&response);
+           
+        } else {
+            self.game_external_data.spring_backend_response_code = _response_code;
+            godot_print!("FAILED to get backend information. Response code: {:?}", _response_code);
+        }
+    }
+
+    // Encapsulates the process of sets the Sunrise/Sunset hours from the Http Response
+    fn set_sunrise_sunset_hours(&mut self, response: &Dictionary) {
+        let sunrise_hour = response.get("sunriseHour").to_string().parse::<i32>().unwrap();
+        self.game_external_data.todays_sunrise_time = utils::convert_from_unix_timestamp(
+            sunrise_hour + game_consts::UNIX_TIMESTAMP_OFFSET);
+
+        let sunset_hour = response.get("sunsetHour").to_string().parse::<i32>().unwrap();
+        self.game_external_data.todays_sunset_time = utils::convert_from_unix_timestamp(
+            sunset_hour + game_consts::UNIX_TIMESTAMP_OFFSET);
+    }
+
+    // Encapsulates the process of sets the weather of all the cities of the game
+    fn set_cities_weather(&mut self, response: &Dictionary) {
+        let current_weather = response.get("gameCities").to_array();
+
+        godot_print!("\nWEATHER: {:?}\n", &current_weather);
+
+        // Iterate all over the game cities / towns
+        let mut idx: i32 = 0;
+        // ! IMPORTANT: Our REST API always send the cities ordered by ID. The `self.game_cities` attribute stores cities created
+        // in base the order that the cities are hardcoded in the vector returned by the `GameCity::values()` associated fn.
+        // That order maps the ID of the cities on the JSON.
+        for location in self.game_cities.iter_mut() {
+            let current_idx_data = current_weather.get(idx).to_dictionary();
+            if location.get_name() == current_idx_data.get("name").to_string() {
+            
+                let external_weather_data = current_idx_data
+                        .get("weather")
+                        .to_dictionary();
+                
+                let location_weather_instance = CityWeather::new(
+                    external_weather_data.get("weatherIDCode").to_i64() as i32,
+                    external_weather_data.get("mainCode").to_string(),
+                    external_weather_data.get("description").to_string(),
+                    external_weather_data.get("icon").to_string()
+                );
+
+                location.set_weather(location_weather_instance);
+                idx += 1;
+                self.game_external_data.cities_weather_loaded = true;
+            } else {
+                godot_print!("Something went wrong retriving data and matching it with the correct city at a given index");
+            }
+        }
+        godot_print!("\n************\nGAME CITIES and it's data: {:?}", &self.game_cities);
+    }
+
+    // <------------------------- WEATHER CONTROL ----------------------->
+    #[export]
+    /// Swaps the weather of the game, like when the player walks into a zone with his own weather
+    fn change_weather(&mut self, owner: &Node2D, weather: String) {
+        match FieldWeather::from_string(&weather) {
+            Some(field_weather) if field_weather != self.field_weather => {
+                self.field_weather = field_weather;
+                owner.emit_signal("weather_changed", &[field_weather.to_weather_str().to_variant()]);
+            },
+            Some(_) => (),
+            None => godot_print!("Unknown weather: {}", weather)
+        }
+    }
+
+    #[export]
+    fn get_field_weather(&self, _owner: &Node2D) -> String {
+        self.field_weather.to_weather_str().to_string()
+    }
+
+    #[export]
+    /// The damage multiplier that the current weather applies to an attack of the given type (with his spanish name,
+    /// as the species data stores them). Used by the battle when calculates the damage
+    fn get_weather_damage_multiplier(&self, _owner: &Node2D, attack_type: String) -> f64 {
+        Type::from_string(&attack_type)
+            .map(|attack_type| self.field_weather.damage_multiplier(attack_type) as f64)
+            .unwrap_or(1.0)
+    }
+
+    #[export]
+    fn weather_control(&mut self, owner: &Node2D) {
+        if self.current_scene_type == CurrentSceneType::Outdoors {
+            for location in self.game_cities.iter_mut() {
+                godot_print!("City name as node path {:?}", &location.get_as_node_path());
+                
+                let mut node_path: String = "Map/".to_owned() + location.get_as_node_path().as_str() + "/Weather";
+                godot_print!("Full final node path {:?}", &node_path);
+                match location.get_weather().as_ref().unwrap().get_main_code_as_weather_variant() {
+                    Weather::Thunderstorm => node_path.push_str("/Thunderstorm"),
+                    Weather::Drizzle => node_path.push_str("/Drizzle"),
+                    Weather::Rain => node_path.push_str("/Rain"),
+                    Weather::Snow => node_path.push_str("/Snow"),
+                    Weather::Sun => node_path.push_str("/Sun"),
+                    Weather::Clouds => node_path.push_str("/Clouds"),
+                };
+                
+                if let Some(weather_node) = unsafe { owner.get_node_as::<Particles2D>(node_path.as_str()) } {
+                    weather_node.set_emitting(true);
+                }
+            }
+        }   
+    }
+
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone)]
+pub struct GameExternalData {
+    todays_date: String,
+    todays_day_of_the_week: String,
     cities_weather_loaded: bool,
     spring_backend_response_code: i64,
     todays_sunrise_time: String,
@@ -636,4 +2756,29 @@ pub enum DayNightCycle {
 
 impl Default for DayNightCycle {
     fn default() -> Self { DayNightCycle::NoData }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_process_counter_wraps_before_overflowing() {
+        assert_eq!(next_process_count(0), 1);
+        assert_eq!(next_process_count(PROCESS_COUNTER_WRAP - 2), PROCESS_COUNTER_WRAP - 1);
+        assert_eq!(next_process_count(PROCESS_COUNTER_WRAP - 1), 0);
+
+        let mut number_of_process = 0;
+        for _ in 0..10 * PROCESS_COUNTER_WRAP {
+            number_of_process = next_process_count(number_of_process);
+            assert!((0..PROCESS_COUNTER_WRAP).contains(&number_of_process));
+        }
+    }
+
+    #[test]
+    fn no_interval_skips_a_beat_when_the_counter_wraps() {
+        for interval in [WAITING_LOG_INTERVAL, WEATHER_CHECK_INTERVAL].iter() {
+            assert_eq!(PROCESS_COUNTER_WRAP % interval, 0, "{}", interval);
+        }
+    }
+}
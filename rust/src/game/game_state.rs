@@ -0,0 +1,128 @@
+/// What the whole game it's doing right now. Only on the `Overworld` the player can be controlled.
+///
+/// The Game node holds it and announces every change, so any system can follow it instead of guessing it
+/// from the statuses of the player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameState {
+    Overworld,
+    Dialogue,
+    Menu,
+    Battle,
+    Cutscene,
+    // A fade between two scenes, or into a battle
+    Transition
+}
+
+impl Default for GameState {
+    fn default() -> Self { GameState::Overworld }
+}
+
+impl GameState {
+    pub fn to_state_str(&self) -> &'static str {
+        match self {
+            GameState::Overworld => "Overworld",
+            GameState::Dialogue => "Dialogue",
+            GameState::Menu => "Menu",
+            GameState::Battle => "Battle",
+            GameState::Cutscene => "Cutscene",
+            GameState::Transition => "Transition"
+        }
+    }
+
+    pub fn from_state_str(state: &str) -> Option<GameState> {
+        match state {
+            "Overworld" => Some(GameState::Overworld),
+            "Dialogue" => Some(GameState::Dialogue),
+            "Menu" => Some(GameState::Menu),
+            "Battle" => Some(GameState::Battle),
+            "Cutscene" => Some(GameState::Cutscene),
+            "Transition" => Some(GameState::Transition),
+            _ => None
+        }
+    }
+
+    /// The state that locks the player for every interaction payload that the PlayerCharacter receives.
+    /// None for the payloads that doesn't change the state of the game
+    pub fn from_interaction(signal_info: &str) -> Option<GameState> {
+        match signal_info {
+            "on_dialogue" | "on_healing" => Some(GameState::Dialogue),
+            "menu_active" | "on_shop" | "on_move_learning" => Some(GameState::Menu),
+            "trainer_engaged" | "on_evolution" | "on_cutscene" | "on_fishing" => Some(GameState::Cutscene),
+            "on_encounter" | "on_transition" => Some(GameState::Transition),
+            "end_interaction" => Some(GameState::Overworld),
+            _ => None
+        }
+    }
+
+    /// If the game can go straight from this state to the given one. Going back to the overworld it's always
+    /// allowed, but the battles only start behind a transition, or after a trainer walks to the player
+    pub fn can_transition_to(&self, next: GameState) -> bool {
+        if *self == next || next == GameState::Overworld {
+            return true;
+        }
+        match self {
+            GameState::Overworld => true,
+            GameState::Dialogue => next != GameState::Battle,
+            GameState::Menu => matches!(next, GameState::Dialogue | GameState::Transition),
+            GameState::Battle => matches!(next, GameState::Dialogue | GameState::Cutscene | GameState::Transition),
+            GameState::Cutscene => true,
+            GameState::Transition => next != GameState::Menu
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATES: [GameState; 6] = [GameState::Overworld, GameState::Dialogue, GameState::Menu,
+        GameState::Battle, GameState::Cutscene, GameState::Transition];
+
+    #[test]
+    fn the_allowed_and_rejected_transitions() {
+        // The rows are the current state and the columns the next one, on the order of `ALL_STATES`
+        let allowed = [
+            /* Overworld  */ [true, true, true, true, true, true],
+            /* Dialogue   */ [true, true, true, false, true, true],
+            /* Menu       */ [true, true, true, false, false, true],
+            /* Battle     */ [true, true, false, true, true, true],
+            /* Cutscene   */ [true, true, true, true, true, true],
+            /* Transition */ [true, true, false, true, true, true],
+        ];
+        for (current, row) in ALL_STATES.iter().zip(allowed.iter()) {
+            for (next, expected) in ALL_STATES.iter().zip(row.iter()) {
+                assert_eq!(current.can_transition_to(*next), *expected, "{:?} -> {:?}", current, next);
+            }
+        }
+    }
+
+    #[test]
+    fn the_state_strings_round_trip() {
+        for state in ALL_STATES.iter() {
+            assert_eq!(GameState::from_state_str(state.to_state_str()), Some(*state));
+        }
+        assert_eq!(GameState::from_state_str("Paused"), None);
+    }
+
+    #[test]
+    fn the_interaction_payloads_lock_the_player() {
+        assert_eq!(GameState::from_interaction("on_dialogue"), Some(GameState::Dialogue));
+        assert_eq!(GameState::from_interaction("on_shop"), Some(GameState::Menu));
+        assert_eq!(GameState::from_interaction("trainer_engaged"), Some(GameState::Cutscene));
+        assert_eq!(GameState::from_interaction("on_encounter"), Some(GameState::Transition));
+        assert_eq!(GameState::from_interaction("end_interaction"), Some(GameState::Overworld));
+        assert_eq!(GameState::from_interaction("unknown"), None);
+    }
+
+    #[test]
+    fn the_evolution_locks_the_player_until_it_finishes() {
+        // The Game sends `on_evolution` when it starts, and the one-shot `evolution_finished` ends it
+        let evolving = GameState::from_interaction("on_evolution").unwrap();
+        assert_eq!(evolving, GameState::Cutscene);
+        assert!(GameState::Overworld.can_transition_to(evolving));
+
+        let finished = GameState::from_interaction("end_interaction").unwrap();
+        assert_eq!(finished, GameState::Overworld);
+        assert!(evolving.can_transition_to(finished));
+    }
+}
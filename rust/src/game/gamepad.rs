@@ -0,0 +1,39 @@
+use gdnative::prelude::*;
+use gdnative::api::GlobalConstants;
+
+// The joypad that moves the player. Only the first one connected it's read
+const PLAYER_DEVICE: i64 = 0;
+// The slowest that the player walks with the stick barely out of the deadzone, as a fraction of the full speed
+pub const MIN_STICK_SPEED_SCALE: f64 = 0.5;
+
+/// Reads the left stick of the gamepad, from -1.0 to 1.0 on every axis
+pub fn read_left_stick(input: &Input) -> Vector2 {
+    Vector2::new(
+        input.get_joy_axis(PLAYER_DEVICE, GlobalConstants::JOY_ANALOG_LX) as f32,
+        input.get_joy_axis(PLAYER_DEVICE, GlobalConstants::JOY_ANALOG_LY) as f32
+    )
+}
+
+/// Turns the deflection of the stick into one of the directions that the player can walk on the grid,
+/// and the fraction of the speed that the player gets from how far the stick it's pushed.
+///
+/// The stick inside the deadzone doesn't move the player. Out of it, the axis pushed the most wins, unless the
+/// diagonals are allowed: then the stick snaps to the closest of the eight directions
+pub fn stick_to_movement(stick: Vector2, deadzone: f64, diagonals: bool) -> Option<(Vector2, f64)> {
+    let deflection = (stick.length() as f64).min(1.0);
+    if deflection <= deadzone {
+        return None;
+    }
+
+    let direction = if diagonals {
+        let snapped_angle = (stick.y.atan2(stick.x) / std::f32::consts::FRAC_PI_4).round() * std::f32::consts::FRAC_PI_4;
+        Vector2::new(snapped_angle.cos().round(), snapped_angle.sin().round())
+    } else if stick.x.abs() >= stick.y.abs() {
+        Vector2::new(stick.x.signum(), 0.0)
+    } else {
+        Vector2::new(0.0, stick.y.signum())
+    };
+    // The deflection starts counting from the edge of the deadzone, so the speed grows smoothly from there
+    let magnitude = ((deflection - deadzone) / (1.0 - deadzone).max(f64::EPSILON)).min(1.0);
+    Some((direction, MIN_STICK_SPEED_SCALE + (1.0 - MIN_STICK_SPEED_SCALE) * magnitude))
+}
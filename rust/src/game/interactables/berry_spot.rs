@@ -0,0 +1,179 @@
+use gdnative::prelude::*;
+
+use crate::game::bag::get_item_name;
+use crate::game::berries::GrowthStage;
+use crate::game::code_abstractions::{
+    interactions::Interactable,
+    signals::connect_oneshot
+};
+
+const DIALOGUE_BOX_PATH: &str = "/root/Game/Player/Camera2D/CanvasLayer/DialogueBox";
+// The sprite of the plant. Every growth stage it's a frame of it, in the same order than the stages
+const PLANT_NODE: &str = "Plant";
+// Seconds between every look at the growth of the plant, while the spot it's on the scene
+const REFRESH_SECONDS: f64 = 1.0;
+
+const SOIL_TEXT: &str = "La tierra es blanda y fértil.";
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+#[derive(Debug)]
+/// A patch of soft soil where the player can plant a berry.
+///
+/// The berry grows with the in-game time, even while the player it's far away or the game it's closed, and once
+/// it's ready the player harvests a handful of new berries, leaving the soil empty again
+pub struct BerrySpot {
+    // Unique identifier of the spot. If it's empty, the path of the node on the scene tree it's used
+    #[property]
+    spot_id: String,
+    // How many berries the player gets on every harvest
+    #[property(default = 3)]
+    harvest_amount: i64,
+
+    // The berry that the player was asked to plant
+    berry_to_plant: String,
+    // Seconds until the next look at the growth of the plant
+    refresh_in: f64,
+}
+
+impl Interactable<Node2D> for BerrySpot {
+    fn on_interact(&mut self, owner: TRef<Node2D>) {
+        let game = match unsafe { owner.get_node("/root/Game") } {
+            Some(game) => unsafe { game.assume_safe() },
+            None => return
+        };
+
+        let berry_spot = unsafe { game.call("get_berry_spot", &[self.spot_id.to_variant()]) }.to_dictionary();
+        if berry_spot.is_empty() {
+            self.berry_to_plant = unsafe { game.call("get_plantable_berry", &[]) }.to_string();
+            if self.berry_to_plant.is_empty() {
+                self.show_dialogue(owner, (0, Vec::new(), vec![SOIL_TEXT.to_string()]), "_on_dialogue_closed");
+            } else {
+                let dialogue_data = (
+                    1,
+                    vec!["Sí".to_string(), "No".to_string()],
+                    vec![format!("{}\n¿Quieres plantar una {}?", SOIL_TEXT, get_item_name(&self.berry_to_plant))]
+                );
+                self.show_dialogue(owner, dialogue_data, "_on_planting_answered");
+            }
+            return;
+        }
+
+        let name = berry_spot.get("name").to_string();
+        let text = if berry_spot.get("stage").to_string() == GrowthStage::Ready.to_stage_str() {
+            let harvest_error = unsafe { game.call("harvest_berries",
+                &[self.spot_id.to_variant(), self.harvest_amount.to_variant()]) }.to_string();
+            if harvest_error.is_empty() {
+                format!("{}\n¡Has recogido {} x{}!", berry_spot.get("message"), name, self.harvest_amount.max(1))
+            } else {
+                format!("{}\n{}", berry_spot.get("message"), harvest_error)
+            }
+        } else {
+            format!("{}\n({})", berry_spot.get("message"), name)
+        };
+        self.show_dialogue(owner, (0, Vec::new(), vec![text]), "_on_dialogue_closed");
+    }
+}
+
+#[gdnative::methods]
+impl BerrySpot {
+    fn new(_owner: &Node2D) -> Self {
+        Self {
+            spot_id: "".to_string(),
+            harvest_amount: 3,
+            berry_to_plant: "".to_string(),
+            refresh_in: 0.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Node2D) {
+        if self.spot_id.is_empty() {
+            self.spot_id = owner.get_path().to_string();
+        }
+        self.refresh_plant(owner);
+    }
+
+    #[export]
+    /// The time keeps passing while the player it's around, so the plant can grow in front of him
+    fn _process(&mut self, owner: &Node2D, delta: f64) {
+        self.refresh_in -= delta;
+        if self.refresh_in <= 0.0 {
+            self.refresh_in = REFRESH_SECONDS;
+            self.refresh_plant(owner);
+        }
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Node2D>) {
+        self.on_interact(owner);
+    }
+
+    #[export]
+    /// The player answered if he wants to plant his berry. Only the first option (yes) plants it
+    fn _on_planting_answered(&mut self, owner: TRef<Node2D>, _signal_info: Variant) {
+        let decision = unsafe { owner.get_node(DIALOGUE_BOX_PATH) }
+            .map(|dialogue_box| unsafe { dialogue_box.assume_safe().call("get_last_decision", &[]) }.to_i64())
+            .unwrap_or(0);
+        if decision != 1 {
+            return;
+        }
+
+        let error = match unsafe { owner.get_node("/root/Game") } {
+            Some(game) => unsafe { game.assume_safe().call("plant_berry",
+                &[self.spot_id.to_variant(), self.berry_to_plant.to_variant()]) }.to_string(),
+            None => return
+        };
+        let text = if error.is_empty() {
+            format!("¡Has plantado una {}!", get_item_name(&self.berry_to_plant))
+        } else {
+            error
+        };
+        // The dialogue box it's still closing, so the new dialogue waits until the next frame
+        unsafe { owner.call_deferred("_show_planting_result", &[text.to_variant()]) };
+    }
+
+    #[export]
+    fn _show_planting_result(&mut self, owner: TRef<Node2D>, text: String) {
+        self.refresh_plant(&owner);
+        self.show_dialogue(owner, (0, Vec::new(), vec![text]), "_on_dialogue_closed");
+    }
+
+    #[export]
+    fn _on_dialogue_closed(&mut self, owner: TRef<Node2D>, _signal_info: Variant) {
+        self.refresh_plant(&owner);
+    }
+
+    /// Shows the frame of the plant that matches his growth stage, or hides it when the soil it's empty
+    fn refresh_plant(&self, owner: &Node2D) {
+        let plant = match unsafe { owner.get_node_as::<Sprite>(PLANT_NODE) } {
+            Some(plant) => plant,
+            None => return
+        };
+        let stage = unsafe { owner.get_node("/root/Game") }
+            .map(|game| unsafe { game.assume_safe().call("get_berry_spot", &[self.spot_id.to_variant()]) }.to_dictionary())
+            .map(|berry_spot| berry_spot.get("stage").to_string())
+            .and_then(|stage| GrowthStage::ALL.iter().position(|growth_stage| growth_stage.to_stage_str() == stage));
+
+        match stage {
+            Some(stage) => {
+                plant.set_frame(stage as i64);
+                plant.set_visible(true);
+            },
+            None => plant.set_visible(false)
+        }
+    }
+
+    /// Prints the dialogue on the Dialogue Box, calling back the given method when the player closes it
+    fn show_dialogue(&self, owner: TRef<Node2D>, dialogue_data: (i32, Vec<String>, Vec<String>), callback: &str) {
+        let dialogue_box = match unsafe { owner.get_node(DIALOGUE_BOX_PATH) } {
+            Some(dialogue_box) => unsafe { dialogue_box.assume_safe() },
+            None => return
+        };
+        if let Err(err) = connect_oneshot(&dialogue_box, "dialogue_box_inactive", owner, callback) {
+            godot_error!("Can't wait for the dialogue of the berry spot: {:?}", err);
+            return;
+        }
+        unsafe { dialogue_box.call("_print_dialogue", &[dialogue_data.to_variant()]) };
+    }
+}
@@ -0,0 +1,227 @@
+use gdnative::prelude::*;
+use gdnative::api::CanvasItem;
+
+use crate::game::bag::get_item_name;
+use crate::game::code_abstractions::{
+    interactions::Interactable,
+    signals::connect_oneshot
+};
+use crate::game::fishing::{BITE_WINDOW, MAX_BITE_WAIT};
+
+const DIALOGUE_BOX_PATH: &str = "/root/Game/Player/Camera2D/CanvasLayer/DialogueBox";
+// The exclamation mark showed over the water when a Pokémon bites
+const BITE_MARK_NODE: &str = "BiteMark";
+
+const WATER_TEXT: &str = "El agua es de un azul intenso...";
+const NO_BITE_TEXT: &str = "No pican...";
+const ESCAPED_TEXT: &str = "¡Se ha escapado!";
+const NOTHING_HOOKED_TEXT: &str = "No había nada...";
+
+/// What the player it's doing with the rod right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FishingState {
+    Idle,
+    // Waiting for the player to answer if he wants to use his rod
+    Asking,
+    // The hook it's on the water. `bite_in` it's None if nothing is going to bite
+    Waiting { time_left: f64, bite_in: Option<f64> },
+    // A Pokémon bit the hook! The player must press `Interact` before the time runs out
+    Biting { time_left: f64 },
+    // Showing how the fishing ended, until the player closes the dialogue
+    Finished
+}
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+#[derive(Debug)]
+/// A place of the water where the player can fish, facing it with a rod on the bag.
+///
+/// Once the hook it's thrown the player waits for a bite, and then has a short window to press `Interact`. Pressing
+/// it too early, or too late, lets the Pokémon go. `Exit` takes the rod out of the water anytime
+pub struct FishingSpot {
+    // The encounter zone of the water. Every rod has his own Pokémon on it
+    #[property]
+    zone_id: String,
+
+    state: FishingState,
+    // The item id of the rod that the player it's using
+    rod_id: String,
+    // The input that answered the dialogue can't be read again as a try to reel in
+    waiting_first_frame: bool,
+}
+
+impl Interactable<Node2D> for FishingSpot {
+    fn on_interact(&mut self, owner: TRef<Node2D>) {
+        if self.state != FishingState::Idle {
+            return;
+        }
+
+        self.rod_id = match unsafe { owner.get_node("/root/Game") } {
+            Some(game) => unsafe { game.assume_safe().call("get_fishing_rod", &[]) }.to_string(),
+            None => return
+        };
+        if self.rod_id.is_empty() {
+            if self.show_dialogue(owner, (0, Vec::new(), vec![WATER_TEXT.to_string()]), "_on_fishing_finished") {
+                self.state = FishingState::Finished;
+            }
+            return;
+        }
+
+        let dialogue_data = (
+            1,
+            vec!["Sí".to_string(), "No".to_string()],
+            vec![format!("{}\n¿Quieres usar la {}?", WATER_TEXT, get_item_name(&self.rod_id))]
+        );
+        if self.show_dialogue(owner, dialogue_data, "_on_fishing_answered") {
+            self.state = FishingState::Asking;
+        }
+    }
+}
+
+#[gdnative::methods]
+impl FishingSpot {
+    fn new(_owner: &Node2D) -> Self {
+        Self {
+            zone_id: "".to_string(),
+            state: FishingState::Idle,
+            rod_id: "".to_string(),
+            waiting_first_frame: false,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Node2D) {
+        self.set_bite_mark_visible(owner, false);
+        owner.set_process(false);
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Node2D>) {
+        self.on_interact(owner);
+    }
+
+    #[export]
+    /// The player answered if he wants to fish. Only the first option (yes) throws the hook
+    fn _on_fishing_answered(&mut self, owner: TRef<Node2D>, _signal_info: Variant) {
+        let decision = unsafe { owner.get_node(DIALOGUE_BOX_PATH) }
+            .map(|dialogue_box| unsafe { dialogue_box.assume_safe().call("get_last_decision", &[]) }.to_i64())
+            .unwrap_or(0);
+        if decision != 1 {
+            self.state = FishingState::Idle;
+            return;
+        }
+
+        let bite_in = unsafe { owner.get_node("/root/Game") }
+            .map(|game| unsafe { game.assume_safe().call("roll_fishing_bite", &[self.rod_id.to_variant()]) }.to_f64())
+            .filter(|bite_in| *bite_in >= 0.0);
+        self.state = FishingState::Waiting { time_left: MAX_BITE_WAIT, bite_in };
+        self.waiting_first_frame = true;
+        self.set_player_interaction(owner, "on_fishing");
+        owner.set_process(true);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: TRef<Node2D>, delta: f64) {
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        if Input::is_action_just_pressed(input, "Exit") {
+            self.state = FishingState::Idle;
+            self.set_bite_mark_visible(&owner, false);
+            owner.set_process(false);
+            self.set_player_interaction(owner, "end_interaction");
+            return;
+        }
+        let reeled_in = Input::is_action_just_pressed(input, "Interact");
+
+        match self.state {
+            FishingState::Waiting { time_left, bite_in } => {
+                if reeled_in {
+                    return self.finish_fishing(owner, NOTHING_HOOKED_TEXT);
+                }
+                match bite_in {
+                    Some(bite_in) if bite_in <= delta => {
+                        self.state = FishingState::Biting { time_left: BITE_WINDOW };
+                        self.set_bite_mark_visible(&owner, true);
+                    },
+                    Some(bite_in) => self.state = FishingState::Waiting { time_left, bite_in: Some(bite_in - delta) },
+                    None if time_left <= delta => self.finish_fishing(owner, NO_BITE_TEXT),
+                    None => self.state = FishingState::Waiting { time_left: time_left - delta, bite_in: None }
+                }
+            },
+            FishingState::Biting { time_left } => {
+                if reeled_in {
+                    self.hook_pokemon(owner);
+                } else if time_left <= delta {
+                    self.finish_fishing(owner, ESCAPED_TEXT);
+                } else {
+                    self.state = FishingState::Biting { time_left: time_left - delta };
+                }
+            },
+            FishingState::Idle | FishingState::Asking | FishingState::Finished => owner.set_process(false)
+        }
+    }
+
+    #[export]
+    /// Closing the dialogue box already releases the player, so the spot just gets ready for the next time
+    fn _on_fishing_finished(&mut self, _owner: TRef<Node2D>, _signal_info: Variant) {
+        self.state = FishingState::Idle;
+    }
+
+    /// The player pressed `Interact` on time. The battle locks the player by itself
+    fn hook_pokemon(&mut self, owner: TRef<Node2D>) {
+        let hooked = unsafe { owner.get_node("/root/Game") }
+            .map(|game| unsafe { game.assume_safe().call("start_fishing_encounter",
+                &[self.zone_id.to_variant(), self.rod_id.to_variant()]) }.to_bool())
+            .unwrap_or(false);
+        if hooked {
+            self.state = FishingState::Idle;
+            self.set_bite_mark_visible(&owner, false);
+            owner.set_process(false);
+        } else {
+            // The rod reaches no Pokémon on this water
+            self.finish_fishing(owner, NOTHING_HOOKED_TEXT);
+        }
+    }
+
+    /// Takes the rod out of the water without any Pokémon, telling the player why
+    fn finish_fishing(&mut self, owner: TRef<Node2D>, text: &str) {
+        self.set_bite_mark_visible(&owner, false);
+        owner.set_process(false);
+        if self.show_dialogue(owner, (0, Vec::new(), vec![text.to_string()]), "_on_fishing_finished") {
+            self.state = FishingState::Finished;
+        } else {
+            self.state = FishingState::Idle;
+            self.set_player_interaction(owner, "end_interaction");
+        }
+    }
+
+    /// Prints the dialogue on the Dialogue Box, calling back the given method when the player closes it
+    fn show_dialogue(&self, owner: TRef<Node2D>, dialogue_data: (i32, Vec<String>, Vec<String>), callback: &str) -> bool {
+        let dialogue_box = match unsafe { owner.get_node(DIALOGUE_BOX_PATH) } {
+            Some(dialogue_box) => unsafe { dialogue_box.assume_safe() },
+            None => return false
+        };
+        if let Err(err) = connect_oneshot(&dialogue_box, "dialogue_box_inactive", owner, callback) {
+            godot_error!("Can't wait for the dialogue of the fishing spot: {:?}", err);
+            return false;
+        }
+        unsafe { dialogue_box.call("_print_dialogue", &[dialogue_data.to_variant()]) };
+        true
+    }
+
+    fn set_bite_mark_visible(&self, owner: &Node2D, visible: bool) {
+        if let Some(bite_mark) = unsafe { owner.get_node_as::<CanvasItem>(BITE_MARK_NODE) } {
+            bite_mark.set_visible(visible);
+        }
+    }
+
+    fn set_player_interaction(&self, owner: TRef<Node2D>, signal_info: &str) {
+        if let Some(player) = unsafe { owner.get_node("/root/Game/Player") } {
+            unsafe { player.assume_safe().call_deferred("handle_interaction", &[Variant::from_str(signal_info)]) };
+        }
+    }
+}
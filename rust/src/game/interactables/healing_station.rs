@@ -0,0 +1,147 @@
+use gdnative::prelude::*;
+use gdnative::api::AnimatedSprite;
+
+use crate::game::code_abstractions::{
+    interactions::Interactable,
+    signals::connect_oneshot
+};
+
+const DIALOGUE_BOX_PATH: &str = "/root/Game/Player/Camera2D/CanvasLayer/DialogueBox";
+// The machine where the Poké Balls get placed, a child of the station, and his animation
+const HEALING_MACHINE_NODE: &str = "HealingMachine";
+const HEALING_ANIMATION: &str = "healing";
+
+const WELCOME_TEXT: &str = "¡Bienvenido al Centro Pokémon!\n¿Quieres que cure a tus Pokémon?";
+const ACCEPTED_TEXT: &str = "De acuerdo. Déjame tus Pokémon un momento.";
+const DECLINED_TEXT: &str = "¡Esperamos verte pronto!";
+const HEALED_TEXT: &str = "¡Gracias por esperar!\nTus Pokémon ya están en plena forma.\n¡Esperamos verte pronto!";
+
+/// What the healing station it's doing right now
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HealingState {
+    Idle,
+    // Waiting for the player to answer if he wants to heal his party
+    Asking,
+    Healing,
+    Farewell
+}
+
+#[derive(NativeClass)]
+#[inherit(Sprite)]
+#[derive(Debug)]
+/// The nurse (or the counter) of a Pokémon Center. When the player accepts, every Pokémon of the party gets
+/// fully healed, and the Pokémon Center becomes the place where the player comes back after a whiteout.
+///
+/// The whole healing (the animation and the farewell) it's always played, even if the party was already fine
+pub struct HealingStation {
+    state: HealingState,
+}
+
+impl Interactable<Sprite> for HealingStation {
+    fn on_interact(&mut self, owner: TRef<Sprite>) {
+        if self.state != HealingState::Idle {
+            return;
+        }
+        let dialogue_data = (
+            1,
+            vec!["Sí".to_string(), "No".to_string()],
+            vec![WELCOME_TEXT.to_string(), ACCEPTED_TEXT.to_string(), DECLINED_TEXT.to_string()]
+        );
+        if self.show_dialogue(owner, dialogue_data, "_on_healing_answered") {
+            self.state = HealingState::Asking;
+        }
+    }
+}
+
+#[gdnative::methods]
+impl HealingStation {
+    fn new(_owner: &Sprite) -> Self {
+        Self {
+            state: HealingState::Idle,
+        }
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Sprite>) {
+        self.on_interact(owner);
+    }
+
+    #[export]
+    /// The player closed the welcome dialogue. Only the first option (yes) heals the party
+    fn _on_healing_answered(&mut self, owner: TRef<Sprite>, _signal_info: Variant) {
+        let decision = unsafe { owner.get_node(DIALOGUE_BOX_PATH) }
+            .map(|dialogue_box| unsafe { dialogue_box.assume_safe().call("get_last_decision", &[]) }.to_i64())
+            .unwrap_or(0);
+        if decision != 1 {
+            self.state = HealingState::Idle;
+            return;
+        }
+
+        self.state = HealingState::Healing;
+        self.set_player_interaction(owner, "on_healing");
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            unsafe { game.assume_safe().call("heal_party_at_pokemon_center", &[]) };
+        }
+
+        match self.get_healing_machine(owner) {
+            Some(healing_machine) => {
+                if let Err(err) = connect_oneshot(&healing_machine, "animation_finished", owner, "_on_healing_finished") {
+                    godot_error!("Can't wait for the healing animation: {:?}", err);
+                    return self._on_healing_finished(owner);
+                }
+                healing_machine.play(HEALING_ANIMATION, false);
+            },
+            None => self._on_healing_finished(owner)
+        }
+    }
+
+    #[export]
+    fn _on_healing_finished(&mut self, owner: TRef<Sprite>) {
+        if let Some(healing_machine) = self.get_healing_machine(owner) {
+            healing_machine.stop();
+            healing_machine.set_frame(0);
+        }
+
+        let dialogue_data = (0, Vec::<String>::new(), vec![HEALED_TEXT.to_string()]);
+        if self.show_dialogue(owner, dialogue_data, "_on_farewell_finished") {
+            self.state = HealingState::Farewell;
+        } else {
+            self.state = HealingState::Idle;
+            self.set_player_interaction(owner, "end_interaction");
+        }
+    }
+
+    #[export]
+    /// Closing the dialogue box already releases the player, so the station just gets ready for the next time
+    fn _on_farewell_finished(&mut self, _owner: TRef<Sprite>, _signal_info: Variant) {
+        self.state = HealingState::Idle;
+    }
+
+    /// Prints the dialogue on the Dialogue Box, calling back the given method when the player closes it
+    fn show_dialogue(&self, owner: TRef<Sprite>, dialogue_data: (i32, Vec<String>, Vec<String>), callback: &str) -> bool {
+        let dialogue_box = match unsafe { owner.get_node(DIALOGUE_BOX_PATH) } {
+            Some(dialogue_box) => unsafe { dialogue_box.assume_safe() },
+            None => return false
+        };
+        if let Err(err) = connect_oneshot(&dialogue_box, "dialogue_box_inactive", owner, callback) {
+            godot_error!("Can't wait for the dialogue of the Pokémon Center: {:?}", err);
+            return false;
+        }
+        unsafe { dialogue_box.call("_print_dialogue", &[dialogue_data.to_variant()]) };
+        true
+    }
+
+    /// The animated machine of the station, if it has one with the healing animation
+    fn get_healing_machine(&self, owner: TRef<Sprite>) -> Option<TRef<AnimatedSprite>> {
+        let healing_machine = unsafe { owner.get_node_as::<AnimatedSprite>(HEALING_MACHINE_NODE)? };
+        let has_animation = healing_machine.sprite_frames()
+            .map_or(false, |frames| unsafe { frames.assume_safe() }.has_animation(HEALING_ANIMATION));
+        if has_animation { Some(healing_machine) } else { None }
+    }
+
+    fn set_player_interaction(&self, owner: TRef<Sprite>, signal_info: &str) {
+        if let Some(player) = unsafe { owner.get_node("/root/Game/Player") } {
+            unsafe { player.assume_safe().call_deferred("handle_interaction", &[Variant::from_str(signal_info)]) };
+        }
+    }
+}
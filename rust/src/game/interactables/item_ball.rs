@@ -0,0 +1,108 @@
+use gdnative::prelude::*;
+
+use crate::game::bag::{get_item_name, ItemError};
+use crate::game::code_abstractions::{
+    dialogue_connections::DialogueBoxActions,
+    interactions::Interactable,
+    signals::RegisterSignal
+};
+
+#[derive(NativeClass)]
+#[inherit(Sprite)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// A Poké Ball lying on the ground with an item inside. The player picks it up interacting with it.
+///
+/// Once collected, the ball it's remembered on the saved game, so it never appears again
+pub struct ItemBall {
+    // Unique identifier of the ball. If it's empty, the path of the node on the scene tree it's used
+    #[property]
+    item_ball_id: String,
+    // The id of the item that the player gets, like `potion`
+    #[property]
+    item_id: String,
+    #[property(default = 1)]
+    quantity: i64,
+
+    collected: bool,
+}
+
+// Implements the necesary methods that make this struct able to print text on screen.
+impl DialogueBoxActions for ItemBall { }
+
+impl RegisterSignal<Self> for ItemBall {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "print_to_dialogue_box",
+            args: &[],
+        });
+    }
+}
+
+impl Interactable<Sprite> for ItemBall {
+    fn on_interact(&mut self, owner: TRef<Sprite>) {
+        // The ball could still be on screen for a while after being collected
+        if self.collected {
+            return;
+        }
+
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        // The ball stays on the ground until the player makes room for the items
+        let fits = unsafe { game.call("fits_in_bag", &[self.item_id.to_variant(), self.quantity.to_variant()]) }.to_bool();
+        if !fits {
+            let dialogue_data = (0, Vec::<String>::new(), vec![ItemError::BagFull.to_message_str().to_string()]);
+            owner.emit_signal("print_to_dialogue_box", &[dialogue_data.to_variant()]);
+            return;
+        }
+
+        let collected = unsafe { game.call("collect_item_ball", &[
+            self.item_ball_id.to_variant(), self.item_id.to_variant(), self.quantity.to_variant()
+        ]) }.to_bool();
+
+        if collected {
+            self.collected = true;
+            let found_text = if self.quantity > 1 {
+                format!("¡Has encontrado {} x{}!", get_item_name(&self.item_id), self.quantity)
+            } else {
+                format!("¡Has encontrado {}!", get_item_name(&self.item_id))
+            };
+            let dialogue_data = (0, Vec::<String>::new(), vec![found_text]);
+            owner.emit_signal("print_to_dialogue_box", &[dialogue_data.to_variant()]);
+        }
+        owner.queue_free();
+    }
+}
+
+#[gdnative::methods]
+impl ItemBall {
+    fn new(_owner: &Sprite) -> Self {
+        Self {
+            item_ball_id: "".to_string(),
+            item_id: "".to_string(),
+            quantity: 1,
+            collected: false,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<Sprite>) {
+        if self.item_ball_id.is_empty() {
+            self.item_ball_id = owner.get_path().to_string();
+        }
+
+        // Balls already collected on previous games doesn't respawn
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        if unsafe { game.call("is_item_ball_collected", &[self.item_ball_id.to_variant()]) }.to_bool() {
+            self.collected = true;
+            owner.queue_free();
+            return;
+        }
+
+        self.connect_signal_to_dialogue_box(&owner);
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Sprite>) {
+        self.on_interact(owner);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod sign;
+pub mod item_ball;
+pub mod warp;
+pub mod shop;
+pub mod healing_station;
+pub mod fishing_spot;
+pub mod berry_spot;
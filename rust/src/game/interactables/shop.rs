@@ -0,0 +1,67 @@
+use gdnative::prelude::*;
+
+use crate::game::code_abstractions::interactions::Interactable;
+
+// The screen of the shop, and where it's placed so it follows the camera like the rest of the menus
+const SHOP_MENU_SCENE: &str = "res://godot/Game/ShopMenu.tscn";
+const MENUS_CANVAS_LAYER: &str = "/root/Game/Player/Camera2D/CanvasLayer";
+
+#[derive(NativeClass)]
+#[inherit(Sprite)]
+#[derive(Debug)]
+/// A shopkeeper (or the counter of a shop). Talking to him opens the shop screen with the items of `shop_id`,
+/// and the player can't move until the screen gets closed
+pub struct Shop {
+    // The id of the shop on the shops config
+    #[property]
+    shop_id: String,
+}
+
+impl Interactable<Sprite> for Shop {
+    fn on_interact(&mut self, owner: TRef<Sprite>) {
+        let canvas_layer = match unsafe { owner.get_node(MENUS_CANVAS_LAYER) } {
+            Some(canvas_layer) => unsafe { canvas_layer.assume_safe() },
+            None => return
+        };
+        let shop_menu = unsafe { ResourceLoader::godot_singleton()
+            .load(SHOP_MENU_SCENE, "", false)
+            .and_then(|scene| scene.assume_safe().cast::<PackedScene>()?.instance(0))
+            .map(|node| node.assume_safe()) };
+
+        if let Some(shop_menu) = shop_menu {
+            // Set before entering the tree, so the menu already knows his shop on `_ready`
+            shop_menu.set("shop_id", self.shop_id.to_owned());
+            shop_menu.connect("shop_menu_closed", owner, "_on_shop_menu_closed",
+                VariantArray::new_shared(), 0).unwrap();
+
+            self.set_player_interaction(owner, "on_shop");
+            canvas_layer.add_child(shop_menu, false);
+        }
+    }
+}
+
+#[gdnative::methods]
+impl Shop {
+    fn new(_owner: &Sprite) -> Self {
+        Self {
+            shop_id: "".to_string(),
+        }
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Sprite>) {
+        self.on_interact(owner);
+    }
+
+    #[export]
+    /// The player said goodbye, so he can walk again
+    fn _on_shop_menu_closed(&self, owner: TRef<Sprite>) {
+        self.set_player_interaction(owner, "end_interaction");
+    }
+
+    fn set_player_interaction(&self, owner: TRef<Sprite>, signal_info: &str) {
+        if let Some(player) = unsafe { owner.get_node("/root/Game/Player") } {
+            unsafe { player.assume_safe().call_deferred("handle_interaction", &[Variant::from_str(signal_info)]) };
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use gdnative::prelude::*;
+
+use crate::game::code_abstractions::{
+    dialogue_connections::DialogueBoxActions,
+    interactions::Interactable,
+    signals::RegisterSignal
+};
+
+#[derive(NativeClass)]
+#[inherit(Sprite)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// A sign of the world, that shows his text on the Dialogue Box when the player reads it.
+///
+/// Signs can only be read from the front, so interacting with them from any other side does nothing
+pub struct Sign {
+    // The id of the text of the sign on the dialogues config
+    #[property]
+    dialogue_id: String,
+    // Used when the sign doesn't have a dialogue id, or when the dialogue doesn't exist
+    #[property]
+    text: String,
+    // Where the player has to be looking at to read the sign: Upwards, Downwards, Left or Right.
+    // An empty value lets the player read it from any side
+    #[property]
+    readable_facing: String,
+}
+
+// Implements the necesary methods that make this struct able to print text on screen.
+impl DialogueBoxActions for Sign { }
+
+impl RegisterSignal<Self> for Sign {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "print_to_dialogue_box",
+            args: &[],
+        });
+    }
+}
+
+impl Interactable<Sprite> for Sign {
+    fn on_interact(&mut self, owner: TRef<Sprite>) {
+        if !self.is_player_facing_front(owner) {
+            return;
+        }
+        let lines = self.get_lines(owner);
+        if lines.is_empty() {
+            return;
+        }
+        // Signs doesn't have elections, just the text
+        let dialogue_data = (0, Vec::<String>::new(), lines);
+        owner.emit_signal("print_to_dialogue_box", &[dialogue_data.to_variant()]);
+    }
+}
+
+#[gdnative::methods]
+impl Sign {
+    fn new(_owner: &Sprite) -> Self {
+        Self {
+            dialogue_id: "".to_string(),
+            text: "".to_string(),
+            readable_facing: "Upwards".to_string(),
+        }
+    }
+
+    #[export]
+    fn _ready(&self, owner: TRef<Sprite>) {
+        self.connect_signal_to_dialogue_box(&owner);
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Sprite>) {
+        self.on_interact(owner);
+    }
+
+    fn is_player_facing_front(&self, owner: TRef<Sprite>) -> bool {
+        if self.readable_facing.is_empty() {
+            return true;
+        }
+        match unsafe { owner.get_node("/root/Game/Player") } {
+            Some(player) => unsafe { player.assume_safe().call("get_facing_direction", &[]) }
+                .to_string() == self.readable_facing,
+            None => false
+        }
+    }
+
+    /// The lines of the sign, from the dialogues config if it has a dialogue id
+    fn get_lines(&self, owner: TRef<Sprite>) -> Vec<String> {
+        if !self.dialogue_id.is_empty() {
+            if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+                let lines: Vec<String> = unsafe { game.assume_safe().call("get_dialogue", &[self.dialogue_id.to_variant()]) }
+                    .to_array()
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect();
+                if !lines.is_empty() {
+                    return lines;
+                }
+            }
+        }
+        if self.text.is_empty() { Vec::new() } else { vec![self.text.to_owned()] }
+    }
+}
@@ -0,0 +1,85 @@
+use gdnative::prelude::*;
+use gdnative::api::Area2D;
+
+use crate::game::minimap::MINIMAP_WARPS_GROUP;
+use crate::game::code_abstractions::{
+    interactions::Interactable,
+    signals::RegisterSignal
+};
+
+#[derive(NativeClass)]
+#[inherit(Area2D)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// A door, a cave entrance or any other place that takes the player to another scene.
+///
+/// The player appears on `target_position`, relative to the origin of the target scene. Two warps pointing
+/// one to the other (the door of a house and the exit carpet inside) makes the round trip.
+pub struct Warp {
+    // The full path to the scene where the player goes, like `res://godot/Game/Map.tscn`
+    #[property]
+    target_scene: String,
+    #[property]
+    target_position: Vector2,
+    // Doors that needs the player to press the interact button, instead of just walking over them
+    #[property]
+    requires_interaction: bool,
+}
+
+impl RegisterSignal<Self> for Warp {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "warp_player",
+            args: &[ SignalArgument {
+                name: "target_scene",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }, SignalArgument {
+                name: "target_position",
+                default: Variant::from_vector2(&Vector2::new(0.0, 0.0)),
+                export_info: ExportInfo::new(VariantType::Vector2),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+    }
+}
+
+impl Interactable<Area2D> for Warp {
+    fn on_interact(&mut self, owner: TRef<Area2D>) {
+        owner.emit_signal("warp_player", &[self.target_scene.to_variant(), self.target_position.to_variant()]);
+    }
+}
+
+#[gdnative::methods]
+impl Warp {
+    fn new(_owner: &Area2D) -> Self {
+        Self {
+            target_scene: "".to_string(),
+            target_position: Vector2::new(0.0, 0.0),
+            requires_interaction: false,
+        }
+    }
+
+    #[export]
+    fn _ready(&self, owner: TRef<Area2D>) {
+        owner.add_to_group(MINIMAP_WARPS_GROUP, false);
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        owner.connect("warp_player", game, "warp_player",
+            VariantArray::new_shared(), 0).unwrap();
+    }
+
+    #[export]
+    /// Receives a signal when a body enteres the Warp (connected on the Godot GUI)
+    fn _on_area2d_body_entered(&mut self, owner: TRef<Area2D>, body: Ref<Node>) {
+        let is_player = unsafe { body.assume_safe() }.name().to_string() == "Player";
+        if is_player && !self.requires_interaction {
+            self.on_interact(owner);
+        }
+    }
+
+    #[export]
+    fn interact(&mut self, owner: TRef<Area2D>) {
+        self.on_interact(owner);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The id of an item, like `potion`
+pub type ItemId = String;
+
+/// The most units of a single item that can be carried, like on the original games
+pub const MAX_ITEM_STACK: u32 = 99;
+
+/// How many units of every item there are, by item id. It only counts them: what every item does, and the pocket
+/// where it goes, it's up to the `Bag`.
+///
+/// It's stored on the saves as a plain map of item ids and quantities
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Inventory {
+    items: HashMap<ItemId, u32>,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds some units of an item, never past his maximum stack.
+    ///
+    /// Returns the units that really got in, the rest don't fit
+    pub fn add_item(&mut self, item_id: &str, quantity: u32) -> u32 {
+        let added = quantity.min(self.free_space(item_id));
+        if added > 0 {
+            *self.items.entry(item_id.to_string()).or_insert(0) += added;
+        }
+        added
+    }
+
+    /// Takes out the given units of an item. Fails, without taking anything, if there aren't that many.
+    /// The entry it's removed when there's no more units left
+    pub fn remove_item(&mut self, item_id: &str, quantity: u32) -> bool {
+        let held = self.count(item_id);
+        if quantity == 0 || quantity > held {
+            return false;
+        }
+
+        if quantity == held {
+            self.items.remove(item_id);
+        } else {
+            self.items.insert(item_id.to_string(), held - quantity);
+        }
+        true
+    }
+
+    pub fn count(&self, item_id: &str) -> u32 {
+        *self.items.get(item_id).unwrap_or(&0)
+    }
+
+    pub fn has(&self, item_id: &str) -> bool {
+        self.count(item_id) > 0
+    }
+
+    /// How many units more of the item fit
+    pub fn free_space(&self, item_id: &str) -> u32 {
+        MAX_ITEM_STACK.saturating_sub(self.count(item_id))
+    }
+
+    pub fn get_items(&self) -> &HashMap<ItemId, u32> {
+        &self.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_stacks_are_capped_at_the_maximum() {
+        let mut inventory = Inventory::new();
+        assert_eq!(inventory.add_item("potion", 90), 90);
+        assert_eq!(inventory.add_item("potion", 20), 9);
+        assert_eq!(inventory.count("potion"), MAX_ITEM_STACK);
+        assert_eq!(inventory.add_item("potion", 1), 0);
+        assert_eq!(inventory.free_space("potion"), 0);
+        // Every item has his own stack
+        assert_eq!(inventory.add_item("antidote", 1), 1);
+    }
+
+    #[test]
+    fn removing_more_than_held_takes_nothing() {
+        let mut inventory = Inventory::new();
+        inventory.add_item("potion", 3);
+        assert!(!inventory.remove_item("potion", 4));
+        assert_eq!(inventory.count("potion"), 3);
+        assert!(!inventory.remove_item("antidote", 1));
+        assert!(!inventory.remove_item("potion", 0));
+    }
+
+    #[test]
+    fn removing_every_unit_empties_the_entry() {
+        let mut inventory = Inventory::new();
+        inventory.add_item("potion", 3);
+        assert!(inventory.remove_item("potion", 2));
+        assert!(inventory.has("potion"));
+        assert!(inventory.remove_item("potion", 1));
+        assert!(!inventory.has("potion"));
+        assert!(inventory.get_items().is_empty());
+    }
+
+    #[test]
+    fn it_is_saved_as_a_plain_map() {
+        let mut inventory = Inventory::new();
+        inventory.add_item("potion", 2);
+        let json = serde_json::to_string(&inventory).unwrap();
+        assert_eq!(json, "{\"potion\":2}");
+        assert_eq!(serde_json::from_str::<Inventory>(&json).unwrap(), inventory);
+    }
+}
@@ -0,0 +1,384 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::game::bag::{self, ItemCategory, ItemError};
+use crate::game::code_abstractions::signals::{AddTypedSignal, RegisterSignal};
+use crate::game::menu::menu_cursor::MenuCursor;
+use crate::game::menu::party_menu::wrap_index;
+
+/// How many items of a pocket fit on the screen at the same time
+const VISIBLE_ITEMS: usize = 8;
+
+/// Where the player is moving the cursor on the bag screen
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum BagMenuState {
+    // Choosing an item of the current pocket
+    Browsing,
+    // Choosing what to do with the selected item
+    Actions,
+    // Choosing the Pokémon that will receive the item
+    ChoosingPokemon(BagAction),
+    // Choosing how many units of the item will be tossed
+    ChoosingQuantity
+}
+impl Default for BagMenuState {
+    fn default() -> Self { BagMenuState::Browsing }
+}
+
+/// The things that can be done with an item of the bag
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum BagAction {
+    Use,
+    Give,
+    Toss,
+    Cancel
+}
+
+impl BagAction {
+    pub fn to_label_str(&self) -> &'static str {
+        match self {
+            BagAction::Use => "Usar",
+            BagAction::Give => "Dar",
+            BagAction::Toss => "Tirar",
+            BagAction::Cancel => "Salir"
+        }
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The screen that shows the items of the bag, grouped by their pocket.
+///
+/// It's opened from the main menu, and also from the battle with `in_battle` set, where only the items that
+/// can be used while battling are showed
+pub struct BagMenu {
+    #[property(default = false)]
+    in_battle: bool,
+
+    state: BagMenuState,
+    current_category: usize,
+    current_item: usize,
+    current_action: usize,
+    current_party_slot: usize,
+    toss_quantity: u32,
+
+    category_items: VariantArray,
+    party_summary: VariantArray,
+    // The input that opened this menu can't be read again by it
+    waiting_first_frame: bool,
+    // Items and categories scroll while the direction keys are held
+    item_cursor: MenuCursor,
+    category_cursor: MenuCursor,
+}
+
+impl RegisterSignal<Self> for BagMenu {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // The player goes back to the previous menu
+        builder.add_signal( Signal {
+            name: "bag_menu_closed",
+            args: &[],
+        });
+        // On battle, using an item spends the turn. A Poké Ball it's not used here, it's just choosen and the battle throws it
+        builder.add_typed_signal("item_used", "item_id", GodotString::from(""));
+        builder.add_typed_signal("pokeball_selected", "item_id", GodotString::from(""));
+    }
+}
+
+#[gdnative::methods]
+impl BagMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            in_battle: false,
+            state: BagMenuState::default(),
+            current_category: 0,
+            current_item: 0,
+            current_action: 0,
+            current_party_slot: 0,
+            toss_quantity: 1,
+            category_items: VariantArray::new().into_shared(),
+            party_summary: VariantArray::new().into_shared(),
+            waiting_first_frame: true,
+            item_cursor: MenuCursor::vertical(),
+            category_cursor: MenuCursor::horizontal(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &NinePatchRect) {
+        // Out of the battles it's opened with the game paused by the main menu
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+        owner.set_process(true);
+        self.refresh_items(owner);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, delta: f64) {
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        let vertical_delta = self.item_cursor.poll(input, delta);
+        let horizontal_delta = self.category_cursor.poll(input, delta);
+
+        if vertical_delta != 0 {
+            self.move_cursor(owner, vertical_delta);
+        } else if horizontal_delta != 0 && self.state == BagMenuState::Browsing {
+            self.change_category(owner, horizontal_delta);
+        } else if Input::is_action_just_pressed(input, "Interact") || Input::is_action_just_pressed(input, "Enter") {
+            self.accept(owner);
+        } else if Input::is_action_just_pressed(input, "Exit") {
+            self.go_back(owner);
+        }
+    }
+
+    /// The categories availiable right now. On battle, just the ones with items that can be used while battling
+    fn get_categories(&self) -> Vec<ItemCategory> {
+        ItemCategory::ALL.iter()
+            .filter(|category| !self.in_battle || category.is_usable_in_battle())
+            .copied()
+            .collect()
+    }
+
+    fn get_actions(&self) -> Vec<BagAction> {
+        if self.in_battle {
+            vec![BagAction::Use, BagAction::Cancel]
+        } else {
+            vec![BagAction::Use, BagAction::Give, BagAction::Toss, BagAction::Cancel]
+        }
+    }
+
+    fn get_selected_item(&self) -> Option<Dictionary> {
+        if (self.current_item as i32) < self.category_items.len() {
+            Some(self.category_items.get(self.current_item as i32).to_dictionary())
+        } else {
+            None
+        }
+    }
+
+    fn change_category(&mut self, owner: &NinePatchRect, delta: i32) {
+        self.current_category = wrap_index(self.current_category, delta, self.get_categories().len());
+        self.current_item = 0;
+        self.refresh_items(owner);
+    }
+
+    fn move_cursor(&mut self, owner: &NinePatchRect, delta: i32) {
+        match self.state {
+            BagMenuState::Browsing => {
+                self.current_item = wrap_index(self.current_item, delta, self.category_items.len() as usize);
+                self.update_item_list(owner);
+            },
+            BagMenuState::Actions => {
+                self.current_action = wrap_index(self.current_action, delta, self.get_actions().len());
+                self.update_selector(owner);
+            },
+            BagMenuState::ChoosingPokemon(_) => {
+                self.current_party_slot = wrap_index(self.current_party_slot, delta, self.party_summary.len() as usize);
+                self.update_selector(owner);
+            },
+            BagMenuState::ChoosingQuantity => {
+                let carried = self.get_selected_item()
+                    .map(|item| item.get("quantity").to_i64() as u32)
+                    .unwrap_or(1);
+                // Going up adds units, the same way that on the main games
+                self.toss_quantity = (self.toss_quantity as i32 - delta).clamp(1, carried.max(1) as i32) as u32;
+                self.update_selector(owner);
+            }
+        }
+    }
+
+    fn accept(&mut self, owner: &NinePatchRect) {
+        let item = match self.get_selected_item() {
+            Some(item) => item,
+            None => return
+        };
+        let item_id = item.get("item_id").to_string();
+
+        match self.state {
+            BagMenuState::Browsing => {
+                self.state = BagMenuState::Actions;
+                self.current_action = 0;
+                self.update_selector(owner);
+            },
+            BagMenuState::Actions => {
+                match self.get_actions()[self.current_action] {
+                    BagAction::Use => self.start_using(owner, &item_id),
+                    BagAction::Give => self.choose_pokemon(owner, BagAction::Give),
+                    BagAction::Toss => {
+                        if ItemCategory::from_item_id(&item_id).can_be_tossed() {
+                            self.state = BagMenuState::ChoosingQuantity;
+                            self.toss_quantity = 1;
+                            self.update_selector(owner);
+                        } else {
+                            self.finish_action(owner, ItemError::KeyItem.to_message_str());
+                        }
+                    },
+                    BagAction::Cancel => self.go_back(owner)
+                }
+            },
+            BagMenuState::ChoosingPokemon(action) => {
+                let method = if action == BagAction::Give { "give_item" } else { "use_item" };
+                let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+                let error_message = unsafe { game.call(method,
+                    &[item_id.to_variant(), (self.current_party_slot as i64).to_variant()]) }.to_string();
+
+                if error_message.is_empty() {
+                    let pokemon_name = self.party_summary.get(self.current_party_slot as i32)
+                        .to_dictionary().get("name").to_string();
+                    if action == BagAction::Give {
+                        self.finish_action(owner, &format!("{} lleva ahora {}.", pokemon_name, item.get("name").to_string()));
+                    } else if self.in_battle {
+                        owner.emit_signal("item_used", &[item_id.to_variant()]);
+                        self.close(owner);
+                    } else {
+                        self.finish_action(owner, &format!("Has usado {} en {}.", item.get("name").to_string(), pokemon_name));
+                    }
+                } else {
+                    self.finish_action(owner, &error_message);
+                }
+            },
+            BagMenuState::ChoosingQuantity => {
+                let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+                let error_message = unsafe { game.call("toss_item",
+                    &[item_id.to_variant(), (self.toss_quantity as i64).to_variant()]) }.to_string();
+
+                if error_message.is_empty() {
+                    self.finish_action(owner, &format!("Has tirado {} x{}.", item.get("name").to_string(), self.toss_quantity));
+                } else {
+                    self.finish_action(owner, &error_message);
+                }
+            }
+        }
+    }
+
+    fn start_using(&mut self, owner: &NinePatchRect, item_id: &str) {
+        match ItemCategory::from_item_id(item_id) {
+            ItemCategory::Medicine => self.choose_pokemon(owner, BagAction::Use),
+            ItemCategory::Pokeballs if self.in_battle => {
+                owner.emit_signal("pokeball_selected", &[item_id.to_variant()]);
+                self.close(owner);
+            },
+            // The repels work on the whole party, so there's no Pokémon to choose
+            ItemCategory::Items if !self.in_battle && bag::get_repel_steps(item_id).is_some() => {
+                let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+                let error_message = unsafe { game.call("use_repel", &[item_id.to_variant()]) }.to_string();
+                if error_message.is_empty() {
+                    self.finish_action(owner, &format!("Has usado {}.", bag::get_item_name(item_id)));
+                } else {
+                    self.finish_action(owner, &error_message);
+                }
+            },
+            _ => self.finish_action(owner, ItemError::UnknownItem.to_message_str())
+        }
+    }
+
+    fn choose_pokemon(&mut self, owner: &NinePatchRect, action: BagAction) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        self.party_summary = unsafe { game.call("get_party_summary", &[]) }.to_array();
+        if self.party_summary.is_empty() {
+            self.finish_action(owner, ItemError::NoPokemon.to_message_str());
+            return;
+        }
+        self.state = BagMenuState::ChoosingPokemon(action);
+        self.current_party_slot = 0;
+        self.update_selector(owner);
+    }
+
+    /// Shows how the action ended and goes back to the item list, that could have changed
+    fn finish_action(&mut self, owner: &NinePatchRect, message: &str) {
+        self.state = BagMenuState::Browsing;
+        self.refresh_items(owner);
+        self.set_message(owner, message);
+    }
+
+    /// The cancel path. Every step goes back to the previous one, until the bag gets closed
+    fn go_back(&mut self, owner: &NinePatchRect) {
+        match self.state {
+            BagMenuState::Browsing => self.close(owner),
+            BagMenuState::Actions => {
+                self.state = BagMenuState::Browsing;
+                self.update_selector(owner);
+            },
+            BagMenuState::ChoosingPokemon(_) | BagMenuState::ChoosingQuantity => {
+                self.state = BagMenuState::Actions;
+                self.update_selector(owner);
+            }
+        }
+    }
+
+    fn close(&mut self, owner: &NinePatchRect) {
+        owner.emit_signal("bag_menu_closed", &[]);
+        owner.queue_free();
+    }
+
+    /// Reads again the items of the current pocket from the Game
+    fn refresh_items(&mut self, owner: &NinePatchRect) {
+        let categories = self.get_categories();
+        let category = categories[self.current_category.min(categories.len() - 1)];
+
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        self.category_items = unsafe { game.call("get_bag_category",
+            &[category.to_category_str().to_variant()]) }.to_array();
+        self.current_item = self.current_item.min((self.category_items.len() as usize).saturating_sub(1));
+
+        let category_label = unsafe { owner.get_node_as::<Label>("Category").unwrap() };
+        category_label.set_text(format!("< {} >", category.to_label_str()));
+
+        self.set_message(owner, "");
+        self.update_item_list(owner);
+        self.update_selector(owner);
+    }
+
+    /// Writes the items of the pocket that fit on the screen, scrolling the list to keep the cursor visible
+    fn update_item_list(&self, owner: &NinePatchRect) {
+        let item_list = unsafe { owner.get_node_as::<Label>("ItemList").unwrap() };
+        if self.category_items.is_empty() {
+            item_list.set_text("  No hay nada aquí.");
+            return;
+        }
+
+        let first_visible = (self.current_item + 1).saturating_sub(VISIBLE_ITEMS);
+        let visible_items: Vec<String> = self.category_items.iter()
+            .enumerate()
+            .skip(first_visible)
+            .take(VISIBLE_ITEMS)
+            .map(|(index, item)| {
+                let item = item.to_dictionary();
+                let cursor = if index == self.current_item { "> " } else { "  " };
+                format!("{}{}  x{}", cursor, item.get("name").to_string(), item.get("quantity").to_i64())
+            })
+            .collect();
+        item_list.set_text(visible_items.join("\n"));
+    }
+
+    /// Writes the options of the current step: the actions, the Pokémon of the party or the quantity to toss
+    fn update_selector(&self, owner: &NinePatchRect) {
+        let selector = unsafe { owner.get_node_as::<Label>("Selector").unwrap() };
+        let options: Vec<String> = match self.state {
+            BagMenuState::Browsing => Vec::new(),
+            BagMenuState::Actions => self.get_actions().iter().enumerate()
+                .map(|(index, action)| with_cursor(index == self.current_action, action.to_label_str()))
+                .collect(),
+            BagMenuState::ChoosingPokemon(_) => self.party_summary.iter().enumerate()
+                .map(|(index, pokemon)| with_cursor(index == self.current_party_slot,
+                    &pokemon.to_dictionary().get("name").to_string()))
+                .collect(),
+            BagMenuState::ChoosingQuantity => vec![format!("x{}", self.toss_quantity)]
+        };
+
+        selector.set_visible(!options.is_empty());
+        selector.set_text(options.join("\n"));
+    }
+
+    fn set_message(&self, owner: &NinePatchRect, message: &str) {
+        let message_label = unsafe { owner.get_node_as::<Label>("Message").unwrap() };
+        message_label.set_text(message);
+    }
+}
+
+pub(crate) fn with_cursor(selected: bool, text: &str) -> String {
+    format!("{}{}", if selected { "> " } else { "  " }, text)
+}
@@ -1,8 +1,9 @@
 use gdnative::prelude::*;
-use gdnative::api::NinePatchRect;
+use gdnative::api::{NinePatchRect, PackedScene};
 
 use crate::game::code_abstractions::signals::RegisterSignal;
 use crate::game::code_abstractions::node_operations::NodeReferences;
+use crate::game::menu::menu_cursor::MenuCursor;
 
 use crate::utils::utils;
 
@@ -38,6 +39,23 @@ pub enum MenuOptions {
     Exit
 }
 
+impl MenuOptions {
+    /// Matches the position of an option label on the menu with the option itself
+    pub fn from_index(index: i32) -> Option<MenuOptions> {
+        match index {
+            0 => Some(MenuOptions::Pokedex),
+            1 => Some(MenuOptions::Pokemon),
+            2 => Some(MenuOptions::Pokegear),
+            3 => Some(MenuOptions::Bag),
+            4 => Some(MenuOptions::Player),
+            5 => Some(MenuOptions::Options),
+            6 => Some(MenuOptions::Save),
+            7 => Some(MenuOptions::Exit),
+            _ => None
+        }
+    }
+}
+
 #[derive(NativeClass)]
 #[inherit(NinePatchRect)]
 #[register_with(Self::register_signal)]
@@ -49,6 +67,9 @@ pub struct Menu {
     current_menu_option: i32,
     menu_labels: VariantArray,
     cursor_pointer: Option<Ref<Node>>,
+    // While a screen opened from the menu (like the party one) it's being showed, the menu ignores the input
+    submenu_active: bool,
+    cursor: MenuCursor,
 
     player_current_abs_position: (f32, f32)
 }
@@ -87,6 +108,8 @@ impl Menu {
             current_menu_option: 0,
             menu_labels: VariantArray::new().into_shared(),
             cursor_pointer: None,
+            submenu_active: false,
+            cursor: MenuCursor::vertical(),
             player_current_abs_position: (0.0, 0.0)
         }
     }
@@ -97,6 +120,8 @@ impl Menu {
 
         // Menu always spawns from a base hidden status
         owner.set_visible(false);
+        // The whole game gets paused while the menu it's open, but the menu itself must keep reading the input
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
 
         // Retrieves and saves a reference as a VariantArray of the menu options
         self.retrieve_menu_options(&owner);
@@ -116,65 +141,78 @@ impl Menu {
     }
 
     #[export]
-    fn _process(&mut self, owner: &NinePatchRect, _delta: f64) {
-        self.handle_menu_input_events(owner);
+    fn _process(&mut self, owner: TRef<NinePatchRect>, delta: f64) {
+        if !self.submenu_active {
+            self.handle_menu_input_events(owner, delta);
+        }
     }
 
-    fn handle_menu_input_events(&mut self, owner: &NinePatchRect) {
+    fn handle_menu_input_events(&mut self, owner: TRef<NinePatchRect>, delta: f64) {
 
         // Gets an input singleton to point to the input events
         let input: &Input = Input::godot_singleton();
+        let cursor_move = self.cursor.poll(input, delta);
 
         // This block of code matches a keyboard input event with the actions over the menu
         if Input::is_action_just_pressed(&input, "Menu") {
             if self.menu_status == MenuStatus::Closed {
-                owner.emit_signal("menu_opened", &[Variant::from_str("menu_active")]);
-                self.open_menu(owner);
-                self.player_current_abs_position = utils::get_player_absolute_position();
+                self.player_current_abs_position = utils::get_player_absolute_position().unwrap_or_default();
+                self.open_menu(&owner);
             } else {
-                owner.emit_signal("menu_closed", &[Variant::from_str("")]);
-                self.close_menu(owner)
+                self.close_menu(&owner)
             }  
-        } else if Input::is_action_just_pressed(&input, "Menu_Up") {
+        } else if self.menu_status == MenuStatus::Closed {
+            // Nothing else to do with the menu hidden
+            return;
+        } else if cursor_move < 0 {
             match self.current_menu_option {
                 x if x == 0 => self.current_menu_option = self.menu_labels.len() - 1,
                 _ => self.current_menu_option -= 1
             }
-            self.cursor_pointer_update(owner);
-        } else if Input::is_action_just_pressed(&input, "Menu_Down") {
+            self.cursor_pointer_update(&owner);
+        } else if cursor_move > 0 {
             match self.current_menu_option {
                 x if x == self.menu_labels.len() - 1 => self.current_menu_option = 0,
                 _ => self.current_menu_option += 1
             }
-            self.cursor_pointer_update(owner);
-        } else if Input::is_action_just_pressed(&input, "Interact") && self.menu_status == MenuStatus::Open 
-                || Input::is_action_just_pressed(&input, "Enter") && self.menu_status == MenuStatus::Open {
+            self.cursor_pointer_update(&owner);
+        } else if Input::is_action_just_pressed(&input, "Interact") 
+                || Input::is_action_just_pressed(&input, "Enter") {
             godot_print!("Option nº {}, {:?} has been selected!",
             self.current_menu_option + 1, self.menu_labels.get(self.current_menu_option));
             // Method that handles the next scene given a choice on the menu
             self.menu_option_to_scene(owner, self.current_menu_option);
 
             let scene_tree_ref = 
-                unsafe { Node::get_tree(owner)
+                unsafe { Node::get_tree(&owner)
                 .unwrap().assume_safe() };
             godot_print!("Current Scene, selected from Menú: {:?}", SceneTree::current_scene(&scene_tree_ref));
         }
-        else if Input::is_action_pressed(&input, "Exit") && self.menu_status == MenuStatus::Open{
-            owner.emit_signal("menu_closed", &[]);
-            self.close_menu(owner)
+        else if Input::is_action_just_pressed(&input, "Exit") {
+            self.close_menu(&owner)
         }
     }
 
+    /// Shows the menu, locking the player and pausing everything else on the game until it gets closed
     fn open_menu(&mut self, owner: &NinePatchRect) {
+        owner.emit_signal("menu_opened", &[Variant::from_str("menu_active")]);
         self.menu_status = MenuStatus::Open;
-        owner.set_visible(true)
+        owner.set_visible(true);
+        self.set_game_paused(owner, true)
     }
 
     fn close_menu(&mut self, owner: &NinePatchRect) {
+        self.set_game_paused(owner, false);
+        owner.emit_signal("menu_closed", &[Variant::from_str("end_interaction")]);
         self.menu_status = MenuStatus::Closed;
         owner.set_visible(false)
     }
 
+    fn set_game_paused(&self, owner: &NinePatchRect, paused: bool) {
+        let scene_tree = unsafe { owner.get_tree().unwrap().assume_safe() };
+        scene_tree.set_pause(paused);
+    }
+
     fn retrieve_menu_options(&mut self, owner: &NinePatchRect) {
         let menu_options_ref: TRef<Node> = unsafe { owner.get_node("MenuOptions").unwrap().assume_safe() };
         let menu_options: VariantArray = menu_options_ref.get_children();
@@ -211,11 +249,61 @@ impl Menu {
             VariantArray::new_shared(), 0).unwrap();
     }
 
+    /// Opens a screen (like the party or the bag one) over the menu. The menu comes back when that screen
+    /// emits his closed signal
+    fn open_submenu(&mut self, owner: TRef<NinePatchRect>, scene_path: &str, closed_signal: &str) {
+        let submenu = unsafe { ResourceLoader::godot_singleton()
+            .load(scene_path, "", false)
+            .unwrap()
+            .assume_safe()
+            .cast::<PackedScene>()
+            .unwrap()
+            .instance(0)
+            .unwrap()
+            .assume_safe() };
+
+        submenu.connect(closed_signal, owner, "_on_submenu_closed",
+            VariantArray::new_shared(), 0).unwrap();
+        unsafe { owner.get_parent().unwrap().assume_safe().add_child(submenu, false) };
+
+        self.submenu_active = true;
+        owner.set_visible(false);
+    }
+
+    #[export]
+    fn _on_submenu_closed(&mut self, owner: &NinePatchRect) {
+        self.submenu_active = false;
+        owner.set_visible(true);
+    }
+
+    /// Asks the game to persist the current game data. It's deferred, so the game can gather the data of every node
+    /// on the save group without any of them being in the middle of this call
+    fn save_game(&self, owner: &NinePatchRect) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        unsafe { game.call_deferred("save_game_data", &[]) };
+        godot_print!("Partida guardada.");
+    }
+
     /// Changes the scene to a designed one when a menu option is selected by the player
     #[export]
-    fn menu_option_to_scene(&mut self, owner: &NinePatchRect, menu_option: i32) {
-        match menu_option + 1 {
-            1 => utils::change_scene(owner, "res://godot/Game/Pokedex.tscn".to_string()),
+    fn menu_option_to_scene(&mut self, owner: TRef<NinePatchRect>, menu_option: i32) {
+        match MenuOptions::from_index(menu_option) {
+            Some(MenuOptions::Pokedex) => {
+                // The new scene can't start paused
+                self.close_menu(&owner);
+                if let Err(err) = utils::change_scene(&owner, "res://godot/Game/Pokedex.tscn".to_string()) {
+                    godot_print!("The Pokédex can't be opened. {}", err);
+                }
+            },
+            Some(MenuOptions::Pokemon) => self.open_submenu(
+                owner, "res://godot/Game/PartyMenu.tscn", "party_menu_closed"),
+            // The Pokégear keeps the quest log
+            Some(MenuOptions::Pokegear) => self.open_submenu(
+                owner, "res://godot/Game/QuestMenu.tscn", "quest_menu_closed"),
+            Some(MenuOptions::Bag) => self.open_submenu(
+                owner, "res://godot/Game/BagMenu.tscn", "bag_menu_closed"),
+            Some(MenuOptions::Save) => self.save_game(&owner),
+            Some(MenuOptions::Exit) => self.close_menu(&owner),
             _ => godot_print!("Menu option implemented yet!")
         }
     }
@@ -0,0 +1,83 @@
+use gdnative::prelude::*;
+
+// Seconds that a direction has to be held before the cursor starts moving by itself, and seconds between the next moves
+pub const MENU_REPEAT_DELAY: f64 = 0.4;
+pub const MENU_REPEAT_RATE: f64 = 0.1;
+
+/// Turns the direction keys into the moves of the cursor of a menu, so every menu navigates the same way.
+///
+/// A tap moves the cursor once. Holding the key moves it again after `MENU_REPEAT_DELAY` seconds, and then
+/// once every `MENU_REPEAT_RATE` seconds until the key gets released
+#[derive(Debug, Clone)]
+pub struct MenuCursor {
+    // The actions that move the cursor backwards (-1) and forwards (1)
+    previous_action: &'static str,
+    next_action: &'static str,
+    held_direction: i32,
+    held_time: f64,
+    // When the held direction will move the cursor again, counting from the moment that it was pressed
+    next_repeat_time: f64,
+}
+
+impl MenuCursor {
+    pub fn new(previous_action: &'static str, next_action: &'static str) -> Self {
+        Self {
+            previous_action,
+            next_action,
+            held_direction: 0,
+            held_time: 0.0,
+            next_repeat_time: MENU_REPEAT_DELAY,
+        }
+    }
+
+    /// Moves through the options of a list
+    pub fn vertical() -> Self {
+        Self::new("Menu_Up", "Menu_Down")
+    }
+
+    /// Moves through tabs, like the categories of the bag
+    pub fn horizontal() -> Self {
+        Self::new("Left", "Right")
+    }
+
+    /// Reads the keys. Returns -1 or 1 when the cursor has to move on this frame, or 0 if it stays where it is
+    pub fn poll(&mut self, input: &Input, delta: f64) -> i32 {
+        let direction = if Input::is_action_pressed(input, self.previous_action) {
+            -1
+        } else if Input::is_action_pressed(input, self.next_action) {
+            1
+        } else {
+            0
+        };
+        self.update(direction, delta)
+    }
+
+    /// Advances the time that the given direction has been held (0 if none), returning the move of this frame
+    pub fn update(&mut self, direction: i32, delta: f64) -> i32 {
+        if direction == 0 {
+            self.reset();
+            return 0;
+        }
+        if direction != self.held_direction {
+            self.held_direction = direction;
+            self.held_time = 0.0;
+            self.next_repeat_time = MENU_REPEAT_DELAY;
+            return direction;
+        }
+
+        self.held_time += delta;
+        if self.held_time >= self.next_repeat_time {
+            self.next_repeat_time += MENU_REPEAT_RATE;
+            direction
+        } else {
+            0
+        }
+    }
+
+    /// Forgets the held key, so the next press moves the cursor right away
+    pub fn reset(&mut self) {
+        self.held_direction = 0;
+        self.held_time = 0.0;
+        self.next_repeat_time = MENU_REPEAT_DELAY;
+    }
+}
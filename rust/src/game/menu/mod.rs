@@ -1,2 +1,10 @@
 pub mod menu;
-pub mod pokedex;
\ No newline at end of file
+pub mod menu_cursor;
+pub mod bag_menu;
+pub mod party_menu;
+pub mod summary_menu;
+pub mod nickname_menu;
+pub mod shop_menu;
+pub mod pokedex;
+pub mod quest_menu;
+pub mod move_learn_menu;
\ No newline at end of file
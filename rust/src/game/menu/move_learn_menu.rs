@@ -0,0 +1,103 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::game::menu::bag_menu::with_cursor;
+use crate::game::menu::menu_cursor::MenuCursor;
+use crate::game::menu::party_menu::wrap_index;
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[derive(Debug)]
+/// The screen where the player chooses the move that a Pokémon forgets to learn a new one, when it already
+/// knows four moves.
+///
+/// The last option (and `Exit`) gives up on the new move, keeping the moves that the Pokémon already knows, like
+/// on the original games. The Game waits for the answer before going on with the rest of the level up
+pub struct MoveLearnMenu {
+    pokemon_name: String,
+    new_move: String,
+    known_moves: Vec<String>,
+    current_option: usize,
+    // The input that opened this menu can't be read again by it
+    waiting_first_frame: bool,
+    cursor: MenuCursor,
+}
+
+#[gdnative::methods]
+impl MoveLearnMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            pokemon_name: "".to_string(),
+            new_move: "".to_string(),
+            known_moves: Vec::new(),
+            current_option: 0,
+            waiting_first_frame: true,
+            cursor: MenuCursor::vertical(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &NinePatchRect) {
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+        owner.set_process(true);
+
+        let title = unsafe { owner.get_node_as::<Label>("Title").unwrap() };
+        title.set_text(format!("{} quiere aprender {}, pero ya conoce {} movimientos. ¿Cuál debería olvidar?",
+            self.pokemon_name, self.new_move, self.known_moves.len()));
+        self.update_move_list(owner);
+    }
+
+    #[export]
+    /// Receives from the Game the Pokémon, the new move and the moves that it already knows, before the menu
+    /// enters the scene tree
+    fn set_move_learning_info(&mut self, _owner: &NinePatchRect, move_learning_info: Dictionary) {
+        self.pokemon_name = move_learning_info.get("pokemon_name").to_string();
+        self.new_move = move_learning_info.get("new_move").to_string();
+        self.known_moves = move_learning_info.get("known_moves").to_array().iter()
+            .map(|known_move| known_move.to_string())
+            .collect();
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, delta: f64) {
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        let vertical_delta = self.cursor.poll(input, delta);
+        if vertical_delta != 0 {
+            // Every known move, and the option to keep them
+            self.current_option = wrap_index(self.current_option, vertical_delta, self.known_moves.len() + 1);
+            self.update_move_list(owner);
+        } else if Input::is_action_just_pressed(input, "Interact") || Input::is_action_just_pressed(input, "Enter") {
+            if self.current_option < self.known_moves.len() {
+                self.close(owner, "forget_move", &[(self.current_option as i64).to_variant()]);
+            } else {
+                self.close(owner, "cancel_move_learning", &[]);
+            }
+        } else if Input::is_action_just_pressed(input, "Exit") {
+            self.close(owner, "cancel_move_learning", &[]);
+        }
+    }
+
+    /// Sends the choice of the player to the Game, that goes on with the rest of the level up. Deferred, because the
+    /// Game could open this menu again for the next move
+    fn close(&self, owner: &NinePatchRect, method: &str, args: &[Variant]) {
+        owner.set_process(false);
+        owner.queue_free();
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        unsafe { game.call_deferred(method, args) };
+    }
+
+    fn update_move_list(&self, owner: &NinePatchRect) {
+        let move_list = unsafe { owner.get_node_as::<Label>("MoveList").unwrap() };
+        let mut options: Vec<String> = self.known_moves.iter().enumerate()
+            .map(|(index, known_move)| with_cursor(index == self.current_option, known_move))
+            .collect();
+        options.push(with_cursor(self.current_option == self.known_moves.len(),
+            &format!("No aprender {}", self.new_move)));
+        move_list.set_text(options.join("\n"));
+    }
+}
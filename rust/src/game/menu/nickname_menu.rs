@@ -0,0 +1,106 @@
+use gdnative::prelude::*;
+use gdnative::api::{LineEdit, NinePatchRect};
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::pokemon::MAX_NICKNAME_LENGTH;
+use crate::utils::consts::line_edit;
+use crate::utils::utils;
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The screen where the player writes the nickname of the Pokémon that just caught.
+///
+/// `Enter` accepts the written nickname (a blank one keeps the specie name), and `Exit` cancels it, keeping
+/// the specie name too. The nicknames with disallowed characters are refused, and the screen stays open
+pub struct NicknameMenu {
+    // The name of the specie, the one that the Pokémon keeps without nickname
+    specie_name: String,
+}
+
+impl RegisterSignal<Self> for NicknameMenu {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "nickname_menu_closed",
+            args: &[],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl NicknameMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            specie_name: "".to_string(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<NinePatchRect>) {
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+        owner.set_process(true);
+
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            self.specie_name = unsafe { game.assume_safe().call("get_caught_pokemon_name", &[]) }.to_string();
+        }
+        self.set_label_text(&owner, "Title", &format!("¿Quieres ponerle un mote a {}?", self.specie_name));
+        self.set_label_text(&owner, "Error", "");
+
+        if let Some(nickname_input) = utils::try_get_node_as::<LineEdit>(&owner, line_edit::NICKNAME_LINE_EDIT_PATH) {
+            nickname_input.set_max_length(MAX_NICKNAME_LENGTH as i64);
+            nickname_input.set_placeholder(self.specie_name.as_str());
+            nickname_input.connect("text_entered", owner, "_on_nickname_entered",
+                VariantArray::new_shared(), 0).unwrap();
+            nickname_input.grab_focus();
+        }
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, _delta: f64) {
+        // Cancelling keeps the specie name
+        if Input::is_action_just_pressed(Input::godot_singleton(), "Exit") {
+            self.close(owner);
+        }
+    }
+
+    #[export]
+    /// Receives the signal of the LineEdit when the player presses `Enter`
+    fn _on_nickname_entered(&mut self, owner: &NinePatchRect, _text: GodotString) {
+        let nickname = match self.retrieve_nickname(owner) {
+            Some(nickname) => nickname,
+            None => return self.close(owner)
+        };
+
+        let error = match unsafe { owner.get_node("/root/Game") } {
+            Some(game) => unsafe { game.assume_safe().call("set_caught_pokemon_nickname", &[nickname.to_variant()]) }
+                .to_string(),
+            None => "".to_string()
+        };
+        if error.is_empty() {
+            self.close(owner);
+        } else {
+            self.set_label_text(owner, "Error", &error);
+        }
+    }
+
+    /// Gets the nickname written on the LineEdit.
+    ///
+    /// Returns None when the LineEdit is missing on the scene
+    fn retrieve_nickname(&self, owner: &NinePatchRect) -> Option<String> {
+        let nickname_on_input = utils::try_get_node_as::<LineEdit>(owner, line_edit::NICKNAME_LINE_EDIT_PATH)?
+            .text();
+        Some(nickname_on_input.to_string())
+    }
+
+    fn close(&self, owner: &NinePatchRect) {
+        owner.emit_signal("nickname_menu_closed", &[]);
+        owner.queue_free();
+    }
+
+    fn set_label_text(&self, owner: &NinePatchRect, label_path: &str, text: &str) {
+        if let Some(label) = unsafe { owner.get_node_as::<Label>(label_path) } {
+            label.set_text(text);
+        }
+    }
+}
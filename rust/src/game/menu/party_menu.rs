@@ -0,0 +1,309 @@
+use gdnative::prelude::*;
+use gdnative::api::{NinePatchRect, ProgressBar};
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::menu::menu_cursor::MenuCursor;
+
+const SUMMARY_MENU_SCENE: &str = "res://godot/Game/SummaryMenu.tscn";
+
+/// Where the player is moving the cursor on the party menu
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PartyMenuState {
+    // Choosing a Pokémon of the party
+    Browsing,
+    // Choosing what to do with the selected Pokémon
+    Submenu,
+    // Choosing the Pokémon that will swap his position with the one on the given slot
+    Switching(usize)
+}
+impl Default for PartyMenuState {
+    fn default() -> Self { PartyMenuState::Browsing }
+}
+
+/// The actions availiable for a Pokémon of the party
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PartySubmenuOption {
+    Summary,
+    Switch,
+    Item,
+    Cancel
+}
+
+impl PartySubmenuOption {
+    pub const ALL: [PartySubmenuOption; 4] = [
+        PartySubmenuOption::Summary, PartySubmenuOption::Switch, PartySubmenuOption::Item, PartySubmenuOption::Cancel
+    ];
+
+    pub fn to_label_str(&self) -> &'static str {
+        match self {
+            PartySubmenuOption::Summary => "Datos",
+            PartySubmenuOption::Switch => "Mover",
+            PartySubmenuOption::Item => "Objeto",
+            PartySubmenuOption::Cancel => "Salir"
+        }
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The screen that shows the Pokémon of the party, opened from the main menu.
+///
+/// All the data comes from the `Party` stored on the `PlayerData` of the Game, so the HP and the status are always the real ones
+pub struct PartyMenu {
+    state: PartyMenuState,
+    current_slot: usize,
+    current_submenu_option: usize,
+    party_summary: VariantArray,
+
+    slot_labels: Vec<Ref<Label>>,
+    // The input that opened this menu can't be read again by it
+    waiting_first_frame: bool,
+    cursor: MenuCursor,
+    // While the summary screen it's showed, the party menu ignores the input
+    summary_open: bool,
+}
+
+impl RegisterSignal<Self> for PartyMenu {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // The player goes back to the main menu
+        builder.add_signal( Signal {
+            name: "party_menu_closed",
+            args: &[],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl PartyMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            state: PartyMenuState::default(),
+            current_slot: 0,
+            current_submenu_option: 0,
+            party_summary: VariantArray::new().into_shared(),
+            slot_labels: Vec::new(),
+            waiting_first_frame: true,
+            cursor: MenuCursor::vertical(),
+            summary_open: false,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &NinePatchRect) {
+        // It's opened with the game paused by the main menu
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+        owner.set_process(true);
+        self.refresh_party(owner);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, delta: f64) {
+        if self.summary_open {
+            return;
+        }
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        let selection_delta = self.cursor.poll(input, delta);
+
+        if selection_delta != 0 {
+            self.move_cursor(owner, selection_delta);
+        } else if Input::is_action_just_pressed(input, "Interact") || Input::is_action_just_pressed(input, "Enter") {
+            self.accept(owner);
+        } else if Input::is_action_just_pressed(input, "Exit") {
+            self.go_back(owner);
+        }
+    }
+
+    fn move_cursor(&mut self, owner: &NinePatchRect, selection_delta: i32) {
+        match self.state {
+            PartyMenuState::Submenu => {
+                self.current_submenu_option = wrap_index(
+                    self.current_submenu_option, selection_delta, PartySubmenuOption::ALL.len());
+                self.update_submenu(owner);
+            },
+            _ => {
+                self.current_slot = wrap_index(self.current_slot, selection_delta, self.slot_labels.len());
+                self.update_slots();
+            }
+        }
+    }
+
+    fn accept(&mut self, owner: &NinePatchRect) {
+        if self.slot_labels.is_empty() {
+            return;
+        }
+
+        match self.state {
+            PartyMenuState::Browsing => {
+                self.state = PartyMenuState::Submenu;
+                self.current_submenu_option = 0;
+                self.update_submenu(owner);
+            },
+            PartyMenuState::Submenu => {
+                match PartySubmenuOption::ALL[self.current_submenu_option] {
+                    PartySubmenuOption::Summary => self.open_summary(owner),
+                    PartySubmenuOption::Switch => {
+                        self.state = PartyMenuState::Switching(self.current_slot);
+                        self.set_message(owner, "¿Con cuál lo quieres cambiar?");
+                        self.update_submenu(owner);
+                    },
+                    PartySubmenuOption::Item => self.show_held_item(owner),
+                    PartySubmenuOption::Cancel => self.go_back(owner)
+                }
+            },
+            PartyMenuState::Switching(first_slot) => {
+                let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+                unsafe { game.call("swap_party_members",
+                    &[(first_slot as i64).to_variant(), (self.current_slot as i64).to_variant()]) };
+                self.state = PartyMenuState::Browsing;
+                self.set_message(owner, "");
+                self.refresh_party(owner);
+            }
+        }
+    }
+
+    /// The cancel path. Every step goes back to the previous one, until the party menu gets closed
+    fn go_back(&mut self, owner: &NinePatchRect) {
+        match self.state {
+            PartyMenuState::Browsing => {
+                owner.emit_signal("party_menu_closed", &[]);
+                owner.queue_free();
+            },
+            PartyMenuState::Submenu | PartyMenuState::Switching(_) => {
+                self.state = PartyMenuState::Browsing;
+                self.set_message(owner, "");
+                self.update_submenu(owner);
+            }
+        }
+    }
+
+    /// Reads again the party from the Game and rebuilds the slots
+    fn refresh_party(&mut self, owner: &NinePatchRect) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        self.party_summary = unsafe { game.call("get_party_summary", &[]) }.to_array();
+
+        let party_slots = unsafe { owner.get_node_as::<VBoxContainer>("PartySlots").unwrap() };
+        for child in party_slots.get_children().iter() {
+            if let Some(child) = child.try_to_object::<Node>() {
+                unsafe { child.assume_safe().queue_free() };
+            }
+        }
+        self.slot_labels.clear();
+
+        let font = unsafe { owner.get_node_as::<Label>("Message").unwrap() }.get_font("font", "");
+        for pokemon in self.party_summary.iter() {
+            let pokemon = pokemon.to_dictionary();
+            let current_hp = pokemon.get("current_hp").to_i64();
+            let max_hp = pokemon.get("max_hp").to_i64();
+
+            let slot_label = Label::new();
+            if let Some(font) = &font {
+                slot_label.add_font_override("font", font);
+            }
+            slot_label.add_color_override("font_color", Color::rgb(0.2, 0.2, 0.2));
+
+            let hp_bar = ProgressBar::new();
+            hp_bar.set_max(max_hp as f64);
+            hp_bar.set_value(current_hp as f64);
+            hp_bar.set_percent_visible(false);
+            hp_bar.set_custom_minimum_size(Vector2::new(0.0, 8.0));
+
+            let slot_label = slot_label.into_shared();
+            party_slots.add_child(slot_label, false);
+            party_slots.add_child(hp_bar, false);
+            self.slot_labels.push(slot_label);
+        }
+
+        if self.slot_labels.is_empty() {
+            self.set_message(owner, "No llevas ningún Pokémon.");
+        }
+        self.current_slot = self.current_slot.min(self.slot_labels.len().saturating_sub(1));
+        self.update_slots();
+    }
+
+    /// Writes the data of every Pokémon on his slot, marking the one under the cursor
+    fn update_slots(&self) {
+        for (slot, slot_label) in self.slot_labels.iter().enumerate() {
+            let pokemon = self.party_summary.get(slot as i32).to_dictionary();
+            let cursor = if slot == self.current_slot { "> " } else { "  " };
+            let slot_label = unsafe { slot_label.assume_safe() };
+            slot_label.set_text(format!("{}{}  Nv. {}  PS {}/{}  {}",
+                cursor,
+                pokemon.get("name").to_string(),
+                pokemon.get("level").to_i64(),
+                pokemon.get("current_hp").to_i64(),
+                pokemon.get("max_hp").to_i64(),
+                pokemon.get("status").to_string()
+            ));
+        }
+    }
+
+    fn update_submenu(&self, owner: &NinePatchRect) {
+        let submenu = unsafe { owner.get_node_as::<Label>("Submenu").unwrap() };
+        submenu.set_visible(self.state == PartyMenuState::Submenu);
+
+        let submenu_text: Vec<String> = PartySubmenuOption::ALL.iter().enumerate()
+            .map(|(index, option)| {
+                let cursor = if index == self.current_submenu_option { "> " } else { "  " };
+                format!("{}{}", cursor, option.to_label_str())
+            })
+            .collect();
+        submenu.set_text(submenu_text.join("\n"));
+    }
+
+    /// Shows the summary screen of the selected Pokémon over the party menu
+    fn open_summary(&mut self, owner: &NinePatchRect) {
+        let summary_menu = unsafe { ResourceLoader::godot_singleton()
+            .load(SUMMARY_MENU_SCENE, "", false)
+            .and_then(|scene| scene.assume_safe().cast::<PackedScene>()?.instance(0))
+            .map(|node| node.assume_safe()) };
+        let parent = owner.get_parent().map(|parent| unsafe { parent.assume_safe() });
+
+        if let (Some(summary_menu), Some(parent)) = (summary_menu, parent) {
+            // Set before entering the tree, so the screen already knows his Pokémon on `_ready`
+            summary_menu.set("party_slot", self.current_slot as i64);
+            summary_menu.connect("summary_menu_closed", unsafe { owner.assume_shared() }, "_on_summary_menu_closed",
+                VariantArray::new_shared(), 0).unwrap();
+            parent.add_child(summary_menu, false);
+            self.summary_open = true;
+            owner.set_visible(false);
+        }
+    }
+
+    #[export]
+    fn _on_summary_menu_closed(&mut self, owner: &NinePatchRect) {
+        self.summary_open = false;
+        // The key that closed the summary can't be read again by the party menu
+        self.waiting_first_frame = true;
+        owner.set_visible(true);
+    }
+
+    fn show_held_item(&self, owner: &NinePatchRect) {
+        let pokemon = self.party_summary.get(self.current_slot as i32).to_dictionary();
+        let held_item = pokemon.get("held_item").to_string();
+        if held_item.is_empty() {
+            self.set_message(owner, &format!("{} no lleva ningún objeto.", pokemon.get("name").to_string()));
+        } else {
+            self.set_message(owner, &format!("{} lleva {}.", pokemon.get("name").to_string(), held_item));
+        }
+    }
+
+    fn set_message(&self, owner: &NinePatchRect, message: &str) {
+        let message_label = unsafe { owner.get_node_as::<Label>("Message").unwrap() };
+        message_label.set_text(message);
+    }
+}
+
+/// Moves an index over a list, going back to the other end when it gets out of it
+pub(crate) fn wrap_index(index: usize, delta: i32, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    (index as i32 + delta).rem_euclid(len as i32) as usize
+}
@@ -1,6 +1,7 @@
 use gdnative::prelude::*;
 use gdnative::api::{NinePatchRect, PackedScene, Resource};
 
+use crate::game::pokedex::Pokedex as PlayerPokedex;
 use crate::utils::utils;
 
 // use crate::game::pokemon::Pokemon;
@@ -17,6 +18,8 @@ use crate::utils::utils;
 /// The code representation on the `LEGENDARY` Pokémon's Pokédex
 pub struct Pokedex {
     pokedex_entries: Vec<PokedexEntry>,
+    // The species seen and caught by the player, from the last saved game
+    player_pokedex: PlayerPokedex,
     
     pokedex_items_holder_node: Option<Ref<Node>>,
     pokedex_item_scene_resource: Option<Ref<Resource>>,
@@ -34,6 +37,7 @@ impl Pokedex {
     fn new(_owner: &Control) -> Self {
         Self {
             pokedex_entries: Vec::<PokedexEntry>::new(),
+            player_pokedex: PlayerPokedex::new(),
             pokedex_items_holder_node: None,
             pokedex_item_scene_resource: None,
             x_entry_position: 300.0,
@@ -56,6 +60,12 @@ impl Pokedex {
         // And this pretty one is a reference to a child node of the Pokédex that will get as many childs as Pokémon are in the game
         self.pokedex_items_holder_node = _owner.get_node("PokedexItems");
 
+        self.player_pokedex = utils::retrieve_game_data().get_player_data().get_pokedex().to_owned();
+        if let Some(counts_label) = unsafe { _owner.get_node_as::<Label>("Counts") } {
+            counts_label.set_text(format!("Vistos: {}   Capturados: {}",
+                self.player_pokedex.seen_count(), self.player_pokedex.caught_count()));
+        }
+
         // Currently just makes as much entries as availiable Pokémons are.
         self.init_pokedex();
 
@@ -153,7 +163,9 @@ impl Pokedex {
 
         // Exits the Pokédex scene and goes back to the Game
         if Input::is_action_just_pressed(&input, "Exit") {
-            utils::change_scene(owner, "res://godot/Game/Game.tscn".to_string())
+            if let Err(err) = utils::change_scene(owner, "res://godot/Game/Game.tscn".to_string()) {
+                godot_print!("Can't go back to the game. {}", err);
+            }
         }
     }
 
@@ -317,6 +329,18 @@ impl Pokedex {
                 self.pokedex_entries.push(pokemon)
             }  
         }
+
+        // Only the species that the player already found are revealed
+        let species_table = utils::retrieve_species_table();
+        for entry in self.pokedex_entries.iter_mut() {
+            entry.spotted_by_player = self.player_pokedex.is_seen(entry.pokedex_entry_number);
+            entry.captured_by_player = self.player_pokedex.is_caught(entry.pokedex_entry_number);
+            if entry.spotted_by_player && entry.name.is_empty() {
+                if let Some(specie) = species_table.get_species(entry.pokedex_entry_number) {
+                    entry.name = specie.name.to_owned();
+                }
+            }
+        }
     }
 }
 
@@ -0,0 +1,142 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::menu::bag_menu::with_cursor;
+use crate::game::menu::menu_cursor::MenuCursor;
+use crate::game::menu::party_menu::wrap_index;
+use crate::game::quests::QuestState;
+
+/// How many quests fit on the screen at the same time
+const VISIBLE_QUESTS: usize = 8;
+/// The two pages of the quest log, the quests in course and the ones already finished
+const QUEST_PAGES: [QuestState; 2] = [QuestState::Active, QuestState::Completed];
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The screen that shows the quests of the player, with the progress of every objective of the selected one.
+///
+/// It's opened from the Pokégear option of the main menu. Left and right change between the active and the
+/// completed quests
+pub struct QuestMenu {
+    current_page: usize,
+    current_quest: usize,
+    quests: VariantArray,
+    // The input that opened this menu can't be read again by it
+    waiting_first_frame: bool,
+    quest_cursor: MenuCursor,
+    page_cursor: MenuCursor,
+}
+
+impl RegisterSignal<Self> for QuestMenu {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // The player goes back to the previous menu
+        builder.add_signal( Signal {
+            name: "quest_menu_closed",
+            args: &[],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl QuestMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            current_page: 0,
+            current_quest: 0,
+            quests: VariantArray::new().into_shared(),
+            waiting_first_frame: true,
+            quest_cursor: MenuCursor::vertical(),
+            page_cursor: MenuCursor::horizontal(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &NinePatchRect) {
+        // It's opened with the game paused by the main menu
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+        owner.set_process(true);
+        self.refresh_quests(owner);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, delta: f64) {
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        let vertical_delta = self.quest_cursor.poll(input, delta);
+        let horizontal_delta = self.page_cursor.poll(input, delta);
+
+        if vertical_delta != 0 {
+            self.current_quest = wrap_index(self.current_quest, vertical_delta, self.quests.len() as usize);
+            self.update_quest_list(owner);
+            self.update_objectives(owner);
+        } else if horizontal_delta != 0 {
+            self.current_page = wrap_index(self.current_page, horizontal_delta, QUEST_PAGES.len());
+            self.current_quest = 0;
+            self.refresh_quests(owner);
+        } else if Input::is_action_just_pressed(input, "Exit") {
+            owner.emit_signal("quest_menu_closed", &[]);
+            owner.queue_free();
+        }
+    }
+
+    /// Reads again the quests of the current page from the Game
+    fn refresh_quests(&mut self, owner: &NinePatchRect) {
+        let page = QUEST_PAGES[self.current_page];
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        self.quests = unsafe { game.call("get_quests", &[page.to_state_str().to_variant()]) }.to_array();
+        self.current_quest = self.current_quest.min((self.quests.len() as usize).saturating_sub(1));
+
+        let page_label = unsafe { owner.get_node_as::<Label>("Page").unwrap() };
+        page_label.set_text(format!("< {} >", match page {
+            QuestState::Completed => "Completadas",
+            _ => "En curso"
+        }));
+
+        self.update_quest_list(owner);
+        self.update_objectives(owner);
+    }
+
+    /// Writes the names of the quests that fit on the screen, scrolling the list to keep the cursor visible
+    fn update_quest_list(&self, owner: &NinePatchRect) {
+        let quest_list = unsafe { owner.get_node_as::<Label>("QuestList").unwrap() };
+        if self.quests.is_empty() {
+            quest_list.set_text("  No hay misiones.");
+            return;
+        }
+
+        let first_visible = (self.current_quest + 1).saturating_sub(VISIBLE_QUESTS);
+        let visible_quests: Vec<String> = self.quests.iter()
+            .enumerate()
+            .skip(first_visible)
+            .take(VISIBLE_QUESTS)
+            .map(|(index, quest)| with_cursor(index == self.current_quest,
+                &quest.to_dictionary().get("name").to_string()))
+            .collect();
+        quest_list.set_text(visible_quests.join("\n"));
+    }
+
+    /// Writes the description of the selected quest, and how far the player got on every objective
+    fn update_objectives(&self, owner: &NinePatchRect) {
+        let objectives_label = unsafe { owner.get_node_as::<Label>("Objectives").unwrap() };
+        if self.quests.is_empty() {
+            objectives_label.set_text("");
+            return;
+        }
+
+        let quest = self.quests.get(self.current_quest as i32).to_dictionary();
+        let mut lines = vec![quest.get("description").to_string()];
+        for objective in quest.get("objectives").to_array().iter() {
+            let objective = objective.to_dictionary();
+            lines.push(format!("{}  {}/{}", objective.get("description").to_string(),
+                objective.get("progress").to_i64(), objective.get("goal").to_i64()));
+        }
+        objectives_label.set_text(lines.join("\n"));
+    }
+}
@@ -0,0 +1,312 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::menu::bag_menu::with_cursor;
+use crate::game::menu::menu_cursor::MenuCursor;
+use crate::game::menu::party_menu::wrap_index;
+
+/// How many items fit on the screen at the same time
+const VISIBLE_ITEMS: usize = 8;
+// The most units of an item that can be bought at once
+const MAX_BUY_QUANTITY: u32 = 99;
+
+/// What the player came to do to the shop
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ShopMode {
+    Buy,
+    Sell,
+    Exit
+}
+
+impl ShopMode {
+    pub const ALL: [ShopMode; 3] = [ShopMode::Buy, ShopMode::Sell, ShopMode::Exit];
+
+    pub fn to_label_str(&self) -> &'static str {
+        match self {
+            ShopMode::Buy => "Comprar",
+            ShopMode::Sell => "Vender",
+            ShopMode::Exit => "Salir"
+        }
+    }
+}
+
+/// Where the player is moving the cursor on the shop screen
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ShopMenuState {
+    // Choosing between buying, selling or leaving
+    ChoosingMode,
+    // Choosing an item of the shop, or of the bag when selling
+    Browsing(ShopMode),
+    // Choosing how many units of the item will be bought or sold
+    ChoosingQuantity(ShopMode)
+}
+
+impl Default for ShopMenuState {
+    fn default() -> Self { ShopMenuState::ChoosingMode }
+}
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The screen of a shop, opened when the player talks to a shopkeeper.
+///
+/// Buying takes the items from the inventory of the shop, and selling from the bag of the player, paying half of the price
+pub struct ShopMenu {
+    // The shop of the shops config whose items are on sale
+    #[property]
+    shop_id: String,
+
+    state: ShopMenuState,
+    current_mode: usize,
+    current_item: usize,
+    quantity: u32,
+    // The money of the player, as the Game told the last time
+    money: i64,
+
+    items: VariantArray,
+    // The input that opened this menu can't be read again by it
+    waiting_first_frame: bool,
+    cursor: MenuCursor,
+}
+
+impl RegisterSignal<Self> for ShopMenu {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "shop_menu_closed",
+            args: &[],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl ShopMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            shop_id: "".to_string(),
+            state: ShopMenuState::default(),
+            current_mode: 0,
+            current_item: 0,
+            quantity: 1,
+            money: 0,
+            items: VariantArray::new().into_shared(),
+            waiting_first_frame: true,
+            cursor: MenuCursor::vertical(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &NinePatchRect) {
+        owner.set_process(true);
+        self.set_message(owner, "¡Hola! ¿Qué deseas?");
+        self.update_money(owner);
+        self.update_item_list(owner);
+        self.update_selector(owner);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, delta: f64) {
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        let selection_delta = self.cursor.poll(input, delta);
+        if selection_delta != 0 {
+            self.move_cursor(owner, selection_delta);
+        } else if Input::is_action_just_pressed(input, "Interact") || Input::is_action_just_pressed(input, "Enter") {
+            self.accept(owner);
+        } else if Input::is_action_just_pressed(input, "Exit") {
+            self.go_back(owner);
+        }
+    }
+
+    fn get_selected_item(&self) -> Option<Dictionary> {
+        if (self.current_item as i32) < self.items.len() {
+            Some(self.items.get(self.current_item as i32).to_dictionary())
+        } else {
+            None
+        }
+    }
+
+    /// The most units of the selected item that could be chosen: the ones that the player can pay, or the ones that he carries
+    fn get_max_quantity(&self, mode: ShopMode) -> u32 {
+        let item = match self.get_selected_item() {
+            Some(item) => item,
+            None => return 1
+        };
+        let max_quantity = if mode == ShopMode::Buy {
+            let price = item.get("price").to_i64().max(1);
+            (self.money / price).min(MAX_BUY_QUANTITY as i64)
+        } else {
+            item.get("quantity").to_i64()
+        };
+        max_quantity.max(1) as u32
+    }
+
+    fn move_cursor(&mut self, owner: &NinePatchRect, delta: i32) {
+        match self.state {
+            ShopMenuState::ChoosingMode => {
+                self.current_mode = wrap_index(self.current_mode, delta, ShopMode::ALL.len());
+                self.update_selector(owner);
+            },
+            ShopMenuState::Browsing(_) => {
+                self.current_item = wrap_index(self.current_item, delta, self.items.len() as usize);
+                self.update_item_list(owner);
+            },
+            ShopMenuState::ChoosingQuantity(mode) => {
+                // Going up adds units, the same way that on the main games
+                self.quantity = (self.quantity as i32 - delta).clamp(1, self.get_max_quantity(mode) as i32) as u32;
+                self.update_selector(owner);
+            }
+        }
+    }
+
+    fn accept(&mut self, owner: &NinePatchRect) {
+        match self.state {
+            ShopMenuState::ChoosingMode => match ShopMode::ALL[self.current_mode] {
+                ShopMode::Exit => self.close(owner),
+                mode => {
+                    self.state = ShopMenuState::Browsing(mode);
+                    self.current_item = 0;
+                    self.refresh_items(owner);
+                    self.update_selector(owner);
+                }
+            },
+            ShopMenuState::Browsing(mode) => {
+                if self.get_selected_item().is_some() {
+                    self.state = ShopMenuState::ChoosingQuantity(mode);
+                    self.quantity = 1;
+                    self.update_selector(owner);
+                }
+            },
+            ShopMenuState::ChoosingQuantity(mode) => self.trade(owner, mode)
+        }
+    }
+
+    /// Buys or sells the chosen units of the selected item
+    fn trade(&mut self, owner: &NinePatchRect, mode: ShopMode) {
+        let item = match self.get_selected_item() {
+            Some(item) => item,
+            None => return
+        };
+        let item_id = item.get("item_id").to_string();
+        let item_name = item.get("name").to_string();
+
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        let error_message = if mode == ShopMode::Buy {
+            unsafe { game.call("buy_item", &[self.shop_id.to_variant(), item_id.to_variant(),
+                (self.quantity as i64).to_variant()]) }.to_string()
+        } else {
+            unsafe { game.call("sell_item", &[item_id.to_variant(), (self.quantity as i64).to_variant()]) }.to_string()
+        };
+
+        let message = if !error_message.is_empty() {
+            error_message
+        } else if mode == ShopMode::Buy {
+            format!("Has comprado {} x{}. ¡Gracias!", item_name, self.quantity)
+        } else {
+            format!("Has vendido {} x{}.", item_name, self.quantity)
+        };
+
+        self.state = ShopMenuState::Browsing(mode);
+        self.refresh_items(owner);
+        self.update_money(owner);
+        self.update_selector(owner);
+        self.set_message(owner, &message);
+    }
+
+    /// The cancel path. Every step goes back to the previous one, until the shop gets closed
+    fn go_back(&mut self, owner: &NinePatchRect) {
+        match self.state {
+            ShopMenuState::ChoosingMode => self.close(owner),
+            ShopMenuState::Browsing(_) => {
+                self.state = ShopMenuState::ChoosingMode;
+                self.items = VariantArray::new().into_shared();
+                self.update_item_list(owner);
+                self.update_selector(owner);
+            },
+            ShopMenuState::ChoosingQuantity(mode) => {
+                self.state = ShopMenuState::Browsing(mode);
+                self.update_selector(owner);
+            }
+        }
+    }
+
+    fn close(&mut self, owner: &NinePatchRect) {
+        owner.emit_signal("shop_menu_closed", &[]);
+        owner.queue_free();
+    }
+
+    /// Reads again the items of the shop (or the ones of the bag that can be sold) from the Game
+    fn refresh_items(&mut self, owner: &NinePatchRect) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        self.items = match self.state {
+            ShopMenuState::Browsing(ShopMode::Sell) | ShopMenuState::ChoosingQuantity(ShopMode::Sell) =>
+                unsafe { game.call("get_sellable_items", &[]) }.to_array(),
+            _ => unsafe { game.call("get_shop_inventory", &[self.shop_id.to_variant()]) }.to_array()
+        };
+        self.current_item = self.current_item.min((self.items.len() as usize).saturating_sub(1));
+        self.set_message(owner, "");
+        self.update_item_list(owner);
+    }
+
+    /// Writes the items that fit on the screen with their prices, scrolling the list to keep the cursor visible
+    fn update_item_list(&self, owner: &NinePatchRect) {
+        let item_list = unsafe { owner.get_node_as::<Label>("ItemList").unwrap() };
+        if self.state == ShopMenuState::ChoosingMode {
+            item_list.set_text("");
+            return;
+        }
+        if self.items.is_empty() {
+            item_list.set_text("  No hay nada aquí.");
+            return;
+        }
+
+        let first_visible = (self.current_item + 1).saturating_sub(VISIBLE_ITEMS);
+        let visible_items: Vec<String> = self.items.iter()
+            .enumerate()
+            .skip(first_visible)
+            .take(VISIBLE_ITEMS)
+            .map(|(index, item)| {
+                let item = item.to_dictionary();
+                let text = format!("{}  {}₽", item.get("name").to_string(), item.get("price").to_i64());
+                with_cursor(index == self.current_item, &text)
+            })
+            .collect();
+        item_list.set_text(visible_items.join("\n"));
+    }
+
+    /// Writes the options of the current step: buy or sell, or the quantity to trade with the total price
+    fn update_selector(&self, owner: &NinePatchRect) {
+        let selector = unsafe { owner.get_node_as::<Label>("Selector").unwrap() };
+        let options: Vec<String> = match self.state {
+            ShopMenuState::ChoosingMode => ShopMode::ALL.iter().enumerate()
+                .map(|(index, mode)| with_cursor(index == self.current_mode, mode.to_label_str()))
+                .collect(),
+            ShopMenuState::Browsing(_) => Vec::new(),
+            ShopMenuState::ChoosingQuantity(_) => {
+                let price = self.get_selected_item().map(|item| item.get("price").to_i64()).unwrap_or(0);
+                vec![format!("x{}", self.quantity), format!("{}₽", price * self.quantity as i64)]
+            }
+        };
+
+        selector.set_visible(!options.is_empty());
+        selector.set_text(options.join("\n"));
+    }
+
+    /// Reads again the money of the player from the Game
+    fn update_money(&mut self, owner: &NinePatchRect) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        self.money = unsafe { game.call("get_money", &[]) }.to_i64();
+        let money_label = unsafe { owner.get_node_as::<Label>("Money").unwrap() };
+        money_label.set_text(format!("Dinero: {}₽", self.money));
+    }
+
+    fn set_message(&self, owner: &NinePatchRect, message: &str) {
+        let message_label = unsafe { owner.get_node_as::<Label>("Message").unwrap() };
+        message_label.set_text(message);
+    }
+}
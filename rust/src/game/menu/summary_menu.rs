@@ -0,0 +1,112 @@
+use gdnative::prelude::*;
+use gdnative::api::NinePatchRect;
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+
+#[derive(NativeClass)]
+#[inherit(NinePatchRect)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// The summary screen of a Pokémon of the party, opened from the party menu.
+///
+/// Shows the stats that the Pokémon really has (from his specie, his level, his individual values and his nature),
+/// and his moves with their type and the PP that are left
+pub struct SummaryMenu {
+    // The slot of the party of the Pokémon. Set by the party menu before opening the screen
+    #[property]
+    party_slot: i64,
+    // The input that opened this menu can't be read again by it
+    waiting_first_frame: bool,
+}
+
+impl RegisterSignal<Self> for SummaryMenu {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // The player goes back to the party menu
+        builder.add_signal( Signal {
+            name: "summary_menu_closed",
+            args: &[],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl SummaryMenu {
+    fn new(_owner: &NinePatchRect) -> Self {
+        Self {
+            party_slot: 0,
+            waiting_first_frame: true,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &NinePatchRect) {
+        // It's opened with the game paused by the main menu
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+        owner.set_process(true);
+        self.show_summary(owner);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &NinePatchRect, _delta: f64) {
+        if self.waiting_first_frame {
+            self.waiting_first_frame = false;
+            return;
+        }
+
+        let input = Input::godot_singleton();
+        if Input::is_action_just_pressed(input, "Exit") || Input::is_action_just_pressed(input, "Interact") {
+            owner.emit_signal("summary_menu_closed", &[]);
+            owner.queue_free();
+        }
+    }
+
+    fn show_summary(&self, owner: &NinePatchRect) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        let pokemon = unsafe { game.call("get_pokemon_summary", &[self.party_slot.to_variant()]) }.to_dictionary();
+
+        let types = [pokemon.get("type1").to_string(), pokemon.get("type2").to_string()].iter()
+            .filter(|pokemon_type| !pokemon_type.is_empty())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" / ");
+        self.set_label_text(owner, "Info", &format!("{}{}  Nv. {}\n{}\nTipo: {}\nNaturaleza: {}\n{}",
+            pokemon.get("name").to_string(),
+            if pokemon.get("shiny").to_bool() { " ★" } else { "" },
+            pokemon.get("level").to_i64(),
+            pokemon.get("specie").to_string(),
+            types,
+            pokemon.get("nature").to_string(),
+            pokemon.get("status").to_string()
+        ));
+
+        self.set_label_text(owner, "Stats", &format!(
+            "PS {}/{}\nAtaque {}\nDefensa {}\nAt. Esp. {}\nDef. Esp. {}\nVelocidad {}",
+            pokemon.get("current_hp").to_i64(),
+            pokemon.get("max_hp").to_i64(),
+            pokemon.get("attack").to_i64(),
+            pokemon.get("defense").to_i64(),
+            pokemon.get("special_attack").to_i64(),
+            pokemon.get("special_defense").to_i64(),
+            pokemon.get("speed").to_i64()
+        ));
+
+        let moves: Vec<String> = pokemon.get("moves").to_array().iter()
+            .map(|pokemon_move| {
+                let pokemon_move = pokemon_move.to_dictionary();
+                format!("{}  {}  PP {}/{}",
+                    pokemon_move.get("name").to_string(),
+                    pokemon_move.get("type").to_string(),
+                    pokemon_move.get("pp").to_i64(),
+                    pokemon_move.get("max_pp").to_i64()
+                )
+            })
+            .collect();
+        self.set_label_text(owner, "Moves", &moves.join("\n"));
+    }
+
+    fn set_label_text(&self, owner: &NinePatchRect, label_path: &str, text: &str) {
+        if let Some(label) = unsafe { owner.get_node_as::<Label>(label_path) } {
+            label.set_text(text);
+        }
+    }
+}
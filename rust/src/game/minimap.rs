@@ -0,0 +1,286 @@
+use gdnative::prelude::*;
+use gdnative::api::{Control, TileMap};
+
+use crate::utils::consts::in_game_constant;
+
+// The groups where the NPCs and the warps register themselves, so the minimap can draw them as icons
+pub const MINIMAP_NPCS_GROUP: &str = "minimap_npcs";
+pub const MINIMAP_WARPS_GROUP: &str = "minimap_warps";
+
+const BACKGROUND_COLOR: (f32, f32, f32, f32) = (0.05, 0.05, 0.1, 0.75);
+const WALKABLE_COLOR: (f32, f32, f32, f32) = (0.45, 0.7, 0.4, 1.0);
+const BLOCKED_COLOR: (f32, f32, f32, f32) = (0.25, 0.3, 0.25, 1.0);
+const NPC_COLOR: (f32, f32, f32, f32) = (0.95, 0.8, 0.2, 1.0);
+const WARP_COLOR: (f32, f32, f32, f32) = (0.3, 0.6, 1.0, 1.0);
+const PLAYER_COLOR: (f32, f32, f32, f32) = (1.0, 0.2, 0.2, 1.0);
+
+/// What the minimap draws on a tile
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MinimapCell {
+    Empty,
+    Walkable,
+    // There's a tile with collisions on some of the layers
+    Blocked
+}
+
+/// A simplified top-down view of a map: one cell per tile, covering the bounds of every TileMap of the scene
+#[derive(Clone, Debug, Default)]
+pub struct MinimapGrid {
+    // The tile of the top-left corner of the grid, on world tiles
+    origin: (i32, i32),
+    width: i32,
+    height: i32,
+    cells: Vec<MinimapCell>,
+}
+
+impl MinimapGrid {
+    /// Builds the grid that covers the given tiles. Every tile is empty until it gets marked
+    pub fn from_bounds(min: (i32, i32), max: (i32, i32)) -> Self {
+        let width = (max.0 - min.0 + 1).max(0);
+        let height = (max.1 - min.1 + 1).max(0);
+        Self {
+            origin: min,
+            width,
+            height,
+            cells: vec![MinimapCell::Empty; (width * height) as usize],
+        }
+    }
+
+    pub fn width(&self) -> i32 { self.width }
+    pub fn height(&self) -> i32 { self.height }
+    pub fn origin(&self) -> (i32, i32) { self.origin }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    fn index_of(&self, tile: (i32, i32)) -> Option<usize> {
+        let (x, y) = (tile.0 - self.origin.0, tile.1 - self.origin.1);
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            None
+        } else {
+            Some((y * self.width + x) as usize)
+        }
+    }
+
+    /// Marks a tile of the map. A tile that it's blocked on any layer stays blocked
+    pub fn mark(&mut self, tile: (i32, i32), cell: MinimapCell) {
+        if let Some(index) = self.index_of(tile) {
+            if self.cells[index] != MinimapCell::Blocked {
+                self.cells[index] = cell;
+            }
+        }
+    }
+
+    pub fn get(&self, tile: (i32, i32)) -> MinimapCell {
+        self.index_of(tile).map(|index| self.cells[index]).unwrap_or(MinimapCell::Empty)
+    }
+}
+
+/// Converts a position of the world to the tile where it is
+pub fn world_to_tile(position: Vector2) -> (i32, i32) {
+    (
+        (position.x / in_game_constant::TILE_SIZE).floor() as i32,
+        (position.y / in_game_constant::TILE_SIZE).floor() as i32
+    )
+}
+
+#[derive(NativeClass)]
+#[inherit(Control)]
+#[derive(Debug)]
+/// A small map on a corner of the screen, drawn from the tiles of the scene where the player is.
+///
+/// It's hidden until the player presses the `Minimap` key. The tiles of the scene are only read when it's shown
+/// (or when the scene changes), and it only gets drawn again when the player steps onto another tile
+pub struct Minimap {
+    grid: MinimapGrid,
+    // The scene whose tiles are on the grid
+    grid_scene: Option<Ref<Node>>,
+    player_tile: (i32, i32),
+}
+
+#[gdnative::methods]
+impl Minimap {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            grid: MinimapGrid::default(),
+            grid_scene: None,
+            player_tile: (0, 0),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<Control>) {
+        owner.set_visible(false);
+        owner.set_process(true);
+
+        if let Some(player) = unsafe { owner.get_node("/root/Game/Player") } {
+            unsafe { player.assume_safe() }.connect("player_step_completed", owner, "_on_player_step_completed",
+                VariantArray::new_shared(), 0).unwrap();
+        }
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &Control, _delta: f64) {
+        if Input::is_action_just_pressed(Input::godot_singleton(), "Minimap") {
+            let visible = !owner.is_visible();
+            owner.set_visible(visible);
+            if visible {
+                self.refresh(owner);
+            }
+        }
+    }
+
+    #[export]
+    /// The player finished a step. The minimap only gets drawn again if he's now on another tile
+    fn _on_player_step_completed(&mut self, owner: &Control) {
+        if !owner.is_visible() {
+            return;
+        }
+        // Warps can change the scene in the middle of the walk
+        if self.get_world_scene(owner) != self.grid_scene {
+            self.refresh(owner);
+            return;
+        }
+        if let Some(player_tile) = self.get_player_tile(owner) {
+            if player_tile != self.player_tile {
+                self.player_tile = player_tile;
+                owner.update();
+            }
+        }
+    }
+
+    #[export]
+    fn _draw(&self, owner: &Control) {
+        let size = owner.size();
+        owner.draw_rect(Rect2::new(Point2::new(0.0, 0.0), size.to_size()), color(BACKGROUND_COLOR), true, 1.0, false);
+        if self.grid.is_empty() {
+            return;
+        }
+
+        // The whole map gets scaled to fit the minimap, keeping the tiles square
+        let tile_size = (size.x / self.grid.width() as f32).min(size.y / self.grid.height() as f32);
+        let offset = Vector2::new(
+            (size.x - tile_size * self.grid.width() as f32) / 2.0,
+            (size.y - tile_size * self.grid.height() as f32) / 2.0
+        );
+        let (origin_x, origin_y) = self.grid.origin();
+        let draw_tile = |tile: (i32, i32), tile_color: Color, scale: f32| {
+            let margin = tile_size * (1.0 - scale) / 2.0;
+            let position = offset + Vector2::new(
+                (tile.0 - origin_x) as f32 * tile_size + margin,
+                (tile.1 - origin_y) as f32 * tile_size + margin
+            );
+            let side = tile_size * scale;
+            owner.draw_rect(Rect2::new(position.to_point(), Size2::new(side, side)), tile_color, true, 1.0, false);
+        };
+
+        for y in origin_y..origin_y + self.grid.height() {
+            for x in origin_x..origin_x + self.grid.width() {
+                match self.grid.get((x, y)) {
+                    MinimapCell::Walkable => draw_tile((x, y), color(WALKABLE_COLOR), 1.0),
+                    MinimapCell::Blocked => draw_tile((x, y), color(BLOCKED_COLOR), 1.0),
+                    MinimapCell::Empty => ()
+                }
+            }
+        }
+
+        for (group, icon_color) in [(MINIMAP_WARPS_GROUP, WARP_COLOR), (MINIMAP_NPCS_GROUP, NPC_COLOR)].iter() {
+            for tile in self.get_group_tiles(owner, group) {
+                draw_tile(tile, color(*icon_color), 0.7);
+            }
+        }
+        draw_tile(self.player_tile, color(PLAYER_COLOR), 1.0);
+    }
+
+    /// Reads again the tiles of the scene where the player is, and draws them
+    fn refresh(&mut self, owner: &Control) {
+        self.grid_scene = self.get_world_scene(owner);
+        self.grid = self.grid_scene
+            .map(|scene| build_grid(unsafe { scene.assume_safe() }))
+            .unwrap_or_default();
+        if let Some(player_tile) = self.get_player_tile(owner) {
+            self.player_tile = player_tile;
+        }
+        owner.update();
+    }
+
+    fn get_world_scene(&self, owner: &Control) -> Option<Ref<Node>> {
+        let game = unsafe { owner.get_node("/root/Game")?.assume_safe() };
+        unsafe { game.call("get_world_scene", &[]) }.try_to_object::<Node>()
+    }
+
+    fn get_player_tile(&self, owner: &Control) -> Option<(i32, i32)> {
+        let player = unsafe { owner.get_node_as::<Node2D>("/root/Game/Player")? };
+        Some(world_to_tile(player.global_position()))
+    }
+
+    /// The tiles of the nodes of the group that are on the current scene
+    fn get_group_tiles(&self, owner: &Control, group: &str) -> Vec<(i32, i32)> {
+        let grid_scene = match self.grid_scene {
+            Some(grid_scene) => unsafe { grid_scene.assume_safe() },
+            None => return Vec::new()
+        };
+        let tree = match owner.get_tree() {
+            Some(tree) => unsafe { tree.assume_safe() },
+            None => return Vec::new()
+        };
+
+        tree.get_nodes_in_group(group).iter()
+            .filter_map(|node| node.try_to_object::<Node2D>())
+            .map(|node| unsafe { node.assume_safe() })
+            .filter(|node| grid_scene.is_a_parent_of(*node))
+            .map(|node| world_to_tile(node.global_position()))
+            .collect()
+    }
+}
+
+/// Reads every TileMap under the scene, and marks his tiles on a grid that covers all of them
+fn build_grid(scene: TRef<Node>) -> MinimapGrid {
+    let mut tiles: Vec<((i32, i32), MinimapCell)> = Vec::new();
+    collect_tiles(scene, &mut tiles);
+    if tiles.is_empty() {
+        return MinimapGrid::default();
+    }
+
+    let min = tiles.iter().fold((i32::MAX, i32::MAX), |min, (tile, _)| (min.0.min(tile.0), min.1.min(tile.1)));
+    let max = tiles.iter().fold((i32::MIN, i32::MIN), |max, (tile, _)| (max.0.max(tile.0), max.1.max(tile.1)));
+
+    let mut grid = MinimapGrid::from_bounds(min, max);
+    for (tile, cell) in tiles {
+        grid.mark(tile, cell);
+    }
+    grid
+}
+
+fn collect_tiles(node: TRef<Node>, tiles: &mut Vec<((i32, i32), MinimapCell)>) {
+    if let Some(tilemap) = node.cast::<TileMap>() {
+        let tileset = tilemap.tileset().map(|tileset| unsafe { tileset.assume_safe() });
+        for cell in tilemap.get_used_cells().iter() {
+            let cell = cell.to_vector2();
+            let tile_id = tilemap.get_cellv(cell);
+            let is_blocked = tileset.map_or(false, |tileset| tileset.tile_get_shape_count(tile_id) > 0);
+
+            // The cells of the TileMaps can have any size, so they get converted to world tiles
+            let world_position = tilemap.to_global(tilemap.map_to_world(cell, false));
+            let cell_size = tilemap.cell_size();
+            let first_tile = world_to_tile(world_position);
+            let last_tile = world_to_tile(world_position + cell_size - Vector2::new(1.0, 1.0));
+            for y in first_tile.1..=last_tile.1 {
+                for x in first_tile.0..=last_tile.0 {
+                    tiles.push(((x, y), if is_blocked { MinimapCell::Blocked } else { MinimapCell::Walkable }));
+                }
+            }
+        }
+    }
+
+    for child in node.get_children().iter() {
+        if let Some(child) = child.try_to_object::<Node>() {
+            collect_tiles(unsafe { child.assume_safe() }, tiles);
+        }
+    }
+}
+
+fn color(rgba: (f32, f32, f32, f32)) -> Color {
+    Color::rgba(rgba.0, rgba.1, rgba.2, rgba.3)
+}
@@ -1,17 +1,53 @@
 pub mod game;
+pub mod game_state;
 pub mod city;
 
 pub mod player;
+pub mod trainer;
+pub mod npc;
+pub mod interactables;
 pub mod pokemon;
 pub mod pokemon_specie;
+pub mod species;
+pub mod nature;
 pub mod pokemon_database;
+pub mod party;
+pub mod event_flags;
+pub mod box_storage;
+pub mod pokedex;
+pub mod inventory;
+pub mod bag;
+pub mod shop;
+pub mod dialogues;
+pub mod cutscene;
+pub mod evolution;
+pub mod berries;
+pub mod quests;
+pub mod save_migration;
 
 pub mod map;
 pub mod map_elements__galicia;
 pub mod area_scene_switcher;
 pub mod tall_grass;
+pub mod encounter;
+pub mod fishing;
+pub mod time_of_day;
+pub mod weather;
+pub mod weather_overlay;
+pub mod weather_zone;
+pub mod momentum_zone;
+pub mod terrain;
+pub mod minimap;
+pub mod debug_overlay;
+pub mod battle;
 pub mod grass_step_effect;
 pub mod landing_dust_effect;
+pub mod sprint_dust_effect;
 pub mod menu;
+pub mod music_manager;
+pub mod settings;
+pub mod gamepad;
+pub mod balance;
+pub mod transition_overlay;
 pub mod dialogue_box;
 pub mod code_abstractions;
\ No newline at end of file
@@ -0,0 +1,52 @@
+use gdnative::prelude::*;
+use gdnative::api::Area2D;
+
+#[derive(NativeClass)]
+#[inherit(Area2D)]
+#[derive(Debug)]
+/// A zone of the map where the player moves with momentum (deep sand, ice...): he speeds up and slows down
+/// gradually, instead of reaching the full speed at once.
+///
+/// The momentum movement it's turned on when the player walks into the zone, and off again when he leaves it
+pub struct MomentumZone {
+    // Tiles per second gained every second, until the player reaches his walking or running speed
+    #[property(default = 6.0)]
+    acceleration: f64,
+    // Tiles per second lost every second, while the player stands still or changes to a slower speed
+    #[property(default = 12.0)]
+    deceleration: f64,
+}
+
+#[gdnative::methods]
+impl MomentumZone {
+    fn new(_owner: &Area2D) -> Self {
+        Self {
+            acceleration: 6.0,
+            deceleration: 12.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&self, owner: TRef<Area2D>) {
+        owner.connect("body_entered", owner, "_on_area2d_body_entered", VariantArray::new_shared(), 0).unwrap();
+        owner.connect("body_exited", owner, "_on_area2d_body_exited", VariantArray::new_shared(), 0).unwrap();
+    }
+
+    #[export]
+    fn _on_area2d_body_entered(&self, _owner: TRef<Area2D>, body: Ref<Node>) {
+        self.set_momentum_movement(body, true);
+    }
+
+    #[export]
+    fn _on_area2d_body_exited(&self, _owner: TRef<Area2D>, body: Ref<Node>) {
+        self.set_momentum_movement(body, false);
+    }
+
+    fn set_momentum_movement(&self, body: Ref<Node>, enabled: bool) {
+        let body = unsafe { body.assume_safe() };
+        if body.name().to_string() == "Player" {
+            unsafe { body.call("set_momentum_movement",
+                &[enabled.to_variant(), self.acceleration.to_variant(), self.deceleration.to_variant()]) };
+        }
+    }
+}
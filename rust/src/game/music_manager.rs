@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+
+use gdnative::prelude::*;
+use gdnative::api::{AudioStream, AudioStreamPlayer};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::settings::{volume_to_db, SILENCE_DB};
+use crate::utils::utils;
+
+/// The tracks that sounds on every scene, loaded from the music JSON config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MusicConfig {
+    // Scene name (like `Map`) -> path to the track
+    #[serde(default)]
+    scene_tracks: HashMap<String, String>,
+    #[serde(default)]
+    battle_track: String,
+    // Sound effect name (like `poison_step`) -> path to the sound
+    #[serde(default)]
+    sound_effects: HashMap<String, String>,
+}
+
+impl MusicConfig {
+    /// The track of the given scene, looked up by the name of the scene file
+    pub fn get_scene_track(&self, scene_path: &str) -> Option<&String> {
+        Path::new(scene_path).file_stem()
+            .and_then(OsStr::to_str)
+            .and_then(|scene_name| self.scene_tracks.get(scene_name))
+    }
+
+    pub fn get_battle_track(&self) -> &str {
+        &self.battle_track
+    }
+
+    pub fn get_sound_effect(&self, sound_name: &str) -> Option<&String> {
+        self.sound_effects.get(sound_name)
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[derive(Debug)]
+/// Autoload that plays the background music of the game, cross-fading from one track to the next one
+/// when the player changes of scene. It also plays the short sound effects of the overworld.
+///
+/// Lives on `/root/MusicManager`, so it keeps sounding across scene changes.
+pub struct MusicManager {
+    // Seconds that lasts the cross-fade between two tracks
+    #[property(default = 1.0)]
+    fade_duration: f64,
+
+    music_config: MusicConfig,
+    current_track: String,
+    // The overworld track that sounds again when the battle ends
+    overworld_track: Option<String>,
+    // From 0 to 100
+    volume: f64,
+
+    // Two players, so one can fade out while the other one fades in
+    current_player: Option<Ref<AudioStreamPlayer>>,
+    fading_player: Option<Ref<AudioStreamPlayer>>,
+    fade_progress: f64,
+    // The sound effects sound through their own player, so they never cut the music
+    sound_effect_player: Option<Ref<AudioStreamPlayer>>,
+}
+
+#[gdnative::methods]
+impl MusicManager {
+    fn new(_owner: &Node) -> Self {
+        Self {
+            fade_duration: 1.0,
+            music_config: MusicConfig::default(),
+            current_track: "".to_string(),
+            overworld_track: None,
+            volume: 100.0,
+            current_player: None,
+            fading_player: None,
+            fade_progress: 1.0,
+            sound_effect_player: None,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &Node) {
+        self.music_config = utils::retrieve_music_config();
+        // The music keeps sounding while the game it's paused
+        owner.set_pause_mode(Node::PAUSE_MODE_PROCESS);
+
+        // Both players sound through the Music bus, whose volume it's set from the settings
+        let current_player = AudioStreamPlayer::new();
+        let fading_player = AudioStreamPlayer::new();
+        current_player.set_bus("Music");
+        fading_player.set_bus("Music");
+        let current_player = current_player.into_shared();
+        let fading_player = fading_player.into_shared();
+        owner.add_child(current_player, false);
+        owner.add_child(fading_player, false);
+        self.current_player = Some(current_player);
+        self.fading_player = Some(fading_player);
+
+        let sound_effect_player = AudioStreamPlayer::new();
+        sound_effect_player.set_bus("SFX");
+        let sound_effect_player = sound_effect_player.into_shared();
+        owner.add_child(sound_effect_player, false);
+        self.sound_effect_player = Some(sound_effect_player);
+    }
+
+    #[export]
+    fn _process(&mut self, _owner: &Node, delta: f64) {
+        if self.fade_progress >= 1.0 {
+            return;
+        }
+
+        self.fade_progress = (self.fade_progress + delta / self.fade_duration.max(0.01)).min(1.0);
+
+        let current_player = unsafe { self.current_player.unwrap().assume_safe() };
+        let fading_player = unsafe { self.fading_player.unwrap().assume_safe() };
+        current_player.set_volume_db(self.volume_to_db(self.fade_progress));
+        fading_player.set_volume_db(self.volume_to_db(1.0 - self.fade_progress));
+
+        if self.fade_progress >= 1.0 {
+            fading_player.stop();
+        }
+    }
+
+    #[export]
+    /// Starts playing the given track, cross-fading with the one that was sounding. If the track it's already
+    /// the one that sounds, the music just keeps going
+    fn play_track(&mut self, _owner: &Node, track: String) {
+        if track == self.current_track {
+            return;
+        }
+
+        let stream = ResourceLoader::godot_singleton().load(track.to_owned(), "", false)
+            .and_then(|resource| resource.cast::<AudioStream>());
+        let stream = match stream {
+            Some(stream) => stream,
+            None => {
+                godot_print!("Music track not found: {}", track);
+                return;
+            }
+        };
+
+        // The player that was sounding becomes the one that fades out
+        std::mem::swap(&mut self.current_player, &mut self.fading_player);
+        let current_player = unsafe { self.current_player.unwrap().assume_safe() };
+        current_player.set_stream(stream);
+        current_player.set_volume_db(SILENCE_DB);
+        current_player.play(0.0);
+
+        self.current_track = track;
+        self.fade_progress = 0.0;
+    }
+
+    #[export]
+    /// Plays the sound effect with the given name of the music config. A new sound effect cuts the previous one
+    fn play_sound_effect(&mut self, _owner: &Node, sound_name: String) {
+        let stream = self.music_config.get_sound_effect(&sound_name)
+            .and_then(|sound| ResourceLoader::godot_singleton().load(sound.to_owned(), "", false))
+            .and_then(|resource| resource.cast::<AudioStream>());
+        let stream = match stream {
+            Some(stream) => stream,
+            None => {
+                godot_print!("Sound effect not found: {}", sound_name);
+                return;
+            }
+        };
+
+        if let Some(sound_effect_player) = self.sound_effect_player {
+            let sound_effect_player = unsafe { sound_effect_player.assume_safe() };
+            sound_effect_player.set_stream(stream);
+            sound_effect_player.play(0.0);
+        }
+    }
+
+    #[export]
+    fn stop(&mut self, _owner: &Node) {
+        unsafe { self.current_player.unwrap().assume_safe().stop() };
+        unsafe { self.fading_player.unwrap().assume_safe().stop() };
+        self.current_track = "".to_string();
+        self.fade_progress = 1.0;
+    }
+
+    #[export]
+    /// Sets the music volume, from 0 to 100
+    fn set_volume(&mut self, _owner: &Node, volume: f64) {
+        self.volume = volume.max(0.0).min(100.0);
+        if self.fade_progress >= 1.0 {
+            unsafe { self.current_player.unwrap().assume_safe().set_volume_db(self.volume_to_db(1.0)) };
+        }
+    }
+
+    #[export]
+    /// Plays the track of the scene that has just been loaded, if it has one
+    fn on_scene_changed(&mut self, owner: &Node, scene_path: String) {
+        if let Some(track) = self.music_config.get_scene_track(&scene_path).cloned() {
+            self.play_track(owner, track);
+        }
+    }
+
+    #[export]
+    /// Plays the battle music, remembering the overworld track for when the battle ends
+    fn push_battle_track(&mut self, owner: &Node) {
+        let battle_track = self.music_config.get_battle_track().to_string();
+        if self.overworld_track.is_none() {
+            self.overworld_track = Some(self.current_track.to_owned());
+        }
+        self.play_track(owner, battle_track);
+    }
+
+    #[export]
+    /// Goes back to the overworld track that was sounding before the battle
+    fn pop_battle_track(&mut self, owner: &Node) {
+        if let Some(overworld_track) = self.overworld_track.take() {
+            if overworld_track.is_empty() {
+                self.stop(owner);
+            } else {
+                self.play_track(owner, overworld_track);
+            }
+        }
+    }
+
+    /// Converts the volume of the music, scaled by the given fade factor (from 0 to 1), to decibels
+    fn volume_to_db(&self, fade: f64) -> f64 {
+        volume_to_db(self.volume * fade)
+    }
+}
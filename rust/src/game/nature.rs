@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+/// The stats (every one except the HP) that a nature can raise or lower
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stat {
+    Attack,
+    Defense,
+    Speed,
+    SpecialAttack,
+    SpecialDefense
+}
+
+impl Stat {
+    // The order of the natures table: the raised stat goes on the rows, and the lowered one on the columns
+    pub const ALL: [Stat; 5] = [Stat::Attack, Stat::Defense, Stat::Speed, Stat::SpecialAttack, Stat::SpecialDefense];
+}
+
+/// The nature of a Pokémon. Every nature raises one stat by a 10% and lowers another one by a 10%, except
+/// the neutral ones (the ones that would raise and lower the same stat)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Nature {
+    Hardy, Lonely, Brave, Adamant, Naughty,
+    Bold, Docile, Relaxed, Impish, Lax,
+    Timid, Hasty, Serious, Jolly, Naive,
+    Modest, Mild, Quiet, Bashful, Rash,
+    Calm, Gentle, Sassy, Careful, Quirky
+}
+
+impl Default for Nature {
+    fn default() -> Self { Nature::Hardy }
+}
+
+impl Nature {
+    pub const ALL: [Nature; 25] = [
+        Nature::Hardy, Nature::Lonely, Nature::Brave, Nature::Adamant, Nature::Naughty,
+        Nature::Bold, Nature::Docile, Nature::Relaxed, Nature::Impish, Nature::Lax,
+        Nature::Timid, Nature::Hasty, Nature::Serious, Nature::Jolly, Nature::Naive,
+        Nature::Modest, Nature::Mild, Nature::Quiet, Nature::Bashful, Nature::Rash,
+        Nature::Calm, Nature::Gentle, Nature::Sassy, Nature::Careful, Nature::Quirky
+    ];
+
+    fn index(&self) -> usize {
+        Nature::ALL.iter().position(|nature| nature == self).unwrap_or(0)
+    }
+
+    /// The stat raised by the nature, or None if it's a neutral one
+    pub fn raised_stat(&self) -> Option<Stat> {
+        if self.is_neutral() { None } else { Some(Stat::ALL[self.index() / 5]) }
+    }
+
+    /// The stat lowered by the nature, or None if it's a neutral one
+    pub fn lowered_stat(&self) -> Option<Stat> {
+        if self.is_neutral() { None } else { Some(Stat::ALL[self.index() % 5]) }
+    }
+
+    pub fn is_neutral(&self) -> bool {
+        self.index() / 5 == self.index() % 5
+    }
+
+    /// How the nature modifies the given stat: 1.1 if raises it, 0.9 if lowers it, 1.0 otherwise
+    pub fn stat_multiplier(&self, stat: Stat) -> f64 {
+        if self.raised_stat() == Some(stat) {
+            1.1
+        } else if self.lowered_stat() == Some(stat) {
+            0.9
+        } else {
+            1.0
+        }
+    }
+
+    /// The name showed to the player
+    pub fn to_spanish_str(&self) -> &'static str {
+        match self {
+            Nature::Hardy => "Fuerte",
+            Nature::Lonely => "Huraña",
+            Nature::Brave => "Audaz",
+            Nature::Adamant => "Firme",
+            Nature::Naughty => "Pícara",
+            Nature::Bold => "Osada",
+            Nature::Docile => "Dócil",
+            Nature::Relaxed => "Plácida",
+            Nature::Impish => "Agitada",
+            Nature::Lax => "Floja",
+            Nature::Timid => "Miedosa",
+            Nature::Hasty => "Activa",
+            Nature::Serious => "Seria",
+            Nature::Jolly => "Alegre",
+            Nature::Naive => "Ingenua",
+            Nature::Modest => "Modesta",
+            Nature::Mild => "Afable",
+            Nature::Quiet => "Mansa",
+            Nature::Bashful => "Tímida",
+            Nature::Rash => "Alocada",
+            Nature::Calm => "Serena",
+            Nature::Gentle => "Amable",
+            Nature::Sassy => "Grosera",
+            Nature::Careful => "Cauta",
+            Nature::Quirky => "Rara"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_nature_raises_and_lowers_the_stats_of_his_place_on_the_table() {
+        assert_eq!(Nature::Adamant.raised_stat(), Some(Stat::Attack));
+        assert_eq!(Nature::Adamant.lowered_stat(), Some(Stat::SpecialAttack));
+        assert_eq!(Nature::Timid.raised_stat(), Some(Stat::Speed));
+        assert_eq!(Nature::Timid.lowered_stat(), Some(Stat::Attack));
+        assert_eq!(Nature::Sassy.raised_stat(), Some(Stat::SpecialDefense));
+        assert_eq!(Nature::Sassy.lowered_stat(), Some(Stat::Speed));
+    }
+
+    #[test]
+    fn the_neutral_natures_change_nothing() {
+        let neutral: Vec<Nature> = Nature::ALL.iter().copied().filter(Nature::is_neutral).collect();
+        assert_eq!(neutral, vec![Nature::Hardy, Nature::Docile, Nature::Serious, Nature::Bashful, Nature::Quirky]);
+        for nature in neutral.iter() {
+            assert!(Stat::ALL.iter().all(|stat| nature.stat_multiplier(*stat) == 1.0));
+        }
+    }
+
+    #[test]
+    fn the_other_natures_raise_a_stat_and_lower_another() {
+        for nature in Nature::ALL.iter().filter(|nature| !nature.is_neutral()) {
+            let multipliers: Vec<f64> = Stat::ALL.iter().map(|stat| nature.stat_multiplier(*stat)).collect();
+            assert_eq!(multipliers.iter().filter(|multiplier| **multiplier == 1.1).count(), 1, "{:?}", nature);
+            assert_eq!(multipliers.iter().filter(|multiplier| **multiplier == 0.9).count(), 1, "{:?}", nature);
+        }
+    }
+}
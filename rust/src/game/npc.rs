@@ -0,0 +1,207 @@
+use gdnative::prelude::*;
+use gdnative::api::{AnimatedSprite, KinematicBody2D, RayCast2D};
+
+use crate::game::code_abstractions::{
+    character::CharacterTileMovement,
+    random::{GameRng, SeededRng},
+    signals::{AddTypedSignal, RegisterSignal}
+};
+use crate::game::minimap::MINIMAP_NPCS_GROUP;
+use crate::game::player::PlayerDirection;
+use crate::utils::{consts::in_game_constant, utils};
+
+#[derive(NativeClass)]
+#[inherit(KinematicBody2D)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// An ambient NPC that wanders around his home tile, taking a step on a random direction every few seconds.
+///
+/// The NPC never walks further than `wander_radius` tiles from the place where it was placed on the editor.
+pub struct Npc {
+    // How far (in tiles) the NPC can walk from his home tile
+    #[property(default = 2)]
+    wander_radius: i32,
+    // Seconds between one step and the next one
+    #[property(default = 2.0)]
+    wander_interval: f64,
+
+    home_position: Vector2,
+    time_since_last_step: f64,
+    blocking_raycast: Option<TRef<'static, RayCast2D>>,
+    player: Option<TRef<'static, KinematicBody2D>>,
+
+    // Tile-based movement system, the same one that uses the player
+    initial_position: Vector2,
+    input_direction: Vector2,
+    percent_move_to_next_tile: f64,
+    is_moving: bool,
+    // Tiles per second, from the balance config
+    walk_speed: f64,
+    // Picks the direction of every step
+    rng: SeededRng,
+}
+
+impl RegisterSignal<Self> for Npc {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // Indicates that the NPC it's moving, so the NpcAnimation plays the right animation
+        builder.add_typed_signal("animate", "motion", Vector2::new(0.0, 0.0));
+    }
+}
+
+impl CharacterTileMovement<KinematicBody2D, Input> for Npc {
+    /// NPCs doesn't read the keyboard. They just pick a random direction that keeps them inside his wander bounds
+    fn process_player_input(&mut self, owner: &KinematicBody2D, _input: &Input) {
+        let current_position = owner.global_position();
+        let max_distance = self.wander_radius as f32 * in_game_constant::TILE_SIZE;
+
+        let valid_directions: Vec<Vector2> = [
+            PlayerDirection::Upwards, PlayerDirection::Downwards, PlayerDirection::Left, PlayerDirection::Right
+        ].iter()
+            .map(|direction| direction.to_vector())
+            .filter(|direction| {
+                let target = current_position + *direction * in_game_constant::TILE_SIZE - self.home_position;
+                target.x.abs() <= max_distance && target.y.abs() <= max_distance
+            })
+            .collect();
+
+        if !valid_directions.is_empty() {
+            let direction = valid_directions[self.rng.next_in_range(0, valid_directions.len() as u32) as usize];
+            self.input_direction = direction;
+            self.initial_position = current_position;
+            self.is_moving = true;
+        }
+    }
+
+    /// Moves the NPC one tile, unless there's something blocking his way
+    fn tilemove_or_collide(&mut self, owner: &KinematicBody2D, delta: f32) {
+        let blocking_raycast = self.blocking_raycast.unwrap();
+        blocking_raycast.set_cast_to(self.input_direction * in_game_constant::TILE_SIZE / 2.0);
+        blocking_raycast.force_raycast_update();
+
+        if !blocking_raycast.is_colliding() {
+            self.move_character(owner, delta);
+        } else {
+            self.stop_moving();
+        }
+    }
+
+    /// Creates a `tile based` movement for the NPC
+    fn move_character(&mut self, owner: &KinematicBody2D, delta: f32) {
+        self.percent_move_to_next_tile += self.walk_speed * delta as f64;
+
+        if self.percent_move_to_next_tile >= 1.0 {
+            owner.set_global_position(self.initial_position + self.input_direction * in_game_constant::TILE_SIZE);
+            self.stop_moving();
+        } else {
+            owner.set_global_position(self.initial_position +
+                self.input_direction * in_game_constant::TILE_SIZE * self.percent_move_to_next_tile as f32);
+        }
+    }
+}
+
+#[gdnative::methods]
+impl Npc {
+    fn new(_owner: &KinematicBody2D) -> Self {
+        Self {
+            wander_radius: 2,
+            wander_interval: 2.0,
+            home_position: Vector2::new(0.0, 0.0),
+            time_since_last_step: 0.0,
+            blocking_raycast: None,
+            player: None,
+            initial_position: Vector2::new(0.0, 0.0),
+            input_direction: Vector2::new(0.0, 0.0),
+            percent_move_to_next_tile: 0.0,
+            is_moving: false,
+            walk_speed: in_game_constant::WALK_SPEED,
+            rng: SeededRng::from_time(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<KinematicBody2D>) {
+        self.home_position = owner.global_position();
+        self.walk_speed = utils::retrieve_balance_config().get_walk_speed();
+        owner.add_to_group(MINIMAP_NPCS_GROUP, false);
+        self.blocking_raycast = unsafe { owner.get_node_as::<RayCast2D>("BlockingRayCast") };
+        self.player = unsafe { owner.get_node_as::<KinematicBody2D>("/root/Game/Player") };
+
+        let npc_animation = unsafe { owner.get_node("AnimatedSprite").unwrap().assume_safe() };
+        owner.connect("animate", npc_animation, "_on_npc_animate",
+            VariantArray::new_shared(), 0).unwrap();
+    }
+
+    #[export]
+    fn _physics_process(&mut self, owner: &KinematicBody2D, delta: f32) {
+        if self.is_talking_with_player(owner) {
+            // Stays quiet, looking at the player, until the interaction ends
+            self.time_since_last_step = 0.0;
+        } else if self.is_moving {
+            self.tilemove_or_collide(owner, delta);
+        } else {
+            self.time_since_last_step += delta as f64;
+            if self.time_since_last_step >= self.wander_interval {
+                self.time_since_last_step = 0.0;
+                self.process_player_input(owner, Input::godot_singleton());
+            }
+        }
+
+        owner.emit_signal("animate", &[self.input_direction.to_variant()]);
+    }
+
+    /// The NPC stops wandering while the player it's interacting just in front of him. An NPC that it's
+    /// already walking finishes his step first, so it never stays between two tiles
+    fn is_talking_with_player(&self, owner: &KinematicBody2D) -> bool {
+        if self.is_moving {
+            return false;
+        }
+
+        match self.player {
+            Some(player) => {
+                let distance = (player.global_position() - owner.global_position()).length();
+                distance <= in_game_constant::TILE_SIZE * 1.5 &&
+                    unsafe { player.call("is_interacting", &[]) }.to_bool()
+            },
+            None => false
+        }
+    }
+
+    fn stop_moving(&mut self) {
+        self.percent_move_to_next_tile = 0.0;
+        self.input_direction = Vector2::zero();
+        self.is_moving = false;
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(AnimatedSprite)]
+#[derive(Debug)]
+/// Animates the NPC sprite, the same way that `PlayerAnimation` does with the player
+pub struct NpcAnimation {
+    idle_direction: PlayerDirection,
+}
+
+#[gdnative::methods]
+impl NpcAnimation {
+    fn new(_owner: &AnimatedSprite) -> Self {
+        Self {
+            idle_direction: PlayerDirection::default(),
+        }
+    }
+
+    #[export]
+    fn _on_npc_animate(&mut self, owner: &AnimatedSprite, motion: Vector2) {
+        match motion {
+            x if x.x > 0.0 => { owner.play("walk right", false); self.idle_direction = PlayerDirection::Right; },
+            x if x.x < 0.0 => { owner.play("walk left", false); self.idle_direction = PlayerDirection::Left; },
+            x if x.y < 0.0 => { owner.play("walk upwards", false); self.idle_direction = PlayerDirection::Upwards; },
+            x if x.y > 0.0 => { owner.play("walk downwards", false); self.idle_direction = PlayerDirection::Downwards; },
+            _ => match self.idle_direction {
+                PlayerDirection::Downwards => { owner.play("idle front", false); }
+                PlayerDirection::Upwards => { owner.play("idle back", false); }
+                PlayerDirection::Left => { owner.play("idle left", false); }
+                PlayerDirection::Right => { owner.play("idle right", false); }
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+
+use super::pokemon::{OverworldPoisonResult, Pokemon};
+
+/// As in every Pokémon game, a trainer can't carry more than six Pokémon with him
+pub const MAX_PARTY_SIZE: usize = 6;
+
+/// The Pokémon that the player carries with him
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Party {
+    members: Vec<Pokemon>,
+}
+
+impl Party {
+    pub fn new() -> Self {
+        Self { members: Vec::new() }
+    }
+
+    /// Adds a new Pokémon to the party.
+    ///
+    /// If the party it's already full, the Pokémon it's given back on the `Err` variant, so the caller can decide where it should go
+    pub fn add_member(&mut self, pokemon: Pokemon) -> Result<(), Pokemon> {
+        if self.is_full() {
+            return Err(pokemon);
+        }
+        self.members.push(pokemon);
+        Ok(())
+    }
+
+    /// Removes the Pokémon placed at the given position of the party, if exists
+    pub fn remove_member(&mut self, index: usize) -> Option<Pokemon> {
+        if index < self.members.len() {
+            Some(self.members.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Swaps the position of two Pokémon in the party. Returns false if any of the positions are out of the party
+    pub fn swap_members(&mut self, first: usize, second: usize) -> bool {
+        if first < self.members.len() && second < self.members.len() {
+            self.members.swap(first, second);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Fully restores the HP of all the Pokémon in the party
+    pub fn heal_all(&mut self) {
+        self.members.iter_mut().for_each(|pokemon| pokemon.heal());
+    }
+
+    /// If every Pokémon of the party fainted, so the player blacks out
+    pub fn is_wiped_out(&self) -> bool {
+        !self.members.is_empty() && self.members.iter().all(Pokemon::is_fainted)
+    }
+
+    /// Makes every poisoned Pokémon of the party lose HP because of walking on the overworld.
+    ///
+    /// Returns the party slot of every Pokémon that got hurt, and if it survived the poison, so the player sprite
+    /// can flash and the UI can tell it
+    pub fn apply_overworld_poison(&mut self) -> Vec<(usize, OverworldPoisonResult)> {
+        self.members.iter_mut()
+            .enumerate()
+            .map(|(party_slot, pokemon)| (party_slot, pokemon.apply_overworld_poison()))
+            .filter(|(_, result)| *result != OverworldPoisonResult::Unaffected)
+            .collect()
+    }
+
+    pub fn get_members(&self) -> &Vec<Pokemon> {
+        &self.members
+    }
+
+    pub fn get_member_mut(&mut self, index: usize) -> Option<&mut Pokemon> {
+        self.members.get_mut(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= MAX_PARTY_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::PlayerData;
+    use crate::game::battle::status_condition::StatusCondition;
+    use crate::game::pokemon::PokemonMove;
+
+    fn pokemon(id: i32, name: &str) -> Pokemon {
+        Pokemon::for_tests(id, name, vec![PokemonMove::new("Placaje", "Normal", 40, 35)])
+    }
+
+    fn party_ids(party: &Party) -> Vec<i32> {
+        party.get_members().iter().map(|pokemon| pokemon.id).collect()
+    }
+
+    #[test]
+    fn the_party_survives_a_save_and_load() {
+        let mut player_data = PlayerData::new();
+        player_data.receive_pokemon(pokemon(16, "Pidgey")).unwrap();
+        player_data.receive_pokemon(pokemon(19, "Rattata")).unwrap();
+        let save = serde_json::to_string(&player_data).unwrap();
+
+        // What happens after the save isn't on it
+        let party = player_data.get_party_mut();
+        party.swap_members(0, 1);
+        party.get_member_mut(0).unwrap().set_current_hp(3);
+        party.add_member(pokemon(10, "Caterpie")).unwrap();
+        assert_eq!(party_ids(party), vec![19, 16, 10]);
+
+        let loaded: PlayerData = serde_json::from_str(&save).unwrap();
+        assert_eq!(party_ids(loaded.get_party()), vec![16, 19]);
+        assert!(loaded.get_party().get_members().iter().all(|pokemon| pokemon.get_current_hp() == 20));
+    }
+
+    #[test]
+    fn the_mutations_of_the_party_are_saved() {
+        let mut party = Party::new();
+        party.add_member(pokemon(16, "Pidgey")).unwrap();
+        party.add_member(pokemon(19, "Rattata")).unwrap();
+        let pidgey = party.get_member_mut(0).unwrap();
+        pidgey.set_nickname("Plumas");
+        pidgey.set_current_hp(7);
+        pidgey.use_move(0);
+        party.swap_members(0, 1);
+
+        let loaded: Party = serde_json::from_str(&serde_json::to_string(&party).unwrap()).unwrap();
+        assert_eq!(party_ids(&loaded), vec![19, 16]);
+        let pidgey = &loaded.get_members()[1];
+        assert_eq!(pidgey.get_display_name(), "Plumas");
+        assert_eq!(pidgey.get_current_hp(), 7);
+        assert_eq!(pidgey.get_moves()[0].pp, 34);
+    }
+
+    #[test]
+    fn the_party_holds_six_pokemon() {
+        let mut party = Party::new();
+        for id in 1..=MAX_PARTY_SIZE as i32 {
+            assert!(party.add_member(pokemon(id, "Pokémon")).is_ok());
+        }
+        assert!(party.is_full());
+        assert_eq!(party.add_member(pokemon(7, "Pokémon")).unwrap_err().id, 7);
+        assert_eq!(party.len(), MAX_PARTY_SIZE);
+    }
+
+    #[test]
+    fn the_members_can_be_swapped_and_removed() {
+        let mut party = Party::new();
+        party.add_member(pokemon(1, "Bulbasaur")).unwrap();
+        party.add_member(pokemon(4, "Charmander")).unwrap();
+        party.add_member(pokemon(7, "Squirtle")).unwrap();
+
+        assert!(party.swap_members(0, 2));
+        assert!(!party.swap_members(0, 3));
+        assert_eq!(party_ids(&party), vec![7, 4, 1]);
+        assert_eq!(party.remove_member(1).unwrap().id, 4);
+        assert!(party.remove_member(2).is_none());
+        assert_eq!(party_ids(&party), vec![7, 1]);
+    }
+
+    #[test]
+    fn healing_the_party_brings_back_the_fainted_pokemon() {
+        let mut party = Party::new();
+        party.add_member(pokemon(1, "Bulbasaur")).unwrap();
+        party.add_member(pokemon(4, "Charmander")).unwrap();
+        party.get_member_mut(0).unwrap().set_current_hp(0);
+        assert!(!party.is_wiped_out());
+        party.get_member_mut(1).unwrap().set_current_hp(0);
+        assert!(party.is_wiped_out());
+
+        party.heal_all();
+        assert!(!party.is_wiped_out());
+        assert!(party.get_members().iter().all(|pokemon| pokemon.get_current_hp() == pokemon.get_max_hp()));
+        assert!(!Party::new().is_wiped_out());
+    }
+
+    #[test]
+    fn the_poison_hurts_only_the_poisoned_members() {
+        let mut party = Party::new();
+        party.add_member(pokemon(16, "Pidgey")).unwrap();
+        party.add_member(pokemon(19, "Rattata")).unwrap();
+        party.add_member(pokemon(10, "Caterpie")).unwrap();
+        party.get_member_mut(1).unwrap().set_status(StatusCondition::Poisoned);
+        party.get_member_mut(2).unwrap().set_status(StatusCondition::Burned);
+
+        assert_eq!(party.apply_overworld_poison(), vec![(1, OverworldPoisonResult::Hurt)]);
+        let current_hp: Vec<u32> = party.get_members().iter().map(Pokemon::get_current_hp).collect();
+        assert_eq!(current_hp, vec![20, 19, 20]);
+    }
+
+    #[test]
+    fn the_poison_wears_off_at_one_hp_on_the_overworld() {
+        let mut party = Party::new();
+        party.add_member(pokemon(19, "Rattata")).unwrap();
+        let rattata = party.get_member_mut(0).unwrap();
+        rattata.set_status(StatusCondition::Poisoned);
+        rattata.set_current_hp(3);
+
+        assert_eq!(party.apply_overworld_poison(), vec![(0, OverworldPoisonResult::Hurt)]);
+        assert_eq!(party.apply_overworld_poison(), vec![(0, OverworldPoisonResult::Survived)]);
+        let rattata = &party.get_members()[0];
+        assert_eq!(rattata.get_current_hp(), 1);
+        assert_eq!(rattata.get_status(), StatusCondition::Healthy);
+        assert!(!party.is_wiped_out());
+
+        // Already cured, so walking more doesn't hurt it anymore
+        assert!(party.apply_overworld_poison().is_empty());
+        assert_eq!(party.get_members()[0].get_current_hp(), 1);
+    }
+
+    #[test]
+    fn a_fainted_member_isnt_affected_by_the_poison() {
+        let mut party = Party::new();
+        party.add_member(pokemon(19, "Rattata")).unwrap();
+        let rattata = party.get_member_mut(0).unwrap();
+        rattata.set_status(StatusCondition::Poisoned);
+        rattata.set_current_hp(0);
+
+        assert!(party.apply_overworld_poison().is_empty());
+        assert_eq!(party.get_members()[0].get_current_hp(), 0);
+    }
+}
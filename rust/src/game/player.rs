@@ -2,13 +2,15 @@ use std::collections::HashMap;
 
 use serde::{Serialize, Deserialize};
 use serde::ser::Serializer;
+use serde::de::Deserializer;
 
 use gdnative::{api::RayCast2D, prelude::*};
 use gdnative::api::{AnimatedSprite, KinematicBody2D};
 
 use crate::{game::dialogue_box::DialogueBoxStatus};
 use crate::game::code_abstractions::{
-    character::{CharacterTileMovement, CharacterJump},
+    character::{self, CharacterTileMovement, CharacterJump},
+    interactions,
     signals::RegisterSignal
 };
 
@@ -16,7 +18,32 @@ use crate::utils::utils;
 use crate::utils::consts::in_game_constant;
 
 use super::menu::menu::MenuStatus;
-
+use super::gamepad;
+use super::game_state::GameState;
+use super::settings::DEFAULT_STICK_DEADZONE;
+use super::party::Party;
+use super::box_storage::{BoxStorage, PokemonDestination};
+use super::pokedex::Pokedex;
+use super::pokemon::Pokemon;
+use super::bag::{self, ActiveRepel, Bag, ItemCategory, ItemError, RUNNING_SHOES, SHINY_CHARM};
+use super::event_flags::{namespaces, EventFlags};
+use super::shop::{self, ShopError};
+use super::balance::BalanceConfig;
+use super::terrain::{self, Terrain};
+use super::save_migration;
+use super::berries::{BerryError, BerryGarden, BerryPlant, GrowthStage};
+use super::quests::{QuestError, QuestLog, QuestProgress, QuestState, QuestTable};
+
+
+/// Where the player comes back after a whiteout: the last Pokémon Center where his party got healed.
+///
+/// The position it's relative to the origin of the scene, the same way that the warps work
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RespawnPoint {
+    pub scene_path: String,
+    pub x: f64,
+    pub y: f64,
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 #[derive(Clone)]
@@ -24,30 +51,444 @@ use super::menu::menu::MenuStatus;
 /// different PLAYER "classes", processing that data and store it on an external resource
 /// where the data can persist
 pub struct PlayerData {
+    // The shape of the data, so the saves of the older builds can be upgraded before reading them
+    #[serde(default)]
+    version: u32,
     name: String,
     player_direction: PlayerDirection,
     player_position: HashMap<String, f64>,
+    // What the player was doing when the game got saved. The older saves doesn't have it, so they load as Idle
+    #[serde(default)]
+    player_status: PlayerStatus,
+    // Tracks if the player it's currently riding his bike
+    #[serde(default)]
+    on_bike: bool,
+    // The Pokémon that the player carries with him
+    #[serde(default)]
+    party: Party,
+    // The Pokémon stored on the PC
+    #[serde(default)]
+    box_storage: BoxStorage,
+    // The species that the player has seen and caught
+    #[serde(default)]
+    pokedex: Pokedex,
+    // The items that the player carries with him
+    #[serde(default)]
+    bag: Bag,
+    // One-time events already done by the player: defeated trainers, collected items...
+    #[serde(default)]
+    event_flags: EventFlags,
+    // The money of the player, to spend on the shops
+    #[serde(default = "starting_money", deserialize_with = "deserialize_money")]
+    money: u32,
+    // The last Pokémon Center visited. None until the player heals his party for the first time
+    #[serde(default)]
+    respawn_point: Option<RespawnPoint>,
+    // The berries planted on the berry spots, growing with the in-game time
+    #[serde(default)]
+    berry_garden: BerryGarden,
+    // The repel that keeps the wild Pokémon away, while it has steps left
+    #[serde(default)]
+    active_repel: Option<ActiveRepel>,
+    // The quests started by the player, and how far he got on them
+    #[serde(default)]
+    quest_log: QuestLog,
 }
 
 impl PlayerData {
 
     pub fn new() -> Self {
         Self {
+            version: save_migration::CURRENT_SAVE_VERSION,
             name: "".to_owned(),
             player_direction: PlayerDirection::default(),
             player_position: HashMap::new(),
+            player_status: PlayerStatus::default(),
+            on_bike: false,
+            party: Party::new(),
+            box_storage: BoxStorage::new(),
+            pokedex: Pokedex::new(),
+            bag: Bag::new(),
+            event_flags: EventFlags::new(),
+            money: shop::STARTING_MONEY,
+            respawn_point: None,
+            berry_garden: BerryGarden::new(),
+            active_repel: None,
+            quest_log: QuestLog::new(),
+        }
+    }
+
+    pub fn get_money(&self) -> u32 {
+        self.money
+    }
+
+    /// Gives money to the player, up to the maximum that he can carry.
+    ///
+    /// Returns false if some of the money didn't fit on the wallet
+    pub fn earn(&mut self, amount: u32) -> bool {
+        let total = self.money.saturating_add(amount);
+        self.money = total.min(shop::MAX_MONEY);
+        total <= shop::MAX_MONEY
+    }
+
+    /// Takes money from the player. Fails, without taking anything, if he doesn't have enough
+    pub fn try_spend(&mut self, amount: u32) -> bool {
+        if amount > self.money {
+            return false;
+        }
+        self.money -= amount;
+        true
+    }
+
+    /// The whole party fainted. The player loses part of his money and his Pokémon get healed, ready to
+    /// go back to the last Pokémon Center. Returns the money lost
+    pub fn black_out(&mut self) -> u32 {
+        let money_lost = (self.money as f64 * in_game_constant::BLACKOUT_MONEY_LOSS) as u32;
+        self.money -= money_lost.min(self.money);
+        self.party.heal_all();
+        money_lost
+    }
+
+    /// Buys some units of an item, paying his full price. Returns the money spent
+    pub fn buy_item(&mut self, item_id: &str, quantity: u32) -> Result<u32, ShopError> {
+        let price = shop::get_item_price(item_id).ok_or(ShopError::NotOnSale)?;
+        if quantity > self.bag.get_free_space(item_id) {
+            return Err(ShopError::BagFull);
+        }
+        let total_price = price.saturating_mul(quantity);
+        if !self.try_spend(total_price) {
+            return Err(ShopError::NotEnoughMoney);
+        }
+        self.bag.add_item(item_id, quantity);
+        Ok(total_price)
+    }
+
+    /// Sells some units of an item of the bag, at half of his price. Returns the money earned
+    pub fn sell_item(&mut self, item_id: &str, quantity: u32) -> Result<u32, ShopError> {
+        let price = shop::get_sell_price(item_id).ok_or(ShopError::CantBeSold)?;
+        if quantity == 0 || self.bag.get_quantity(item_id) < quantity {
+            return Err(ShopError::NotInBag);
+        }
+        self.bag.toss_item(item_id, quantity).map_err(|_| ShopError::CantBeSold)?;
+
+        let total_price = price.saturating_mul(quantity);
+        self.earn(total_price);
+        Ok(total_price)
+    }
+
+    /// The player can only run once he has the running shoes on his bag
+    pub fn has_running_shoes(&self) -> bool {
+        self.bag.get_quantity(RUNNING_SHOES) > 0
+    }
+
+    /// One of every this many wild Pokémon will be shiny. Better odds with the shiny charm on the bag
+    pub fn get_shiny_odds(&self) -> u32 {
+        if self.bag.get_quantity(SHINY_CHARM) > 0 {
+            in_game_constant::SHINY_CHARM_ODDS
+        } else {
+            in_game_constant::SHINY_ODDS
+        }
+    }
+
+    pub fn get_flag(&self, key: &str) -> bool {
+        self.event_flags.get_flag(key)
+    }
+
+    pub fn set_flag(&mut self, key: &str, value: bool) {
+        self.event_flags.set_flag(key, value);
+    }
+
+    pub fn is_trainer_defeated(&self, trainer_id: &str) -> bool {
+        self.event_flags.get_namespaced_flag(namespaces::TRAINER_DEFEATED, trainer_id)
+    }
+
+    pub fn set_trainer_defeated(&mut self, trainer_id: &str) {
+        self.event_flags.set_namespaced_flag(namespaces::TRAINER_DEFEATED, trainer_id, true);
+    }
+
+    pub fn is_item_ball_collected(&self, item_ball_id: &str) -> bool {
+        self.event_flags.get_namespaced_flag(namespaces::ITEM_COLLECTED, item_ball_id)
+    }
+
+    pub fn set_item_ball_collected(&mut self, item_ball_id: &str) {
+        self.event_flags.set_namespaced_flag(namespaces::ITEM_COLLECTED, item_ball_id, true);
+    }
+
+    pub fn get_party(&self) -> &Party {
+        &self.party
+    }
+
+    pub fn get_party_mut(&mut self) -> &mut Party {
+        &mut self.party
+    }
+
+    pub fn get_box_storage(&self) -> &BoxStorage {
+        &self.box_storage
+    }
+
+    pub fn get_box_storage_mut(&mut self) -> &mut BoxStorage {
+        &mut self.box_storage
+    }
+
+    /// If a new Pokémon would fit anywhere, on the party or on the PC
+    pub fn has_room_for_pokemon(&self) -> bool {
+        !self.party.is_full() || !self.box_storage.is_full()
+    }
+
+    /// Gives a new Pokémon to the player. It joins the party if there's room for it, otherwise
+    /// it's sent to the PC. Only when every box it's full the Pokémon it's given back
+    pub fn receive_pokemon(&mut self, pokemon: Pokemon) -> Result<PokemonDestination, Pokemon> {
+        match self.party.add_member(pokemon) {
+            Ok(()) => Ok(PokemonDestination::Party),
+            Err(pokemon) => self.box_storage.deposit(pokemon, 0)
+                .map(PokemonDestination::Box)
+        }
+    }
+
+    /// The last Pokémon received on the given destination, like the one that was just caught
+    pub fn get_received_pokemon(&self, destination: PokemonDestination) -> Option<&Pokemon> {
+        match destination {
+            PokemonDestination::Party => self.party.get_members().last(),
+            PokemonDestination::Box(box_index) => self.box_storage.get_box(box_index)?.get_pokemon().last()
+        }
+    }
+
+    /// The last Pokémon received on the given destination, so it can be nicknamed after the catch
+    pub fn get_received_pokemon_mut(&mut self, destination: PokemonDestination) -> Option<&mut Pokemon> {
+        match destination {
+            PokemonDestination::Party => {
+                let last_slot = self.party.len().checked_sub(1)?;
+                self.party.get_member_mut(last_slot)
+            },
+            PokemonDestination::Box(box_index) => self.box_storage.get_last_pokemon_mut(box_index)
+        }
+    }
+
+    /// Sends a Pokémon of the party to a box of the PC
+    pub fn deposit_pokemon(&mut self, party_slot: usize, box_index: usize) -> bool {
+        self.box_storage.deposit_from_party(&mut self.party, party_slot, box_index)
+    }
+
+    /// Brings a Pokémon from a box of the PC to the party. Fails if the party it's full
+    pub fn withdraw_pokemon(&mut self, box_index: usize, slot: usize) -> bool {
+        self.box_storage.withdraw_to_party(&mut self.party, box_index, slot)
+    }
+
+    pub fn get_pokedex(&self) -> &Pokedex {
+        &self.pokedex
+    }
+
+    pub fn get_pokedex_mut(&mut self) -> &mut Pokedex {
+        &mut self.pokedex
+    }
+
+    pub fn get_bag(&self) -> &Bag {
+        &self.bag
+    }
+
+    pub fn get_bag_mut(&mut self) -> &mut Bag {
+        &mut self.bag
+    }
+
+    /// Uses an item of the bag on the Pokémon placed at the given position of the party
+    pub fn use_item(&mut self, item_id: &str, party_slot: usize) -> Result<(), ItemError> {
+        let pokemon = self.party.get_member_mut(party_slot).ok_or(ItemError::NoPokemon)?;
+        self.bag.use_item(item_id, pokemon)
+    }
+
+    /// Gives an item of the bag to the Pokémon placed at the given position of the party, so he holds it.
+    /// The item that the Pokémon was holding before goes back to the bag
+    pub fn give_item(&mut self, item_id: &str, party_slot: usize) -> Result<(), ItemError> {
+        if !ItemCategory::from_item_id(item_id).can_be_tossed() {
+            return Err(ItemError::KeyItem);
+        }
+        if self.bag.get_quantity(item_id) == 0 {
+            return Err(ItemError::NotInBag);
+        }
+        let pokemon = self.party.get_member_mut(party_slot).ok_or(ItemError::NoPokemon)?;
+
+        let previous_item = pokemon.get_held_item().map(|held_item| held_item.to_string());
+        // The item that the Pokémon was holding must fit back on the bag
+        if let Some(previous_item) = previous_item.as_deref() {
+            if previous_item != item_id && self.bag.get_free_space(previous_item) == 0 {
+                return Err(ItemError::BagFull);
+            }
+        }
+        pokemon.set_held_item(Some(item_id.to_string()));
+        self.bag.remove_item(item_id);
+        if let Some(previous_item) = previous_item {
+            self.bag.add_item(&previous_item, 1);
+        }
+        Ok(())
+    }
+
+    /// The steps left until the wild Pokémon can appear again. 0 without any repel working
+    pub fn get_repel_steps(&self) -> u32 {
+        self.active_repel.as_ref().map_or(0, |repel| repel.steps_left)
+    }
+
+    pub fn is_repel_active(&self) -> bool {
+        self.get_repel_steps() > 0
+    }
+
+    /// Uses a repel of the bag. It can't be used while another one it's still working
+    pub fn use_repel(&mut self, item_id: &str) -> Result<(), ItemError> {
+        let steps = bag::get_repel_steps(item_id).ok_or(ItemError::UnknownItem)?;
+        if self.bag.get_quantity(item_id) == 0 {
+            return Err(ItemError::NotInBag);
+        }
+        if self.is_repel_active() {
+            return Err(ItemError::NoEffect);
+        }
+        self.bag.remove_item(item_id);
+        self.active_repel = Some(ActiveRepel { item_id: item_id.to_string(), steps_left: steps });
+        Ok(())
+    }
+
+    /// Counts a step of the player against the working repel.
+    ///
+    /// Returns the id of the repel if it stopped working with this step, so the player can be asked to use another
+    pub fn take_repel_step(&mut self) -> Option<String> {
+        let repel = self.active_repel.as_mut()?;
+        if !repel.take_step() {
+            return None;
+        }
+        self.active_repel.take().map(|repel| repel.item_id)
+    }
+
+    pub fn get_berry_plant(&self, spot_id: &str) -> Option<&BerryPlant> {
+        self.berry_garden.get_plant(spot_id)
+    }
+
+    /// Plants a berry of the bag on the given berry spot, at the given in-game hours since the game started
+    pub fn plant_berry(&mut self, spot_id: &str, berry_id: &str, elapsed_hours: f64) -> Result<(), BerryError> {
+        if self.bag.get_quantity(berry_id) == 0 {
+            return Err(BerryError::NotInBag);
+        }
+        self.berry_garden.plant(spot_id, berry_id, elapsed_hours)?;
+        self.bag.remove_item(berry_id);
+        Ok(())
+    }
+
+    /// Picks the berries of the plant of the given spot, if it's ready, putting the given amount of them
+    /// on the bag. The plant stays on the spot while the berries don't fit on the bag.
+    ///
+    /// Returns the id of the harvested berry
+    pub fn harvest_berries(&mut self, spot_id: &str, amount: u32, elapsed_hours: f64) -> Result<String, BerryError> {
+        let berry_id = match self.berry_garden.get_plant(spot_id) {
+            Some(plant) if plant.stage(elapsed_hours) == GrowthStage::Ready => plant.get_berry_id().to_string(),
+            _ => return Err(BerryError::NotReady)
+        };
+        if self.bag.get_free_space(&berry_id) < amount {
+            return Err(BerryError::BagFull);
+        }
+        self.berry_garden.harvest(spot_id, elapsed_hours);
+        self.bag.add_item(&berry_id, amount);
+        Ok(berry_id)
+    }
+
+    pub fn get_quest_state(&self, quest_id: &str) -> QuestState {
+        self.quest_log.get_state(quest_id)
+    }
+
+    pub fn get_quest_progress(&self, quest_id: &str) -> Option<&QuestProgress> {
+        self.quest_log.get_progress(quest_id)
+    }
+
+    pub fn get_quest_ids(&self, state: QuestState) -> Vec<String> {
+        self.quest_log.get_quest_ids(state)
+    }
+
+    /// Starts one of the quests of the game. Only the quests that exist on the table can be started
+    pub fn start_quest(&mut self, quest_table: &QuestTable, quest_id: &str) -> Result<(), QuestError> {
+        if quest_table.get_quest(quest_id).is_none() {
+            return Err(QuestError::UnknownQuest);
         }
+        self.quest_log.start_quest(quest_id)
+    }
+
+    /// Counts the given times on an objective of an active quest. Returns if the quest got completed by it
+    pub fn advance_quest_objective(&mut self, quest_table: &QuestTable, quest_id: &str, objective_id: &str, amount: u32)
+        -> Result<bool, QuestError>
+    {
+        let definition = quest_table.get_quest(quest_id).ok_or(QuestError::UnknownQuest)?;
+        self.quest_log.advance_objective(quest_id, definition, objective_id, amount)
+    }
+
+    pub fn complete_quest(&mut self, quest_id: &str) -> Result<(), QuestError> {
+        self.quest_log.complete_quest(quest_id)
+    }
+
+    /// Moves forward the objectives of the active quests that listen to the event. Returns the completed quests
+    pub fn record_quest_event(&mut self, quest_table: &QuestTable, event: &str, amount: u32) -> Vec<String> {
+        self.quest_log.record_event(quest_table, event, amount)
+    }
+
+    pub fn is_on_bike(&self) -> bool {
+        self.on_bike
+    }
+
+    pub fn set_on_bike(&mut self, on_bike: bool) {
+        self.on_bike = on_bike;
+    }
+
+    pub fn get_respawn_point(&self) -> Option<&RespawnPoint> {
+        self.respawn_point.as_ref()
+    }
+
+    pub fn set_respawn_point(&mut self, respawn_point: RespawnPoint) {
+        self.respawn_point = Some(respawn_point);
     }
 
     pub fn set_player_direction(&mut self, player_current_direction: &PlayerDirection) {
         self.player_direction = player_current_direction.to_owned();
     }
+
+    pub fn get_player_status(&self) -> &PlayerStatus {
+        &self.player_status
+    }
+
+    pub fn set_player_status(&mut self, player_status: PlayerStatus) {
+        self.player_status = player_status;
+    }
+
     pub fn set_player_position(&mut self, x: f64, y: f64) {
         self.player_position.insert("x".to_owned(), x);
         self.player_position.insert("y".to_owned(), y);
     }
 }
 
+fn starting_money() -> u32 {
+    shop::STARTING_MONEY
+}
+
+/// The money of a save, never past the maximum that the player can carry, even if the save got edited by hand
+fn deserialize_money<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    u32::deserialize(deserializer).map(|money| money.min(shop::MAX_MONEY))
+}
+
+// The signals of the PlayerCharacter that the Game listens to, and the Game methods that receives them
+const PLAYER_GAME_DATA_CONNECTIONS: [(&str, &str); 4] = [
+    ("player_position", "_save_player_position"),
+    ("player_step_completed", "_on_player_step_completed"),
+    ("tall_grass_step", "_on_tall_grass_step"),
+    ("player_status", "_save_player_status"),
+];
+
+// The same for the PlayerAnimation
+const PLAYER_ANIMATION_GAME_DATA_CONNECTIONS: [(&str, &str); 2] = [
+    ("player_direction", "_save_player_direction"),
+    ("player_direction_changed", "_update_player_direction"),
+];
+
+// The child of the PlayerAnimation that raises the dust while the player runs
+const SPRINT_DUST_NODE: &str = "SprintDust";
+const INTERACTION_PROMPT_NODE: &str = "InteractionPrompt";
+// The name of the ledges, or of the child that marks any other body as one
+const LEDGE_NODE: &str = "Ledge";
+// How long the player sprite flashes when the party gets hurt on the overworld, and how fast it blinks
+const DAMAGE_FLASH_SECONDS: f64 = 0.6;
+const DAMAGE_FLASH_INTERVAL: f64 = 0.1;
 
 #[derive(NativeClass)]
 #[inherit(KinematicBody2D)]
@@ -75,10 +516,30 @@ pub struct PlayerCharacter {
     // Player Shadow
     #[serde(skip)]
     player_shadow: Option<TRef<'static, Sprite>>,
+    // The mark shown over the interactable that the player it's facing. Optional, so other scenes can go without it
+    #[serde(skip)]
+    interaction_prompt: Option<TRef<'static, Node2D>>,
     #[serde(skip)]
     landing_dust_effect_node: Option<TRef<'static, Node>>,
     #[serde(skip)]
     landing_dust_effect: Option<TRef<'static, AnimatedSprite>>,
+    // The Game node, that receives the data to persist. Resolved once on `_ready`
+    #[serde(skip)]
+    game_node: Option<Ref<Node>>,
+
+    // Lets the player step to the diagonal tiles, holding two directions at the same time. Off, the first
+    // direction held wins, like on the classic games
+    #[serde(skip)]
+    #[property(default = false)]
+    diagonal_movement: bool,
+    // Lets the player walk freely, any distance and out of the grid, sliding along the walls. Off, every
+    // input commits the player to a whole tile step, like on the classic games
+    #[serde(skip)]
+    #[property(default = false)]
+    free_movement: bool,
+    // Pixels walked freely since the last completed step, so the free movement still counts the steps
+    #[serde(skip)]
+    free_walked_distance: f32,
 
     // Player Tile-based movement system (under development)
     #[serde(skip)]
@@ -91,19 +552,64 @@ pub struct PlayerCharacter {
     percent_move_to_next_tile: f64,
     #[serde(skip)]
     jumping_over_ledge: bool,
+    // Where the player it's looking at, even when he's standing still
+    #[serde(skip)]
+    facing_direction: PlayerDirection,
+    // The walking, running and jumping speeds
+    #[serde(skip)]
+    balance_config: BalanceConfig,
+    // The fraction of the speed of the current step. Always the full speed with the keys, but the stick of the
+    // gamepad can walk slower when it isn't pushed all the way
+    #[serde(skip)]
+    speed_scale: f64,
+    // The autoload that knows the deadzone of the stick chosen by the player. Resolved once on `_ready`
+    #[serde(skip)]
+    settings_controller: Option<Ref<Node>>,
+    // The state of the whole game, as the Game announces it. Always the overworld on the scenes without the Game
+    #[serde(skip)]
+    game_state: GameState,
+    // The motion and speed tier sent on the last `animate` signal. None until the first one it's sent
+    #[serde(skip)]
+    last_animation: Option<(Vector2, SpeedTier)>,
+    // How the speed changes on the areas with momentum movement (sand...). None moves at the full speed instantly
+    #[serde(skip)]
+    momentum: Option<Momentum>,
+    // Tiles per second that the player it's moving at right now. Only tracked with the momentum movement
+    #[serde(skip)]
+    current_speed: f64,
+    // The terrain under the player when the current step started, that changes how fast he walks
+    #[serde(skip)]
+    current_terrain: Terrain,
+    // If the player was riding his bike when the current step started
+    #[serde(skip)]
+    on_bike: bool,
+    // The direction of the last step over the ice, so the player slides one more tile once the keys are released
+    #[serde(skip)]
+    ice_slide_direction: Option<Vector2>,
+    // If the current step it's the slide after walking over the ice, that never chains another slide
+    #[serde(skip)]
+    sliding: bool,
 }
 
 impl RegisterSignal<Self> for PlayerCharacter {
     fn register_signal(builder: &ClassBuilder<Self>) {
-        // Indicates that the Player is moving
+        // Indicates that the Player is moving, and how fast (Walk, Run or Bike)
         builder.add_signal( Signal {
             name: "animate",
-            args: &[ SignalArgument {
-                name: "motion",
-                default: Variant::from_vector2(&Vector2::new(0.0, 0.0)),
-                export_info: ExportInfo::new(VariantType::Vector2),
-                usage: PropertyUsage::DEFAULT,
-            }],
+            args: &[
+                SignalArgument {
+                    name: "motion",
+                    default: Vector2::new(0.0, 0.0).to_variant(),
+                    export_info: ExportInfo::new(VariantType::Vector2),
+                    usage: PropertyUsage::DEFAULT,
+                },
+                SignalArgument {
+                    name: "speed_tier",
+                    default: Variant::from_str(SpeedTier::Walk.to_tier_str()),
+                    export_info: ExportInfo::new(VariantType::GodotString),
+                    usage: PropertyUsage::DEFAULT,
+                }
+            ],
         });
 
         // Indicates that the Player is interacting
@@ -117,6 +623,17 @@ impl RegisterSignal<Self> for PlayerCharacter {
             args: &[]
         });
 
+        // Sends what the player it's doing, as part of the save process
+        builder.add_signal( Signal {
+            name: "player_status",
+            args: &[ SignalArgument {
+                name: "status",
+                default: Variant::from_str(PlayerStatus::default().to_status_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
         builder.add_signal( Signal {
             name: "player_moving",
             args: &[]
@@ -126,6 +643,24 @@ impl RegisterSignal<Self> for PlayerCharacter {
             name: "player_stopped",
             args: &[]
         });
+
+        // The player completed a whole step
+        builder.add_signal( Signal {
+            name: "player_step_completed",
+            args: &[]
+        });
+
+        // The player walked a step over the tall grass of the given encounter area. It's sent on every one of
+        // those steps: the Game rolls if a wild Pokémon appears, since the repel, the bike and the weather are his
+        builder.add_signal( Signal {
+            name: "tall_grass_step",
+            args: &[ SignalArgument {
+                name: "area_id",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
     }
 }
 
@@ -133,24 +668,51 @@ impl CharacterTileMovement<KinematicBody2D, Input> for PlayerCharacter {
     /// The fn that manages the player motion on the `Map`, and updates the `self.player_status: PlayerStatus`, 
     /// which represents the current variant of the player different status and behaviours. 
     fn process_player_input(&mut self, owner: &KinematicBody2D, input: &Input) {
-        if self.input_direction.y == 0.0 {
-            self.input_direction.x = Input::is_action_pressed(&input, "Right") as i32 as f32 - Input::is_action_pressed(&input, "Left") as i32 as f32; 
+        let horizontal = Input::is_action_pressed(&input, "Right") as i32 as f32 - Input::is_action_pressed(&input, "Left") as i32 as f32;
+        let vertical = Input::is_action_pressed(&input, "Down") as i32 as f32 - Input::is_action_pressed(&input, "Up") as i32 as f32;
+        if self.diagonal_movement {
+            self.input_direction = Vector2::new(horizontal, vertical);
+        } else {
+            if self.input_direction.y == 0.0 {
+                self.input_direction.x = horizontal;
+            }
+            if self.input_direction.x == 0.0 {
+                self.input_direction.y = vertical;
+            }
+        }
+        // The keys and the d-pad always win over the stick, and walk at the full speed
+        self.speed_scale = 1.0;
+        if self.input_direction == Vector2::zero() {
+            if let Some((direction, speed_scale)) = gamepad::stick_to_movement(gamepad::read_left_stick(input),
+                self.get_stick_deadzone(), self.diagonal_movement) {
+                self.input_direction = direction;
+                self.speed_scale = speed_scale;
+            }
         }
-        if self.input_direction.x == 0.0 {
-            self.input_direction.y = Input::is_action_pressed(&input, "Down") as i32 as f32 - Input::is_action_pressed(&input, "Up") as i32 as f32;
+        // The player can't stop at once over the ice: releasing the keys still slides him one more tile
+        if self.input_direction == Vector2::zero() {
+            if let Some(direction) = self.ice_slide_direction.take() {
+                self.input_direction = direction;
+                self.sliding = true;
+            }
+        } else {
+            self.ice_slide_direction = None;
         }
-        if self.input_direction != Vector2::zero() {
+        if let Some(direction) = PlayerDirection::from_vector(self.input_direction) {
             self.initial_position = owner.global_position();
+            self.current_terrain = terrain::terrain_at(owner, self.initial_position);
             self.is_moving = true;
+            self.facing_direction = direction;
+            self.player_status = step_status(Input::is_action_pressed(&input, "Run"), || self.has_running_shoes());
+            self.on_bike = self.is_on_bike();
+            self.refresh_interaction_prompt();
         }
         // Check when the player press the `space bar` == "Interact" key binding. If the player isn't interacting with anything else
         // calls the `interact method`.
         if Input::is_action_just_pressed(self.input.unwrap(), "Interact") {
-            if self.player_status != PlayerStatus::Interacting {
-                if let Some(collider) = self.blocking_raycast.unwrap().get_collider() {
-                    if let Some(interaction) = unsafe { collider.assume_safe().cast::<Node>() } {
-                        self.interact(owner, interaction)
-                    }
+            if self.can_act(owner) {
+                if let Some(interaction) = self.get_facing_collider() {
+                    self.interact(owner, interaction)
                 }
             }
         } 
@@ -158,33 +720,77 @@ impl CharacterTileMovement<KinematicBody2D, Input> for PlayerCharacter {
 
     /// Moves the player 1 whole tile for every input command along a 2D surface
     fn tilemove_or_collide(&mut self, owner: &KinematicBody2D, delta: f32) {
+        // Without the raycasts there's no way to know what blocks the player, so he just stays where he is
+        let (blocking_raycast, ledge_raycast) = match (self.blocking_raycast, self.ledge_raycast) {
+            (Some(blocking_raycast), Some(ledge_raycast)) => (blocking_raycast, ledge_raycast),
+            _ => {
+                self.is_moving = false;
+                return;
+            }
+        };
         // Variable to store where the Raycast should point based on the player movement
         let raycast_vector_length_and_direction: Vector2 = self.input_direction * in_game_constant::TILE_SIZE / 2.0;
-        // If we have Some(RayCast2D), then we can safetly operate over the pointer and set the Raycast casting direction and longitude
-        self.blocking_raycast.unwrap().set_cast_to(raycast_vector_length_and_direction);
-        self.blocking_raycast.unwrap().force_raycast_update();
-        self.ledge_raycast.unwrap().set_cast_to(raycast_vector_length_and_direction);
-        self.ledge_raycast.unwrap().force_raycast_update();
-
-        if (self.ledge_raycast.unwrap().is_colliding() && self.input_direction == Vector2::new(0.0, 1.0)) || self.jumping_over_ledge {
+        // Sets the Raycast casting direction and longitude
+        ledge_raycast.set_cast_to(raycast_vector_length_and_direction);
+        ledge_raycast.force_raycast_update();
+        // A diagonal step can't cut the corner of a wall, so both of his sides have to be free too
+        let is_diagonal = self.input_direction.x != 0.0 && self.input_direction.y != 0.0;
+        let blocked = Self::is_blocked_towards(blocking_raycast, self.input_direction) || (is_diagonal && (
+            Self::is_blocked_towards(blocking_raycast, Vector2::new(self.input_direction.x, 0.0)) ||
+            Self::is_blocked_towards(blocking_raycast, Vector2::new(0.0, self.input_direction.y))));
+
+        if (self.input_direction == Vector2::new(0.0, 1.0) && Self::is_facing_ledge(ledge_raycast)) || self.jumping_over_ledge {
             self.jump_over_ledge(owner, delta);
-        } else if !self.blocking_raycast.unwrap().is_colliding() {
+        } else if !blocked {
             self.move_character(owner, delta);
         } else {
             self.is_moving = false;
+            // Something got in the way in the middle of the step (an NPC walking into the tile...), so the
+            // player goes back to the tile where he started it, instead of staying between two tiles
+            if self.percent_move_to_next_tile > 0.0 {
+                owner.set_global_position(self.initial_position);
+                self.percent_move_to_next_tile = 0.0;
+            }
+            // Walking into a wall stops any momentum at once, and any slide too
+            self.current_speed = 0.0;
+            self.sliding = false;
+            // The player could have just turned to face something
+            self.refresh_interaction_prompt();
         }
     }
 
     /// Creates a `tile based` movement for the given Kinematic Body
     fn move_character(&mut self, owner: &KinematicBody2D, delta: f32) {
         // Increment the variable that tracks the position on the road between one tile and another
-        self.percent_move_to_next_tile += in_game_constant::WALK_SPEED * delta as f64;
+        let target_speed = self.get_target_speed();
+        let speed = match self.momentum {
+            Some(momentum) => {
+                self.current_speed = momentum.approach(self.current_speed, target_speed, delta as f64);
+                self.current_speed
+            },
+            None => target_speed
+        };
+        // The diagonal steps are longer, so they take the time that the same distance takes on a straight line
+        self.percent_move_to_next_tile += speed * delta as f64 / (self.input_direction.length() as f64).max(1.0);
         // If the player already moved an entire tile...
         if self.percent_move_to_next_tile >= 1.0 {
             owner.set_global_position(self.initial_position + Vector2::new(in_game_constant::TILE_SIZE * self.input_direction.x, 
                 in_game_constant::TILE_SIZE * self.input_direction.y));
             self.percent_move_to_next_tile = 0.0; // Set to zero to be ready for the next tile movement
             self.is_moving = false; // The player completed a whole step (moved one entire tile)
+            let walking = self.player_status == PlayerStatus::Walking;
+            self.player_status = PlayerStatus::Idle;
+            self.ice_slide_direction = if self.current_terrain == Terrain::Ice && !self.sliding {
+                Some(self.input_direction)
+            } else {
+                None
+            };
+            self.sliding = false;
+            owner.emit_signal("player_step_completed", &[]);
+            if walking {
+                self.notify_tall_grass_step(owner);
+            }
+            self.refresh_interaction_prompt();
         // Else, sets the player position to a "somewhere-in-between" point
         } else {
             owner.set_global_position(self.initial_position + Vector2::new(in_game_constant::TILE_SIZE * self.input_direction.x * self.percent_move_to_next_tile as f32,
@@ -195,7 +801,7 @@ impl CharacterTileMovement<KinematicBody2D, Input> for PlayerCharacter {
 
 impl CharacterJump<KinematicBody2D, Input> for PlayerCharacter {
     fn jump_over_ledge(&mut self, owner: &KinematicBody2D, delta: f32) {
-        self.percent_move_to_next_tile += in_game_constant::JUMP_SPEED * delta as f64;
+        self.percent_move_to_next_tile += self.balance_config.get_jump_speed() * delta as f64;
             // When jump, we want to cover a distance of 2 entire tiles
         if self.percent_move_to_next_tile >= 2.0 {
             // First, when player completes the jump, we should normalize the distance traveled by correcting the "jump simullator ecuation on the else case"
@@ -209,7 +815,11 @@ impl CharacterJump<KinematicBody2D, Input> for PlayerCharacter {
             self.percent_move_to_next_tile = 0.0;
             self.is_moving = false;
             self.jumping_over_ledge = false;
-            self.player_shadow.unwrap().set_visible(false);
+            // Something could have started an interaction while the player was on the air
+            if self.player_status == PlayerStatus::Jumping {
+                self.player_status = PlayerStatus::Idle;
+            }
+            self.set_shadow_visible(false);
             // Manages the landing effect
             self.landing_dust_effect(owner);
 
@@ -217,24 +827,27 @@ impl CharacterJump<KinematicBody2D, Input> for PlayerCharacter {
             let jumping_input = in_game_constant::TILE_SIZE * self.input_direction.y * self.percent_move_to_next_tile as f32;
             let jump_simullator_ecuation = self.initial_position.y + (-0.96 - 0.53 * jumping_input + 0.05 * f32::powf(jumping_input, 2.0));
             self.jumping_over_ledge = true;
+            self.player_status = PlayerStatus::Jumping;
             owner.set_position(Vector2::new(owner.position().x,
             jump_simullator_ecuation.ceil()));
-            self.player_shadow.unwrap().set_visible(true);
+            self.set_shadow_visible(true);
         }
     }
 
     fn landing_dust_effect(&mut self, owner: &KinematicBody2D) {
-        let landing_dust_effect_node = unsafe { ResourceLoader::godot_singleton()
+        let landing_dust_effect = unsafe { ResourceLoader::godot_singleton()
             .load("res://godot/Game/LandingDustEffect.tscn", "", false)
-            .unwrap().assume_safe()
-            .cast::<PackedScene>()
-            .unwrap()
-            .instance(0)
-            .unwrap().assume_safe() };
-        let landing_dust_effect = landing_dust_effect_node.cast::<AnimatedSprite>().unwrap();
-
-        owner.add_child(landing_dust_effect, true);
-        owner.move_child(landing_dust_effect, 0);
+            .and_then(|scene| scene.assume_safe().cast::<PackedScene>()?.instance(0))
+            .and_then(|node| node.assume_safe().cast::<AnimatedSprite>()) };
+
+        // The landing it's just cosmetic, so the jump goes on without the dust if the effect can't be loaded
+        match landing_dust_effect {
+            Some(landing_dust_effect) => {
+                owner.add_child(landing_dust_effect, true);
+                owner.move_child(landing_dust_effect, 0);
+            },
+            None => godot_error!("Can't load the LandingDustEffect scene")
+        }
     }
 }
 
@@ -255,8 +868,14 @@ impl PlayerCharacter {
             blocking_raycast: None,
             ledge_raycast: None,
             player_shadow: None,
+            interaction_prompt: None,
             landing_dust_effect_node: None,
             landing_dust_effect: None,
+            game_node: None,
+
+            diagonal_movement: false,
+            free_movement: false,
+            free_walked_distance: 0.0,
 
             // Tile movement system
             initial_position: Vector2::new(0.0, 0.0),
@@ -264,6 +883,18 @@ impl PlayerCharacter {
             is_moving: false,
             percent_move_to_next_tile: 0.0,
             jumping_over_ledge: false,
+            facing_direction: PlayerDirection::default(),
+            balance_config: BalanceConfig::default(),
+            speed_scale: 1.0,
+            settings_controller: None,
+            game_state: GameState::default(),
+            last_animation: None,
+            momentum: None,
+            current_speed: 0.0,
+            current_terrain: Terrain::default(),
+            on_bike: false,
+            ice_slide_direction: None,
+            sliding: false,
         }
     }
 
@@ -272,33 +903,68 @@ impl PlayerCharacter {
         // Adds the PlayerCharacter Node to the group that takes care about data persistence
         owner.add_to_group("save_game_data", false);
         
-        // Retrieves the player absolute position from a JSON config file
-        self.initial_position.x = utils::get_player_absolute_position().0;
-        self.initial_position.y = utils::get_player_absolute_position().1;
+        // Retrieves the player absolute position from a JSON config file. A missing or broken save can't leave
+        // the player out of the world, so he just appears on the default spawn
+        let (x, y) = utils::get_player_absolute_position().unwrap_or_else(|| {
+            godot_warn!("The position of the player can't be read from the save, so he starts on the default spawn");
+            in_game_constant::DEFAULT_SPAWN_POSITION
+        });
+        self.initial_position.x = x;
+        self.initial_position.y = y;
 
         // Sets the retrieved position
         owner.set_global_position(Vector2::new(self.initial_position.x, self.initial_position.y));
 
         // Connect the Player Character with the Struct that takes care about process, manage and persist PlayerCharacter data
+        // Scenes without the Game (like a standalone test scene) just run without persisting anything
+        self.game_node = unsafe { owner.get_node("/root/Game") };
         self.connect_to_game_data(owner);
 
+        self.balance_config = utils::retrieve_balance_config();
+        self.settings_controller = unsafe { owner.get_node("/root/SettingsController") };
+
+        // The player starts looking at the same direction that he was when the game was saved
+        self.facing_direction = utils::get_player_direction().unwrap_or_default();
+        self.player_status = utils::get_player_status().unwrap_or_default().on_reload();
+
         // Sets the TRefs to the Raycast player nodes
-        self.blocking_raycast = unsafe { owner.get_node_as::<RayCast2D>("BlockingRayCast") };
-        self.ledge_raycast = unsafe { owner.get_node_as::<RayCast2D>("LedgeRayCast") };
+        self.blocking_raycast = utils::try_get_node_as::<RayCast2D>(owner, "BlockingRayCast");
+        self.ledge_raycast = utils::try_get_node_as::<RayCast2D>(owner, "LedgeRayCast");
         // Sets how long is the Vector that looks for collisions on the ledges Raycasts
-        self.ledge_raycast.unwrap().set_cast_to(Vector2::new(0.0,  4.0));
+        if let Some(ledge_raycast) = self.ledge_raycast {
+            ledge_raycast.set_cast_to(Vector2::new(0.0,  4.0));
+        }
         // Set the TRef to the player shadow
-        self.player_shadow = unsafe { owner.get_node_as::<Sprite>("Shadow") };
-        self.player_shadow.unwrap().set_visible(false); // The shadow it's only visible when the player it's jumping
+        self.player_shadow = utils::try_get_node_as::<Sprite>(owner, "Shadow");
+        self.set_shadow_visible(false); // The shadow it's only visible when the player it's jumping
+
+        self.interaction_prompt = unsafe { owner.get_node_as::<Node2D>(INTERACTION_PROMPT_NODE) };
+        self.refresh_interaction_prompt();
     }
 
     #[export]
     fn _physics_process(&mut self, owner: &KinematicBody2D, delta: f32) {
-        // Checks that the player it's able to move
-        if self.player_status != PlayerStatus::Interacting {
+        // A hop down a ledge can't be interrupted, so the input waits until the player lands
+        if self.jumping_over_ledge {
+            self.jump_over_ledge(owner, delta);
+            self.animate_character(&owner);
+            return;
+        }
+        // Checks that the player it's able to move. Never while he's talking, on the menu or on any other interaction
+        if self.can_act(owner) {
             // Moving the player when an input is detected
-            if self.is_moving == false {
-                self.process_player_input(owner, self.input.unwrap())
+            if self.free_movement {
+                self.process_player_input(owner, self.input.unwrap());
+                if self.is_moving {
+                    self.move_freely(owner, delta);
+                } else {
+                    self.slow_down(delta);
+                }
+            } else if self.is_moving == false {
+                self.process_player_input(owner, self.input.unwrap());
+                if !self.is_moving {
+                    self.slow_down(delta);
+                }
             } else if self.input_direction != Vector2::zero() {
                 self.tilemove_or_collide(owner, delta);
             } else {
@@ -309,6 +975,8 @@ impl PlayerCharacter {
         } else {
             // If player it's interacting, set the movement to zero...
             self.input_direction = Vector2::zero();
+            self.current_speed = 0.0;
+            self.ice_slide_direction = None;
             // Notifies the PlayerAnimation class that we are IDLE 'cause interaction
             self.animate_character(&owner); // <- Player interacting
         }
@@ -321,29 +989,106 @@ impl PlayerCharacter {
     /// should not be moving or doing anything else that "reading the Dialogue Box" with the text that the interaction has.
     ///
     /// The info parameter just provides an String that contains info from the signal that will be used to match
-    /// a certain behaviour with that provided String.
+    /// a certain behaviour with that provided String. Only `"end_interaction"` gives back the control to the player,
+    /// and any unknown String gets ignored, so a mistyped payload never drops the player out of an interaction.
     #[export]
     fn handle_interaction(&mut self, _owner: &KinematicBody2D, signal_info: String) {
         // Get a full `slice` of the parameters in order to match it with a `classical` &str
         let signal_info = &signal_info[..];
         // Matching the signal extra data
-        match signal_info {
-            "on_dialogue" => {
+        match PlayerInteraction::from_signal_str(signal_info) {
+            Some(PlayerInteraction::Dialogue) => {
                 self.player_status = PlayerStatus::Interacting;
                 self.is_moving = false;
                 self.dialogue_box_status = DialogueBoxStatus::Active
             },
-            "menu_active" => {
+            Some(PlayerInteraction::Menu) => {
                 self.player_status = PlayerStatus::Interacting;
                 self.is_moving = false;
                 self.menu_status = MenuStatus::Open
             },
-            _ => {
+            Some(PlayerInteraction::Locked) => {
+                self.player_status = PlayerStatus::Interacting;
+                self.is_moving = false;
+            },
+            Some(PlayerInteraction::Ended) => {
                 self.player_status = PlayerStatus::default();
                 self.dialogue_box_status = DialogueBoxStatus::Inactive;
                 self.menu_status = MenuStatus::Closed
-            }
+            },
+            None => godot_warn!("Player ignored an unknown interaction signal: {:?}", signal_info)
+        }
+
+        // The Game holds the state of the whole game. Deferred, because the Game answers announcing the new state
+        // to the player, that can't receive it while it's still handling this interaction
+        if let (Some(game_state), Some(game)) = (GameState::from_interaction(signal_info), utils::cached_node(self.game_node)) {
+            unsafe { game.call_deferred("set_game_state", &[Variant::from_str(game_state.to_state_str())]) };
         }
+        self.refresh_interaction_prompt();
+    }
+
+    #[export]
+    /// Receives every change of the state of the game, to know when the player can be controlled
+    fn _on_game_state_changed(&mut self, _owner: &KinematicBody2D, _previous: String, current: String) {
+        self.game_state = GameState::from_state_str(&current).unwrap_or_default();
+        self.refresh_interaction_prompt();
+    }
+
+    #[export]
+    /// Places the player directly on the given position, cancelling the step that he could be doing
+    fn teleport(&mut self, owner: &KinematicBody2D, position: Vector2) {
+        owner.set_global_position(position);
+        self.initial_position = position;
+        self.percent_move_to_next_tile = 0.0;
+        self.input_direction = Vector2::zero();
+        self.is_moving = false;
+        self.refresh_interaction_prompt();
+    }
+
+    #[export]
+    /// Gives back the control to the player once the evolution of one of his Pokémon has finished
+    fn _on_evolution_finished(&mut self, owner: &KinematicBody2D, _party_slot: i64, _cancelled: bool) {
+        self.handle_interaction(owner, "end_interaction".to_string());
+    }
+
+    #[export]
+    /// Gives back the control to the player once one of his Pokémon learned (or not) a new move
+    fn _on_move_learning_finished(&mut self, owner: &KinematicBody2D, _party_slot: i64, _move_name: String, _forgotten_move: String) {
+        self.handle_interaction(owner, "end_interaction".to_string());
+    }
+
+    #[export]
+    /// Where the player it's looking at: Upwards, Downwards, Left or Right
+    fn get_facing_direction(&self, _owner: &KinematicBody2D) -> String {
+        self.facing_direction.to_direction_str().to_string()
+    }
+
+    #[export]
+    /// Turns on or off the momentum movement, where the player speeds up and slows down gradually instead of
+    /// walking at the full speed at once. The rates are in tiles per second, every second
+    fn set_momentum_movement(&mut self, _owner: &KinematicBody2D, enabled: bool, acceleration: f64, deceleration: f64) {
+        self.momentum = if enabled { Some(Momentum::new(acceleration, deceleration)) } else { None };
+        self.current_speed = 0.0;
+    }
+
+    #[export]
+    /// What the player it's doing right now: Idle, Walking, Running, Jumping or Interacting
+    fn get_player_status(&self, _owner: &KinematicBody2D) -> String {
+        self.player_status.to_status_str().to_string()
+    }
+
+    #[export]
+    /// Let's other nodes (like the trainers) know if the player it's busy with any other interaction
+    fn is_interacting(&self, _owner: &KinematicBody2D) -> bool {
+        self.player_status == PlayerStatus::Interacting
+    }
+
+    #[export]
+    /// If the player it's free to move and interact: not talking, not on the menu, and not locked by any other
+    /// interaction (battles, cutscenes, transitions...). The only place that decides if the player can be controlled,
+    /// so any other node (trainers, encounters, warps...) should ask here
+    pub fn can_act(&self, _owner: &KinematicBody2D) -> bool {
+        is_controllable(self.game_state, &self.player_status, &self.menu_status, &self.dialogue_box_status)
     }
 
     /// The method for the "Interaction" behaviour of the `Player Character`.
@@ -353,12 +1098,58 @@ impl PlayerCharacter {
     /// If there's Some() collision, checks if the object are allowed to interact with the player.
     /// Sends a signal alerting that the player if the object has an "Interact" child.
     fn interact(&mut self, owner: &KinematicBody2D, coll_body: TRef<Node>) {
+        // Objects that implements `Interactable` handles the interaction by themselves
+        if let Some(interactable) = interactions::find_interactable(coll_body) {
+            if self.dialogue_box_status == DialogueBoxStatus::Inactive {
+                unsafe { interactable.call(interactions::INTERACT_METHOD, &[]) };
+            }
         //  Notifies the game that the player is interacting if true
-        if self.is_valid_interaction(coll_body) {
+        } else if self.is_valid_interaction(coll_body) {
             self.player_is_interacting(owner);
         }
     }
 
+    /// Asks the Game if the player already has the running shoes. Only happens when the run key it's held,
+    /// so walking doesn't bother the Game at all
+    fn has_running_shoes(&self) -> bool {
+        utils::cached_node(self.game_node)
+            .map(|game| unsafe { game.call("has_running_shoes", &[]) }.to_bool())
+            .unwrap_or(false)
+    }
+
+    /// Asks the Game if the player it's riding his bike. Without the Game there's no bike at all
+    fn is_on_bike(&self) -> bool {
+        utils::cached_node(self.game_node)
+            .map(|game| unsafe { game.call("is_on_bike", &[]) }.to_bool())
+            .unwrap_or(false)
+    }
+
+    /// Only the ledges (or the bodies with a `Ledge` child) can be hopped down. Anything else found by the ledge
+    /// raycast keeps blocking the player
+    fn is_facing_ledge(ledge_raycast: TRef<RayCast2D>) -> bool {
+        ledge_raycast.is_colliding() && ledge_raycast.get_collider()
+            .and_then(|collider| unsafe { collider.assume_safe() }.cast::<Node>())
+            .map(|collider| collider.name().to_string() == LEDGE_NODE || collider.has_node(LEDGE_NODE))
+            .unwrap_or(false)
+    }
+
+    /// Points the raycast half a tile towards the given direction, to know if something blocks the step
+    fn is_blocked_towards(blocking_raycast: TRef<RayCast2D>, direction: Vector2) -> bool {
+        blocking_raycast.set_cast_to(direction * in_game_constant::TILE_SIZE / 2.0);
+        blocking_raycast.force_raycast_update();
+        blocking_raycast.is_colliding()
+    }
+
+    /// Looks for the object placed on the tile that the player it's facing, no matter if the player moved recently or not
+    fn get_facing_collider(&self) -> Option<TRef<Node>> {
+        let blocking_raycast = self.blocking_raycast?;
+        blocking_raycast.set_cast_to(self.facing_direction.to_vector() * in_game_constant::TILE_SIZE);
+        blocking_raycast.force_raycast_update();
+
+        blocking_raycast.get_collider()
+            .and_then(|collider| unsafe { collider.assume_safe().cast::<Node>() })
+    }
+
     /// Given a body that is colliding with the `Player Character`, checks if has an "Interaction" Node,
     /// that represents that the object holds data for the player, and the `PlayerStatus`, which has to currently be == `PlayerStatus::Interacting`
     ///
@@ -366,7 +1157,7 @@ impl PlayerCharacter {
     /// 
     /// Remember that in Rust, `if` expressions without `else` evaluate to `()`
     fn is_valid_interaction(&self, coll_body: TRef<Node>) -> bool {
-        if coll_body.has_node("Interact") && self.dialogue_box_status == DialogueBoxStatus::Inactive { 
+        if interactions::find_interact_node_owner(coll_body).is_some() && self.dialogue_box_status == DialogueBoxStatus::Inactive { 
             return true; 
         } else { return false; }
     }
@@ -378,21 +1169,152 @@ impl PlayerCharacter {
 
     /// If the player character is moving, should be an animated representation.
     ///
-    /// Emit the signal "animate" and send the current player motion data for the receivers 
-    fn animate_character(&self, owner: &KinematicBody2D) {
-        owner.emit_signal("animate", &[self.input_direction.to_variant()]);
-    }
+    /// Emit the signal "animate" and send the current player motion data for the receivers.
+    ///
+    /// It's only sent when the motion or the speed tier changes, so standing still (or walking straight) doesn't
+    /// flood the animation with the same data on every frame. Stopping it's a change too, so the animation always
+    /// knows when the player goes idle
+    fn animate_character(&mut self, owner: &KinematicBody2D) {
+        let animation = (self.input_direction, self.get_speed_tier());
+        if self.last_animation == Some(animation) {
+            return;
+        }
+        self.last_animation = Some(animation);
+        owner.emit_signal("animate", &[animation.0.to_variant(),
+            Variant::from_str(animation.1.to_tier_str())]);
+    }
+
+    /// The deadzone of the stick chosen on the settings, or the default one without the settings autoload
+    fn get_stick_deadzone(&self) -> f64 {
+        utils::cached_node(self.settings_controller)
+            .map(|settings_controller| unsafe { settings_controller.call("get_stick_deadzone", &[]) }.to_f64())
+            .unwrap_or(DEFAULT_STICK_DEADZONE)
+    }
 
-    /// Connects the PlayerCharacter signal that transmits the current global position
+    /// Lets the momentum fade while the player stands still, so turning around right away keeps part of it
+    fn slow_down(&mut self, delta: f32) {
+        if let Some(momentum) = self.momentum {
+            self.current_speed = momentum.approach(self.current_speed, 0.0, delta as f64);
+        }
+    }
+
+    /// Tiles per second that the player should reach, for his speed, the stick and the terrain under him
+    fn get_target_speed(&self) -> f64 {
+        let speed = if self.on_bike {
+            self.balance_config.get_bike_speed()
+        } else if self.player_status == PlayerStatus::Running {
+            self.balance_config.get_run_speed()
+        } else {
+            self.balance_config.get_walk_speed()
+        };
+        speed * self.speed_scale * self.balance_config.get_terrain_speed_multiplier(self.current_terrain)
+    }
+
+    /// Moves the player out of the grid for one frame, sliding along the walls. The input it's read again on every
+    /// frame, and every tile walked still completes a step for the encounters and the rest of the step listeners
+    fn move_freely(&mut self, owner: &KinematicBody2D, delta: f32) {
+        let motion = self.input_direction.normalize() * self.get_target_speed() as f32 * in_game_constant::TILE_SIZE * delta;
+        let travel = character::move_sliding_on_walls(owner, motion);
+        self.is_moving = false;
+
+        self.free_walked_distance += travel.length();
+        if self.free_walked_distance >= in_game_constant::TILE_SIZE {
+            self.free_walked_distance -= in_game_constant::TILE_SIZE;
+            owner.emit_signal("player_step_completed", &[]);
+            if self.player_status == PlayerStatus::Walking {
+                self.notify_tall_grass_step(owner);
+            }
+        }
+        self.refresh_interaction_prompt();
+    }
+
+    /// Tells the Game about every walked step that ends over the tall grass tiles. The player never rolls the
+    /// encounter himself: the Game does it with the rate of the area of that grass, and chooses the wild Pokémon
+    fn notify_tall_grass_step(&self, owner: &KinematicBody2D) {
+        if let Some(area_id) = terrain::encounter_area_at(owner, owner.global_position()) {
+            owner.emit_signal("tall_grass_step", &[area_id.to_variant()]);
+        }
+    }
+
+    fn get_speed_tier(&self) -> SpeedTier {
+        match self.player_status {
+            PlayerStatus::Jumping => SpeedTier::Jump,
+            PlayerStatus::Walking | PlayerStatus::Running if self.on_bike => SpeedTier::Bike,
+            PlayerStatus::Running => SpeedTier::Run,
+            _ => SpeedTier::Walk
+        }
+    }
+
+    /// Connects the PlayerCharacter signal that transmits the current global position.
+    /// Connections that already exists are skipped, so entering the same scene again never duplicates them
     fn connect_to_game_data(&self, owner: &KinematicBody2D) {
-        let receiver = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
-        owner.connect("player_position", receiver,
-         "_save_player_position", VariantArray::new_shared(), 0).unwrap();
+        let receiver = match utils::cached_node(self.game_node) {
+            Some(receiver) => receiver,
+            None => {
+                godot_warn!("There's no Game on the scene, so the player won't be connected to the game data");
+                return;
+            }
+        };
+        for (signal, method) in PLAYER_GAME_DATA_CONNECTIONS.iter() {
+            if !owner.is_connected(*signal, receiver, *method) {
+                if let Err(err) = owner.connect(*signal, receiver, *method, VariantArray::new_shared(), 0) {
+                    godot_error!("Can't connect the player signal `{}` to the Game: {:?}", signal, err);
+                }
+            }
+        }
+        if !receiver.is_connected("game_state_changed", owner, "_on_game_state_changed") {
+            if let Err(err) = receiver.connect("game_state_changed", owner, "_on_game_state_changed",
+                VariantArray::new_shared(), 0) {
+                    godot_error!("Can't follow the state of the Game: {:?}", err);
+            }
+        }
+    }
+
+    /// Shows the prompt over the tile that the player it's facing when pressing `Interact` would do something there.
+    ///
+    /// Only called when the player ends a step, bumps into something or his state changes, and never while
+    /// walking, talking or on the menu, so the raycast it's barely used
+    fn refresh_interaction_prompt(&self) {
+        let interaction_prompt = match self.interaction_prompt {
+            Some(interaction_prompt) => interaction_prompt,
+            None => return
+        };
+        let can_interact = !self.is_moving && self.player_status != PlayerStatus::Interacting
+            && self.game_state == GameState::Overworld
+            && self.get_facing_collider().map_or(false, |collider|
+                interactions::find_interactable(collider).is_some() || self.is_valid_interaction(collider));
+
+        if can_interact {
+            // Centered over the faced tile, a bit above it
+            interaction_prompt.set_position(self.facing_direction.to_vector() * in_game_constant::TILE_SIZE
+                + Vector2::new(in_game_constant::TILE_SIZE / 2.0, -in_game_constant::TILE_SIZE / 4.0));
+        }
+        interaction_prompt.set_visible(can_interact);
+    }
+
+    /// Shows or hides the shadow of the player, if the player has one
+    fn set_shadow_visible(&self, visible: bool) {
+        if let Some(player_shadow) = self.player_shadow {
+            player_shadow.set_visible(visible);
+        }
+    }
+
+    #[export]
+    /// Disconnects the PlayerCharacter from the Game when the node leaves the scene tree
+    fn _exit_tree(&self, owner: &KinematicBody2D) {
+        if let Some(receiver) = utils::cached_node(self.game_node) {
+            for (signal, method) in PLAYER_GAME_DATA_CONNECTIONS.iter() {
+                if owner.is_connected(*signal, receiver, *method) {
+                    owner.disconnect(*signal, receiver, *method);
+                }
+            }
+        }
     }
 
     #[export]
     fn save_game_data(&self, owner: &KinematicBody2D) {
         owner.emit_signal("player_position", &[(self.initial_position.x, self.initial_position.y).to_variant()]);
+        owner.emit_signal("player_status", &[Variant::from_str(self.player_status.to_status_str())]);
     }
 }
 
@@ -400,10 +1322,84 @@ impl PlayerCharacter {
 #[inherit(AnimatedSprite)]
 #[register_with(Self::register_signal)]
 #[derive(Debug)]
+/// Plays the animations of the player sprite, following the motion that the PlayerCharacter sends on every frame.
+///
+/// The names of the animations can be changed on the editor, so other sprite sets (biking, surfing...) can reuse it
 pub struct PlayerAnimation {
+    // The animations played while the character stands still, for every direction
+    #[property]
+    idle_front_animation: String,
+    #[property]
+    idle_back_animation: String,
+    #[property]
+    idle_left_animation: String,
+    #[property]
+    idle_right_animation: String,
+    // The animations played while the character moves, for every direction
+    #[property]
+    walk_front_animation: String,
+    #[property]
+    walk_back_animation: String,
+    #[property]
+    walk_left_animation: String,
+    #[property]
+    walk_right_animation: String,
+    // The animations played while the character runs. The diagonals, and any of them that the sprite doesn't
+    // have, play the walking ones instead
+    #[property]
+    run_front_animation: String,
+    #[property]
+    run_back_animation: String,
+    #[property]
+    run_left_animation: String,
+    #[property]
+    run_right_animation: String,
+    // The animation played while hopping down a ledge. Without it, the walking one keeps playing
+    #[property]
+    jump_animation: String,
+    // Turns on the diagonal facings. It's only honored if the sprite has all the diagonal animations below,
+    // otherwise the player keeps facing the four main directions
+    #[property(default = false)]
+    eight_directions: bool,
+    // The animations for the diagonals, played instead of the four main ones when the eight facings are on
+    #[property]
+    idle_front_left_animation: String,
+    #[property]
+    idle_front_right_animation: String,
+    #[property]
+    idle_back_left_animation: String,
+    #[property]
+    idle_back_right_animation: String,
+    #[property]
+    walk_front_left_animation: String,
+    #[property]
+    walk_front_right_animation: String,
+    #[property]
+    walk_back_left_animation: String,
+    #[property]
+    walk_back_right_animation: String,
+    // The animation (stretching, looking around...) played when the player stays idle for a while.
+    // An empty name, or one that the sprite doesn't have, turns it off
+    #[property]
+    ambient_idle_animation: String,
+    // Seconds standing still before the ambient animation starts
+    #[property(default = 8.0)]
+    ambient_idle_threshold: f64,
+
     current_player_motion: PlayerStatus,
-    current_player_direction: PlayerDirection,
-    idle_player_direction: PlayerDirection
+    current_facing: PlayerFacing,
+    // The facing shown while standing still, and the direction that gets persisted for it
+    idle_facing: PlayerFacing,
+    idle_player_direction: PlayerDirection,
+    // Seconds that the player has been standing still, and if the ambient animation it's playing right now
+    idle_time: f64,
+    playing_ambient_idle: bool,
+    // The Game node, that receives the direction to persist. Resolved once on `_ready`
+    game_node: Option<Ref<Node>>,
+    // The dust raised while running. Optional, so other sprite sets can go without it
+    sprint_dust: Option<Ref<Node>>,
+    // Seconds left of the flash played when the party gets hurt while walking (by the poison...)
+    damage_flash_time: f64
 }
 
 impl RegisterSignal<Self> for PlayerAnimation {
@@ -413,6 +1409,29 @@ impl RegisterSignal<Self> for PlayerAnimation {
             name: "player_direction",
             args: &[],
         });
+
+        // The player turned to another direction. Unlike `player_direction`, it isn't part of the save process,
+        // so the Game always knows where the player it's looking at, even if he quits without saving
+        builder.add_signal( Signal {
+            name: "player_direction_changed",
+            args: &[ SignalArgument {
+                name: "direction",
+                default: Variant::from_str(PlayerDirection::default().to_direction_str()),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        // A one-shot animation of the sprite ended, so the cutscenes can wait for it
+        builder.add_signal( Signal {
+            name: "sprite_animation_finished",
+            args: &[ SignalArgument {
+                name: "animation_name",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
     }
 }
 
@@ -420,9 +1439,39 @@ impl RegisterSignal<Self> for PlayerAnimation {
 impl PlayerAnimation {
     fn new(_owner: &AnimatedSprite) -> Self {
         Self {
+            idle_front_animation: "idle front".to_string(),
+            idle_back_animation: "idle back".to_string(),
+            idle_left_animation: "idle left".to_string(),
+            idle_right_animation: "idle right".to_string(),
+            walk_front_animation: "walk downwards".to_string(),
+            walk_back_animation: "walk upwards".to_string(),
+            walk_left_animation: "walk left".to_string(),
+            walk_right_animation: "walk right".to_string(),
+            run_front_animation: "run downwards".to_string(),
+            run_back_animation: "run upwards".to_string(),
+            run_left_animation: "run left".to_string(),
+            run_right_animation: "run right".to_string(),
+            jump_animation: "jump".to_string(),
+            eight_directions: false,
+            idle_front_left_animation: "idle front left".to_string(),
+            idle_front_right_animation: "idle front right".to_string(),
+            idle_back_left_animation: "idle back left".to_string(),
+            idle_back_right_animation: "idle back right".to_string(),
+            walk_front_left_animation: "walk downwards left".to_string(),
+            walk_front_right_animation: "walk downwards right".to_string(),
+            walk_back_left_animation: "walk upwards left".to_string(),
+            walk_back_right_animation: "walk upwards right".to_string(),
+            ambient_idle_animation: "idle ambient".to_string(),
+            ambient_idle_threshold: 8.0,
             current_player_motion: Default::default(),
-            current_player_direction: Default::default(),
-            idle_player_direction: Default::default()
+            current_facing: Default::default(),
+            idle_facing: Default::default(),
+            idle_player_direction: Default::default(),
+            idle_time: 0.0,
+            playing_ambient_idle: false,
+            game_node: None,
+            sprint_dust: None,
+            damage_flash_time: 0.0
         }
     }
 
@@ -431,72 +1480,232 @@ impl PlayerAnimation {
         // Adds the PlayerCharacter Node to the group that takes care about data persistence
         owner.add_to_group("save_game_data", false);
 
-        self.idle_player_direction = utils::get_player_direction();
+        if self.eight_directions && !self.has_diagonal_animations(owner) {
+            godot_warn!("The player sprite doesn't have every diagonal animation, so it will only face four directions");
+            self.eight_directions = false;
+        }
 
-        match self.idle_player_direction {
-            PlayerDirection::Downwards => { owner.play("idle front", false); }
-            PlayerDirection::Upwards => { owner.play("idle back", false); }
-            PlayerDirection::Left => { owner.play("idle left", false); }
-            PlayerDirection::Right => { owner.play("idle right", false); }
-        };
+        self.idle_player_direction = utils::get_player_direction().unwrap_or_default();
+        self.idle_facing = PlayerFacing::from_direction(&self.idle_player_direction);
+        owner.play(self.get_animation_name(&PlayerStatus::Idle, &self.idle_facing), false);
+        owner.set_process(true);
+
+        owner.connect("animation_finished", owner, "_on_animation_finished",
+            VariantArray::new_shared(), 0).unwrap();
 
         // Connects with the Game class
+        // Scenes without the Game (like a standalone test scene) just run without persisting anything
+        self.game_node = unsafe { owner.get_node("/root/Game") };
         self.connect_to_game_data(owner);
+
+        self.sprint_dust = unsafe { owner.get_node(SPRINT_DUST_NODE) };
     }
 
     #[export]
-    fn _on_player_animate(&mut self, _owner: &AnimatedSprite, _motion: Vector2) {
-        
-        let character_animated_sprite = unsafe { _owner.get_node_as::<AnimatedSprite>( ".") }.unwrap();
+    /// Receives the motion of the player on every physics frame. The owner it's already the AnimatedSprite, so
+    /// there's no need to look for any node here
+    fn _on_player_animate(&mut self, owner: &AnimatedSprite, _motion: Vector2, speed_tier: String) {
+        let speed_tier = SpeedTier::from_tier_str(&speed_tier);
+        match PlayerFacing::from_motion(_motion, self.eight_directions) {
+            Some(facing) => {
+                self.current_facing = facing;
+                self.current_player_motion = match speed_tier {
+                    SpeedTier::Run => PlayerStatus::Running,
+                    SpeedTier::Jump => PlayerStatus::Jumping,
+                    _ => PlayerStatus::Walking
+                };
+            },
+            None => { self.current_player_motion = PlayerStatus::Idle }
+        }
 
-        match _motion {
-            x if x.x > 0.0 => 
-                { self.current_player_direction = PlayerDirection::Right; self.current_player_motion = PlayerStatus::Walking },
+        if self.current_player_motion == PlayerStatus::Idle {
+            // The ambient animation plays until it finishes, instead of the normal idle one
+            owner.set_speed_scale(1.0);
+            if !self.playing_ambient_idle {
+                owner.play(self.get_animation_name(&PlayerStatus::Idle, &self.idle_facing), false);
+            }
+        } else {
+            self.reset_idle_time();
+            // The faster the player goes, the faster he moves his legs
+            owner.set_speed_scale(speed_tier.to_animation_speed_scale());
+            owner.play(self.get_motion_animation_name(owner, &self.current_player_motion, &self.current_facing), false);
+            self.idle_facing = self.current_facing;
+            // Only the real turns are notified, not every frame of the walk. The diagonals are notified as the
+            // main direction that they're closest to, the only ones that the rest of the game knows about
+            let direction = self.current_facing.to_player_direction();
+            if self.idle_player_direction != direction {
+                self.idle_player_direction = direction;
+                owner.emit_signal("player_direction_changed",
+                    &[Variant::from_str(self.idle_player_direction.to_direction_str())]);
+            }
+        }
 
-            x if x.x < 0.0 => 
-                { self.current_player_direction = PlayerDirection::Left; self.current_player_motion = PlayerStatus::Walking }, 
+        // The dust it's only raised while running. Walking, biking or stopping lets it settle
+        if let Some(sprint_dust) = utils::cached_node(self.sprint_dust) {
+            let running = self.current_player_motion == PlayerStatus::Running;
+            unsafe { sprint_dust.call("set_running", &[running.to_variant(), _motion.to_variant()]) };
+        }
+    }
 
-            x if x.y < 0.0 => 
-                { self.current_player_direction = PlayerDirection::Upwards; self.current_player_motion = PlayerStatus::Walking },
-            
-            x if x.y > 0.0 => 
-                { self.current_player_direction = PlayerDirection::Downwards; self.current_player_motion = PlayerStatus::Walking },
-            
-            _ => 
-                { self.current_player_motion = PlayerStatus::Idle }    
+    #[export]
+    /// Counts the time that the player stands still, and starts the ambient animation when it's long enough.
+    /// Talking, battling or any other interaction doesn't count as being idle
+    fn _process(&mut self, owner: &AnimatedSprite, delta: f64) {
+        if self.damage_flash_time > 0.0 {
+            self.update_damage_flash(owner, delta);
         }
 
-        if self.current_player_motion == PlayerStatus::Idle {
-            match self.idle_player_direction {
-                PlayerDirection::Downwards => { character_animated_sprite.play("idle front", false); }
-                PlayerDirection::Upwards => { character_animated_sprite.play("idle back", false); }
-                PlayerDirection::Left => { character_animated_sprite.play("idle left", false); }
-                PlayerDirection::Right => { character_animated_sprite.play("idle right", false); }
-            }; 
+        if self.current_player_motion != PlayerStatus::Idle || self.is_player_interacting(owner) {
+            if self.playing_ambient_idle {
+                owner.play(self.get_animation_name(&PlayerStatus::Idle, &self.idle_facing), false);
+            }
+            self.reset_idle_time();
+            return;
+        }
 
-        } else if self.current_player_direction == PlayerDirection::Right {
-            character_animated_sprite.play("walk right", false);
-            self.idle_player_direction = PlayerDirection::Right;
+        self.idle_time += delta;
+        if !self.playing_ambient_idle && self.idle_time >= self.ambient_idle_threshold && self.has_ambient_idle_animation(owner) {
+            self.playing_ambient_idle = true;
+            owner.play(self.ambient_idle_animation.as_str(), false);
+        }
+    }
 
-        } else if PlayerDirection::Left == self.current_player_direction {  
-            character_animated_sprite.play("walk left", false);
-            self.idle_player_direction = PlayerDirection::Left;
+    #[export]
+    /// Makes the sprite blink for a moment, as the feedback of the party getting hurt on the overworld
+    fn flash_damage(&mut self, _owner: &AnimatedSprite) {
+        self.damage_flash_time = DAMAGE_FLASH_SECONDS;
+    }
 
-        } else if PlayerDirection::Downwards == self.current_player_direction {
-            character_animated_sprite.play("walk downwards", false);
-            self.idle_player_direction = PlayerDirection::Downwards;
+    /// Dims the sprite on every other interval of the flash, and gives him back his colours when it ends
+    fn update_damage_flash(&mut self, owner: &AnimatedSprite, delta: f64) {
+        self.damage_flash_time = (self.damage_flash_time - delta).max(0.0);
+        let dimmed = self.damage_flash_time > 0.0 && (self.damage_flash_time / DAMAGE_FLASH_INTERVAL) as i64 % 2 == 0;
+        owner.set_modulate(if dimmed { Color::rgba(0.7, 0.3, 0.8, 0.6) } else { Color::rgba(1.0, 1.0, 1.0, 1.0) });
+    }
 
-        } else if PlayerDirection::Upwards == self.current_player_direction {
-            character_animated_sprite.play("walk upwards", false);
-            self.idle_player_direction = PlayerDirection::Upwards;
+    #[export]
+    /// Forwards the end of the one-shot animations to the cutscenes. The looping ones (walking, idle...) finish
+    /// on every loop, so they're never forwarded.
+    ///
+    /// When the ambient animation ends, the player goes back to the normal idle one, and the count starts again
+    fn _on_animation_finished(&mut self, owner: &AnimatedSprite) {
+        let animation_name = owner.animation().to_string();
+        let is_looping = owner.sprite_frames()
+            .map(|frames| unsafe { frames.assume_safe() }.get_animation_loop(animation_name.as_str()))
+            .unwrap_or(true);
+        if !is_looping {
+            owner.emit_signal("sprite_animation_finished", &[animation_name.to_variant()]);
         }
+
+        if self.playing_ambient_idle {
+            self.reset_idle_time();
+            owner.play(self.get_animation_name(&PlayerStatus::Idle, &self.idle_facing), false);
+        }
+    }
+
+    fn reset_idle_time(&mut self) {
+        self.idle_time = 0.0;
+        self.playing_ambient_idle = false;
+    }
+
+    fn has_ambient_idle_animation(&self, owner: &AnimatedSprite) -> bool {
+        !self.ambient_idle_animation.is_empty() && owner.sprite_frames()
+            .map(|frames| unsafe { frames.assume_safe() }.has_animation(self.ambient_idle_animation.as_str()))
+            .unwrap_or(false)
+    }
+
+    /// The PlayerAnimation lives under the PlayerCharacter, so his parent knows if the player it's busy
+    fn is_player_interacting(&self, owner: &AnimatedSprite) -> bool {
+        owner.get_parent()
+            .map(|player| unsafe { player.assume_safe().call("is_interacting", &[]) }.to_bool())
+            .unwrap_or(false)
     }
 
-    /// Connects the PlayerCharacter signal with the Game class
+    /// The animation that plays the sprite for the given motion and facing. Running has his own animations only
+    /// for the four main directions, jumping has a single one, and every other motion that isn't standing still
+    /// uses the walking ones
+    fn get_animation_name(&self, motion: &PlayerStatus, facing: &PlayerFacing) -> &str {
+        match (motion, facing) {
+            (PlayerStatus::Running, PlayerFacing::Down) => &self.run_front_animation,
+            (PlayerStatus::Running, PlayerFacing::Up) => &self.run_back_animation,
+            (PlayerStatus::Running, PlayerFacing::Left) => &self.run_left_animation,
+            (PlayerStatus::Running, PlayerFacing::Right) => &self.run_right_animation,
+            (PlayerStatus::Jumping, _) => &self.jump_animation,
+            (PlayerStatus::Idle, PlayerFacing::Down) => &self.idle_front_animation,
+            (PlayerStatus::Idle, PlayerFacing::Up) => &self.idle_back_animation,
+            (PlayerStatus::Idle, PlayerFacing::Left) => &self.idle_left_animation,
+            (PlayerStatus::Idle, PlayerFacing::Right) => &self.idle_right_animation,
+            (PlayerStatus::Idle, PlayerFacing::DownLeft) => &self.idle_front_left_animation,
+            (PlayerStatus::Idle, PlayerFacing::DownRight) => &self.idle_front_right_animation,
+            (PlayerStatus::Idle, PlayerFacing::UpLeft) => &self.idle_back_left_animation,
+            (PlayerStatus::Idle, PlayerFacing::UpRight) => &self.idle_back_right_animation,
+            (_, PlayerFacing::Down) => &self.walk_front_animation,
+            (_, PlayerFacing::Up) => &self.walk_back_animation,
+            (_, PlayerFacing::Left) => &self.walk_left_animation,
+            (_, PlayerFacing::Right) => &self.walk_right_animation,
+            (_, PlayerFacing::DownLeft) => &self.walk_front_left_animation,
+            (_, PlayerFacing::DownRight) => &self.walk_front_right_animation,
+            (_, PlayerFacing::UpLeft) => &self.walk_back_left_animation,
+            (_, PlayerFacing::UpRight) => &self.walk_back_right_animation,
+        }
+    }
+
+    /// Like `get_animation_name`, but a sprite without the running or jumping animations keeps playing the walking ones
+    fn get_motion_animation_name(&self, owner: &AnimatedSprite, motion: &PlayerStatus, facing: &PlayerFacing) -> &str {
+        let animation = self.get_animation_name(motion, facing);
+        let has_animation = owner.sprite_frames()
+            .map(|frames| unsafe { frames.assume_safe() }.has_animation(animation))
+            .unwrap_or(false);
+        if (*motion == PlayerStatus::Running || *motion == PlayerStatus::Jumping) && !has_animation {
+            self.get_animation_name(&PlayerStatus::Walking, facing)
+        } else {
+            animation
+        }
+    }
+
+    /// If the sprite can show the player looking to every diagonal, both walking and standing still
+    fn has_diagonal_animations(&self, owner: &AnimatedSprite) -> bool {
+        let frames = match owner.sprite_frames() {
+            Some(frames) => unsafe { frames.assume_safe() },
+            None => return false
+        };
+        PlayerFacing::ALL.iter()
+            .filter(|facing| facing.is_diagonal())
+            .flat_map(|facing| vec![
+                self.get_animation_name(&PlayerStatus::Idle, facing),
+                self.get_animation_name(&PlayerStatus::Walking, facing)
+            ])
+            .all(|animation| frames.has_animation(animation))
+    }
+
+    /// Connects the PlayerAnimation signals with the Game class
     fn connect_to_game_data(&self, owner: &AnimatedSprite) {
-        let receiver = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
-        owner.connect("player_direction", receiver,
-            "_save_player_direction", VariantArray::new_shared(), 0).unwrap();
+        let receiver = match utils::cached_node(self.game_node) {
+            Some(receiver) => receiver,
+            None => {
+                godot_warn!("There's no Game on the scene, so the player animation won't be connected to the game data");
+                return;
+            }
+        };
+        for (signal, method) in PLAYER_ANIMATION_GAME_DATA_CONNECTIONS.iter() {
+            if !owner.is_connected(*signal, receiver, *method) {
+                if let Err(err) = owner.connect(*signal, receiver, *method, VariantArray::new_shared(), 0) {
+                    godot_error!("Can't connect the player animation signal `{}` to the Game: {:?}", signal, err);
+                }
+            }
+        }
+    }
+
+    #[export]
+    /// Disconnects the PlayerAnimation from the Game when the node leaves the scene tree
+    fn _exit_tree(&self, owner: &AnimatedSprite) {
+        if let Some(receiver) = utils::cached_node(self.game_node) {
+            for (signal, method) in PLAYER_ANIMATION_GAME_DATA_CONNECTIONS.iter() {
+                if owner.is_connected(*signal, receiver, *method) {
+                    owner.disconnect(*signal, receiver, *method);
+                }
+            }
+        }
     }
 
     #[export]
@@ -505,11 +1714,61 @@ impl PlayerAnimation {
     }
 }
 
+/// The checks behind `PlayerCharacter::can_act`: the game it's on the overworld, and the player isn't busy with
+/// any interaction, the menu or a dialogue
+fn is_controllable(game_state: GameState, player_status: &PlayerStatus, menu_status: &MenuStatus,
+    dialogue_box_status: &DialogueBoxStatus) -> bool
+{
+    game_state == GameState::Overworld &&
+        *player_status != PlayerStatus::Interacting &&
+        *menu_status != MenuStatus::Open &&
+        *dialogue_box_status == DialogueBoxStatus::Inactive
+}
+
+/// How the player takes a new step. Holding the run key does nothing until the player gets the running shoes,
+/// that are only looked for while the key it's held
+fn step_status<F: FnOnce() -> bool>(run_pressed: bool, has_running_shoes: F) -> PlayerStatus {
+    if run_pressed && has_running_shoes() {
+        PlayerStatus::Running
+    } else {
+        PlayerStatus::Walking
+    }
+}
+
+/// The interactions that the player understands, by the payload of the signal that announces them
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PlayerInteraction {
+    // Talking with someone, so the dialogue box it's active
+    Dialogue,
+    Menu,
+    // A wild Pokémon appeared, a trainer spotted the player, a cutscene started... The player can't move until it ends
+    Locked,
+    // The interaction it's over, so the player can move again
+    Ended
+}
+
+impl PlayerInteraction {
+    /// None for any unknown payload, so a mistyped signal never releases the player in the middle of an interaction
+    pub fn from_signal_str(signal_info: &str) -> Option<PlayerInteraction> {
+        match signal_info {
+            "on_dialogue" => Some(PlayerInteraction::Dialogue),
+            "menu_active" => Some(PlayerInteraction::Menu),
+            "on_encounter" | "trainer_engaged" | "on_evolution" | "on_move_learning" | "on_transition" | "on_shop"
+                | "on_cutscene" | "on_healing" | "on_fishing" => Some(PlayerInteraction::Locked),
+            "end_interaction" => Some(PlayerInteraction::Ended),
+            _ => None
+        }
+    }
+}
+
+/// What the player it's doing. It's saved with the game, with the same unit variant names that the
+/// PlayerDirection uses
 #[derive(PartialEq, Clone, Debug)]
 pub enum PlayerStatus {
     Idle,
     Walking,
-    // Running
+    Running,
+    Jumping,
     Interacting
 }
 
@@ -517,6 +1776,133 @@ impl Default for PlayerStatus {
     fn default() -> Self { PlayerStatus::Idle }
 }
 
+impl PlayerStatus {
+    pub fn to_status_str(&self) -> &'static str {
+        match self {
+            PlayerStatus::Idle => "Idle",
+            PlayerStatus::Walking => "Walking",
+            PlayerStatus::Running => "Running",
+            PlayerStatus::Jumping => "Jumping",
+            PlayerStatus::Interacting => "Interacting"
+        }
+    }
+
+    // Anything unknown it's just standing still
+    pub fn from_status_str(status: &str) -> PlayerStatus {
+        match status {
+            "Walking" => PlayerStatus::Walking,
+            "Running" => PlayerStatus::Running,
+            "Jumping" => PlayerStatus::Jumping,
+            "Interacting" => PlayerStatus::Interacting,
+            _ => PlayerStatus::Idle
+        }
+    }
+
+    /// The status that the player gets back when a save it's loaded. The interactions and the jumps can't go on
+    /// after a reload (the dialogue or the jump aren't saved), so the player gets the control back, standing still
+    pub fn on_reload(&self) -> PlayerStatus {
+        match self {
+            PlayerStatus::Interacting | PlayerStatus::Jumping => PlayerStatus::Idle,
+            status => status.to_owned()
+        }
+    }
+}
+
+impl Serialize for PlayerStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match *self {
+            PlayerStatus::Idle => serializer.serialize_unit_variant("PlayerStatus", 0, "Idle"),
+            PlayerStatus::Walking => serializer.serialize_unit_variant("PlayerStatus", 1, "Walking"),
+            PlayerStatus::Running => serializer.serialize_unit_variant("PlayerStatus", 2, "Running"),
+            PlayerStatus::Jumping => serializer.serialize_unit_variant("PlayerStatus", 3, "Jumping"),
+            PlayerStatus::Interacting => serializer.serialize_unit_variant("PlayerStatus", 4, "Interacting"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayerStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let status = String::deserialize(deserializer)?;
+        Ok(PlayerStatus::from_status_str(&status))
+    }
+}
+
+/// How fast the speed of the player changes with the momentum movement, in tiles per second, every second
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Momentum {
+    acceleration: f64,
+    deceleration: f64,
+}
+
+impl Momentum {
+    // Anything slower would leave the player stuck between two tiles for seconds
+    const MIN_RATE: f64 = 0.5;
+
+    pub fn new(acceleration: f64, deceleration: f64) -> Self {
+        Self {
+            acceleration: acceleration.max(Self::MIN_RATE),
+            deceleration: deceleration.max(Self::MIN_RATE),
+        }
+    }
+
+    /// The speed after the given seconds getting closer to the target one. It speeds up or slows down until it
+    /// reaches the target, but it never goes past it
+    pub fn approach(&self, current_speed: f64, target_speed: f64, delta: f64) -> f64 {
+        if current_speed < target_speed {
+            (current_speed + self.acceleration * delta).min(target_speed)
+        } else {
+            (current_speed - self.deceleration * delta).max(target_speed)
+        }
+    }
+}
+
+/// How fast the player it's moving. The walking animations play faster on the faster tiers. Hopping down a
+/// ledge it's a tier of his own, so the animation knows about the jump
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum SpeedTier {
+    Walk,
+    Run,
+    Bike,
+    Jump
+}
+
+impl SpeedTier {
+    pub fn to_tier_str(&self) -> &'static str {
+        match self {
+            SpeedTier::Walk => "Walk",
+            SpeedTier::Run => "Run",
+            SpeedTier::Bike => "Bike",
+            SpeedTier::Jump => "Jump"
+        }
+    }
+
+    // Anything unknown it's just walking
+    pub fn from_tier_str(speed_tier: &str) -> SpeedTier {
+        match speed_tier {
+            "Run" => SpeedTier::Run,
+            "Bike" => SpeedTier::Bike,
+            "Jump" => SpeedTier::Jump,
+            _ => SpeedTier::Walk
+        }
+    }
+
+    /// The `speed_scale` of the AnimatedSprite while moving at this speed
+    pub fn to_animation_speed_scale(&self) -> f64 {
+        match self {
+            SpeedTier::Walk => 1.0,
+            SpeedTier::Run => 2.0,
+            SpeedTier::Bike => 2.5,
+            SpeedTier::Jump => 1.0
+        }
+    }
+}
+
 #[derive(PartialEq, Clone, Debug, ToVariant, Deserialize)]
 pub enum PlayerDirection {
     Upwards,
@@ -529,6 +1915,49 @@ impl Default for PlayerDirection {
     fn default() -> Self { PlayerDirection::Downwards }
 }
 
+impl PlayerDirection {
+    // Associated fn that converts any direction as string to his equivalent PlayerDirection counterpart
+    pub fn from_string(direction: &str) -> PlayerDirection {
+        match direction {
+            "Upwards" => Self::Upwards,
+            "Downwards" => Self::Downwards,
+            "Left" => Self::Left,
+            "Right" => Self::Right,
+            _ => Default::default()
+        }
+    }
+
+    pub fn to_direction_str(&self) -> &'static str {
+        match self {
+            Self::Upwards => "Upwards",
+            Self::Downwards => "Downwards",
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+
+    // Returns an unitary vector pointing to the direction
+    pub fn to_vector(&self) -> Vector2 {
+        match self {
+            Self::Upwards => Vector2::new(0.0, -1.0),
+            Self::Downwards => Vector2::new(0.0, 1.0),
+            Self::Left => Vector2::new(-1.0, 0.0),
+            Self::Right => Vector2::new(1.0, 0.0),
+        }
+    }
+
+    // Converts a movement vector to the direction that the character should face. A zero vector has no direction
+    pub fn from_vector(vector: Vector2) -> Option<PlayerDirection> {
+        match vector {
+            v if v.x > 0.0 => Some(Self::Right),
+            v if v.x < 0.0 => Some(Self::Left),
+            v if v.y < 0.0 => Some(Self::Upwards),
+            v if v.y > 0.0 => Some(Self::Downwards),
+            _ => None
+        }
+    }
+}
+
 impl Serialize for PlayerDirection {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -541,4 +1970,320 @@ impl Serialize for PlayerDirection {
             PlayerDirection::Right => serializer.serialize_unit_variant("PlayerDirection", 3, "Right"),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Where the player sprite it's looking at. Besides the four main directions it has the diagonals, that only
+/// the sprites with diagonal animations can show.
+///
+/// It's kept apart from the PlayerDirection, that it's the one persisted and the only one that the rest of the
+/// game knows about, so a diagonal always ends converted to his closest main direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayerFacing {
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+    Up,
+    UpRight
+}
+
+impl Default for PlayerFacing {
+    fn default() -> Self { PlayerFacing::Down }
+}
+
+impl PlayerFacing {
+    /// Every facing, clockwise from the right, in the same order that the angles grow on the screen
+    pub const ALL: [PlayerFacing; 8] = [
+        PlayerFacing::Right, PlayerFacing::DownRight, PlayerFacing::Down, PlayerFacing::DownLeft,
+        PlayerFacing::Left, PlayerFacing::UpLeft, PlayerFacing::Up, PlayerFacing::UpRight
+    ];
+
+    /// The facing closest to the angle of the motion. With only four directions, the horizontal ones win
+    /// when the motion it's exactly on a diagonal, like they always did. A zero motion has no facing
+    pub fn from_motion(motion: Vector2, eight_directions: bool) -> Option<PlayerFacing> {
+        if motion == Vector2::zero() {
+            return None;
+        }
+        if !eight_directions {
+            let main_axis = if motion.x.abs() >= motion.y.abs() {
+                Vector2::new(motion.x, 0.0)
+            } else {
+                Vector2::new(0.0, motion.y)
+            };
+            return PlayerDirection::from_vector(main_axis).map(|direction| PlayerFacing::from_direction(&direction));
+        }
+
+        // The y axis points down on the screen, so the angles grow clockwise, like the ALL facings
+        let sector = (motion.y as f64).atan2(motion.x as f64) / std::f64::consts::FRAC_PI_4;
+        Some(PlayerFacing::ALL[(sector.round() as i64).rem_euclid(8) as usize])
+    }
+
+    pub fn from_direction(direction: &PlayerDirection) -> PlayerFacing {
+        match direction {
+            PlayerDirection::Upwards => PlayerFacing::Up,
+            PlayerDirection::Downwards => PlayerFacing::Down,
+            PlayerDirection::Left => PlayerFacing::Left,
+            PlayerDirection::Right => PlayerFacing::Right,
+        }
+    }
+
+    /// The main direction closest to the facing. The diagonals keep his horizontal part
+    pub fn to_player_direction(&self) -> PlayerDirection {
+        match self {
+            PlayerFacing::Up => PlayerDirection::Upwards,
+            PlayerFacing::Down => PlayerDirection::Downwards,
+            PlayerFacing::Left | PlayerFacing::UpLeft | PlayerFacing::DownLeft => PlayerDirection::Left,
+            PlayerFacing::Right | PlayerFacing::UpRight | PlayerFacing::DownRight => PlayerDirection::Right,
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(self, PlayerFacing::DownRight | PlayerFacing::DownLeft | PlayerFacing::UpLeft | PlayerFacing::UpRight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::battle::rewards;
+
+    #[test]
+    fn the_money_saturates_at_the_max() {
+        let mut player_data = PlayerData::new();
+        assert!(player_data.earn(shop::MAX_MONEY - player_data.get_money()));
+        assert_eq!(player_data.get_money(), shop::MAX_MONEY);
+
+        assert!(!player_data.earn(1));
+        assert!(!player_data.earn(u32::MAX));
+        assert_eq!(player_data.get_money(), shop::MAX_MONEY);
+    }
+
+    #[test]
+    fn an_overspend_is_refused_without_charging_anything() {
+        let mut player_data = PlayerData::new();
+        let money = player_data.get_money();
+        assert!(!player_data.try_spend(money + 1));
+        assert_eq!(player_data.get_money(), money);
+
+        assert!(player_data.try_spend(money));
+        assert_eq!(player_data.get_money(), 0);
+        assert!(!player_data.try_spend(1));
+    }
+
+    #[test]
+    fn the_saved_money_is_kept_under_the_max() {
+        let mut save = serde_json::to_value(PlayerData::new()).unwrap();
+        save["money"] = serde_json::Value::from(5_000_000);
+        let player_data: PlayerData = serde_json::from_value(save.clone()).unwrap();
+        assert_eq!(player_data.get_money(), shop::MAX_MONEY);
+
+        // The saves from before the money start with the money of a new game
+        save.as_object_mut().unwrap().remove("money");
+        let player_data: PlayerData = serde_json::from_value(save).unwrap();
+        assert_eq!(player_data.get_money(), shop::STARTING_MONEY);
+    }
+
+    #[test]
+    fn an_unknown_interaction_signal_is_ignored() {
+        assert_eq!(PlayerInteraction::from_signal_str("on_dialogue"), Some(PlayerInteraction::Dialogue));
+        assert_eq!(PlayerInteraction::from_signal_str("end_interaction"), Some(PlayerInteraction::Ended));
+        // Mistyped, or the old empty payload that used to release the player
+        for signal_info in &["on_dialog", "End_interaction", "", "unknown"] {
+            assert_eq!(PlayerInteraction::from_signal_str(signal_info), None, "{:?}", signal_info);
+            assert_eq!(GameState::from_interaction(signal_info), None, "{:?}", signal_info);
+        }
+    }
+
+    #[test]
+    fn the_player_only_acts_free_on_the_overworld() {
+        let (idle, closed, inactive) = (PlayerStatus::Idle, MenuStatus::Closed, DialogueBoxStatus::Inactive);
+        assert!(is_controllable(GameState::Overworld, &idle, &closed, &inactive));
+        assert!(is_controllable(GameState::Overworld, &PlayerStatus::Running, &closed, &inactive));
+
+        for game_state in &[GameState::Dialogue, GameState::Menu, GameState::Battle, GameState::Cutscene,
+            GameState::Transition]
+        {
+            assert!(!is_controllable(*game_state, &idle, &closed, &inactive), "{:?}", game_state);
+        }
+        assert!(!is_controllable(GameState::Overworld, &PlayerStatus::Interacting, &closed, &inactive));
+        assert!(!is_controllable(GameState::Overworld, &idle, &MenuStatus::Open, &inactive));
+        assert!(!is_controllable(GameState::Overworld, &idle, &closed, &DialogueBoxStatus::Active));
+    }
+
+    #[test]
+    fn running_needs_the_running_shoes() {
+        let mut player_data = PlayerData::new();
+        assert!(!player_data.has_running_shoes());
+        assert_eq!(step_status(true, || player_data.has_running_shoes()), PlayerStatus::Walking);
+
+        player_data.get_bag_mut().add_item(RUNNING_SHOES, 1);
+        assert!(player_data.has_running_shoes());
+        assert_eq!(step_status(true, || player_data.has_running_shoes()), PlayerStatus::Running);
+        assert_eq!(step_status(false, || player_data.has_running_shoes()), PlayerStatus::Walking);
+        // Walking never asks for the shoes
+        assert_eq!(step_status(false, || panic!("the shoes were looked for")), PlayerStatus::Walking);
+    }
+
+    #[test]
+    fn every_known_interaction_changes_the_game_state() {
+        for signal_info in &["on_dialogue", "menu_active", "on_encounter", "trainer_engaged", "on_evolution",
+            "on_move_learning", "on_transition", "on_shop", "on_cutscene", "on_healing", "on_fishing", "end_interaction"]
+        {
+            assert!(PlayerInteraction::from_signal_str(signal_info).is_some(), "{:?}", signal_info);
+            assert!(GameState::from_interaction(signal_info).is_some(), "{:?}", signal_info);
+        }
+    }
+
+    #[test]
+    fn a_prize_over_the_max_only_fills_the_wallet() {
+        let mut player_data = PlayerData::new();
+        player_data.earn(shop::MAX_MONEY - 100 - player_data.get_money());
+
+        let prize = rewards::prize_money(80, 30);
+        assert!(!player_data.earn(prize));
+        assert_eq!(player_data.get_money(), shop::MAX_MONEY);
+        // A prize that fits is fully paid
+        player_data.try_spend(prize);
+        assert!(player_data.earn(prize));
+        assert_eq!(player_data.get_money(), shop::MAX_MONEY);
+    }
+
+    #[test]
+    fn a_sale_with_a_full_wallet_still_takes_the_item() {
+        let mut player_data = PlayerData::new();
+        player_data.earn(shop::MAX_MONEY);
+        player_data.get_bag_mut().add_item("potion", 2);
+
+        assert_eq!(player_data.sell_item("potion", 1), Ok(shop::get_sell_price("potion").unwrap()));
+        assert_eq!(player_data.get_money(), shop::MAX_MONEY);
+        assert_eq!(player_data.get_bag().get_quantity("potion"), 1);
+    }
+
+    #[test]
+    fn the_repel_wears_off_after_his_steps() {
+        let mut player_data = PlayerData::new();
+        player_data.get_bag_mut().add_item(bag::REPEL, 2);
+        assert_eq!(player_data.take_repel_step(), None);
+        assert_eq!(player_data.use_repel(bag::REPEL), Ok(()));
+        assert_eq!(player_data.get_repel_steps(), 100);
+
+        for _ in 0..99 {
+            assert_eq!(player_data.take_repel_step(), None);
+        }
+        assert_eq!(player_data.get_repel_steps(), 1);
+        assert_eq!(player_data.take_repel_step(), Some(bag::REPEL.to_string()));
+        assert!(!player_data.is_repel_active());
+        assert_eq!(player_data.take_repel_step(), None);
+    }
+
+    #[test]
+    fn a_repel_cant_be_used_while_another_one_works() {
+        let mut player_data = PlayerData::new();
+        player_data.get_bag_mut().add_item(bag::SUPER_REPEL, 1);
+        player_data.get_bag_mut().add_item(bag::MAX_REPEL, 1);
+        assert_eq!(player_data.use_repel(bag::REPEL), Err(ItemError::NotInBag));
+        assert_eq!(player_data.use_repel("potion"), Err(ItemError::UnknownItem));
+
+        player_data.use_repel(bag::SUPER_REPEL).unwrap();
+        player_data.take_repel_step();
+        assert_eq!(player_data.use_repel(bag::MAX_REPEL), Err(ItemError::NoEffect));
+        assert_eq!(player_data.get_bag().get_quantity(bag::MAX_REPEL), 1);
+        assert_eq!(player_data.get_repel_steps(), 199);
+    }
+
+    fn pokemon(id: i32) -> Pokemon {
+        Pokemon::for_tests(id, "Pokémon", Vec::new())
+    }
+
+    const FIRST_CENTER: &str = "res://godot/Game/WorldElements/CampoDePruebas/Scenes/InteriorPokemonCenterCampoDePruebas.tscn";
+    const SECOND_CENTER: &str = "res://godot/Game/WorldElements/CampoDePruebas/Scenes/InteriorPokemonCenterCampoDePruebas2.tscn";
+
+    fn pokemon_center(scene_path: &str) -> RespawnPoint {
+        RespawnPoint { scene_path: scene_path.to_string(), x: 96.0, y: 128.0 }
+    }
+
+    #[test]
+    fn a_wiped_party_goes_back_to_the_last_pokemon_center() {
+        let mut player_data = PlayerData::new();
+        player_data.receive_pokemon(pokemon(1)).unwrap();
+        player_data.receive_pokemon(pokemon(4)).unwrap();
+        player_data.set_respawn_point(pokemon_center(FIRST_CENTER));
+        player_data.set_respawn_point(pokemon_center(SECOND_CENTER));
+
+        player_data.get_party_mut().get_member_mut(0).unwrap().set_current_hp(0);
+        assert!(!player_data.get_party().is_wiped_out());
+        player_data.get_party_mut().get_member_mut(1).unwrap().set_current_hp(0);
+        assert!(player_data.get_party().is_wiped_out());
+
+        let money = player_data.get_money();
+        let money_lost = player_data.black_out();
+        assert_eq!(money_lost, (money as f64 * in_game_constant::BLACKOUT_MONEY_LOSS) as u32);
+        assert_eq!(player_data.get_money(), money - money_lost);
+        assert!(!player_data.get_party().is_wiped_out());
+        assert_eq!(player_data.get_respawn_point(),
+            Some(&pokemon_center(SECOND_CENTER)));
+    }
+
+    #[test]
+    fn without_a_pokemon_center_there_is_no_respawn_point() {
+        let mut player_data = PlayerData::new();
+        player_data.receive_pokemon(pokemon(1)).unwrap();
+        player_data.get_party_mut().get_member_mut(0).unwrap().set_current_hp(0);
+        player_data.black_out();
+        assert_eq!(player_data.get_respawn_point(), None);
+    }
+
+    #[test]
+    fn the_respawn_point_is_saved() {
+        let mut player_data = PlayerData::new();
+        player_data.set_respawn_point(pokemon_center(FIRST_CENTER));
+        let loaded: PlayerData = serde_json::from_str(&serde_json::to_string(&player_data).unwrap()).unwrap();
+        assert_eq!(loaded.get_respawn_point(), player_data.get_respawn_point());
+    }
+
+    /// A motion on the given angle, in degrees clockwise from the right like on the screen
+    fn motion_at(degrees: f32) -> Vector2 {
+        let radians = degrees.to_radians();
+        Vector2::new(radians.cos(), radians.sin()) * 60.0
+    }
+
+    #[test]
+    fn the_motion_faces_the_closest_of_the_eight_directions() {
+        let cases = [
+            (0.0, PlayerFacing::Right), (90.0, PlayerFacing::Down), (180.0, PlayerFacing::Left),
+            (270.0, PlayerFacing::Up), (45.0, PlayerFacing::DownRight), (135.0, PlayerFacing::DownLeft),
+            (225.0, PlayerFacing::UpLeft), (315.0, PlayerFacing::UpRight),
+            // Every facing covers 22.5 degrees to each side
+            (22.0, PlayerFacing::Right), (23.0, PlayerFacing::DownRight), (-22.0, PlayerFacing::Right),
+            (-23.0, PlayerFacing::UpRight), (157.0, PlayerFacing::DownLeft), (158.0, PlayerFacing::Left),
+            (202.0, PlayerFacing::Left), (203.0, PlayerFacing::UpLeft), (337.0, PlayerFacing::UpRight),
+            (338.0, PlayerFacing::Right),
+        ];
+        for (degrees, facing) in cases.iter() {
+            assert_eq!(PlayerFacing::from_motion(motion_at(*degrees), true), Some(*facing), "{} degrees", degrees);
+        }
+    }
+
+    #[test]
+    fn with_four_directions_the_main_axis_wins() {
+        let cases = [
+            (Vector2::new(1.0, 0.0), PlayerFacing::Right), (Vector2::new(0.0, 1.0), PlayerFacing::Down),
+            (Vector2::new(-1.0, 0.0), PlayerFacing::Left), (Vector2::new(0.0, -1.0), PlayerFacing::Up),
+            (Vector2::new(0.4, -1.0), PlayerFacing::Up), (Vector2::new(-1.0, 0.4), PlayerFacing::Left),
+            // Exactly on a diagonal the horizontal direction wins
+            (Vector2::new(1.0, 1.0), PlayerFacing::Right), (Vector2::new(-1.0, -1.0), PlayerFacing::Left),
+            (Vector2::new(-1.0, 1.0), PlayerFacing::Left), (Vector2::new(1.0, -1.0), PlayerFacing::Right),
+        ];
+        for (motion, facing) in cases.iter() {
+            assert_eq!(PlayerFacing::from_motion(*motion, false), Some(*facing), "{:?}", motion);
+        }
+    }
+
+    #[test]
+    fn a_still_player_has_no_facing() {
+        assert_eq!(PlayerFacing::from_motion(Vector2::zero(), true), None);
+        assert_eq!(PlayerFacing::from_motion(Vector2::zero(), false), None);
+    }
+}
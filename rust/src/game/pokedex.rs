@@ -0,0 +1,170 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// What the player knows about a Pokémon specie
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PokedexStatus {
+    Unknown,
+    Seen,
+    Caught
+}
+
+impl PokedexStatus {
+    pub fn to_status_str(&self) -> &'static str {
+        match self {
+            PokedexStatus::Unknown => "Unknown",
+            PokedexStatus::Seen => "Seen",
+            PokedexStatus::Caught => "Caught"
+        }
+    }
+}
+
+/// The Pokémon species that the player already found on his journey, by specie id.
+///
+/// A specie gets seen when the player finds it on a battle, and caught when the player gets one of them.
+/// A caught specie it's always seen too
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pokedex {
+    #[serde(default)]
+    seen: BTreeSet<i32>,
+    #[serde(default)]
+    caught: BTreeSet<i32>,
+}
+
+impl Pokedex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the player had never seen the specie before
+    pub fn mark_seen(&mut self, specie_id: i32) -> bool {
+        self.seen.insert(specie_id)
+    }
+
+    /// Returns true if the player had never caught the specie before
+    pub fn mark_caught(&mut self, specie_id: i32) -> bool {
+        self.seen.insert(specie_id);
+        self.caught.insert(specie_id)
+    }
+
+    /// A caught specie counts as seen even if the save lost it from the seen ones
+    pub fn is_seen(&self, specie_id: i32) -> bool {
+        self.seen.contains(&specie_id) || self.is_caught(specie_id)
+    }
+
+    pub fn is_caught(&self, specie_id: i32) -> bool {
+        self.caught.contains(&specie_id)
+    }
+
+    pub fn get_status(&self, specie_id: i32) -> PokedexStatus {
+        if self.is_caught(specie_id) {
+            PokedexStatus::Caught
+        } else if self.is_seen(specie_id) {
+            PokedexStatus::Seen
+        } else {
+            PokedexStatus::Unknown
+        }
+    }
+
+    pub fn seen_count(&self) -> usize {
+        self.seen.union(&self.caught).count()
+    }
+
+    pub fn caught_count(&self) -> usize {
+        self.caught.len()
+    }
+
+    /// The percentage (from 0 to 100) of the given number of species that the player already caught
+    pub fn completion_percentage(&self, total_species: usize) -> f64 {
+        if total_species == 0 {
+            return 0.0;
+        }
+        (self.caught_count() as f64 / total_species as f64 * 100.0).min(100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::PlayerData;
+
+    #[test]
+    fn a_caught_specie_is_also_seen() {
+        let mut pokedex = Pokedex::new();
+        assert!(pokedex.mark_caught(25));
+        assert!(pokedex.is_seen(25));
+        assert!(pokedex.is_caught(25));
+        assert_eq!(pokedex.get_status(25), PokedexStatus::Caught);
+        // Already seen by the catch
+        assert!(!pokedex.mark_seen(25));
+        assert!(!pokedex.mark_caught(25));
+        assert_eq!((pokedex.seen_count(), pokedex.caught_count()), (1, 1));
+    }
+
+    #[test]
+    fn a_seen_specie_is_not_caught() {
+        let mut pokedex = Pokedex::new();
+        assert!(pokedex.mark_seen(16));
+        assert!(!pokedex.mark_seen(16));
+        assert_eq!(pokedex.get_status(16), PokedexStatus::Seen);
+        assert_eq!(pokedex.get_status(19), PokedexStatus::Unknown);
+        assert_eq!((pokedex.seen_count(), pokedex.caught_count()), (1, 0));
+    }
+
+    #[test]
+    fn the_completion_without_species_is_zero() {
+        let mut pokedex = Pokedex::new();
+        assert_eq!(pokedex.completion_percentage(0), 0.0);
+        pokedex.mark_caught(1);
+        assert_eq!(pokedex.completion_percentage(0), 0.0);
+    }
+
+    #[test]
+    fn the_completion_of_a_partial_pokedex() {
+        let mut pokedex = Pokedex::new();
+        pokedex.mark_caught(1);
+        pokedex.mark_caught(4);
+        pokedex.mark_seen(7);
+        // Only the caught ones count
+        assert_eq!(pokedex.completion_percentage(8), 25.0);
+        assert_eq!(pokedex.completion_percentage(2), 100.0);
+        // Never past the 100%, even with more caught species than the given total
+        assert_eq!(pokedex.completion_percentage(1), 100.0);
+    }
+
+    #[test]
+    fn a_caught_specie_lost_from_the_seen_ones_still_counts_as_seen() {
+        let pokedex: Pokedex = serde_json::from_str(r#"{ "caught": [6] }"#).unwrap();
+        assert!(pokedex.is_seen(6));
+        assert_eq!(pokedex.seen_count(), 1);
+    }
+
+    #[test]
+    fn catching_an_unseen_specie_registers_both() {
+        let mut pokedex = Pokedex::new();
+        pokedex.mark_seen(16);
+        assert_eq!(pokedex.get_status(25), PokedexStatus::Unknown);
+        assert!(pokedex.mark_caught(25));
+        assert!(pokedex.mark_caught(16));
+        assert_eq!(pokedex.get_status(25), PokedexStatus::Caught);
+        assert_eq!(pokedex.get_status(16), PokedexStatus::Caught);
+        assert_eq!((pokedex.seen_count(), pokedex.caught_count()), (2, 2));
+    }
+
+    #[test]
+    fn the_pokedex_is_saved_with_the_player_data() {
+        let mut player_data = PlayerData::new();
+        player_data.get_pokedex_mut().mark_seen(16);
+        player_data.get_pokedex_mut().mark_caught(25);
+
+        let mut save = serde_json::to_value(&player_data).unwrap();
+        let loaded: PlayerData = serde_json::from_value(save.clone()).unwrap();
+        assert_eq!(loaded.get_pokedex(), player_data.get_pokedex());
+
+        // The saves from before the Pokédex start with an empty one
+        save.as_object_mut().unwrap().remove("pokedex");
+        let loaded: PlayerData = serde_json::from_value(save).unwrap();
+        assert_eq!(loaded.get_pokedex(), &Pokedex::new());
+    }
+}
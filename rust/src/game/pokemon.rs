@@ -1,10 +1,34 @@
 use gdnative::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::nature::{Nature, Stat};
+use super::species::{BaseStats, SpeciesData};
+use super::battle::status_condition::StatusCondition;
+use super::code_abstractions::random::GameRng;
+
+/// No Pokémon can grow over this level
+pub const MAX_LEVEL: u8 = 100;
+/// The most moves that a Pokémon can know at the same time
+pub const MAX_MOVES: usize = 4;
+/// The highest individual value that a Pokémon can have on a stat
+pub const MAX_IV: u32 = 31;
+/// The longest nickname that the player can give to a Pokémon
+pub const MAX_NICKNAME_LENGTH: usize = 10;
+// Besides the letters, the digits and the spaces, only these symbols are allowed on a nickname
+const NICKNAME_SYMBOLS: &str = ".,-'!?♂♀";
+/// The most effort values that a Pokémon can earn on a single stat
+pub const MAX_EV_PER_STAT: u32 = 252;
+/// The most effort values that a Pokémon can earn, adding the ones of every stat
+pub const MAX_TOTAL_EVS: u32 = 510;
 
 #[derive(NativeClass)]
 #[inherit(Node2D)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// The base class for a Pokemon data representation and data manipulation
+///
+/// Represents a concrete Pokémon (not a specie), like the ones that the player has on his party.
 pub struct Pokemon {
+    // The id of the Pokémon Specie
     #[property]
     pub id: i32,
     #[property]
@@ -18,30 +42,679 @@ pub struct Pokemon {
     #[property(default = 40.0)]
     pub tall: f64,
     // description: String,
+
+    // The name given by the player. If it's empty, the Pokémon just uses his specie name
+    #[serde(default)]
+    nickname: String,
+    level: u8,
+    // The total experience accumulated by the Pokémon
+    #[serde(default)]
+    experience: u32,
+    current_hp: u32,
+    max_hp: u32,
+    // Poison, burn, paralysis... The condition stays until it gets cured
+    #[serde(default)]
+    status: StatusCondition,
+    // The item that the Pokémon carries, if any
+    #[serde(default)]
+    held_item: Option<String>,
+    // The rare ones with other colours. The sprites use it to pick the alternate palette
+    #[serde(default)]
+    shiny: bool,
+    // Raises one stat and lowers another one
+    #[serde(default)]
+    nature: Nature,
+    // The individual values, from 0 to 31 on every stat. Two Pokémon of the same specie and level can have different
+    // stats because of them
+    #[serde(default)]
+    ivs: StatSpread,
+    // The effort values, earned by defeating other Pokémon. They raise the stats a little, up to 252 per stat
+    // and 510 in total
+    #[serde(default)]
+    evs: StatSpread,
+    stats: PokemonStats,
+    moves: Vec<PokemonMove>,
 }
 
 #[gdnative::methods]
 impl Pokemon {
-    pub fn new(_owner: &Node2D) -> Self { 
-            Self { 
-                id: 0, 
+    pub fn new(_owner: &Node2D) -> Self {
+            Self {
+                id: 0,
                 name: "".to_string(),
                 type1: "".to_string(),
                 type2: "".to_string(),
-                height: 0.0, 
-                tall: 0.0, 
+                height: 0.0,
+                tall: 0.0,
                 // description
-                } 
+                nickname: "".to_string(),
+                level: 1,
+                experience: 0,
+                current_hp: 0,
+                max_hp: 0,
+                status: StatusCondition::default(),
+                held_item: None,
+                shiny: false,
+                nature: Nature::default(),
+                ivs: StatSpread::default(),
+                evs: StatSpread::default(),
+                stats: PokemonStats::default(),
+                moves: Vec::new(),
+                }
             }
 
-    pub fn new_pokemon(id: i32, name: String, type1: String, type2: String, height: f64, tall: f64) -> Self {
+    pub fn new_pokemon(id: i32, name: String, type1: String, type2: String, height: f64, tall: f64,
+        level: u8, max_hp: u32, stats: PokemonStats, moves: Vec<PokemonMove>
+        ) -> Self {
         Self {
             id: id,
             name: name,
             type1: type1,
             type2: type2,
             height: height,
-            tall: tall
+            tall: tall,
+            nickname: "".to_string(),
+            level: level,
+            experience: 0,
+            current_hp: max_hp,
+            max_hp: max_hp,
+            status: StatusCondition::default(),
+            held_item: None,
+            shiny: false,
+            nature: Nature::default(),
+            ivs: StatSpread::default(),
+            evs: StatSpread::default(),
+            stats: stats,
+            moves: moves,
+        }
+    }
+
+    /// Creates a new Pokémon of the given specie, with the stats and the experience that corresponds to his level
+    pub fn from_specie(specie: &SpeciesData, level: u8, moves: Vec<PokemonMove>) -> Self {
+        let level = level.max(1).min(MAX_LEVEL);
+        let mut pokemon = Self::new_pokemon(specie.id, specie.name.to_owned(), specie.type1().to_string(),
+            specie.type2().to_string(), specie.height, specie.tall, level, 0, PokemonStats::default(), moves);
+
+        pokemon.experience = specie.get_growth_rate().experience_for_level(level);
+        pokemon.recalculate_stats(specie.get_base_stats());
+        pokemon
+    }
+
+    /// Adds experience to the Pokémon, leveling it up every time that crosses the threshold of his specie growth rate.
+    ///
+    /// Returns the levels that the Pokémon reached (could be more than one with a big amount of experience), so the UI
+    /// can show a message for every one.
+    pub fn award_experience(&mut self, experience: u32, specie: &SpeciesData) -> Vec<u8> {
+        let mut levels_gained: Vec<u8> = Vec::new();
+        if self.level >= MAX_LEVEL {
+            return levels_gained;
+        }
+
+        self.experience = self.experience.saturating_add(experience);
+
+        let growth_rate = specie.get_growth_rate();
+        while self.level < MAX_LEVEL && self.experience >= growth_rate.experience_for_level(self.level + 1) {
+            self.level += 1;
+            levels_gained.push(self.level);
+        }
+
+        if !levels_gained.is_empty() {
+            self.recalculate_stats(specie.get_base_stats());
+        }
+        levels_gained
+    }
+
+    /// Turns the Pokémon into his evolved specie. The nickname, the moves and the proportion of HP that
+    /// the Pokémon had are kept
+    pub fn evolve_into(&mut self, specie: &SpeciesData) {
+        self.id = specie.id;
+        self.name = specie.name.to_owned();
+        self.type1 = specie.type1().to_string();
+        self.type2 = specie.type2().to_string();
+        self.height = specie.height;
+        self.tall = specie.tall;
+        self.recalculate_stats(specie.get_base_stats());
+    }
+
+    /// Calculates again the stats of the Pokémon for his current level.
+    ///
+    /// The current HP grows in the same proportion that the max HP does, so a Pokémon with half of his health
+    /// keeps having half of his health after level up.
+    pub fn recalculate_stats(&mut self, base_stats: &BaseStats) {
+        let new_max_hp = calculate_hp(base_stats.hp, self.level, self.ivs.hp, self.evs.hp);
+
+        self.current_hp = if self.max_hp == 0 {
+            new_max_hp
+        } else {
+            self.current_hp * new_max_hp / self.max_hp
+        };
+        self.max_hp = new_max_hp;
+
+        let (level, nature) = (self.level, self.nature);
+        let (ivs, evs) = (&self.ivs, &self.evs);
+        let stat = |base_stat: u32, stat: Stat|
+            calculate_stat(base_stat, level, ivs.get(stat), evs.get(stat), nature.stat_multiplier(stat));
+        self.stats = PokemonStats::new(
+            stat(base_stats.attack, Stat::Attack),
+            stat(base_stats.defense, Stat::Defense),
+            stat(base_stats.special_attack, Stat::SpecialAttack),
+            stat(base_stats.special_defense, Stat::SpecialDefense),
+            stat(base_stats.speed, Stat::Speed),
+        );
+    }
+
+    pub fn get_experience(&self) -> u32 {
+        self.experience
+    }
+
+    /// Returns the name that should be showed on screen, the nickname if the player gave one
+    pub fn get_display_name(&self) -> &str {
+        if self.nickname.is_empty() { &self.name } else { &self.nickname }
+    }
+
+    pub fn get_nickname(&self) -> &str {
+        &self.nickname
+    }
+
+    pub fn set_nickname(&mut self, nickname: &str) {
+        self.nickname = nickname.to_string();
+    }
+
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn get_current_hp(&self) -> u32 {
+        self.current_hp
+    }
+
+    /// Sets the current HP of the Pokémon, never going over his max HP
+    pub fn set_current_hp(&mut self, current_hp: u32) {
+        self.current_hp = current_hp.min(self.max_hp);
+    }
+
+    pub fn get_max_hp(&self) -> u32 {
+        self.max_hp
+    }
+
+    pub fn get_stats(&self) -> &PokemonStats {
+        &self.stats
+    }
+
+    pub fn get_held_item(&self) -> Option<&str> {
+        self.held_item.as_deref()
+    }
+
+    pub fn set_held_item(&mut self, held_item: Option<String>) {
+        self.held_item = held_item;
+    }
+
+    pub fn is_shiny(&self) -> bool {
+        self.shiny
+    }
+
+    pub fn set_shiny(&mut self, shiny: bool) {
+        self.shiny = shiny;
+    }
+
+    pub fn get_nature(&self) -> Nature {
+        self.nature
+    }
+
+    pub fn get_ivs(&self) -> &StatSpread {
+        &self.ivs
+    }
+
+    pub fn get_evs(&self) -> &StatSpread {
+        &self.evs
+    }
+
+    /// Gives random individual values and a random nature to the Pokémon, like the ones found on the wild
+    pub fn randomize_ivs_and_nature<R: GameRng>(&mut self, rng: &mut R, specie: &SpeciesData) {
+        let mut roll_iv = || rng.next_in_range(0, MAX_IV + 1);
+        self.ivs = StatSpread::new(roll_iv(), roll_iv(), roll_iv(), roll_iv(), roll_iv(), roll_iv());
+        self.nature = Nature::ALL[rng.next_in_range(0, Nature::ALL.len() as u32) as usize];
+        self.recalculate_stats(specie.get_base_stats());
+    }
+
+    /// Adds the effort values earned by defeating a Pokémon of the given specie, and updates the stats.
+    ///
+    /// No stat goes over 252 EVs, and once the Pokémon reaches 510 EVs in total it doesn't earn any more.
+    /// Returns the EVs that the Pokémon really earned
+    pub fn award_evs(&mut self, gains: &StatSpread, specie: &SpeciesData) -> StatSpread {
+        let mut evs = self.evs.to_array();
+        let mut total = self.evs.total();
+        let mut earned = [0; 6];
+        for (index, gain) in gains.to_array().iter().enumerate() {
+            let gain = (*gain)
+                .min(MAX_EV_PER_STAT.saturating_sub(evs[index]))
+                .min(MAX_TOTAL_EVS.saturating_sub(total));
+            evs[index] += gain;
+            total += gain;
+            earned[index] = gain;
+        }
+        self.evs = StatSpread::from_array(evs);
+        let earned = StatSpread::from_array(earned);
+        if earned.total() > 0 {
+            self.recalculate_stats(specie.get_base_stats());
+        }
+        earned
+    }
+
+    pub fn get_status(&self) -> StatusCondition {
+        self.status
+    }
+
+    /// Sets a new status condition. A fainted Pokémon can't get any status
+    pub fn set_status(&mut self, status: StatusCondition) {
+        if !self.is_fainted() {
+            self.status = status;
+        }
+    }
+
+    pub fn cure_status(&mut self) {
+        self.status = StatusCondition::Healthy;
+    }
+
+    /// The speed that the Pokémon really has on battle, reduced by the paralysis
+    pub fn get_effective_speed(&self) -> u32 {
+        (self.stats.speed as f64 * self.status.speed_modifier()) as u32
+    }
+
+    /// Applies the damage of the status condition at the end of the battle turn.
+    ///
+    /// Returns the HP that the Pokémon lost. A fainted Pokémon loses his status condition
+    pub fn apply_end_of_turn_status(&mut self) -> u32 {
+        let damage = self.status.end_of_turn_damage(self.max_hp).min(self.current_hp);
+        self.current_hp -= damage;
+        if self.is_fainted() {
+            self.cure_status();
+        }
+        damage
+    }
+
+    /// Poison keeps hurting the Pokémon while the player walks on the overworld. Outside the battles the poison can't
+    /// knock out the Pokémon: when it has just 1 HP left, the poison wears off.
+    ///
+    /// Returns what the poison did to the Pokémon on this step
+    pub fn apply_overworld_poison(&mut self) -> OverworldPoisonResult {
+        if self.status != StatusCondition::Poisoned || self.is_fainted() {
+            return OverworldPoisonResult::Unaffected;
+        }
+        self.current_hp = self.current_hp.saturating_sub(1).max(1);
+        if self.current_hp == 1 {
+            self.cure_status();
+            return OverworldPoisonResult::Survived;
+        }
+        OverworldPoisonResult::Hurt
+    }
+
+    pub fn get_moves(&self) -> &Vec<PokemonMove> {
+        &self.moves
+    }
+
+    pub fn knows_move(&self, move_name: &str) -> bool {
+        self.moves.iter().any(|pokemon_move| pokemon_move.name == move_name)
+    }
+
+    /// Teaches a new move to the Pokémon, if it doesn't know it yet and has a free slot for it
+    pub fn learn_move(&mut self, new_move: PokemonMove) -> LearnMoveResult {
+        if self.knows_move(&new_move.name) {
+            return LearnMoveResult::AlreadyKnown;
+        }
+        if self.moves.len() >= MAX_MOVES {
+            return LearnMoveResult::NoFreeSlot;
+        }
+        self.moves.push(new_move);
+        LearnMoveResult::Learned
+    }
+
+    /// Forgets the move of the given slot to learn the new one on his place.
+    ///
+    /// Returns the forgotten move, or None if there's no move on that slot
+    pub fn replace_move(&mut self, move_slot: usize, new_move: PokemonMove) -> Option<PokemonMove> {
+        let known_move = self.moves.get_mut(move_slot)?;
+        Some(std::mem::replace(known_move, new_move))
+    }
+
+    /// If any move has PP left. Otherwise, the Pokémon can only struggle
+    pub fn has_usable_moves(&self) -> bool {
+        self.moves.iter().any(|pokemon_move| pokemon_move.pp > 0)
+    }
+
+    /// Spends one PP of the move of the given slot. Returns false if the move doesn't exist or has no PP left
+    pub fn use_move(&mut self, move_slot: usize) -> bool {
+        match self.moves.get_mut(move_slot) {
+            Some(pokemon_move) if pokemon_move.pp > 0 => {
+                pokemon_move.pp -= 1;
+                true
+            },
+            _ => false
         }
     }
-}
\ No newline at end of file
+
+    /// Restores the given PP to every move, never going over their max PP. Returns false if every move was already full
+    pub fn restore_pp(&mut self, pp: u32) -> bool {
+        let mut restored = false;
+        for pokemon_move in self.moves.iter_mut().filter(|pokemon_move| pokemon_move.pp < pokemon_move.max_pp) {
+            pokemon_move.pp = pokemon_move.pp.saturating_add(pp).min(pokemon_move.max_pp);
+            restored = true;
+        }
+        restored
+    }
+
+    pub fn is_fainted(&self) -> bool {
+        self.current_hp == 0
+    }
+
+    /// Fully restores the HP of the Pokémon, and cures his status condition
+    pub fn heal(&mut self) {
+        self.current_hp = self.max_hp;
+        self.cure_status();
+        self.moves.iter_mut().for_each(|pokemon_move| pokemon_move.pp = pokemon_move.max_pp);
+    }
+}
+
+#[cfg(test)]
+impl Pokemon {
+    /// The plain Pokémon that the tests of the other modules use: a level 5 of Normal type, with 20 HP
+    /// and 10 on every stat
+    pub fn for_tests(id: i32, name: &str, moves: Vec<PokemonMove>) -> Self {
+        Self::new_pokemon(id, name.to_string(), "Normal".to_string(), "".to_string(), 1.0, 40.0, 5, 20,
+            PokemonStats::new(10, 10, 10, 10, 10), moves)
+    }
+}
+
+/// What the poison did to a Pokémon on a step of the overworld
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverworldPoisonResult {
+    // It's not poisoned, or it's already fainted
+    Unaffected,
+    Hurt,
+    // The poison left it with 1 HP, where it would have fainted, so the poison wore off
+    Survived
+}
+
+/// What happened when a Pokémon tried to learn a new move
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LearnMoveResult {
+    Learned,
+    AlreadyKnown,
+    // The Pokémon already knows four moves, so it has to forget one of them first
+    NoFreeSlot
+}
+
+/// The reasons why a nickname isn't accepted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NicknameError {
+    TooLong,
+    InvalidCharacter(char)
+}
+
+impl NicknameError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> String {
+        match self {
+            NicknameError::TooLong => format!("El mote no puede tener más de {} letras.", MAX_NICKNAME_LENGTH),
+            NicknameError::InvalidCharacter(character) => format!("No se puede usar «{}» en un mote.", character)
+        }
+    }
+}
+
+/// Checks the nickname written by the player, and returns it without the spaces of the ends.
+///
+/// A blank nickname it's valid: the Pokémon just keeps his specie name
+pub fn validate_nickname(nickname: &str) -> Result<String, NicknameError> {
+    let nickname = nickname.trim();
+    if nickname.chars().count() > MAX_NICKNAME_LENGTH {
+        return Err(NicknameError::TooLong);
+    }
+    match nickname.chars().find(|character|
+        !(character.is_alphanumeric() || *character == ' ' || NICKNAME_SYMBOLS.contains(*character))
+    ) {
+        Some(character) => Err(NicknameError::InvalidCharacter(character)),
+        None => Ok(nickname.to_string())
+    }
+}
+
+/// The standard formula of the HP of a Pokémon, given his base HP, his level, and his individual and effort values
+pub fn calculate_hp(base_hp: u32, level: u8, iv: u32, ev: u32) -> u32 {
+    (2 * base_hp + iv.min(MAX_IV) + ev / 4) * level as u32 / 100 + level as u32 + 10
+}
+
+/// The standard formula of any stat (except the HP) of a Pokémon, given the base stat, his level, his individual
+/// and effort values, and how his nature modifies the stat
+pub fn calculate_stat(base_stat: u32, level: u8, iv: u32, ev: u32, nature_multiplier: f64) -> u32 {
+    let stat = (2 * base_stat + iv.min(MAX_IV) + ev / 4) * level as u32 / 100 + 5;
+    // On percents, so the multiplier doesn't suffer the rounding errors of the floats
+    stat * (nature_multiplier * 100.0).round() as u32 / 100
+}
+
+/// A value for every stat of a Pokémon, like his individual values
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatSpread {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub special_attack: u32,
+    pub special_defense: u32,
+    pub speed: u32,
+}
+
+impl StatSpread {
+    pub fn new(hp: u32, attack: u32, defense: u32, special_attack: u32, special_defense: u32, speed: u32) -> Self {
+        Self { hp, attack, defense, special_attack, special_defense, speed }
+    }
+
+    /// The value of one of the stats (the HP aren't a `Stat`, so they're read directly)
+    pub fn get(&self, stat: Stat) -> u32 {
+        match stat {
+            Stat::Attack => self.attack,
+            Stat::Defense => self.defense,
+            Stat::SpecialAttack => self.special_attack,
+            Stat::SpecialDefense => self.special_defense,
+            Stat::Speed => self.speed,
+        }
+    }
+
+    /// The values of every stat added together
+    pub fn total(&self) -> u32 {
+        self.to_array().iter().sum()
+    }
+
+    /// The values on the order of the constructor: HP, attack, defense, special attack, special defense, speed
+    pub fn to_array(&self) -> [u32; 6] {
+        [self.hp, self.attack, self.defense, self.special_attack, self.special_defense, self.speed]
+    }
+
+    pub fn from_array(values: [u32; 6]) -> Self {
+        Self::new(values[0], values[1], values[2], values[3], values[4], values[5])
+    }
+}
+
+/// The battle stats of a Pokémon, except the HP that are tracked directly by the `Pokemon`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PokemonStats {
+    pub attack: u32,
+    pub defense: u32,
+    pub special_attack: u32,
+    pub special_defense: u32,
+    pub speed: u32,
+}
+
+impl PokemonStats {
+    pub fn new(attack: u32, defense: u32, special_attack: u32, special_defense: u32, speed: u32) -> Self {
+        Self { attack, defense, special_attack, special_defense, speed }
+    }
+}
+
+/// A move that a Pokémon knows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PokemonMove {
+    pub name: String,
+    pub move_type: String,
+    pub power: u32,
+    // How many times the move can be used before resting on a Pokémon Center
+    #[serde(default)]
+    pub pp: u32,
+    #[serde(default)]
+    pub max_pp: u32,
+}
+
+impl PokemonMove {
+    pub fn new(name: &str, move_type: &str, power: u32, max_pp: u32) -> Self {
+        Self { name: name.to_string(), move_type: move_type.to_string(), power, pp: max_pp, max_pp }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Medium Fast specie, so the level `n` needs `n³` experience points
+    fn specie() -> SpeciesData {
+        serde_json::from_str(r#"{
+            "id": 1, "name": "Bulbasaur", "types": ["Planta", "Veneno"],
+            "base_stats": {"hp": 45, "attack": 49, "defense": 49, "special_attack": 65, "special_defense": 65, "speed": 45},
+            "growth_rate": "MediumFast", "catch_rate": 45
+        }"#).unwrap()
+    }
+
+    #[test]
+    fn a_big_amount_of_experience_gives_many_levels() {
+        let specie = specie();
+        let mut pokemon = Pokemon::from_specie(&specie, 5, Vec::new());
+        let max_hp = pokemon.get_max_hp();
+        assert_eq!(pokemon.get_experience(), 125);
+
+        assert_eq!(pokemon.award_experience(875, &specie), vec![6, 7, 8, 9, 10]);
+        assert_eq!(pokemon.get_level(), 10);
+        assert_eq!(pokemon.get_experience(), 1000);
+        assert!(pokemon.get_max_hp() > max_hp);
+        assert_eq!(pokemon.get_current_hp(), pokemon.get_max_hp());
+    }
+
+    #[test]
+    fn experience_under_the_next_level_keeps_the_level() {
+        let specie = specie();
+        let mut pokemon = Pokemon::from_specie(&specie, 5, Vec::new());
+        assert!(pokemon.award_experience(90, &specie).is_empty());
+        assert_eq!(pokemon.get_level(), 5);
+        assert_eq!(pokemon.get_experience(), 215);
+    }
+
+    #[test]
+    fn the_levels_stop_at_the_max_level() {
+        let specie = specie();
+        let mut pokemon = Pokemon::from_specie(&specie, 98, Vec::new());
+        assert_eq!(pokemon.award_experience(u32::MAX, &specie), vec![99, MAX_LEVEL]);
+        assert!(pokemon.award_experience(1_000, &specie).is_empty());
+        assert_eq!(pokemon.get_level(), MAX_LEVEL);
+    }
+
+    #[test]
+    fn the_stat_formulas_follow_the_ivs_the_evs_and_the_nature() {
+        assert_eq!(calculate_hp(45, 50, 31, 252), 152);
+        assert_eq!(calculate_hp(45, 50, 0, 0), 105);
+        assert_eq!(calculate_stat(49, 50, 31, 252, 1.0), 101);
+        assert_eq!(calculate_stat(49, 50, 31, 252, 1.1), 111);
+        assert_eq!(calculate_stat(49, 50, 31, 252, 0.9), 90);
+        assert_eq!(calculate_stat(100, 100, 31, 252, 1.1), 328);
+        // The individual values never count over the max
+        assert_eq!(calculate_stat(49, 50, 99, 0, 1.0), calculate_stat(49, 50, MAX_IV, 0, 1.0));
+    }
+
+    #[test]
+    fn the_nature_raises_and_lowers_the_stats_of_the_pokemon() {
+        let specie = specie();
+        let mut pokemon = Pokemon::from_specie(&specie, 50, Vec::new());
+        pokemon.ivs = StatSpread::new(31, 31, 31, 31, 31, 31);
+        pokemon.recalculate_stats(specie.get_base_stats());
+        let neutral = pokemon.get_stats().clone();
+
+        pokemon.nature = Nature::Adamant;
+        pokemon.recalculate_stats(specie.get_base_stats());
+        assert_eq!(pokemon.get_max_hp(), 120);
+        assert_eq!(pokemon.get_stats().attack, 75);
+        assert!(pokemon.get_stats().attack > neutral.attack);
+        assert!(pokemon.get_stats().special_attack < neutral.special_attack);
+        assert_eq!(pokemon.get_stats().defense, neutral.defense);
+    }
+
+    #[test]
+    fn no_stat_earns_more_than_252_evs() {
+        let specie = specie();
+        let mut pokemon = Pokemon::from_specie(&specie, 5, Vec::new());
+        let attack = pokemon.get_stats().attack;
+
+        let earned = pokemon.award_evs(&StatSpread::new(0, 300, 0, 0, 0, 0), &specie);
+        assert_eq!(earned, StatSpread::new(0, MAX_EV_PER_STAT, 0, 0, 0, 0));
+        assert!(pokemon.get_stats().attack > attack);
+
+        let earned = pokemon.award_evs(&StatSpread::new(0, 4, 0, 0, 0, 0), &specie);
+        assert_eq!(earned.total(), 0);
+        assert_eq!(pokemon.get_evs().attack, MAX_EV_PER_STAT);
+    }
+
+    #[test]
+    fn no_pokemon_earns_more_than_510_evs() {
+        let specie = specie();
+        let mut pokemon = Pokemon::from_specie(&specie, 5, Vec::new());
+        pokemon.award_evs(&StatSpread::new(0, 252, 252, 0, 0, 0), &specie);
+
+        let earned = pokemon.award_evs(&StatSpread::new(0, 0, 0, 0, 3, 10), &specie);
+        assert_eq!(earned, StatSpread::new(0, 0, 0, 0, 3, 3));
+        assert_eq!(pokemon.get_evs().total(), MAX_TOTAL_EVS);
+        assert_eq!(pokemon.award_evs(&StatSpread::new(1, 1, 1, 1, 1, 1), &specie).total(), 0);
+    }
+
+    #[test]
+    fn the_summary_stats_match_the_ones_of_the_main_games() {
+        // The Garchomp of the stat formulas example of the main games wiki, with his expected stats
+        let garchomp: SpeciesData = serde_json::from_str(r#"{
+            "id": 445, "name": "Garchomp", "types": ["Dragón", "Tierra"],
+            "base_stats": {"hp": 108, "attack": 130, "defense": 95, "special_attack": 80, "special_defense": 85, "speed": 102},
+            "growth_rate": "Slow", "catch_rate": 45
+        }"#).unwrap();
+        let mut pokemon = Pokemon::from_specie(&garchomp, 78, Vec::new());
+        pokemon.nature = Nature::Adamant;
+        pokemon.ivs = StatSpread::new(24, 12, 30, 16, 23, 5);
+        pokemon.evs = StatSpread::new(74, 190, 91, 48, 84, 23);
+        pokemon.recalculate_stats(garchomp.get_base_stats());
+
+        assert_eq!(pokemon.get_max_hp(), 289);
+        assert_eq!(pokemon.get_stats(), &PokemonStats::new(278, 193, 135, 171, 171));
+    }
+
+    fn known_moves(pokemon: &Pokemon) -> Vec<&str> {
+        pokemon.get_moves().iter().map(|pokemon_move| pokemon_move.name.as_str()).collect()
+    }
+
+    #[test]
+    fn a_pokemon_learns_moves_until_his_slots_are_full() {
+        let mut pokemon = Pokemon::from_specie(&specie(), 10, vec![PokemonMove::new("Placaje", "Normal", 40, 35)]);
+        assert_eq!(pokemon.learn_move(PokemonMove::new("Placaje", "Normal", 40, 35)), LearnMoveResult::AlreadyKnown);
+        assert_eq!(pokemon.learn_move(PokemonMove::new("Gruñido", "Normal", 0, 40)), LearnMoveResult::Learned);
+        assert_eq!(pokemon.learn_move(PokemonMove::new("Látigo Cepa", "Planta", 45, 25)), LearnMoveResult::Learned);
+        assert_eq!(pokemon.learn_move(PokemonMove::new("Drenadoras", "Planta", 0, 10)), LearnMoveResult::Learned);
+        assert_eq!(pokemon.get_moves().len(), MAX_MOVES);
+
+        assert_eq!(pokemon.learn_move(PokemonMove::new("Hoja Afilada", "Planta", 55, 25)), LearnMoveResult::NoFreeSlot);
+        assert_eq!(known_moves(&pokemon), vec!["Placaje", "Gruñido", "Látigo Cepa", "Drenadoras"]);
+    }
+
+    #[test]
+    fn replacing_a_move_returns_the_forgotten_one() {
+        let mut pokemon = Pokemon::from_specie(&specie(), 10, vec![
+            PokemonMove::new("Placaje", "Normal", 40, 35),
+            PokemonMove::new("Gruñido", "Normal", 0, 40),
+        ]);
+        let forgotten = pokemon.replace_move(0, PokemonMove::new("Látigo Cepa", "Planta", 45, 25));
+        assert_eq!(forgotten, Some(PokemonMove::new("Placaje", "Normal", 40, 35)));
+        assert_eq!(known_moves(&pokemon), vec!["Látigo Cepa", "Gruñido"]);
+
+        // A free slot it's not a move to forget
+        assert_eq!(pokemon.replace_move(2, PokemonMove::new("Drenadoras", "Planta", 0, 10)), None);
+        assert_eq!(known_moves(&pokemon), vec!["Látigo Cepa", "Gruñido"]);
+    }
+}
@@ -1,7 +1,7 @@
 use gdnative::prelude::*;
 
-use super::pokemon_specie::PokemonSpecie;
-use crate::utils::all_pokemon_species::all_pokemon_species_data;
+use super::species::SpeciesData;
+use crate::utils::utils;
 
 /// Acts as a wrapper over some type T, representing a DB Row of elements of that T type
 pub struct DBRow<'a, T> {
@@ -78,11 +78,11 @@ impl PokemonDB {
 
     /// Method that retrieves all the data of all the Pokémon Species availiable in the game.
     /// 
-    /// Gets the data from an external (to this crate) function, where all the values are handcoded (say tnx for the work... bit..)
-    /// which return a Vec<PokemonSpecie>. For each one, creates a new node with the instance data.
+    /// Gets the data from the species JSON config, and for every specie creates a new node with the instance data.
     fn populate_pokemon_table(&mut self, owner: &Node2D) {
-        for pokemon_specie in all_pokemon_species_data().iter() {
-            let pokemon_as_row = DBRow::<PokemonSpecie>::new(
+        let species_table = utils::retrieve_species_table();
+        for pokemon_specie in species_table.get_all_species() {
+            let pokemon_as_row = DBRow::<SpeciesData>::new(
                 pokemon_specie
                 );
             self.create_pokemon_row(owner, pokemon_as_row)
@@ -90,7 +90,7 @@ impl PokemonDB {
     }
 
     /// Creates a new NODE2D representing a row inside a DB table
-    fn create_pokemon_row(&self, _owner: &Node2D, row: DBRow<PokemonSpecie>) {
+    fn create_pokemon_row(&self, _owner: &Node2D, row: DBRow<SpeciesData>) {
         // Creates a new node to hold the PokémonSpecies instances data
         let new_row = PokemonDB::get_pokemon_class_as_resource();
         
@@ -4,6 +4,8 @@ use gdnative::prelude::*;
 #[inherit(Node2D)]
 #[derive(Debug)]
 /// The base class for a Pokemon data representation and data manipulation, holded as a Godot Node using another node as parent and as a DB simulator
+///
+/// The numbers of every specie (base stats, catch rate...) live on the species table, this node only shows his data on the scene tree
 pub struct PokemonSpecie {
     #[property]
     pub id: i32,
@@ -17,7 +19,6 @@ pub struct PokemonSpecie {
     pub height: f64,
     #[property(default = 40.0)]
     pub tall: f64,
-    pokedex_description: String,
 }
 
 #[gdnative::methods]
@@ -31,22 +32,6 @@ impl PokemonSpecie {
                 type2: "".to_string(),
                 height: 0.0, 
                 tall: 0.0, 
-                pokedex_description: "".to_string(),
                 } 
             }
-    
-    // The constructor that given the needed data, creates a new Pokémon Specie
-    pub fn new_pokemon(id: i32, name: &str, type1: &str, type2: &str, height: f64, tall: f64,
-        pokedex_description: &str
-        ) -> Self {
-        Self {
-            id: id,
-            name: name.to_string(),
-            type1: type1.to_string(),
-            type2: type2.to_string(),
-            height: height,
-            tall: tall,
-            pokedex_description: pokedex_description.to_string(),
-        }
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The kinds of events that move the objectives of the quests forward. Every objective listens to one event
+/// key, built as `kind:target`, like `talk:cutscene_welcome_to_teo` or `catch:25`
+pub mod quest_events {
+    // The player read a dialogue. The target it's the dialogue id
+    pub const TALK: &str = "talk";
+    // The player caught a Pokémon. The target it's the species id
+    pub const CATCH: &str = "catch";
+}
+
+/// Builds the key of the event of the given kind, for the given target
+pub fn quest_event_key(kind: &str, target: &str) -> String {
+    format!("{}:{}", kind, target)
+}
+
+/// One thing that the player has to do to finish a quest, and how many times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestObjective {
+    pub id: String,
+    pub description: String,
+    // The event that counts for this objective, like `catch:25`
+    pub event: String,
+    #[serde(default = "single_time")]
+    pub goal: u32,
+}
+
+fn single_time() -> u32 { 1 }
+
+/// A quest, as it's written on the quests JSON config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub objectives: Vec<QuestObjective>,
+}
+
+impl QuestDefinition {
+    pub fn get_objective(&self, objective_id: &str) -> Option<&QuestObjective> {
+        self.objectives.iter().find(|objective| objective.id == objective_id)
+    }
+}
+
+/// Every quest of the game, by quest id, loaded from the quests JSON config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuestTable {
+    #[serde(default)]
+    quests: HashMap<String, QuestDefinition>,
+}
+
+impl QuestTable {
+    pub fn get_quest(&self, quest_id: &str) -> Option<&QuestDefinition> {
+        self.quests.get(quest_id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum QuestState {
+    NotStarted,
+    Active,
+    Completed
+}
+
+impl Default for QuestState {
+    fn default() -> Self { QuestState::NotStarted }
+}
+
+impl QuestState {
+    pub fn to_state_str(&self) -> &'static str {
+        match self {
+            QuestState::NotStarted => "NotStarted",
+            QuestState::Active => "Active",
+            QuestState::Completed => "Completed"
+        }
+    }
+}
+
+/// The reasons why a quest can't be started or moved forward
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuestError {
+    UnknownQuest,
+    UnknownObjective,
+    AlreadyStarted,
+    NotActive
+}
+
+impl QuestError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            QuestError::UnknownQuest => "Esa misión no existe.",
+            QuestError::UnknownObjective => "Esa misión no tiene ese objetivo.",
+            QuestError::AlreadyStarted => "Ya has empezado esa misión.",
+            QuestError::NotActive => "Esa misión no está en curso."
+        }
+    }
+}
+
+/// The state of a quest for the player, and how far he got on every objective
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuestProgress {
+    state: QuestState,
+    objectives: HashMap<String, u32>,
+}
+
+impl QuestProgress {
+    pub fn get_state(&self) -> QuestState {
+        self.state
+    }
+
+    pub fn get_objective_progress(&self, objective_id: &str) -> u32 {
+        *self.objectives.get(objective_id).unwrap_or(&0)
+    }
+
+    fn is_done(&self, definition: &QuestDefinition) -> bool {
+        definition.objectives.iter().all(|objective| self.get_objective_progress(&objective.id) >= objective.goal)
+    }
+}
+
+/// The quests of the player. A quest that it's not here it's just not started yet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuestLog {
+    quests: HashMap<String, QuestProgress>,
+}
+
+impl QuestLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_state(&self, quest_id: &str) -> QuestState {
+        self.quests.get(quest_id).map(QuestProgress::get_state).unwrap_or_default()
+    }
+
+    pub fn get_progress(&self, quest_id: &str) -> Option<&QuestProgress> {
+        self.quests.get(quest_id)
+    }
+
+    /// The ids of the quests on the given state, sorted so the menus always list them on the same order
+    pub fn get_quest_ids(&self, state: QuestState) -> Vec<String> {
+        let mut quest_ids: Vec<String> = self.quests.iter()
+            .filter(|(_, progress)| progress.state == state)
+            .map(|(quest_id, _)| quest_id.to_owned())
+            .collect();
+        quest_ids.sort();
+        quest_ids
+    }
+
+    pub fn start_quest(&mut self, quest_id: &str) -> Result<(), QuestError> {
+        if self.get_state(quest_id) != QuestState::NotStarted {
+            return Err(QuestError::AlreadyStarted);
+        }
+        self.quests.insert(quest_id.to_string(), QuestProgress { state: QuestState::Active, objectives: HashMap::new() });
+        Ok(())
+    }
+
+    /// Counts the given times on an objective of an active quest, never past his goal. Once every objective
+    /// reaches his goal, the quest gets completed.
+    ///
+    /// Returns if the quest got completed right now
+    pub fn advance_objective(&mut self, quest_id: &str, definition: &QuestDefinition, objective_id: &str, amount: u32)
+        -> Result<bool, QuestError>
+    {
+        let goal = definition.get_objective(objective_id).ok_or(QuestError::UnknownObjective)?.goal;
+        let progress = match self.quests.get_mut(quest_id) {
+            Some(progress) if progress.state == QuestState::Active => progress,
+            _ => return Err(QuestError::NotActive)
+        };
+
+        let count = progress.objectives.entry(objective_id.to_string()).or_insert(0);
+        *count = count.saturating_add(amount).min(goal);
+
+        if progress.is_done(definition) {
+            progress.state = QuestState::Completed;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Finishes an active quest, even if some objective it's still missing (a quest completed by a cutscene...)
+    pub fn complete_quest(&mut self, quest_id: &str) -> Result<(), QuestError> {
+        match self.quests.get_mut(quest_id) {
+            Some(progress) if progress.state == QuestState::Active => {
+                progress.state = QuestState::Completed;
+                Ok(())
+            },
+            _ => Err(QuestError::NotActive)
+        }
+    }
+
+    /// Moves forward every objective of the active quests that listens to the given event.
+    ///
+    /// Returns the ids of the quests that got completed by it
+    pub fn record_event(&mut self, quest_table: &QuestTable, event: &str, amount: u32) -> Vec<String> {
+        let mut completed_quests = Vec::new();
+        for quest_id in self.get_quest_ids(QuestState::Active) {
+            let definition = match quest_table.get_quest(&quest_id) {
+                Some(definition) => definition,
+                None => continue
+            };
+            let objective_ids: Vec<String> = definition.objectives.iter()
+                .filter(|objective| objective.event == event)
+                .map(|objective| objective.id.to_owned())
+                .collect();
+            for objective_id in objective_ids {
+                if let Ok(true) = self.advance_objective(&quest_id, definition, &objective_id, amount) {
+                    completed_quests.push(quest_id.to_owned());
+                }
+            }
+        }
+        completed_quests
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::PlayerData;
+
+    const QUESTS: &str = r#"{ "quests": {
+        "pikachu_hunt": { "name": "Caza de Pikachus", "objectives": [
+            { "id": "talk_to_teo", "description": "Habla con Teo", "event": "talk:teo_quest" },
+            { "id": "catch_pikachu", "description": "Captura tres Pikachus", "event": "catch:25", "goal": 3 }
+        ] }
+    } }"#;
+
+    fn quest_table() -> QuestTable {
+        serde_json::from_str(QUESTS).unwrap()
+    }
+
+    #[test]
+    fn an_unknown_quest_cant_be_started() {
+        let mut player_data = PlayerData::new();
+        assert_eq!(player_data.start_quest(&quest_table(), "unknown"), Err(QuestError::UnknownQuest));
+        assert_eq!(player_data.get_quest_state("unknown"), QuestState::NotStarted);
+        assert!(player_data.get_quest_ids(QuestState::Active).is_empty());
+    }
+
+    #[test]
+    fn a_quest_only_starts_once() {
+        let quest_table = quest_table();
+        let mut player_data = PlayerData::new();
+        assert_eq!(player_data.start_quest(&quest_table, "pikachu_hunt"), Ok(()));
+        assert_eq!(player_data.get_quest_state("pikachu_hunt"), QuestState::Active);
+        assert_eq!(player_data.start_quest(&quest_table, "pikachu_hunt"), Err(QuestError::AlreadyStarted));
+    }
+
+    #[test]
+    fn the_objectives_only_move_on_active_quests() {
+        let quest_table = quest_table();
+        let mut player_data = PlayerData::new();
+        assert_eq!(player_data.advance_quest_objective(&quest_table, "pikachu_hunt", "talk_to_teo", 1),
+            Err(QuestError::NotActive));
+
+        player_data.start_quest(&quest_table, "pikachu_hunt").unwrap();
+        assert_eq!(player_data.advance_quest_objective(&quest_table, "pikachu_hunt", "unknown", 1),
+            Err(QuestError::UnknownObjective));
+        assert_eq!(player_data.advance_quest_objective(&quest_table, "pikachu_hunt", "catch_pikachu", 2), Ok(false));
+        // Never past the goal
+        assert_eq!(player_data.advance_quest_objective(&quest_table, "pikachu_hunt", "catch_pikachu", 5), Ok(false));
+        let progress = player_data.get_quest_progress("pikachu_hunt").unwrap();
+        assert_eq!(progress.get_objective_progress("catch_pikachu"), 3);
+        assert_eq!(progress.get_objective_progress("talk_to_teo"), 0);
+    }
+
+    #[test]
+    fn the_quest_completes_with_every_objective_done() {
+        let quest_table = quest_table();
+        let mut player_data = PlayerData::new();
+        player_data.start_quest(&quest_table, "pikachu_hunt").unwrap();
+
+        let catch_event = quest_event_key(quest_events::CATCH, "25");
+        assert!(player_data.record_quest_event(&quest_table, &catch_event, 3).is_empty());
+        assert!(player_data.record_quest_event(&quest_table, "catch:16", 1).is_empty());
+        let talk_event = quest_event_key(quest_events::TALK, "teo_quest");
+        assert_eq!(player_data.record_quest_event(&quest_table, &talk_event, 1), vec!["pikachu_hunt".to_string()]);
+
+        assert_eq!(player_data.get_quest_state("pikachu_hunt"), QuestState::Completed);
+        assert_eq!(player_data.get_quest_ids(QuestState::Completed), vec!["pikachu_hunt".to_string()]);
+        // A completed quest doesn't listen to the events anymore
+        assert!(player_data.record_quest_event(&quest_table, &talk_event, 1).is_empty());
+        assert_eq!(player_data.complete_quest("pikachu_hunt"), Err(QuestError::NotActive));
+    }
+
+    #[test]
+    fn a_quest_can_be_completed_before_his_objectives() {
+        let quest_table = quest_table();
+        let mut player_data = PlayerData::new();
+        assert_eq!(player_data.complete_quest("pikachu_hunt"), Err(QuestError::NotActive));
+        player_data.start_quest(&quest_table, "pikachu_hunt").unwrap();
+        assert_eq!(player_data.complete_quest("pikachu_hunt"), Ok(()));
+        assert_eq!(player_data.get_quest_state("pikachu_hunt"), QuestState::Completed);
+    }
+}
@@ -0,0 +1,133 @@
+use serde_json::{Map, Value};
+
+/// The version of the player data written by this build. Every time that the shape of the `PlayerData` changes in
+/// a way that the old saves can't be read, it goes up by one, with a new migration for the previous version
+pub const CURRENT_SAVE_VERSION: u32 = 2;
+
+// The saves from before the versions existed. They don't have any `version`
+const FIRST_SAVE_VERSION: u32 = 1;
+
+// Every migration upgrades the player data of the version on his index (plus the first one) to the next version
+const MIGRATIONS: [fn(&mut Map<String, Value>); (CURRENT_SAVE_VERSION - FIRST_SAVE_VERSION) as usize] = [
+    migrate_v1_to_v2,
+];
+
+/// The reasons why the player data of a save can't be upgraded
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveMigrationError {
+    // The save doesn't have any player data to upgrade
+    MissingPlayerData,
+    // The save comes from a newer build of the game, that this one can't know about
+    NewerVersion(u32),
+}
+
+/// The version of the given player data. The ones without it are the first version
+pub fn get_save_version(player_data: &Map<String, Value>) -> u32 {
+    player_data.get("version")
+        .and_then(Value::as_u64)
+        .map(|version| version as u32)
+        .unwrap_or(FIRST_SAVE_VERSION)
+}
+
+/// Upgrades the player data of a whole save (as it's stored, with the rest of the Game data) to the current
+/// shape, running one after the other every migration from his version. Once it's done, the save can be read as
+/// the current `PlayerData`.
+///
+/// Returns the version that the save had
+pub fn migrate_save(save: &mut Value) -> Result<u32, SaveMigrationError> {
+    let player_data = save.get_mut("player_data")
+        .and_then(Value::as_object_mut)
+        .ok_or(SaveMigrationError::MissingPlayerData)?;
+
+    let version = get_save_version(player_data);
+    if version > CURRENT_SAVE_VERSION {
+        return Err(SaveMigrationError::NewerVersion(version));
+    }
+
+    for migration in MIGRATIONS.iter().skip((version.max(FIRST_SAVE_VERSION) - FIRST_SAVE_VERSION) as usize) {
+        migration(player_data);
+    }
+    player_data.insert("version".to_string(), Value::from(CURRENT_SAVE_VERSION));
+    Ok(version)
+}
+
+/// The version 2 stores what the player was doing when the game got saved
+fn migrate_v1_to_v2(player_data: &mut Map<String, Value>) {
+    player_data.entry("player_status").or_insert_with(|| Value::from("Idle"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::game::game::Game;
+    use crate::game::player::{PlayerData, PlayerStatus};
+
+    // The `gamestate.json` shipped before the saves had a version, or the status of the player
+    const V1_SAVE: &str = r#"{
+      "player_data": {
+        "name": "",
+        "player_direction": "Upwards",
+        "player_position": {
+          "x": 1456.5333251953125,
+          "y": 717.5999755859375
+        }
+      },
+      "received_signals": 2,
+      "total_registered_signals": 2,
+      "number_of_process": 497,
+      "current_scene_type": "Outdoors",
+      "current_scene_path": "res://godot/Game/Map.tscn",
+      "game_external_data": {
+        "todays_date": "Lunes, 21-06-2021",
+        "todays_day_of_the_week": "Lunes",
+        "cities_weather_loaded": true,
+        "spring_backend_response_code": 200,
+        "todays_sunrise_time": "05:55:34",
+        "todays_sunset_time": "21:16:21",
+        "current_dn_cycle": "Day"
+      },
+      "next_api_call": "09:18:45",
+      "current_time": "00:00:00"
+    }"#;
+
+    #[test]
+    fn a_v1_save_gets_upgraded() {
+        let mut save: Value = serde_json::from_str(V1_SAVE).unwrap();
+        assert_eq!(migrate_save(&mut save), Ok(FIRST_SAVE_VERSION));
+        assert_eq!(save["player_data"]["version"], json!(CURRENT_SAVE_VERSION));
+        assert_eq!(save["player_data"]["player_status"], json!("Idle"));
+        // The rest of the save stays as it was
+        assert_eq!(save["player_data"]["player_direction"], json!("Upwards"));
+        assert_eq!(save["current_scene_path"], json!("res://godot/Game/Map.tscn"));
+
+        // And it's read as the current player data, inside of the whole Game
+        let player_data: PlayerData = serde_json::from_value(save["player_data"].clone()).unwrap();
+        assert_eq!(player_data.get_player_status(), &PlayerStatus::Idle);
+        assert!(player_data.get_party().is_empty());
+        let game: Game = serde_json::from_value(save).unwrap();
+        assert_eq!(game.get_player_data().get_player_status(), &PlayerStatus::Idle);
+    }
+
+    #[test]
+    fn a_current_save_is_left_untouched() {
+        let original = json!({ "player_data": { "version": CURRENT_SAVE_VERSION, "player_status": "Walking" } });
+        let mut save = original.clone();
+        assert_eq!(migrate_save(&mut save), Ok(CURRENT_SAVE_VERSION));
+        assert_eq!(save, original);
+    }
+
+    #[test]
+    fn a_save_from_a_newer_build_is_rejected() {
+        let original = json!({ "player_data": { "version": CURRENT_SAVE_VERSION + 1 } });
+        let mut save = original.clone();
+        assert_eq!(migrate_save(&mut save), Err(SaveMigrationError::NewerVersion(CURRENT_SAVE_VERSION + 1)));
+        assert_eq!(save, original);
+    }
+
+    #[test]
+    fn a_save_without_player_data_cant_be_upgraded() {
+        assert_eq!(migrate_save(&mut json!({ "money": 10 })), Err(SaveMigrationError::MissingPlayerData));
+        assert_eq!(migrate_save(&mut json!({ "player_data": 3 })), Err(SaveMigrationError::MissingPlayerData));
+    }
+}
@@ -0,0 +1,250 @@
+use gdnative::prelude::*;
+use gdnative::api::AudioServer;
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::utils;
+
+// The volume (in dB) of a muted bus or player
+pub const SILENCE_DB: f64 = -80.0;
+// How far the stick of the gamepad must be pushed (0.0 - 1.0) before the player starts walking
+pub const DEFAULT_STICK_DEADZONE: f64 = 0.2;
+// A bigger deadzone would leave almost no room to control the speed with the stick
+const MAX_STICK_DEADZONE: f64 = 0.9;
+
+/// Converts a volume from 0 to 100 to decibels, the unit that uses Godot for the audio
+pub fn volume_to_db(volume: f64) -> f64 {
+    let linear = volume.max(0.0).min(100.0) / 100.0;
+    if linear <= 0.0 { SILENCE_DB } else { (20.0 * linear.log10()).max(SILENCE_DB) }
+}
+
+/// How fast the text appears on the Dialogue Box
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TextSpeed {
+    Slow,
+    Medium,
+    Fast
+}
+
+impl Default for TextSpeed {
+    fn default() -> Self { TextSpeed::Medium }
+}
+
+impl TextSpeed {
+    /// Seconds between one character and the next one
+    pub fn seconds_per_char(&self) -> f64 {
+        match self {
+            TextSpeed::Slow => 0.08,
+            TextSpeed::Medium => 0.05,
+            TextSpeed::Fast => 0.02
+        }
+    }
+
+    pub fn from_string(text_speed: &str) -> TextSpeed {
+        match text_speed {
+            "Slow" => TextSpeed::Slow,
+            "Fast" => TextSpeed::Fast,
+            _ => TextSpeed::Medium
+        }
+    }
+}
+
+/// The options of the game chosen by the player. They aren't part of the saved game, so they're kept
+/// on his own file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // Volumes, from 0 to 100
+    master_volume: f64,
+    music_volume: f64,
+    sfx_volume: f64,
+    text_speed: TextSpeed,
+    language: String,
+    stick_deadzone: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 100.0,
+            music_volume: 80.0,
+            sfx_volume: 80.0,
+            text_speed: TextSpeed::default(),
+            language: "es".to_string(),
+            stick_deadzone: DEFAULT_STICK_DEADZONE,
+        }
+    }
+}
+
+impl Settings {
+    pub fn get_master_volume(&self) -> f64 {
+        self.master_volume
+    }
+
+    pub fn set_master_volume(&mut self, volume: f64) {
+        self.master_volume = volume.max(0.0).min(100.0);
+    }
+
+    pub fn get_music_volume(&self) -> f64 {
+        self.music_volume
+    }
+
+    pub fn set_music_volume(&mut self, volume: f64) {
+        self.music_volume = volume.max(0.0).min(100.0);
+    }
+
+    pub fn get_sfx_volume(&self) -> f64 {
+        self.sfx_volume
+    }
+
+    pub fn set_sfx_volume(&mut self, volume: f64) {
+        self.sfx_volume = volume.max(0.0).min(100.0);
+    }
+
+    pub fn get_text_speed(&self) -> TextSpeed {
+        self.text_speed
+    }
+
+    pub fn set_text_speed(&mut self, text_speed: TextSpeed) {
+        self.text_speed = text_speed;
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn set_language(&mut self, language: &str) {
+        self.language = language.to_string();
+    }
+
+    pub fn get_stick_deadzone(&self) -> f64 {
+        self.stick_deadzone
+    }
+
+    pub fn set_stick_deadzone(&mut self, deadzone: f64) {
+        self.stick_deadzone = deadzone.max(0.0).min(MAX_STICK_DEADZONE);
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+#[derive(Debug)]
+/// Autoload that loads the settings when the game starts, applies the volumes to the audio buses
+/// and saves the settings every time that the player changes any of them.
+///
+/// The settings menu reads and writes the options through this node, on `/root/SettingsController`
+pub struct SettingsController {
+    settings: Settings,
+    // Scales the text speeds, from the balance config
+    text_speed_multiplier: f64,
+}
+
+#[gdnative::methods]
+impl SettingsController {
+    fn new(_owner: &Node) -> Self {
+        Self {
+            settings: Settings::default(),
+            text_speed_multiplier: 1.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, _owner: &Node) {
+        self.settings = utils::retrieve_settings();
+        self.text_speed_multiplier = utils::retrieve_balance_config().get_text_speed_multiplier();
+        self.apply_volumes();
+    }
+
+    #[export]
+    fn get_master_volume(&self, _owner: &Node) -> f64 {
+        self.settings.get_master_volume()
+    }
+
+    #[export]
+    fn set_master_volume(&mut self, _owner: &Node, volume: f64) {
+        self.settings.set_master_volume(volume);
+        self.save_and_apply();
+    }
+
+    #[export]
+    fn get_music_volume(&self, _owner: &Node) -> f64 {
+        self.settings.get_music_volume()
+    }
+
+    #[export]
+    fn set_music_volume(&mut self, _owner: &Node, volume: f64) {
+        self.settings.set_music_volume(volume);
+        self.save_and_apply();
+    }
+
+    #[export]
+    fn get_sfx_volume(&self, _owner: &Node) -> f64 {
+        self.settings.get_sfx_volume()
+    }
+
+    #[export]
+    fn set_sfx_volume(&mut self, _owner: &Node, volume: f64) {
+        self.settings.set_sfx_volume(volume);
+        self.save_and_apply();
+    }
+
+    #[export]
+    fn get_text_speed(&self, _owner: &Node) -> f64 {
+        self.settings.get_text_speed().seconds_per_char() * self.text_speed_multiplier
+    }
+
+    #[export]
+    /// Sets the text speed: Slow, Medium or Fast
+    fn set_text_speed(&mut self, _owner: &Node, text_speed: String) {
+        self.settings.set_text_speed(TextSpeed::from_string(&text_speed));
+        utils::save_settings(&self.settings);
+    }
+
+    #[export]
+    fn get_language(&self, _owner: &Node) -> String {
+        self.settings.get_language().to_string()
+    }
+
+    #[export]
+    fn set_language(&mut self, _owner: &Node, language: String) {
+        self.settings.set_language(&language);
+        utils::save_settings(&self.settings);
+    }
+
+    #[export]
+    fn get_stick_deadzone(&self, _owner: &Node) -> f64 {
+        self.settings.get_stick_deadzone()
+    }
+
+    #[export]
+    /// Sets how far the stick of the gamepad must be pushed to move the player, from 0.0 to 0.9
+    fn set_stick_deadzone(&mut self, _owner: &Node, deadzone: f64) {
+        self.settings.set_stick_deadzone(deadzone);
+        utils::save_settings(&self.settings);
+    }
+
+    fn save_and_apply(&self) {
+        utils::save_settings(&self.settings);
+        self.apply_volumes();
+    }
+
+    /// Sets the volume of every audio bus. A bus at zero gets muted
+    fn apply_volumes(&self) {
+        let audio_server = AudioServer::godot_singleton();
+        let bus_volumes = [
+            ("Master", self.settings.get_master_volume()),
+            ("Music", self.settings.get_music_volume()),
+            ("SFX", self.settings.get_sfx_volume()),
+        ];
+
+        for (bus_name, volume) in bus_volumes.iter() {
+            let bus_index = audio_server.get_bus_index(*bus_name);
+            if bus_index < 0 {
+                godot_print!("Audio bus not found: {}", bus_name);
+                continue;
+            }
+            audio_server.set_bus_volume_db(bus_index, volume_to_db(*volume));
+            audio_server.set_bus_mute(bus_index, *volume <= 0.0);
+        }
+    }
+}
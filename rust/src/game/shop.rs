@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::bag::{ItemCategory, MAX_REPEL, REPEL, SUPER_REPEL};
+
+// The player can't carry more money than this
+pub const MAX_MONEY: u32 = 999_999;
+// The money that the player has when a new game starts
+pub const STARTING_MONEY: u32 = 3000;
+
+/// The price of an item on the shops. The items without price (like the key items) can't be bought nor sold
+pub fn get_item_price(item_id: &str) -> Option<u32> {
+    match item_id {
+        "potion" => Some(300),
+        "super_potion" => Some(700),
+        "hyper_potion" => Some(1200),
+        "antidote" => Some(100),
+        "burn_heal" => Some(250),
+        "paralyze_heal" => Some(200),
+        "revive" => Some(1500),
+        "poke_ball" => Some(200),
+        "great_ball" => Some(600),
+        "ultra_ball" => Some(1200),
+        REPEL => Some(350),
+        SUPER_REPEL => Some(500),
+        MAX_REPEL => Some(700),
+        _ => None
+    }
+}
+
+/// The shops pay half of the price of an item
+pub fn get_sell_price(item_id: &str) -> Option<u32> {
+    if !ItemCategory::from_item_id(item_id).can_be_tossed() {
+        return None;
+    }
+    get_item_price(item_id).map(|price| price / 2)
+}
+
+/// The reasons why an item can't be bought or sold
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShopError {
+    NotEnoughMoney,
+    // The shop doesn't have the item on his inventory
+    NotOnSale,
+    // The shop doesn't want the item, like the key items
+    CantBeSold,
+    NotInBag,
+    // The player already carries all the units of the item that fit on the bag
+    BagFull
+}
+
+impl ShopError {
+    /// The message that the UI shows to the player
+    pub fn to_message_str(&self) -> &'static str {
+        match self {
+            ShopError::NotEnoughMoney => "No tienes suficiente dinero.",
+            ShopError::NotOnSale => "Lo siento, no vendemos eso.",
+            ShopError::CantBeSold => "Lo siento, no puedo comprarte eso.",
+            ShopError::NotInBag => "No te quedan más.",
+            ShopError::BagFull => "No te caben más en la mochila."
+        }
+    }
+}
+
+/// The items that every shop sells, by shop id, loaded from the shops JSON config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShopConfig {
+    #[serde(default)]
+    shops: HashMap<String, Vec<String>>,
+}
+
+impl ShopConfig {
+    /// The ids of the items of the given shop. A shop that isn't on the config doesn't sell anything
+    pub fn get_inventory(&self, shop_id: &str) -> &[String] {
+        self.shops.get(shop_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn sells(&self, shop_id: &str, item_id: &str) -> bool {
+        self.get_inventory(shop_id).iter().any(|shop_item| shop_item == item_id)
+    }
+}
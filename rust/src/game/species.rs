@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::battle::type_chart::Type;
+use super::evolution::EvolutionCondition;
+use super::pokemon::{Pokemon, PokemonMove, StatSpread, MAX_LEVEL, MAX_MOVES};
+
+/// The base stats of a Pokémon Specie, used to calculate the real stats of every Pokémon given his level
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BaseStats {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub special_attack: u32,
+    pub special_defense: u32,
+    pub speed: u32,
+}
+
+impl BaseStats {
+    pub fn new(hp: u32, attack: u32, defense: u32, special_attack: u32, special_defense: u32, speed: u32) -> Self {
+        Self { hp, attack, defense, special_attack, special_defense, speed }
+    }
+}
+
+/// How much experience a Pokémon Specie needs to level up
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum GrowthRate {
+    Fast,
+    MediumFast,
+    MediumSlow,
+    Slow,
+    // Needs a lot of experience on the first levels, and very little on the last ones
+    Erratic
+}
+
+impl Default for GrowthRate {
+    fn default() -> Self { GrowthRate::MediumFast }
+}
+
+impl GrowthRate {
+    /// Returns the total amount of experience that a Pokémon needs to reach the given level
+    pub fn experience_for_level(&self, level: u8) -> u32 {
+        let n = level as i64;
+        let experience = match self {
+            Self::Fast => 4 * n.pow(3) / 5,
+            Self::MediumFast => n.pow(3),
+            Self::MediumSlow => 6 * n.pow(3) / 5 - 15 * n.pow(2) + 100 * n - 140,
+            Self::Slow => 5 * n.pow(3) / 4,
+            Self::Erratic => match n {
+                n if n < 50 => n.pow(3) * (100 - n) / 50,
+                n if n < 68 => n.pow(3) * (150 - n) / 100,
+                n if n < 98 => n.pow(3) * ((1911 - 10 * n) / 3) / 500,
+                n => n.pow(3) * (160 - n) / 100,
+            },
+        };
+        // The Medium Slow formula gives negative values on the first levels
+        experience.max(0) as u32
+    }
+}
+
+/// A move that the Pokémon of a specie learn when they reach the given level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnsetMove {
+    pub level: u8,
+    pub name: String,
+    pub move_type: String,
+    #[serde(default)]
+    pub power: u32,
+    pub pp: u32,
+}
+
+impl LearnsetMove {
+    pub fn to_pokemon_move(&self) -> PokemonMove {
+        PokemonMove::new(&self.name, &self.move_type, self.power, self.pp)
+    }
+}
+
+/// The specie that a Pokémon turns into, and what it needs to do it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesEvolution {
+    pub evolves_to: i32,
+    pub condition: EvolutionCondition,
+}
+
+/// Everything that the Pokémon of a specie share, as it's written on the species JSON config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesData {
+    // The Pokédex number
+    pub id: i32,
+    pub name: String,
+    // One or two types, on the Spanish names that the type chart reads
+    pub types: Vec<String>,
+    #[serde(default = "default_size")]
+    pub height: f64,
+    #[serde(default = "default_size")]
+    pub tall: f64,
+    #[serde(default)]
+    pub pokedex_description: String,
+    pub base_stats: BaseStats,
+    #[serde(default)]
+    pub growth_rate: GrowthRate,
+    // How easy it's to catch a wild Pokémon of this specie (from 1 to 255)
+    pub catch_rate: u32,
+    // The effort values that a Pokémon earns when it defeats one of this specie
+    #[serde(default)]
+    pub ev_yield: StatSpread,
+    // The base of the experience that gives a defeated Pokémon of this specie
+    #[serde(default)]
+    pub base_experience: u32,
+    #[serde(default)]
+    pub evolutions: Vec<SpeciesEvolution>,
+    // The moves learned by leveling up, sorted by level
+    #[serde(default)]
+    pub learnset: Vec<LearnsetMove>,
+}
+
+fn default_size() -> f64 { 60.0 }
+
+impl SpeciesData {
+    pub fn get_base_stats(&self) -> &BaseStats {
+        &self.base_stats
+    }
+
+    pub fn get_growth_rate(&self) -> &GrowthRate {
+        &self.growth_rate
+    }
+
+    pub fn get_catch_rate(&self) -> u32 {
+        self.catch_rate
+    }
+
+    pub fn get_ev_yield(&self) -> &StatSpread {
+        &self.ev_yield
+    }
+
+    pub fn get_base_experience(&self) -> u32 {
+        self.base_experience
+    }
+
+    pub fn type1(&self) -> &str {
+        self.types.get(0).map(String::as_str).unwrap_or("")
+    }
+
+    /// The second type, or an empty one for the species with a single type
+    pub fn type2(&self) -> &str {
+        self.types.get(1).map(String::as_str).unwrap_or("")
+    }
+
+    /// The moves that a Pokémon of this specie learns right when it reaches the given level
+    pub fn get_moves_learned_at(&self, level: u8) -> Vec<PokemonMove> {
+        self.learnset.iter()
+            .filter(|learnset_move| learnset_move.level == level)
+            .map(LearnsetMove::to_pokemon_move)
+            .collect()
+    }
+
+    /// The moves that knows a Pokémon of this specie found at the given level: the last ones that it would have
+    /// learned by leveling up, without repeating any of them
+    pub fn get_starting_moves(&self, level: u8) -> Vec<PokemonMove> {
+        let mut moves: Vec<PokemonMove> = Vec::new();
+        for learnset_move in self.learnset.iter().filter(|learnset_move| learnset_move.level <= level) {
+            moves.retain(|known_move| known_move.name != learnset_move.name);
+            moves.push(learnset_move.to_pokemon_move());
+        }
+        let forgotten = moves.len().saturating_sub(MAX_MOVES);
+        moves.split_off(forgotten)
+    }
+
+    /// Looks for anything on the record that the game can't work with
+    fn validate(&self) -> Result<(), String> {
+        if self.id <= 0 {
+            return Err("the id must be a positive Pokédex number".to_string());
+        }
+        if self.name.trim().is_empty() {
+            return Err("the name can't be empty".to_string());
+        }
+        if self.types.is_empty() || self.types.len() > 2 {
+            return Err("a specie must have one or two types".to_string());
+        }
+        if let Some(unknown_type) = self.types.iter().find(|specie_type| Type::from_string(specie_type).is_none()) {
+            return Err(format!("unknown type {}", unknown_type));
+        }
+        let base_stats = &self.base_stats;
+        if [base_stats.hp, base_stats.attack, base_stats.defense, base_stats.special_attack,
+            base_stats.special_defense, base_stats.speed].contains(&0) {
+            return Err("every base stat must be greater than zero".to_string());
+        }
+        if self.catch_rate == 0 || self.catch_rate > 255 {
+            return Err("the catch rate must be between 1 and 255".to_string());
+        }
+        let impossible_move = self.learnset.iter()
+            .find(|learnset_move| learnset_move.level == 0 || learnset_move.level > MAX_LEVEL);
+        if let Some(learnset_move) = impossible_move {
+            return Err(format!("the move {} is learned at an impossible level", learnset_move.name));
+        }
+        if self.evolutions.iter().any(|evolution| evolution.evolves_to == self.id) {
+            return Err("a specie can't evolve into itself".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A species JSON file. The entries are kept raw until every one of them it's checked, so a wrong one doesn't
+/// spoil the rest of the file
+#[derive(Debug, Default, Deserialize)]
+struct SpeciesFile {
+    #[serde(default)]
+    species: Vec<Value>,
+}
+
+/// Every Pokémon specie of the game by his Pokédex number, read from the species JSON config once, when the game
+/// starts. The Pokémon, the battles and the evolutions read the numbers of every specie from here, so adding a new
+/// Pokémon only needs his data on the config
+#[derive(Debug, Clone, Default)]
+pub struct SpeciesTable {
+    species: HashMap<i32, SpeciesData>,
+}
+
+impl SpeciesTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the species of a JSON file.
+    ///
+    /// Returns what was wrong, as `file/entry`. A malformed specie (or one with an id already loaded) it's skipped,
+    /// but the rest of the species of the file are still added
+    pub fn load_json(&mut self, file_name: &str, json: &str) -> Vec<String> {
+        let species_file: SpeciesFile = match serde_json::from_str(json) {
+            Ok(species_file) => species_file,
+            Err(err) => return vec![format!("{}: {}", file_name, err)]
+        };
+
+        let mut errors = Vec::new();
+        for (index, entry) in species_file.species.into_iter().enumerate() {
+            let specie = match serde_json::from_value::<SpeciesData>(entry) {
+                Ok(specie) => specie,
+                Err(err) => {
+                    errors.push(format!("{}/{}: {}", file_name, index, err));
+                    continue;
+                }
+            };
+            if let Err(err) = specie.validate() {
+                errors.push(format!("{}/{}: {}", file_name, specie.name, err));
+            } else if self.species.contains_key(&specie.id) {
+                errors.push(format!("{}/{}: the id {} it's already used", file_name, specie.name, specie.id));
+            } else {
+                self.species.insert(specie.id, specie);
+            }
+        }
+
+        // The evolutions are checked once every specie of the file it's known
+        for specie in self.species.values() {
+            for evolution in specie.evolutions.iter().filter(|evolution| !self.species.contains_key(&evolution.evolves_to)) {
+                errors.push(format!("{}/{}: evolves into the unknown specie {}", file_name, specie.name, evolution.evolves_to));
+            }
+        }
+        errors
+    }
+
+    pub fn get_species(&self, species_id: i32) -> Option<&SpeciesData> {
+        self.species.get(&species_id)
+    }
+
+    /// Every specie, sorted by his Pokédex number
+    pub fn get_all_species(&self) -> Vec<&SpeciesData> {
+        let mut all_species: Vec<&SpeciesData> = self.species.values().collect();
+        all_species.sort_by_key(|specie| specie.id);
+        all_species
+    }
+
+    /// Checks if the Pokémon meets any of the conditions to evolve, returning the id of the specie that it would evolve into
+    pub fn check_evolution(&self, pokemon: &Pokemon) -> Option<i32> {
+        self.get_species(pokemon.id)?
+            .evolutions.iter()
+            .filter(|evolution| self.species.contains_key(&evolution.evolves_to))
+            .find(|evolution| evolution.condition.is_met_by(pokemon))
+            .map(|evolution| evolution.evolves_to)
+    }
+
+    pub fn len(&self) -> usize {
+        self.species.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.species.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_experience_of_every_growth_rate() {
+        let expected = [
+            (GrowthRate::Fast, [0, 100_000, 800_000]),
+            (GrowthRate::MediumFast, [1, 125_000, 1_000_000]),
+            // The formula goes under zero on the first levels
+            (GrowthRate::MediumSlow, [0, 117_360, 1_059_860]),
+            (GrowthRate::Slow, [1, 156_250, 1_250_000]),
+            (GrowthRate::Erratic, [1, 125_000, 600_000]),
+        ];
+        for (growth_rate, experience) in expected.iter() {
+            let levels = [1, 50, 100];
+            for (level, experience) in levels.iter().zip(experience.iter()) {
+                assert_eq!(growth_rate.experience_for_level(*level), *experience, "{:?} {}", growth_rate, level);
+            }
+        }
+    }
+
+    #[test]
+    fn the_experience_never_goes_down_with_the_level() {
+        for growth_rate in [GrowthRate::Fast, GrowthRate::MediumFast, GrowthRate::MediumSlow, GrowthRate::Slow,
+            GrowthRate::Erratic].iter()
+        {
+            for level in 1..MAX_LEVEL {
+                assert!(growth_rate.experience_for_level(level) <= growth_rate.experience_for_level(level + 1),
+                    "{:?} {}", growth_rate, level);
+            }
+        }
+    }
+
+    const EVOLUTION_LINE: &str = r#"{ "species": [
+        { "id": 1, "name": "Bulbasaur", "types": ["Planta", "Veneno"], "catch_rate": 45,
+          "base_stats": {"hp": 45, "attack": 49, "defense": 49, "special_attack": 65, "special_defense": 65, "speed": 45},
+          "evolutions": [ { "evolves_to": 2, "condition": { "type": "Level", "level": 16 } } ] },
+        { "id": 2, "name": "Ivysaur", "types": ["Planta", "Veneno"], "catch_rate": 45,
+          "base_stats": {"hp": 60, "attack": 62, "defense": 63, "special_attack": 80, "special_defense": 80, "speed": 60},
+          "evolutions": [ { "evolves_to": 3, "condition": { "type": "HeldItem", "item_id": "piedra_hoja" } } ] },
+        { "id": 3, "name": "Venusaur", "types": ["Planta", "Veneno"], "catch_rate": 45,
+          "base_stats": {"hp": 80, "attack": 82, "defense": 83, "special_attack": 100, "special_defense": 100, "speed": 80} },
+        { "id": 128, "name": "Tauros", "types": ["Normal"], "catch_rate": 45,
+          "base_stats": {"hp": 75, "attack": 100, "defense": 95, "special_attack": 40, "special_defense": 70, "speed": 110} }
+    ] }"#;
+
+    fn species_table() -> SpeciesTable {
+        let mut species_table = SpeciesTable::new();
+        assert!(species_table.load_json("evolutions.json", EVOLUTION_LINE).is_empty());
+        species_table
+    }
+
+    #[test]
+    fn the_pokemon_evolves_at_his_evolution_level() {
+        let species_table = species_table();
+        let bulbasaur = species_table.get_species(1).unwrap();
+
+        assert_eq!(species_table.check_evolution(&Pokemon::from_specie(bulbasaur, 15, Vec::new())), None);
+        assert_eq!(species_table.check_evolution(&Pokemon::from_specie(bulbasaur, 16, Vec::new())), Some(2));
+        assert_eq!(species_table.check_evolution(&Pokemon::from_specie(bulbasaur, 40, Vec::new())), Some(2));
+    }
+
+    #[test]
+    fn the_pokemon_evolves_after_the_level_up_that_reaches_his_evolution_level() {
+        let species_table = species_table();
+        let bulbasaur = species_table.get_species(1).unwrap();
+        let mut pokemon = Pokemon::from_specie(bulbasaur, 15, Vec::new());
+        let missing_experience = bulbasaur.get_growth_rate().experience_for_level(16) - pokemon.get_experience();
+
+        assert!(pokemon.award_experience(missing_experience - 1, bulbasaur).is_empty());
+        assert_eq!(species_table.check_evolution(&pokemon), None);
+        assert_eq!(pokemon.award_experience(1, bulbasaur), vec![16]);
+        assert_eq!(species_table.check_evolution(&pokemon), Some(2));
+    }
+
+    #[test]
+    fn the_evolution_keeps_the_nickname_the_moves_and_the_proportion_of_hp() {
+        let species_table = species_table();
+        let moves = vec![PokemonMove::new("Placaje", "Normal", 40, 35), PokemonMove::new("Látigo Cepa", "Planta", 45, 25)];
+        let mut pokemon = Pokemon::from_specie(species_table.get_species(1).unwrap(), 16, moves.clone());
+        pokemon.set_nickname("Bulbi");
+        let old_max_hp = pokemon.get_max_hp();
+        pokemon.set_current_hp(old_max_hp / 2);
+        let old_current_hp = pokemon.get_current_hp();
+
+        pokemon.evolve_into(species_table.get_species(2).unwrap());
+
+        assert_eq!(pokemon.id, 2);
+        assert_eq!(pokemon.name, "Ivysaur");
+        assert_eq!(pokemon.get_display_name(), "Bulbi");
+        assert_eq!(pokemon.get_level(), 16);
+        assert_eq!(pokemon.get_moves(), &moves);
+        assert!(pokemon.get_max_hp() > old_max_hp);
+        assert_eq!(pokemon.get_current_hp(), old_current_hp * pokemon.get_max_hp() / old_max_hp);
+    }
+
+    #[test]
+    fn the_pokemon_evolves_holding_his_evolution_item() {
+        let species_table = species_table();
+        let mut ivysaur = Pokemon::from_specie(species_table.get_species(2).unwrap(), 20, Vec::new());
+
+        assert_eq!(species_table.check_evolution(&ivysaur), None);
+        ivysaur.set_held_item(Some("pocion".to_string()));
+        assert_eq!(species_table.check_evolution(&ivysaur), None);
+        ivysaur.set_held_item(Some("piedra_hoja".to_string()));
+        assert_eq!(species_table.check_evolution(&ivysaur), Some(3));
+    }
+
+    #[test]
+    fn a_specie_without_evolutions_never_evolves() {
+        let species_table = species_table();
+        let tauros = Pokemon::from_specie(species_table.get_species(128).unwrap(), MAX_LEVEL, Vec::new());
+        assert_eq!(species_table.check_evolution(&tauros), None);
+    }
+
+    #[test]
+    fn the_species_of_a_file_are_found_by_his_id() {
+        let species_table = species_table();
+        assert_eq!(species_table.len(), 4);
+        assert_eq!(species_table.get_species(128).unwrap().name, "Tauros");
+        assert!(species_table.get_species(999).is_none());
+        let ids: Vec<i32> = species_table.get_all_species().iter().map(|specie| specie.id).collect();
+        assert_eq!(ids, vec![1, 2, 3, 128]);
+    }
+
+    #[test]
+    fn a_wrong_specie_doesnt_spoil_the_rest_of_the_file() {
+        let mut species_table = SpeciesTable::new();
+        let errors = species_table.load_json("broken.json", r#"{ "species": [
+            { "id": 25, "name": "Pikachu", "types": ["Eléctrico"], "catch_rate": 190,
+              "base_stats": {"hp": 35, "attack": 55, "defense": 40, "special_attack": 50, "special_defense": 50, "speed": 90} },
+            { "id": 26, "name": "Raichu" },
+            { "id": 27, "name": "Sandshrew", "types": ["Arena"], "catch_rate": 255,
+              "base_stats": {"hp": 50, "attack": 75, "defense": 85, "special_attack": 20, "special_defense": 30, "speed": 40} },
+            { "id": 25, "name": "Pikachu2", "types": ["Eléctrico"], "catch_rate": 190,
+              "base_stats": {"hp": 35, "attack": 55, "defense": 40, "special_attack": 50, "special_defense": 50, "speed": 90} }
+        ] }"#);
+
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].starts_with("broken.json/1: "), "{}", errors[0]);
+        assert_eq!(errors[1], "broken.json/Sandshrew: unknown type Arena");
+        assert_eq!(errors[2], "broken.json/Pikachu2: the id 25 it's already used");
+        assert_eq!(species_table.len(), 1);
+        assert_eq!(species_table.get_species(25).unwrap().name, "Pikachu");
+    }
+
+    #[test]
+    fn the_impossible_numbers_are_rejected() {
+        let mut species_table = SpeciesTable::new();
+        let errors = species_table.load_json("numbers.json", r#"{ "species": [
+            { "id": 0, "name": "MissingNo", "types": ["Normal"], "catch_rate": 3,
+              "base_stats": {"hp": 33, "attack": 136, "defense": 1, "special_attack": 6, "special_defense": 6, "speed": 29} },
+            { "id": 52, "name": "Meowth", "types": ["Normal"], "catch_rate": 300,
+              "base_stats": {"hp": 40, "attack": 45, "defense": 35, "special_attack": 40, "special_defense": 40, "speed": 90} },
+            { "id": 54, "name": "Psyduck", "types": ["Agua"], "catch_rate": 190,
+              "base_stats": {"hp": 50, "attack": 52, "defense": 48, "special_attack": 65, "special_defense": 50, "speed": 0} },
+            { "id": 56, "name": " ", "types": ["Lucha"], "catch_rate": 190,
+              "base_stats": {"hp": 40, "attack": 80, "defense": 35, "special_attack": 35, "special_defense": 45, "speed": 70} },
+            { "id": 58, "name": "Growlithe", "types": ["Fuego", "Normal", "Agua"], "catch_rate": 190,
+              "base_stats": {"hp": 55, "attack": 70, "defense": 45, "special_attack": 70, "special_defense": 50, "speed": 60} },
+            { "id": 60, "name": "Poliwag", "types": ["Agua"], "catch_rate": 255,
+              "base_stats": {"hp": 40, "attack": 50, "defense": 40, "special_attack": 40, "special_defense": 40, "speed": 90},
+              "learnset": [ { "level": 101, "name": "Hidrobomba", "move_type": "Agua", "power": 110, "pp": 5 } ] },
+            { "id": 63, "name": "Abra", "types": ["Psíquico"], "catch_rate": 200,
+              "base_stats": {"hp": 25, "attack": 20, "defense": 15, "special_attack": 105, "special_defense": 55, "speed": 90},
+              "evolutions": [ { "evolves_to": 63, "condition": { "type": "Level", "level": 16 } } ] }
+        ] }"#);
+        assert_eq!(errors, vec![
+            "numbers.json/MissingNo: the id must be a positive Pokédex number".to_string(),
+            "numbers.json/Meowth: the catch rate must be between 1 and 255".to_string(),
+            "numbers.json/Psyduck: every base stat must be greater than zero".to_string(),
+            "numbers.json/ : the name can't be empty".to_string(),
+            "numbers.json/Growlithe: a specie must have one or two types".to_string(),
+            "numbers.json/Poliwag: the move Hidrobomba is learned at an impossible level".to_string(),
+            "numbers.json/Abra: a specie can't evolve into itself".to_string(),
+        ]);
+        assert!(species_table.is_empty());
+    }
+
+    #[test]
+    fn a_malformed_file_is_reported_whole() {
+        let mut species_table = SpeciesTable::new();
+        let errors = species_table.load_json("garbage.json", "{ species: ");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("garbage.json: "), "{}", errors[0]);
+        assert!(species_table.is_empty());
+    }
+
+    #[test]
+    fn an_evolution_into_an_unknown_specie_is_reported() {
+        let mut species_table = SpeciesTable::new();
+        let errors = species_table.load_json("evolutions.json", r#"{ "species": [
+            { "id": 133, "name": "Eevee", "types": ["Normal"], "catch_rate": 45,
+              "base_stats": {"hp": 55, "attack": 55, "defense": 50, "special_attack": 45, "special_defense": 65, "speed": 55},
+              "evolutions": [ { "evolves_to": 134, "condition": { "type": "HeldItem", "item_id": "piedra_agua" } } ] }
+        ] }"#);
+        assert_eq!(errors, vec!["evolutions.json/Eevee: evolves into the unknown specie 134".to_string()]);
+        // The specie it's still loaded, but it never evolves into the missing one
+        let eevee = species_table.get_species(133).unwrap();
+        let mut pokemon = Pokemon::from_specie(eevee, 20, Vec::new());
+        pokemon.set_held_item(Some("piedra_agua".to_string()));
+        assert_eq!(species_table.check_evolution(&pokemon), None);
+    }
+}
@@ -0,0 +1,113 @@
+use gdnative::prelude::*;
+use gdnative::api::CPUParticles2D;
+
+/// The ground that the player it's running over. Every surface raises a dust of his own color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Surface {
+    Ground,
+    Grass,
+    Sand
+}
+
+impl Default for Surface {
+    fn default() -> Self { Surface::Ground }
+}
+
+impl Surface {
+    pub fn to_surface_str(&self) -> &'static str {
+        match self {
+            Surface::Ground => "Ground",
+            Surface::Grass => "Grass",
+            Surface::Sand => "Sand"
+        }
+    }
+
+    // Anything unknown it's just ground
+    pub fn from_surface_str(surface: &str) -> Surface {
+        match surface {
+            "Grass" => Surface::Grass,
+            "Sand" => Surface::Sand,
+            _ => Surface::Ground
+        }
+    }
+
+    pub fn to_dust_color(&self) -> Color {
+        match self {
+            Surface::Ground => Color::rgba(0.72, 0.62, 0.48, 0.8),
+            Surface::Grass => Color::rgba(0.45, 0.68, 0.32, 0.8),
+            Surface::Sand => Color::rgba(0.93, 0.86, 0.62, 0.8)
+        }
+    }
+}
+
+#[derive(NativeClass)]
+#[inherit(CPUParticles2D)]
+#[derive(Debug)]
+/// The dust that the player raises behind him while running.
+///
+/// The PlayerAnimation tells it on every frame if the player it's running and where, but the particles are only
+/// touched when any of that changes, so it's cheap to keep it alive all the time
+pub struct SprintDustEffect {
+    // How far behind the feet of the player the dust appears
+    #[property(default = 4.0)]
+    trail_distance: f32,
+
+    surface: Surface,
+    // Where the node was placed on the editor, at the feet of the player
+    feet_position: Vector2,
+    // The direction of the player while the dust it's being raised. Zero when it isn't
+    running_direction: Vector2,
+}
+
+#[gdnative::methods]
+impl SprintDustEffect {
+    fn new(_owner: &CPUParticles2D) -> Self {
+        Self {
+            trail_distance: 4.0,
+            surface: Surface::default(),
+            feet_position: Vector2::zero(),
+            running_direction: Vector2::zero(),
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &CPUParticles2D) {
+        self.feet_position = owner.position();
+        owner.set_emitting(false);
+        owner.set_color(self.surface.to_dust_color());
+    }
+
+    #[export]
+    /// Raises the dust behind the player while he runs on the given direction, and stops it otherwise
+    fn set_running(&mut self, owner: &CPUParticles2D, running: bool, motion: Vector2) {
+        let direction = if running && motion != Vector2::zero() { motion.normalize() } else { Vector2::zero() };
+        if direction == self.running_direction {
+            return;
+        }
+        self.running_direction = direction;
+
+        if direction == Vector2::zero() {
+            // The particles already on the air fade by themselves
+            owner.set_emitting(false);
+            return;
+        }
+        owner.set_position(self.feet_position - direction * self.trail_distance);
+        owner.set_direction(-direction);
+        owner.set_emitting(true);
+    }
+
+    #[export]
+    /// Changes the color of the dust to the one of the surface that the player it's stepping on
+    fn set_surface(&mut self, owner: &CPUParticles2D, surface: String) {
+        let surface = Surface::from_surface_str(&surface);
+        if surface != self.surface {
+            self.surface = surface;
+            owner.set_color(surface.to_dust_color());
+        }
+    }
+
+    #[export]
+    fn get_surface(&self, _owner: &CPUParticles2D) -> String {
+        self.surface.to_surface_str().to_string()
+    }
+}
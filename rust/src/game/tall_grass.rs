@@ -2,6 +2,12 @@ use gdnative::prelude::*;
 use gdnative::api::AnimationPlayer;
 
 use crate::game::code_abstractions::signals::RegisterSignal;
+use crate::game::sprint_dust_effect::Surface;
+use crate::utils::utils;
+
+const PLAYER_PATH: &str = "/root/Game/Player";
+// The dust that the player raises while running, that turns green over the grass
+const SPRINT_DUST_PATH: &str = "/root/Game/Player/PlayerAnimation/SprintDust";
 
 #[derive(NativeClass)]
 #[inherit(Node2D)]
@@ -50,6 +56,7 @@ impl TallGrass {
     fn _on_area2d_body_entered(&mut self, owner: TRef<Node2D>, _body: Variant) {
         self.player_in_grass(owner);
         self.animation_player.unwrap().play("Stepped", 0.0, 1.0, false);
+        self.set_sprint_dust_surface(owner, Surface::Grass);
     }
 
     #[export]
@@ -59,6 +66,13 @@ impl TallGrass {
             self.grass_overlay.queue_free();
             owner.remove_child(self.grass_overlay);
         }  
+        self.set_sprint_dust_surface(owner, Surface::Ground);
+    }
+
+    fn set_sprint_dust_surface(&self, owner: &Node2D, surface: Surface) {
+        if let Some(sprint_dust) = unsafe { owner.get_node(SPRINT_DUST_PATH) } {
+            unsafe { sprint_dust.assume_safe().call("set_surface", &[Variant::from_str(surface.to_surface_str())]) };
+        }
     }
 
     #[export]
@@ -91,8 +105,9 @@ impl TallGrass {
                 self.grass_overlay.set("z_index", 2);
                 self.grass_overlay.set_position(Vector2::new(8.0, 8.0));
 
-                let player_node = unsafe { owner.get_node("/root/Game/Player").expect("Bad route to Game/Player").assume_safe().cast::<Node2D>().unwrap() };
-                player_node.set("z_index", 1);
+                if let Some(player_node) = utils::try_get_node_as::<Node2D>(&owner, PLAYER_PATH) {
+                    player_node.set("z_index", 1);
+                }
             },
 
             Some(_x) => ()
@@ -0,0 +1,67 @@
+use gdnative::prelude::*;
+use gdnative::api::TileMap;
+
+/// The kinds of ground that change how fast the player walks over them. The maps mark them by adding the TileMaps
+/// that paint them to the group of the terrain, so every tile of those TileMaps it's that terrain
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Terrain {
+    Ground,
+    TallGrass,
+    Sand,
+    Ice
+}
+
+impl Default for Terrain {
+    fn default() -> Self { Terrain::Ground }
+}
+
+impl Terrain {
+    /// The terrains that the maps can paint, on the order that wins when two TileMaps overlap on the same tile
+    pub const PAINTED: [Terrain; 3] = [Terrain::Ice, Terrain::Sand, Terrain::TallGrass];
+
+    pub fn to_terrain_str(&self) -> &'static str {
+        match self {
+            Terrain::Ground => "Ground",
+            Terrain::TallGrass => "TallGrass",
+            Terrain::Sand => "Sand",
+            Terrain::Ice => "Ice"
+        }
+    }
+
+    /// The group of the TileMaps that paint this terrain. The plain ground doesn't need any
+    pub fn to_group_str(&self) -> &'static str {
+        match self {
+            Terrain::Ground => "",
+            Terrain::TallGrass => "terrain_tall_grass",
+            Terrain::Sand => "terrain_sand",
+            Terrain::Ice => "terrain_ice"
+        }
+    }
+}
+
+/// Looks for the terrain painted on the tile under the given position. Any tile without a terrain TileMap
+/// (or a scene out of the tree) it's just the plain ground
+pub fn terrain_at(node: &Node, global_position: Vector2) -> Terrain {
+    Terrain::PAINTED.iter()
+        .copied()
+        .find(|terrain| painted_tilemap_at(node, *terrain, global_position).is_some())
+        .unwrap_or_default()
+}
+
+/// The encounter area of the tall grass under the given position, if there's any. Every TileMap of tall grass
+/// it's named after the encounter zone of his grass, so one map can have grass of many zones
+pub fn encounter_area_at(node: &Node, global_position: Vector2) -> Option<String> {
+    painted_tilemap_at(node, Terrain::TallGrass, global_position).map(|tilemap| tilemap.name().to_string())
+}
+
+/// The TileMap of the given terrain that has a tile under the given position
+fn painted_tilemap_at<'a>(node: &Node, terrain: Terrain, global_position: Vector2) -> Option<TRef<'a, TileMap>> {
+    let tree = unsafe { node.get_tree()?.assume_safe() };
+    tree.get_nodes_in_group(terrain.to_group_str()).iter()
+        .filter_map(|node| node.try_to_object::<TileMap>())
+        .map(|tilemap| unsafe { tilemap.assume_safe() })
+        .find(|tilemap| {
+            let cell = tilemap.world_to_map(tilemap.to_local(global_position));
+            tilemap.get_cellv(cell) != TileMap::INVALID_CELL
+        })
+}
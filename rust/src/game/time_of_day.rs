@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::consts::in_game_constant;
+
+// Game hours that lasts the blend between the tint of a period and the tint of the next one.
+// The blend happens at the end of every period, so the new period starts with his own tint already applied
+pub const TINT_TRANSITION_HOURS: f64 = 1.0;
+
+/// The parts of the in-game day. Every period has his own screen tint, and some wild Pokémon only
+/// appear during some of them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DayPeriod {
+    Morning,
+    Day,
+    Evening,
+    Night
+}
+
+impl Default for DayPeriod {
+    fn default() -> Self { DayPeriod::Day }
+}
+
+impl DayPeriod {
+    /// The periods in the order that they happen, starting at midnight
+    pub const ALL: [DayPeriod; 4] = [
+        DayPeriod::Morning, DayPeriod::Day, DayPeriod::Evening, DayPeriod::Night
+    ];
+
+    /// The game hour (0 - 24) when the period starts
+    pub fn start_hour(&self) -> f64 {
+        match self {
+            DayPeriod::Morning => 5.0,
+            DayPeriod::Day => 10.0,
+            DayPeriod::Evening => 17.0,
+            DayPeriod::Night => 20.0
+        }
+    }
+
+    /// The period that the given game hour belongs to
+    pub fn from_hour(hour: f64) -> DayPeriod {
+        let hour = hour.rem_euclid(24.0);
+        DayPeriod::ALL.iter().rev()
+            .find(|period| hour >= period.start_hour())
+            .copied()
+            // Between midnight and the morning, it's still the night
+            .unwrap_or(DayPeriod::Night)
+    }
+
+    pub fn next(&self) -> DayPeriod {
+        match self {
+            DayPeriod::Morning => DayPeriod::Day,
+            DayPeriod::Day => DayPeriod::Evening,
+            DayPeriod::Evening => DayPeriod::Night,
+            DayPeriod::Night => DayPeriod::Morning
+        }
+    }
+
+    /// The color (r, g, b) that the CanvasModulate of the world gets during the period
+    pub fn tint(&self) -> (f32, f32, f32) {
+        match self {
+            DayPeriod::Morning => (0.95, 0.88, 0.8),
+            DayPeriod::Day => (1.0, 1.0, 1.0),
+            DayPeriod::Evening => (0.95, 0.7, 0.55),
+            DayPeriod::Night => (0.2, 0.2, 0.3)
+        }
+    }
+
+    pub fn from_string(period: &str) -> Option<DayPeriod> {
+        match period {
+            "Morning" => Some(DayPeriod::Morning),
+            "Day" => Some(DayPeriod::Day),
+            "Evening" => Some(DayPeriod::Evening),
+            "Night" => Some(DayPeriod::Night),
+            _ => None
+        }
+    }
+
+    pub fn to_period_str(&self) -> &'static str {
+        match self {
+            DayPeriod::Morning => "Morning",
+            DayPeriod::Day => "Day",
+            DayPeriod::Evening => "Evening",
+            DayPeriod::Night => "Night"
+        }
+    }
+}
+
+/// The in-game clock. It runs faster than the real one (how much faster it's up to the Game), and it's
+/// persisted on the saved game, so the player comes back at the same hour that he left
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeOfDay {
+    // Game hours since midnight, from 0 to 24
+    hour: f64,
+    // Game hours since the game started. It never goes back, so it can measure how long things take (like the
+    // growth of the berries), even between saved games
+    elapsed_hours: f64,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self { hour: in_game_constant::STARTING_GAME_HOUR, elapsed_hours: 0.0 }
+    }
+}
+
+impl TimeOfDay {
+    pub fn new(hour: f64) -> Self {
+        Self { hour: hour.rem_euclid(24.0), elapsed_hours: 0.0 }
+    }
+
+    pub fn get_hour(&self) -> f64 {
+        self.hour
+    }
+
+    pub fn get_elapsed_hours(&self) -> f64 {
+        self.elapsed_hours
+    }
+
+    /// Moves the clock forward until the given hour, like when the player sleeps. The time never goes back,
+    /// so an earlier hour means the next day
+    pub fn set_hour(&mut self, hour: f64) {
+        let hour = hour.rem_euclid(24.0);
+        self.elapsed_hours += (hour - self.hour).rem_euclid(24.0);
+        self.hour = hour;
+    }
+
+    /// The game time as (hours, minutes), ready to be shown on a clock
+    pub fn get_hour_and_minute(&self) -> (u32, u32) {
+        let total_minutes = (self.hour * 60.0) as u32;
+        (total_minutes / 60, total_minutes % 60)
+    }
+
+    pub fn get_period(&self) -> DayPeriod {
+        DayPeriod::from_hour(self.hour)
+    }
+
+    /// Moves the clock forward the given real seconds.
+    ///
+    /// Returns the new period when the clock crosses the start of one, so the listeners can be notified
+    pub fn advance(&mut self, real_seconds: f64, seconds_per_game_hour: f64) -> Option<DayPeriod> {
+        if seconds_per_game_hour <= 0.0 {
+            return None;
+        }
+        let previous_period = self.get_period();
+        self.hour = (self.hour + real_seconds / seconds_per_game_hour).rem_euclid(24.0);
+        self.elapsed_hours += real_seconds.max(0.0) / seconds_per_game_hour;
+
+        let current_period = self.get_period();
+        if current_period != previous_period { Some(current_period) } else { None }
+    }
+
+    /// The tint of the current period, blended with the tint of the next one during the last
+    /// `TINT_TRANSITION_HOURS` of the period, so the color never snaps at the boundaries
+    pub fn current_tint(&self) -> (f32, f32, f32) {
+        let period = self.get_period();
+        let next_period = period.next();
+
+        let hours_left = (next_period.start_hour() - self.hour).rem_euclid(24.0);
+        if hours_left >= TINT_TRANSITION_HOURS {
+            return period.tint();
+        }
+
+        let weight = (1.0 - hours_left / TINT_TRANSITION_HOURS) as f32;
+        let (from, to) = (period.tint(), next_period.tint());
+        (
+            from.0 + (to.0 - from.0) * weight,
+            from.1 + (to.1 - from.1) * weight,
+            from.2 + (to.2 - from.2) * weight
+        )
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use gdnative::prelude::*;
+use gdnative::api::{KinematicBody2D, OS, RayCast2D};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::code_abstractions::{
+    character::CharacterTileMovement,
+    signals::RegisterSignal
+};
+use crate::game::player::PlayerDirection;
+use crate::utils::{consts::in_game_constant, utils};
+
+const SIGHT_RAYCAST_NODE: &str = "SightRayCast";
+// The layer of the player (1), plus the ones that block his movement (2 and 4), so the walls cut the sight
+const SIGHT_COLLISION_MASK: i64 = 7;
+// The colors of the line of sight on the debug mode: clear until the end, or cut by an obstacle
+const CLEAR_SIGHT_COLOR: (f32, f32, f32, f32) = (0.2, 0.9, 0.3, 0.8);
+const BLOCKED_SIGHT_COLOR: (f32, f32, f32, f32) = (0.95, 0.25, 0.2, 0.8);
+
+/// A Pokémon of the team of a trainer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainerPokemon {
+    pub species_id: i32,
+    pub level: u8,
+}
+
+/// The team of a trainer, and the money that he pays (for every level of his strongest Pokémon) when he's defeated
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrainerData {
+    #[serde(default)]
+    pub base_payout: u32,
+    #[serde(default)]
+    pub team: Vec<TrainerPokemon>,
+}
+
+impl TrainerData {
+    /// The level of the strongest Pokémon of the team, the one that sets the prize money
+    pub fn highest_level(&self) -> u8 {
+        self.team.iter().map(|pokemon| pokemon.level).max().unwrap_or(0)
+    }
+}
+
+/// The data of every trainer, by trainer id, loaded from the trainers JSON config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrainerTable {
+    #[serde(default)]
+    trainers: HashMap<String, TrainerData>,
+}
+
+impl TrainerTable {
+    pub fn get_trainer(&self, trainer_id: &str) -> Option<&TrainerData> {
+        self.trainers.get(trainer_id)
+    }
+}
+
+/// Where the line of sight of a trainer ends, relative to where it starts: as many tiles as his sight range,
+/// on the direction that he's facing
+pub fn sight_vector(facing: &str, sight_range: i32) -> Vector2 {
+    PlayerDirection::from_string(facing).to_vector() * in_game_constant::TILE_SIZE * sight_range.max(0) as f32
+}
+
+/// If the first body hit by the line of sight (the ray stops at the first one, so a wall hides anything behind it)
+/// it's the player, and he's free to be challenged. `player_can_act` it's only asked when the player was hit
+pub fn is_player_spotted<F: FnOnce() -> bool>(first_collider: Option<&str>, player_can_act: F) -> bool {
+    first_collider == Some("Player") && player_can_act()
+}
+
+/// The tiles that a trainer walks towards the player, stopping on the one just in front of him
+pub fn tiles_to_walk(trainer_position: Vector2, player_position: Vector2) -> i32 {
+    let distance_in_tiles = (player_position - trainer_position).length() / in_game_constant::TILE_SIZE;
+    distance_in_tiles.round() as i32 - 1
+}
+
+/// The posible states of an NPC trainer
+#[derive(PartialEq, Clone, Debug)]
+pub enum TrainerStatus {
+    // Looking for trainers to challenge
+    Watching,
+    // Walking towards the player that just spotted
+    Approaching,
+    // In front of the player, waiting for the battle
+    Engaged,
+    // Already defeated by the player, so never challenges him again
+    Defeated
+}
+impl Default for TrainerStatus {
+    fn default() -> Self { TrainerStatus::Watching }
+}
+
+#[derive(NativeClass)]
+#[inherit(KinematicBody2D)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// An NPC trainer that challenges the player when he walks into his line of sight.
+///
+/// The trainer looks through a RayCast2D on the direction that it's facing, so walls and
+/// any other obstacle blocks his sight.
+pub struct Trainer {
+    // Unique identifier of the trainer, used to remember if the player already defeated him
+    #[property]
+    trainer_id: String,
+    // Where the trainer it's looking at: Upwards, Downwards, Left or Right
+    #[property]
+    facing: String,
+    // How far (in tiles) the trainer can see
+    #[property(default = 4)]
+    sight_range: i32,
+    // Draws the line of sight, up to where the trainer can really see, so the trainers can be placed on the map.
+    // Only on the debug builds
+    #[property(default = false)]
+    show_sight_line: bool,
+
+    sight_raycast: Option<TRef<'static, RayCast2D>>,
+    trainer_status: TrainerStatus,
+    tiles_to_walk: i32,
+
+    // Tile-based movement system, the same one that uses the player
+    initial_position: Vector2,
+    input_direction: Vector2,
+    percent_move_to_next_tile: f64,
+    // Tiles per second, from the balance config
+    walk_speed: f64,
+}
+
+impl RegisterSignal<Self> for Trainer {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        // Notifies the player that has been spotted, so he stops moving
+        builder.add_signal( Signal {
+            name: "trainer_engaged",
+            args: &[],
+        });
+        // The trainer it's in front of the player, so the battle can start
+        builder.add_signal( Signal {
+            name: "trainer_battle_started",
+            args: &[ SignalArgument {
+                name: "trainer_id",
+                default: Variant::from_str(""),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+    }
+}
+
+impl CharacterTileMovement<KinematicBody2D, Input> for Trainer {
+    /// Trainers doesn't read the keyboard. When they are approaching the player, they just walk
+    /// straight on the direction that they are facing
+    fn process_player_input(&mut self, owner: &KinematicBody2D, _input: &Input) {
+        self.input_direction = PlayerDirection::from_string(&self.facing).to_vector();
+        self.initial_position = owner.global_position();
+    }
+
+    /// The path to the player it's always free, 'cause otherwise the trainer couldn't see him
+    fn tilemove_or_collide(&mut self, owner: &KinematicBody2D, delta: f32) {
+        self.move_character(owner, delta);
+    }
+
+    /// Moves the trainer one entire tile towards the player
+    fn move_character(&mut self, owner: &KinematicBody2D, delta: f32) {
+        self.percent_move_to_next_tile += self.walk_speed * delta as f64;
+
+        if self.percent_move_to_next_tile >= 1.0 {
+            owner.set_global_position(self.initial_position + self.input_direction * in_game_constant::TILE_SIZE);
+            self.percent_move_to_next_tile = 0.0;
+            self.tiles_to_walk -= 1;
+            // Ready for the next step
+            self.process_player_input(owner, Input::godot_singleton());
+        } else {
+            owner.set_global_position(self.initial_position +
+                self.input_direction * in_game_constant::TILE_SIZE * self.percent_move_to_next_tile as f32);
+        }
+    }
+}
+
+#[gdnative::methods]
+impl Trainer {
+    fn new(_owner: &KinematicBody2D) -> Self {
+        Self {
+            trainer_id: "".to_string(),
+            facing: "Downwards".to_string(),
+            sight_range: 4,
+            show_sight_line: false,
+            sight_raycast: None,
+            trainer_status: Default::default(),
+            tiles_to_walk: 0,
+            initial_position: Vector2::new(0.0, 0.0),
+            input_direction: Vector2::new(0.0, 0.0),
+            percent_move_to_next_tile: 0.0,
+            walk_speed: in_game_constant::WALK_SPEED,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<KinematicBody2D>) {
+        // Trainers without an explicit id are identified by his node name
+        if self.trainer_id.is_empty() {
+            self.trainer_id = owner.name().to_string();
+        }
+
+        self.walk_speed = utils::retrieve_balance_config().get_walk_speed();
+
+        // Sets the line of sight of the trainer
+        self.sight_raycast = Some(self.get_or_create_sight_raycast(owner));
+        self.update_sight_line();
+        self.show_sight_line = self.show_sight_line && OS::godot_singleton().is_debug_build();
+
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        if unsafe { game.call("is_trainer_defeated", &[self.trainer_id.to_variant()]) }.to_bool() {
+            self.trainer_status = TrainerStatus::Defeated;
+        }
+
+        self.connect_signals(owner, game);
+    }
+
+    #[export]
+    fn _physics_process(&mut self, owner: &KinematicBody2D, delta: f32) {
+        if self.show_sight_line {
+            owner.update();
+        }
+        match self.trainer_status {
+            TrainerStatus::Watching => self.look_for_player(owner),
+            TrainerStatus::Approaching => {
+                if self.tiles_to_walk > 0 {
+                    self.tilemove_or_collide(owner, delta);
+                } else {
+                    self.trainer_status = TrainerStatus::Engaged;
+                    owner.emit_signal("trainer_battle_started", &[self.trainer_id.to_variant()]);
+                }
+            },
+            TrainerStatus::Engaged | TrainerStatus::Defeated => ()
+        }
+    }
+
+    #[export]
+    /// Draws the line of sight of the trainer, when the debug mode it's on. The line ends where the sight gets cut
+    fn _draw(&self, owner: &KinematicBody2D) {
+        if !self.show_sight_line || self.trainer_status != TrainerStatus::Watching {
+            return;
+        }
+        let sight_raycast = match self.sight_raycast {
+            Some(sight_raycast) => sight_raycast,
+            None => return
+        };
+
+        let start = sight_raycast.position();
+        let (end, line_color) = if sight_raycast.is_colliding() {
+            (owner.to_local(sight_raycast.get_collision_point()), BLOCKED_SIGHT_COLOR)
+        } else {
+            (start + sight_raycast.cast_to(), CLEAR_SIGHT_COLOR)
+        };
+        let line_color = Color::rgba(line_color.0, line_color.1, line_color.2, line_color.3);
+        owner.draw_line(start, end, line_color, 2.0, false);
+        owner.draw_circle(end, 2.0, line_color);
+    }
+
+    #[export]
+    /// Turns the trainer to look at another direction: Upwards, Downwards, Left or Right
+    fn set_facing(&mut self, _owner: &KinematicBody2D, facing: String) {
+        self.facing = facing;
+        self.update_sight_line();
+    }
+
+    /// Points the line of sight to where the trainer it's looking at, as far as his sight range
+    fn update_sight_line(&self) {
+        if let Some(sight_raycast) = self.sight_raycast {
+            sight_raycast.set_cast_to(sight_vector(&self.facing, self.sight_range));
+        }
+    }
+
+    /// The RayCast2D that the trainer looks through. The trainers placed without one get it created here, so
+    /// only the facing and the range have to be set on the editor
+    fn get_or_create_sight_raycast(&self, owner: TRef<KinematicBody2D>) -> TRef<'static, RayCast2D> {
+        let sight_raycast = match unsafe { owner.get_node_as::<RayCast2D>(SIGHT_RAYCAST_NODE) } {
+            Some(sight_raycast) => sight_raycast,
+            None => {
+                let sight_raycast = RayCast2D::new();
+                sight_raycast.set_name(SIGHT_RAYCAST_NODE);
+                sight_raycast.set_enabled(true);
+                // Centered on the tile of the trainer, like the player
+                sight_raycast.set_position(Vector2::new(in_game_constant::TILE_SIZE / 2.0, in_game_constant::TILE_SIZE / 2.0));
+                let sight_raycast = sight_raycast.into_shared();
+                owner.add_child(sight_raycast, false);
+                unsafe { sight_raycast.assume_safe() }
+            }
+        };
+        sight_raycast.set_collision_mask(SIGHT_COLLISION_MASK);
+        sight_raycast.set_exclude_parent_body(true);
+        sight_raycast
+    }
+
+    /// Checks if the player it's inside the line of sight of the trainer. If the player it's busy with another
+    /// interaction (reading a sign, on the menu...) the trainer just waits until the player it's free again.
+    ///
+    /// The ray stops at the first thing that it hits, so a wall (or anything else) between the trainer and the
+    /// player hides him. Once spotted, the player gets locked, and the trainer stops watching until the battle
+    fn look_for_player(&mut self, owner: &KinematicBody2D) {
+        let sight_raycast = self.sight_raycast.unwrap();
+        sight_raycast.force_raycast_update();
+
+        if let Some(collider) = sight_raycast.get_collider() {
+            if let Some(player) = unsafe { collider.assume_safe().cast::<Node2D>() } {
+                let player_name = player.name().to_string();
+                if is_player_spotted(Some(&player_name), || unsafe { player.call("can_act", &[]) }.to_bool()) {
+                        self.tiles_to_walk = tiles_to_walk(owner.global_position(), player.global_position());
+
+                        self.trainer_status = TrainerStatus::Approaching;
+                        self.process_player_input(owner, Input::godot_singleton());
+                        owner.emit_signal("trainer_engaged", &[Variant::from_str("trainer_engaged")]);
+                }
+            }
+        }
+    }
+
+    /// Connects the trainer signals with the player (that should stop moving) and with the Game, that loads the battle
+    fn connect_signals(&self, owner: TRef<KinematicBody2D>, game: TRef<Node>) {
+        let player = unsafe { game.get_node("Player").unwrap().assume_safe() };
+        owner.connect("trainer_engaged", player, "handle_interaction",
+            VariantArray::new_shared(), 0).unwrap();
+        owner.connect("trainer_battle_started", game, "_on_trainer_battle_started",
+            VariantArray::new_shared(), 0).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE_SIZE: f32 = in_game_constant::TILE_SIZE;
+
+    #[test]
+    fn the_sight_reaches_his_range_on_the_facing_direction() {
+        assert_eq!(sight_vector("Right", 4), Vector2::new(4.0 * TILE_SIZE, 0.0));
+        assert_eq!(sight_vector("Upwards", 2), Vector2::new(0.0, -2.0 * TILE_SIZE));
+        assert_eq!(sight_vector("Left", -3), Vector2::zero());
+    }
+
+    #[test]
+    fn a_wall_hides_the_player() {
+        // The ray stopped on a wall before reaching the player
+        assert!(!is_player_spotted(Some("Walls"), || true));
+        // Nothing on the line of sight
+        assert!(!is_player_spotted(None, || true));
+        // A clear path up to the player
+        assert!(is_player_spotted(Some("Player"), || true));
+    }
+
+    #[test]
+    fn a_busy_player_is_not_challenged() {
+        assert!(!is_player_spotted(Some("Player"), || false));
+    }
+
+    #[test]
+    fn the_trainer_stops_in_front_of_the_player() {
+        let trainer = Vector2::new(0.0, 0.0);
+        assert_eq!(tiles_to_walk(trainer, Vector2::new(4.0 * TILE_SIZE, 0.0)), 3);
+        assert_eq!(tiles_to_walk(trainer, Vector2::new(0.0, -TILE_SIZE)), 0);
+    }
+}
@@ -0,0 +1,173 @@
+use gdnative::prelude::*;
+use gdnative::api::{CanvasLayer, ColorRect, Control};
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+
+/// The steps of a transition between two scenes
+#[derive(PartialEq, Clone, Debug)]
+pub enum TransitionState {
+    Idle,
+    FadingOut,
+    FadingIn
+}
+
+/// What happens when the screen it's completly black
+#[derive(Debug)]
+pub enum TransitionAction {
+    // Replaces the whole scene tree with the given scene
+    ChangeScene(String),
+    // Calls a method of any node, with the given arguments
+    Call(Ref<Object>, String, VariantArray)
+}
+
+#[derive(NativeClass)]
+#[inherit(CanvasLayer)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// Autoload that fades the screen to black, does the scene swap while nobody can see it, and fades back.
+///
+/// Every change of scene (`utils::change_scene`, warps, doors and battles) goes through this overlay, so all
+/// the transitions look the same. The player can't move while the transition lasts.
+pub struct TransitionOverlay {
+    // Seconds that lasts every half of the transition (to black, and back)
+    #[property(default = 0.4)]
+    fade_duration: f64,
+
+    overlay: Option<Ref<ColorRect>>,
+    state: TransitionState,
+    pending_action: Option<TransitionAction>,
+    release_player: bool,
+    alpha: f32,
+}
+
+impl RegisterSignal<Self> for TransitionOverlay {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "screen_black",
+            args: &[],
+        });
+        builder.add_signal( Signal {
+            name: "transition_finished",
+            args: &[],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl TransitionOverlay {
+    fn new(_owner: &CanvasLayer) -> Self {
+        Self {
+            fade_duration: 0.4,
+            overlay: None,
+            state: TransitionState::Idle,
+            pending_action: None,
+            release_player: true,
+            alpha: 0.0,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: &CanvasLayer) {
+        // Always over everything else
+        owner.set_layer(100);
+
+        let overlay = ColorRect::new();
+        overlay.set_anchors_preset(Control::PRESET_WIDE, false);
+        overlay.set_frame_color(Color::rgba(0.0, 0.0, 0.0, 0.0));
+        overlay.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+        let overlay = overlay.into_shared();
+        owner.add_child(overlay, false);
+        self.overlay = Some(overlay);
+    }
+
+    #[export]
+    fn _process(&mut self, owner: &CanvasLayer, delta: f64) {
+        let step = (delta / self.fade_duration.max(0.01)) as f32;
+
+        match self.state {
+            TransitionState::Idle => return,
+            TransitionState::FadingOut => {
+                self.alpha = (self.alpha + step).min(1.0);
+                if self.alpha >= 1.0 {
+                    self.run_pending_action(owner);
+                    owner.emit_signal("screen_black", &[]);
+                    self.state = TransitionState::FadingIn;
+                }
+            },
+            TransitionState::FadingIn => {
+                self.alpha = (self.alpha - step).max(0.0);
+                if self.alpha <= 0.0 {
+                    self.state = TransitionState::Idle;
+                    if self.release_player {
+                        self.set_player_locked(owner, false);
+                    }
+                    owner.emit_signal("transition_finished", &[]);
+                }
+            }
+        }
+
+        unsafe { self.overlay.unwrap().assume_safe().set_frame_color(Color::rgba(0.0, 0.0, 0.0, self.alpha)) };
+    }
+
+    #[export]
+    /// Fades to black, replaces the current scene with the given one, and fades back
+    fn change_scene(&mut self, owner: &CanvasLayer, scene_path: String) -> bool {
+        self.start_transition(owner, TransitionAction::ChangeScene(scene_path), true)
+    }
+
+    #[export]
+    /// Fades to black, calls the given method of the target when nobody can see the screen, and fades back.
+    ///
+    /// When `release_player` it's false, the player stays locked after the transition (like when a battle starts)
+    fn transition_and_call(&mut self, owner: &CanvasLayer, target: Ref<Object>, method: String,
+        args: VariantArray, release_player: bool) -> bool {
+            self.start_transition(owner, TransitionAction::Call(target, method, args), release_player)
+    }
+
+    #[export]
+    /// Sets how many seconds lasts every half of the transition
+    fn set_fade_duration(&mut self, _owner: &CanvasLayer, fade_duration: f64) {
+        self.fade_duration = fade_duration.max(0.0);
+    }
+
+    #[export]
+    fn is_transitioning(&self, _owner: &CanvasLayer) -> bool {
+        self.state != TransitionState::Idle
+    }
+
+    /// Starts a new transition. Returns false if there's another transition running
+    fn start_transition(&mut self, owner: &CanvasLayer, action: TransitionAction, release_player: bool) -> bool {
+        if self.state != TransitionState::Idle {
+            return false;
+        }
+        self.pending_action = Some(action);
+        self.release_player = release_player;
+        self.state = TransitionState::FadingOut;
+        self.set_player_locked(owner, true);
+        true
+    }
+
+    fn run_pending_action(&mut self, owner: &CanvasLayer) {
+        match self.pending_action.take() {
+            Some(TransitionAction::ChangeScene(scene_path)) => {
+                let scene_tree = unsafe { owner.get_tree().unwrap().assume_safe() };
+                if let Err(err) = scene_tree.change_scene(scene_path) {
+                    godot_print!("{}", err);
+                }
+            },
+            Some(TransitionAction::Call(target, method, args)) => {
+                unsafe { target.assume_safe().callv(method, args) };
+            },
+            None => ()
+        }
+    }
+
+    /// Locks (or releases) the player, if there's a player on the current scene. The call it's deferred, 'cause
+    /// the transition could have been started by the player himself (interacting with a door)
+    fn set_player_locked(&self, owner: &CanvasLayer, locked: bool) {
+        if let Some(player) = unsafe { owner.get_node("/root/Game/Player") } {
+            let signal_info = if locked { "on_transition" } else { "end_interaction" };
+            unsafe { player.assume_safe().call_deferred("handle_interaction", &[Variant::from_str(signal_info)]) };
+        }
+    }
+}
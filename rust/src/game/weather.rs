@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use super::battle::type_chart::Type;
+use super::game::Weather;
+
+// How much the wild encounters on the water grow while it's raining
+pub const RAIN_WATER_ENCOUNTER_BOOST: f64 = 1.5;
+// Damage multipliers of the weather over the attacks of the boosted and the weakened types
+pub const WEATHER_BOOST: f32 = 1.5;
+pub const WEATHER_WEAKEN: f32 = 0.5;
+
+/// The weather of the zone where the player is. Unlike the real weather that comes from the backend (`Weather`),
+/// this one has gameplay effects: it changes the wild encounters and the damage of the attacks on battle.
+///
+/// It's just game logic. The particles that shows it on the screen lives on the `WeatherOverlay`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FieldWeather {
+    Clear,
+    Rain,
+    Sandstorm,
+    Snow
+}
+
+impl Default for FieldWeather {
+    fn default() -> Self { FieldWeather::Clear }
+}
+
+impl FieldWeather {
+    pub fn from_string(weather: &str) -> Option<FieldWeather> {
+        match weather {
+            "Clear" => Some(FieldWeather::Clear),
+            "Rain" => Some(FieldWeather::Rain),
+            "Sandstorm" => Some(FieldWeather::Sandstorm),
+            "Snow" => Some(FieldWeather::Snow),
+            _ => None
+        }
+    }
+
+    pub fn to_weather_str(&self) -> &'static str {
+        match self {
+            FieldWeather::Clear => "Clear",
+            FieldWeather::Rain => "Rain",
+            FieldWeather::Sandstorm => "Sandstorm",
+            FieldWeather::Snow => "Snow"
+        }
+    }
+
+    /// The field weather that matches the real weather of a city, for the zones that follows the real one
+    pub fn from_real_weather(weather: &Weather) -> FieldWeather {
+        match weather {
+            Weather::Thunderstorm | Weather::Drizzle | Weather::Rain => FieldWeather::Rain,
+            Weather::Snow => FieldWeather::Snow,
+            Weather::Sun | Weather::Clouds => FieldWeather::Clear
+        }
+    }
+
+    /// Multiplier of the encounter rate of a zone. The rain brings out the Pokémon that lives on the water
+    pub fn encounter_rate_multiplier(&self, water_zone: bool) -> f64 {
+        match self {
+            FieldWeather::Rain if water_zone => RAIN_WATER_ENCOUNTER_BOOST,
+            _ => 1.0
+        }
+    }
+
+    /// Multiplier of the damage of an attack of the given type, made while this weather it's active
+    pub fn damage_multiplier(&self, attack: Type) -> f32 {
+        match (self, attack) {
+            (FieldWeather::Rain, Type::Water) => WEATHER_BOOST,
+            (FieldWeather::Rain, Type::Fire) => WEATHER_WEAKEN,
+            _ => 1.0
+        }
+    }
+}
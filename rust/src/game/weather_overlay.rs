@@ -0,0 +1,67 @@
+use gdnative::prelude::*;
+use gdnative::api::Particles2D;
+
+use super::weather::FieldWeather;
+
+// The Particles2D children of the overlay, one for every weather that shows something on the screen
+const WEATHER_PARTICLES: [(FieldWeather, &str); 3] = [
+    (FieldWeather::Rain, "Rain"),
+    (FieldWeather::Sandstorm, "Sandstorm"),
+    (FieldWeather::Snow, "Snow"),
+];
+
+#[derive(NativeClass)]
+#[inherit(Node2D)]
+#[derive(Debug)]
+/// Draws the weather of the zone where the player is, turning on the particles that matches it.
+///
+/// Doesn't know anything about the rules of the weather: it just listens to the `weather_changed` signal of the Game
+pub struct WeatherOverlay {
+    current_weather: FieldWeather,
+}
+
+#[gdnative::methods]
+impl WeatherOverlay {
+    fn new(_owner: &Node2D) -> Self {
+        Self {
+            current_weather: FieldWeather::Clear,
+        }
+    }
+
+    #[export]
+    fn _ready(&mut self, owner: TRef<Node2D>) {
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            self.connect_to_game(owner, unsafe { game.assume_safe() });
+        }
+        // The Game could be busy adding this scene to the tree, so it gets asked on the next idle frame
+        unsafe { owner.call_deferred("_sync_with_game", &[]) };
+    }
+
+    #[export]
+    /// Draws the weather that the game already has, like the one restored from the saved game
+    fn _sync_with_game(&mut self, owner: TRef<Node2D>) {
+        if let Some(game) = unsafe { owner.get_node("/root/Game") } {
+            let weather = unsafe { game.assume_safe().call("get_field_weather", &[]) }.to_string();
+            self._on_weather_changed(owner, weather);
+        }
+    }
+
+    #[export]
+    fn _on_weather_changed(&mut self, owner: TRef<Node2D>, weather: String) {
+        self.current_weather = FieldWeather::from_string(&weather).unwrap_or_default();
+
+        for (particles_weather, node_name) in WEATHER_PARTICLES.iter() {
+            if let Some(particles) = unsafe { owner.get_node_as::<Particles2D>(*node_name) } {
+                particles.set_emitting(*particles_weather == self.current_weather);
+            }
+        }
+    }
+
+    /// Listens the weather changes of the Game
+    fn connect_to_game(&self, owner: TRef<Node2D>, game: TRef<Node>) {
+        if !game.is_connected("weather_changed", owner, "_on_weather_changed") {
+            game.connect("weather_changed", owner, "_on_weather_changed",
+                VariantArray::new_shared(), 0).unwrap();
+        }
+    }
+}
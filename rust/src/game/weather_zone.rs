@@ -0,0 +1,55 @@
+use gdnative::prelude::*;
+use gdnative::api::Area2D;
+
+use crate::game::code_abstractions::signals::RegisterSignal;
+
+#[derive(NativeClass)]
+#[inherit(Area2D)]
+#[register_with(Self::register_signal)]
+#[derive(Debug)]
+/// A zone of the map with his own weather (a route where it's always raining, a desert...).
+///
+/// When the player walks into the zone, the Game swaps the current weather for the one of the zone
+pub struct WeatherZone {
+    // Clear, Rain, Sandstorm or Snow
+    #[property]
+    weather: String,
+}
+
+impl RegisterSignal<Self> for WeatherZone {
+    fn register_signal(builder: &ClassBuilder<Self>) {
+        builder.add_signal( Signal {
+            name: "weather_zone_entered",
+            args: &[ SignalArgument {
+                name: "weather",
+                default: Variant::from_str("Clear"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+    }
+}
+
+#[gdnative::methods]
+impl WeatherZone {
+    fn new(_owner: &Area2D) -> Self {
+        Self {
+            weather: "Clear".to_string(),
+        }
+    }
+
+    #[export]
+    fn _ready(&self, owner: TRef<Area2D>) {
+        let game = unsafe { owner.get_node("/root/Game").unwrap().assume_safe() };
+        owner.connect("weather_zone_entered", game, "change_weather",
+            VariantArray::new_shared(), 0).unwrap();
+    }
+
+    #[export]
+    /// Receives a signal when a body enteres the WeatherZone (connected on the Godot GUI)
+    fn _on_area2d_body_entered(&self, owner: TRef<Area2D>, body: Ref<Node>) {
+        if unsafe { body.assume_safe() }.name().to_string() == "Player" {
+            owner.emit_signal("weather_zone_entered", &[self.weather.to_variant()]);
+        }
+    }
+}
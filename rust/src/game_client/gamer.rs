@@ -1,5 +1,108 @@
 use gdnative::prelude::*;
 
+use crate::game::pokemon::MAX_LEVEL;
+use crate::game::species::GrowthRate;
+
+/// The stats of the Player Character of a Gamer, that grow with his level
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStats {
+    max_hp: u32,
+    current_hp: u32,
+    // The experience earned since the beginning, not only on the current level
+    experience: u32,
+    attack: u32,
+    defense: u32,
+    speed: u32,
+}
+
+impl PlayerStats {
+
+    pub fn new(max_hp: u32, attack: u32, defense: u32, speed: u32) -> Self {
+        Self {
+            max_hp,
+            current_hp: max_hp,
+            experience: 0,
+            attack,
+            defense,
+            speed,
+        }
+    }
+
+    /// The stats of a fresh character of the given level: every stat grows a fixed amount over his base with
+    /// every level, and the character starts with his HP full
+    pub fn from_level(level: i8) -> Self {
+        let level = level.max(1) as u32;
+        Self::new(20 + 5 * level, 5 + 2 * level, 5 + 2 * level, 5 + level)
+    }
+
+    pub fn get_max_hp(&self) -> u32 {
+        self.max_hp
+    }
+
+    pub fn get_current_hp(&self) -> u32 {
+        self.current_hp
+    }
+
+    pub fn get_experience(&self) -> u32 {
+        self.experience
+    }
+
+    pub fn get_attack(&self) -> u32 {
+        self.attack
+    }
+
+    pub fn get_defense(&self) -> u32 {
+        self.defense
+    }
+
+    pub fn get_speed(&self) -> u32 {
+        self.speed
+    }
+
+    /// Grows the stats up to the ones of the given level, keeping the experience. The HP gained with the level
+    /// gets also healed, so the character doesn't end the level up hurt
+    fn grow_to_level(&mut self, level: i8) {
+        let grown = Self::from_level(level);
+        self.current_hp = self.current_hp + grown.max_hp.saturating_sub(self.max_hp);
+        self.max_hp = grown.max_hp;
+        self.attack = grown.attack;
+        self.defense = grown.defense;
+        self.speed = grown.speed;
+    }
+}
+
+/// What changed on the Player Character after earning experience, so the UI can show it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LevelUpSummary {
+    pub levels_gained: u8,
+    pub max_hp_increase: u32,
+    pub attack_increase: u32,
+    pub defense_increase: u32,
+    pub speed_increase: u32,
+}
+
+impl LevelUpSummary {
+    fn between(before: &PlayerStats, after: &PlayerStats, levels_gained: u8) -> Self {
+        Self {
+            levels_gained,
+            max_hp_increase: after.max_hp.saturating_sub(before.max_hp),
+            attack_increase: after.attack.saturating_sub(before.attack),
+            defense_increase: after.defense.saturating_sub(before.defense),
+            speed_increase: after.speed.saturating_sub(before.speed),
+        }
+    }
+
+    /// The names of the stats that went up, on the Spanish that the UI shows
+    pub fn get_increased_stats(&self) -> Vec<&'static str> {
+        [("PS", self.max_hp_increase), ("Ataque", self.attack_increase),
+            ("Defensa", self.defense_increase), ("Velocidad", self.speed_increase)]
+            .iter()
+            .filter(|(_, increase)| *increase > 0)
+            .map(|(stat, _)| *stat)
+            .collect()
+    }
+}
+
 /// Base class for that holds the user's account related data of the real person
 ///
 /// This one allows to create new Gamer instances mapping the `client actions`: 
@@ -14,6 +117,9 @@ pub struct Gamer {
     username: Option<String>,
     password: Option<String>,
     level: Option<i8>, // This should be a hash map that tracks language : level
+    stats: PlayerStats,
+    // How much experience the Player Character needs to level up
+    growth_rate: GrowthRate,
 }
 
 impl Gamer {
@@ -24,14 +130,68 @@ impl Gamer {
         password: String, 
         level: i8) -> Self {
 
+        let growth_rate = GrowthRate::default();
+        let mut stats = PlayerStats::from_level(level);
+        stats.experience = growth_rate.experience_for_level(level.max(1) as u8);
+
         let gamer: Gamer = Gamer { 
             username: Some(username), 
             password: Some(password), 
             level: Some(level),
+            stats,
+            growth_rate,
         };
         gamer
     }
 
+    pub fn get_username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    pub fn get_level(&self) -> Option<i8> {
+        self.level
+    }
+
+    pub fn get_stats(&self) -> &PlayerStats {
+        &self.stats
+    }
+
+    pub fn get_growth_rate(&self) -> &GrowthRate {
+        &self.growth_rate
+    }
+
+    /// Changes the curve that the Player Character follows to level up. The experience already earned it's kept,
+    /// so the next level up comes sooner or later than before
+    pub fn set_growth_rate(&mut self, growth_rate: GrowthRate) {
+        self.growth_rate = growth_rate;
+    }
+
+    /// Adds experience to the Player Character, leveling him up every time that crosses the threshold of the next
+    /// level on his growth rate. A big amount of experience can give more than one level at once, but never past the maximum level.
+    ///
+    /// Returns the levels gained and how much grew every stat
+    pub fn add_experience(&mut self, amount: u32) -> LevelUpSummary {
+        let mut level = self.level.unwrap_or(1).max(1) as u8;
+        if level >= MAX_LEVEL {
+            return LevelUpSummary::default();
+        }
+
+        let before = self.stats.clone();
+        self.stats.experience = self.stats.experience.saturating_add(amount);
+
+        let mut levels_gained = 0;
+        while level < MAX_LEVEL && self.stats.experience >= self.growth_rate.experience_for_level(level + 1) {
+            level += 1;
+            levels_gained += 1;
+        }
+
+        if levels_gained > 0 {
+            self.level = Some(level as i8);
+            self.stats.grow_to_level(level as i8);
+        }
+        LevelUpSummary::between(&before, &self.stats, levels_gained)
+    }
+
     pub fn check_credentials(username: Option<&String>, password: Option<&String>) -> (bool, bool) {
 
         let mut credentials_flag: (bool, bool) = (false, false);
@@ -61,3 +221,98 @@ impl Gamer {
         (credentials.0.to_string(), credentials.1.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GROWTH_RATES: [GrowthRate; 5] = [GrowthRate::Fast, GrowthRate::MediumFast, GrowthRate::MediumSlow,
+        GrowthRate::Slow, GrowthRate::Erratic];
+
+    fn gamer(level: i8) -> Gamer {
+        Gamer::gamer_login("Teo".to_string(), "root".to_string(), level)
+    }
+
+    #[test]
+    fn the_stats_come_from_the_level() {
+        let stats = PlayerStats::from_level(5);
+        assert_eq!(stats, PlayerStats::new(45, 15, 15, 10));
+        assert_eq!(stats.get_current_hp(), stats.get_max_hp());
+        assert_eq!(stats.get_experience(), 0);
+        // A level under 1 it's the level 1
+        assert_eq!(PlayerStats::from_level(-3), PlayerStats::from_level(1));
+    }
+
+    #[test]
+    fn a_new_gamer_has_the_experience_of_his_level() {
+        let gamer = gamer(5);
+        assert_eq!(gamer.get_stats().get_experience(), GrowthRate::MediumFast.experience_for_level(5));
+        assert_eq!(gamer.get_stats().get_attack(), 15);
+    }
+
+    #[test]
+    fn a_big_amount_of_experience_gives_many_levels() {
+        let mut gamer = gamer(5);
+        // The Medium Fast level 10 needs 1000 points, and the level 5 already has 125
+        let summary = gamer.add_experience(875);
+
+        assert_eq!(gamer.get_level(), Some(10));
+        assert_eq!(summary, LevelUpSummary {
+            levels_gained: 5,
+            max_hp_increase: 25,
+            attack_increase: 10,
+            defense_increase: 10,
+            speed_increase: 5,
+        });
+        assert_eq!(summary.get_increased_stats(), vec!["PS", "Ataque", "Defensa", "Velocidad"]);
+        assert_eq!(gamer.get_stats().get_attack(), PlayerStats::from_level(10).get_attack());
+        assert_eq!(gamer.get_stats().get_experience(), 1000);
+    }
+
+    #[test]
+    fn experience_under_the_next_level_keeps_the_level() {
+        let mut gamer = gamer(5);
+        let summary = gamer.add_experience(GrowthRate::MediumFast.experience_for_level(6) - 126);
+        assert_eq!(summary, LevelUpSummary::default());
+        assert!(summary.get_increased_stats().is_empty());
+        assert_eq!(gamer.get_level(), Some(5));
+    }
+
+    #[test]
+    fn the_level_up_heals_the_hp_gained() {
+        let mut gamer = gamer(5);
+        gamer.stats.current_hp = 10;
+        gamer.add_experience(GrowthRate::MediumFast.experience_for_level(6) - 125);
+        assert_eq!(gamer.get_stats().get_max_hp(), 50);
+        assert_eq!(gamer.get_stats().get_current_hp(), 15);
+    }
+
+    #[test]
+    fn every_growth_rate_stops_at_the_max_level() {
+        for growth_rate in GROWTH_RATES.iter() {
+            let mut gamer = gamer(1);
+            gamer.set_growth_rate(growth_rate.clone());
+
+            let summary = gamer.add_experience(u32::MAX);
+            assert_eq!(summary.levels_gained, MAX_LEVEL - 1, "{:?}", growth_rate);
+            assert_eq!(gamer.get_level(), Some(MAX_LEVEL as i8), "{:?}", growth_rate);
+
+            assert_eq!(gamer.add_experience(1_000_000), LevelUpSummary::default(), "{:?}", growth_rate);
+            assert_eq!(gamer.get_level(), Some(MAX_LEVEL as i8), "{:?}", growth_rate);
+        }
+    }
+
+    #[test]
+    fn every_growth_rate_reaches_the_max_level_with_his_own_experience() {
+        for growth_rate in GROWTH_RATES.iter() {
+            let mut gamer = gamer(1);
+            gamer.set_growth_rate(growth_rate.clone());
+            let needed = growth_rate.experience_for_level(MAX_LEVEL) - gamer.get_stats().get_experience();
+
+            gamer.add_experience(needed - 1);
+            assert_eq!(gamer.get_level(), Some(MAX_LEVEL as i8 - 1), "{:?}", growth_rate);
+            gamer.add_experience(1);
+            assert_eq!(gamer.get_level(), Some(MAX_LEVEL as i8), "{:?}", growth_rate);
+        }
+    }
+}
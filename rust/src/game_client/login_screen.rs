@@ -3,6 +3,7 @@ use gdnative::api::{LineEdit, Node};
 
 use crate::utils::utils;
 use crate::game_client::gamer::Gamer;
+use crate::game_client::session;
 use crate::utils::consts::{labels, line_edit, scenes};
 #[derive(NativeClass)]
 #[inherit(Node)]
@@ -34,28 +35,29 @@ impl LoginScreen {
             );
     }
 
-    /// Gets the inputed credentials on the Login Screen Line Edits
-    fn retrieve_credentials(&self, _owner: &Node) -> (String, String){
-        let get_username_on_input = unsafe 
-            { _owner.get_node_as::<LineEdit>(
-                &line_edit::USERNAME_LINE_EDIT_PATH) }
-            .unwrap()
+    /// Gets the inputed credentials on the Login Screen Line Edits.
+    ///
+    /// Returns None when some of the Line Edits are missing on the scene
+    fn retrieve_credentials(&self, _owner: &Node) -> Option<(String, String)> {
+        let get_username_on_input = utils::try_get_node_as::<LineEdit>(
+            _owner, line_edit::USERNAME_LINE_EDIT_PATH)?
             .text();
-        let get_password_on_input = unsafe 
-            { _owner.get_node_as::<LineEdit>(
-                &line_edit::PASSWORD_LINE_EDIT_PATH) }
-            .unwrap()
+        let get_password_on_input = utils::try_get_node_as::<LineEdit>(
+            _owner, line_edit::PASSWORD_LINE_EDIT_PATH)?
             .text();
 
         // Returns a tuple with the credentials converted from GodotString to Rust String
-        Gamer::credentials_to_rust_string((get_username_on_input, get_password_on_input))
+        Some(Gamer::credentials_to_rust_string((get_username_on_input, get_password_on_input)))
     }
 
     #[export]
     /// The receiver of the signal from Godot when the login button gets pressed
     fn _on_login_button_pressed(&mut self, _owner: &Node) {
 
-        let (username, password): (String, String) = self.retrieve_credentials(_owner);
+        let (username, password): (String, String) = match self.retrieve_credentials(_owner) {
+            Some(credentials) => credentials,
+            None => return
+        };
 
         let credentials_status = 
             Gamer::check_credentials(
@@ -68,12 +70,19 @@ impl LoginScreen {
                 // Credentials are correct, so a new Gamer is instanciated
                 new_player = Gamer::gamer_login(username, password, 1);
                 utils::show_player_attributes(&new_player);
+
+                // The save system picks the progress of the account from the session
+                if let Some(username) = new_player.get_username() {
+                    session::log_in(username);
+                }
                 
                 // Storing a reference to the new player as the current Gamer for the "game session"
                 &mut self.set_player(Some(new_player));
                 
                 // Finally, with the new player creaded we can move to the main scene
-                utils::change_scene(_owner, scenes::LEVEL_1.to_string());
+                if let Err(err) = utils::change_scene(_owner, scenes::LEVEL_1.to_string()) {
+                    godot_print!("The game can't start. {}", err);
+                }
             },
             // This should be changed for on screen labels on the future. Fine for now ;)
             (true, false) => godot_print!("Wrong password. Try again."),
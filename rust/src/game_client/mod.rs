@@ -1,2 +1,3 @@
 pub mod gamer;
-pub mod login_screen;
\ No newline at end of file
+pub mod login_screen;
+pub mod session;
@@ -0,0 +1,77 @@
+use std::sync::Mutex;
+
+// The username of the gamer that logged in. It outlives the login screen, so the Game (and the save system)
+// know whose progress they're working with once the world scene it's loaded
+static LOGGED_IN_USERNAME: Mutex<Option<String>> = Mutex::new(None);
+// The save slot that the game saves to and loads from. Every account has the same slots
+static SAVE_SLOT: Mutex<u8> = Mutex::new(1);
+
+/// How many independent saves every account has. The slots go from 1 to this one
+pub const SAVE_SLOTS: u8 = 3;
+
+/// Starts the session of the given gamer. Logging in with another account just replaces the previous one
+pub fn log_in(username: &str) {
+    if let Ok(mut logged_in_username) = LOGGED_IN_USERNAME.lock() {
+        *logged_in_username = Some(username.to_string());
+    }
+}
+
+/// The username of the gamer currently logged in, if anyone it's
+pub fn get_logged_in_username() -> Option<String> {
+    LOGGED_IN_USERNAME.lock().ok().and_then(|logged_in_username| logged_in_username.clone())
+}
+
+/// Chooses the save slot used by the next saves and loads. A slot out of range it's refused, keeping the current one
+pub fn select_save_slot(slot: u8) -> bool {
+    if slot == 0 || slot > SAVE_SLOTS {
+        return false;
+    }
+    match SAVE_SLOT.lock() {
+        Ok(mut save_slot) => {
+            *save_slot = slot;
+            true
+        },
+        Err(_) => false
+    }
+}
+
+/// The save slot currently in use
+pub fn get_save_slot() -> u8 {
+    SAVE_SLOT.lock().map(|save_slot| *save_slot).unwrap_or(1)
+}
+
+/// Turns a username into a piece of a file name that's safe on every filesystem.
+///
+/// The ASCII letters (lowercased, so `Root` and `root` share the progress, the same way that they share the
+/// credentials), digits, `-` and `_` are kept. Every other character it's encoded as `~` and his code point in hex,
+/// always with six digits (enough for any code point), so the kept characters after it can't be read as part of
+/// the code and two different usernames never end up on the same file
+pub fn sanitize_username(username: &str) -> String {
+    username.chars()
+        .map(|character| match character {
+            'a'..='z' | '0'..='9' | '-' | '_' => character.to_string(),
+            'A'..='Z' => character.to_ascii_lowercase().to_string(),
+            _ => format!("~{:06x}", character as u32)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_plain_usernames_keep_their_name() {
+        assert_eq!(sanitize_username("ash_ketchum-99"), "ash_ketchum-99");
+        assert_eq!(sanitize_username("Root"), "root");
+    }
+
+    #[test]
+    fn the_escaped_characters_never_collide() {
+        assert_eq!(sanitize_username("é1"), "~0000e91");
+        assert_eq!(sanitize_username("\u{e91}"), "~000e91");
+        assert_ne!(sanitize_username("é1"), sanitize_username("\u{e91}"));
+        assert_eq!(sanitize_username("../save"), "~00002e~00002e~00002fsave");
+        assert_eq!(sanitize_username("\u{10ffff}"), "~10ffff");
+    }
+}
@@ -14,10 +14,34 @@ use game::area_scene_switcher::AreaSceneSwitcher;
 use game::tall_grass::TallGrass;
 use game::grass_step_effect::GrassStepEffect;
 use game::landing_dust_effect::LandingDustEffect;
+use game::sprint_dust_effect::SprintDustEffect;
 use game::map::Map;
 use game::player::{PlayerAnimation, PlayerCharacter};
+use game::trainer::Trainer;
+use game::npc::{Npc, NpcAnimation};
+use game::interactables::{
+    berry_spot::BerrySpot, fishing_spot::FishingSpot, healing_station::HealingStation, item_ball::ItemBall, shop::Shop,
+    sign::Sign, warp::Warp
+};
 use game::dialogue_box::DialogueBox;
 use game::menu::menu::Menu;
+use game::menu::party_menu::PartyMenu;
+use game::menu::summary_menu::SummaryMenu;
+use game::menu::nickname_menu::NicknameMenu;
+use game::menu::bag_menu::BagMenu;
+use game::menu::shop_menu::ShopMenu;
+use game::menu::quest_menu::QuestMenu;
+use game::menu::move_learn_menu::MoveLearnMenu;
+use game::music_manager::MusicManager;
+use game::settings::SettingsController;
+use game::transition_overlay::TransitionOverlay;
+use game::weather_overlay::WeatherOverlay;
+use game::weather_zone::WeatherZone;
+use game::momentum_zone::MomentumZone;
+use game::minimap::Minimap;
+use game::debug_overlay::DebugOverlay;
+use game::cutscene::Cutscene;
+use game::battle::hp_bar::HpBar;
 use game::menu::pokedex::pokedex::Pokedex;
 
 use game::map_elements__galicia::{
@@ -34,6 +58,9 @@ fn init(handle: InitHandle) {
     handle.add_class::<LoginScreen>();
     handle.add_class::<PlayerCharacter>();
     handle.add_class::<PlayerAnimation>();
+    handle.add_class::<Trainer>();
+    handle.add_class::<Npc>();
+    handle.add_class::<NpcAnimation>();
 
     handle.add_class::<Game>();
     handle.add_class::<Pokemon>();
@@ -44,11 +71,36 @@ fn init(handle: InitHandle) {
     handle.add_class::<TallGrass>();
     handle.add_class::<GrassStepEffect>();
     handle.add_class::<LandingDustEffect>();
+    handle.add_class::<SprintDustEffect>();
     
     handle.add_class::<Menu>();
+    handle.add_class::<PartyMenu>();
+    handle.add_class::<SummaryMenu>();
+    handle.add_class::<NicknameMenu>();
+    handle.add_class::<BagMenu>();
+    handle.add_class::<ShopMenu>();
+    handle.add_class::<QuestMenu>();
+    handle.add_class::<MoveLearnMenu>();
+    handle.add_class::<MusicManager>();
+    handle.add_class::<SettingsController>();
+    handle.add_class::<TransitionOverlay>();
+    handle.add_class::<WeatherOverlay>();
+    handle.add_class::<WeatherZone>();
+    handle.add_class::<MomentumZone>();
+    handle.add_class::<Minimap>();
+    handle.add_class::<DebugOverlay>();
+    handle.add_class::<Cutscene>();
+    handle.add_class::<HpBar>();
     handle.add_class::<DialogueBox>();
     handle.add_class::<Pokedex>();
     
+    handle.add_class::<Sign>();
+    handle.add_class::<ItemBall>();
+    handle.add_class::<Warp>();
+    handle.add_class::<Shop>();
+    handle.add_class::<HealingStation>();
+    handle.add_class::<FishingSpot>();
+    handle.add_class::<BerrySpot>();
     handle.add_class::<area1_pueblo_de_teo::truck::Truck>();
 }
 
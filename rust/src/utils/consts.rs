@@ -7,9 +7,47 @@ pub mod in_game_constant {
 
     pub const VELOCITY: f32 = 100.0;
     pub const WALK_SPEED: f64 = 4.0;
+    pub const RUN_SPEED: f64 = 8.0;
+    pub const BIKE_SPEED: f64 = 10.0;
     pub const JUMP_SPEED: f64 = 4.0;
     pub const TILE_SIZE: f32 = 16.0;
+    // Where the player appears when his position can't be read from the save. The same place where the new games start
+    pub const DEFAULT_SPAWN_POSITION: (f32, f32) = (1456.5333, 717.6);
+    // How the walking and running speeds change over every terrain. The plain ground keeps them as they are
+    pub const TALL_GRASS_SPEED_MULTIPLIER: f64 = 0.8;
+    pub const SAND_SPEED_MULTIPLIER: f64 = 0.7;
+    pub const ICE_SPEED_MULTIPLIER: f64 = 1.25;
     pub const UP: Vector2 = Vector2::new(0.0, -1.0);
+    // Chance of find a wild Pokémon per step, when the encounter zone doesn't provide his own rate
+    pub const DEFAULT_ENCOUNTER_RATE: f64 = 0.1;
+    // Chance of find a wild Pokémon per step over the tall grass tiles of every area, by area name. The areas
+    // that aren't here use the rate of the balance config
+    pub const AREA_ENCOUNTER_RATES: [(&str, f64); 5] = [
+        ("PuebloDeTeo", 0.1),
+        ("Ames", 0.12),
+        ("SantiagoDeCompostela", 0.05),
+        ("ACoruña", 0.08),
+        ("CampoDePruebas", 0.25),
+    ];
+    // One of every this many wild Pokémon it's shiny. The shiny charm makes them three times more common
+    pub const SHINY_ODDS: u32 = 4096;
+    pub const SHINY_CHARM_ODDS: u32 = 1365;
+    // The part of his money that the player loses when his whole party faints
+    pub const BLACKOUT_MONEY_LOSS: f64 = 0.5;
+    // Real seconds that lasts an hour of the in-game clock, and the hour when a new game starts
+    pub const DEFAULT_SECONDS_PER_GAME_HOUR: f64 = 60.0;
+    pub const STARTING_GAME_HOUR: f64 = 10.0;
+    // Real seconds of play between every autosave
+    pub const AUTOSAVE_INTERVAL_SECONDS: f64 = 300.0;
+}
+
+pub mod save_thumbnails {
+    // Where the screenshots of the saved games are stored, and how big they are
+    pub const SAVE_THUMBNAILS_DIR: &str = "user://thumbnails";
+    pub const SAVE_THUMBNAIL_WIDTH: i64 = 160;
+    pub const SAVE_THUMBNAIL_HEIGHT: i64 = 90;
+    // Shown when the screenshot couldn't be taken, or when it's gone
+    pub const DEFAULT_SAVE_THUMBNAIL: &str = "res://gfx/Misc/gallaecia_logo.png";
 }
 
 pub mod game_options {
@@ -30,6 +68,8 @@ pub mod line_edit {
         "VBoxContainer/HBoxContainer/UsernameInput";
     pub const PASSWORD_LINE_EDIT_PATH: &str =
         "VBoxContainer/HBoxContainer/PasswordInput";
+    pub const NICKNAME_LINE_EDIT_PATH: &str =
+        "Nickname";
 }
 
 pub mod scenes {
@@ -38,7 +78,15 @@ pub mod scenes {
     // pub const PLAYER_AS_SCENE: &str =
     //     "res://godot/Player.tscn";
 
+    pub const GAME: &str =
+        "res://godot/Game/Game.tscn";
     pub const LEVEL_1: &str =
         "res://godot/Levels/Level_1.tscn";
+    pub const BATTLE: &str =
+        "res://godot/Game/Battle.tscn";
+    pub const NICKNAME_MENU: &str =
+        "res://godot/Game/NicknameMenu.tscn";
+    pub const MOVE_LEARN_MENU: &str =
+        "res://godot/Game/MoveLearnMenu.tscn";
 }
 
@@ -1,6 +1,5 @@
 pub mod consts;
 pub mod utils;
 pub mod networking;
+pub mod save_integrity;
 pub mod secret;
-
-pub mod all_pokemon_species;
\ No newline at end of file
@@ -0,0 +1,145 @@
+// Every save carries a checksum on his last line, so a save half-written (by a power loss in the middle of the
+// write...) it's detected before its data gets trusted. This it's the beginning of that line
+const CHECKSUM_PREFIX: &str = "#checksum:";
+
+/// The reasons why the text of a save can't be trusted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveIntegrityError {
+    // There's no checksum line. The saves from before the checksums don't have it, but neither the truncated ones
+    MissingChecksum,
+    // The checksum doesn't match the data, so something changed it after the write
+    Corrupted,
+}
+
+/// A 64 bits FNV-1a hash of the given data. It isn't meant to stop anyone from editing the saves on purpose, only
+/// to notice the accidents
+pub fn checksum(data: &str) -> u64 {
+    data.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// The text of a save ready to be written, with the checksum of the data appended on his last line
+pub fn seal(save: &str) -> String {
+    format!("{}\n{}{:016x}\n", save, CHECKSUM_PREFIX, checksum(save))
+}
+
+/// Gives back the data of a sealed save, once it's checked against his checksum
+pub fn unseal(sealed_save: &str) -> Result<&str, SaveIntegrityError> {
+    let sealed_save = sealed_save.trim_end();
+    let checksum_start = sealed_save.rfind(&format!("\n{}", CHECKSUM_PREFIX))
+        .ok_or(SaveIntegrityError::MissingChecksum)?;
+    let (save, checksum_line) = sealed_save.split_at(checksum_start);
+    let stored_checksum = u64::from_str_radix(&checksum_line[CHECKSUM_PREFIX.len() + 1..], 16)
+        .map_err(|_| SaveIntegrityError::Corrupted)?;
+
+    if stored_checksum == checksum(save) {
+        Ok(save)
+    } else {
+        Err(SaveIntegrityError::Corrupted)
+    }
+}
+
+/// The data of a save that can be trusted: a sealed one whose checksum matches, or one from before the checksums
+/// that's still a whole JSON
+pub fn trusted_save_data(save: &str) -> Option<&str> {
+    match unseal(save) {
+        Ok(save_data) => Some(save_data),
+        Err(SaveIntegrityError::MissingChecksum) if serde_json::from_str::<serde_json::Value>(save).is_ok() => Some(save),
+        Err(_) => None
+    }
+}
+
+/// Where the data of a loaded save came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveSource {
+    Save,
+    // The last good save, because the current one can't be trusted
+    Backup,
+}
+
+/// Why neither a save nor his backup can be loaded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveReadError {
+    // There's no save at all, like on an empty slot
+    Missing,
+    // There's a save, but neither it nor his backup can be trusted
+    Corrupted,
+}
+
+/// Picks the data to load between the text of a save and the one of his backup (None when the file doesn't exist).
+/// The save always wins while it can be trusted
+pub fn pick_trusted_save<'a>(save: Option<&'a str>, backup: Option<&'a str>)
+    -> Result<(&'a str, SaveSource), SaveReadError>
+{
+    if let Some(save_data) = save.and_then(trusted_save_data) {
+        return Ok((save_data, SaveSource::Save));
+    }
+    match backup.and_then(trusted_save_data) {
+        Some(backup_data) => Ok((backup_data, SaveSource::Backup)),
+        None if save.is_none() => Err(SaveReadError::Missing),
+        None => Err(SaveReadError::Corrupted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAVE: &str = "{\"player_data\": {\"name\": \"Teo\", \"money\": 3000}}";
+
+    #[test]
+    fn a_sealed_save_unseals_to_the_same_data() {
+        let sealed_save = seal(SAVE);
+        assert_eq!(unseal(&sealed_save), Ok(SAVE));
+        assert_eq!(trusted_save_data(&sealed_save), Some(SAVE));
+    }
+
+    #[test]
+    fn a_flipped_byte_gets_detected() {
+        let mut sealed_save = seal(SAVE).into_bytes();
+        // The 3000 of the money turns into 3001
+        let money_position = SAVE.find("3000").unwrap() + 3;
+        sealed_save[money_position] = b'1';
+        let sealed_save = String::from_utf8(sealed_save).unwrap();
+
+        assert_eq!(unseal(&sealed_save), Err(SaveIntegrityError::Corrupted));
+        assert_eq!(trusted_save_data(&sealed_save), None);
+    }
+
+    #[test]
+    fn a_truncated_save_gets_detected() {
+        let sealed_save = seal(SAVE);
+        let truncated_save = &sealed_save[..SAVE.len() / 2];
+        assert_eq!(unseal(truncated_save), Err(SaveIntegrityError::MissingChecksum));
+        assert_eq!(trusted_save_data(truncated_save), None);
+    }
+
+    #[test]
+    fn a_save_from_before_the_checksums_is_trusted_while_it_is_whole() {
+        assert_eq!(trusted_save_data(SAVE), Some(SAVE));
+    }
+
+    #[test]
+    fn the_backup_is_loaded_when_the_save_is_corrupted() {
+        let corrupted_save = seal(SAVE).replace("Teo", "Tea");
+        let backup = seal(SAVE);
+        assert_eq!(pick_trusted_save(Some(&corrupted_save), Some(&backup)), Ok((SAVE, SaveSource::Backup)));
+    }
+
+    #[test]
+    fn the_save_wins_over_the_backup() {
+        let save = seal(SAVE);
+        let backup = seal("{}");
+        assert_eq!(pick_trusted_save(Some(&save), Some(&backup)), Ok((SAVE, SaveSource::Save)));
+    }
+
+    #[test]
+    fn a_corrupted_save_without_backup_can_not_be_loaded() {
+        let corrupted_save = seal(SAVE).replace("Teo", "Tea");
+        assert_eq!(pick_trusted_save(Some(&corrupted_save), None), Err(SaveReadError::Corrupted));
+    }
+
+    #[test]
+    fn a_missing_save_is_not_corrupted() {
+        assert_eq!(pick_trusted_save(None, None), Err(SaveReadError::Missing));
+    }
+}
@@ -1,14 +1,45 @@
 use gdnative::prelude::*;
-use gdnative::api::{File, JSON, Node};
+use gdnative::api::{Directory, File, Image, JSON, Node};
 
 use crate::game::game::Game;
+use crate::game::encounter::EncounterTable;
+use crate::game::species::SpeciesTable;
+use crate::game::music_manager::MusicConfig;
+use crate::game::settings::Settings;
+use crate::game::shop::ShopConfig;
+use crate::game::dialogues::DialogueRegistry;
+use crate::game::cutscene::CutsceneTable;
+use crate::game::trainer::TrainerTable;
+use crate::game::quests::QuestTable;
+use crate::game::balance::BalanceConfig;
+use crate::game::save_migration;
 
 use crate::game_client::gamer::Gamer;
-use crate::game::player::PlayerDirection;
+use crate::game_client::session;
+use crate::utils::consts::save_thumbnails;
+use crate::utils::save_integrity::{self, SaveReadError, SaveSource};
+use crate::game::player::{PlayerDirection, PlayerStatus};
 
 use chrono::{Datelike, Duration as Dur, NaiveDate, NaiveTime, Utc, Weekday};
 use chrono::prelude::{DateTime, Local};
 use std::time::{UNIX_EPOCH, Duration};
+use std::sync::Mutex;
+
+// The save that every empty slot starts from, with the starting progress of the game
+const STARTING_SAVE_FILE: &str = "gamestate";
+// The save file of every slot it's named with this and the number of the slot
+const SAVE_SLOT_FILE_PREFIX: &str = "save_slot_";
+// Appended to the path of a save: the last good save, kept in case the current one gets corrupted, and the
+// new save while it's being written
+const BACKUP_SAVE_EXTENSION: &str = ".bak";
+const TEMP_SAVE_EXTENSION: &str = ".tmp";
+
+// The warnings about the saves already logged. The Game reads the save on every frame until the external data
+// arrives, so every warning it's only logged the first time
+static LOGGED_SAVE_WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+// The main dialogues file, and the folder where the rest of the dialogues files can be placed
+const DIALOGUES_FILE: &str = "res://godot/dialogues.json";
+const DIALOGUES_DIR: &str = "res://godot/dialogues";
 
 /// Used to match week days integer values with Variants
 #[derive(PartialEq, Clone, Debug, ToVariant)]
@@ -105,102 +136,571 @@ pub fn print_login_credentials(credentials_tup: (&String, &String)) {
 /// Prints on console the current data on the Player struct
 /// This can be useful to debug the "in place" current values of Player attributes
 pub fn show_player_attributes(player: &Gamer) {
-    godot_print!("New Player is: {:?}", player);
+    godot_print!("New Player is: {:?}", player.get_username());
+    let stats = player.get_stats();
+    godot_print!("Level: {:?} | HP: {}/{} | Experience: {} | Attack: {} | Defense: {} | Speed: {}",
+        player.get_level(), stats.get_current_hp(), stats.get_max_hp(), stats.get_experience(),
+        stats.get_attack(), stats.get_defense(), stats.get_speed());
 }
 
 /// Changes the text of a label, if an _owner, a text and a path are provided.
 /// The path to the label are a String like "res://path_to_the_label"
 pub fn set_label_text(_owner: &Node, _label_path: &String, text: &String) {
-    let app_title_label = unsafe { 
-        _owner.get_node_as::<Label>(&_label_path) }
-        .unwrap();
-        
-    app_title_label.
-        set_text(text);
+    if let Some(app_title_label) = try_get_node_as::<Label>(_owner, _label_path) {
+        app_title_label.set_text(text);
+    }
+}
+
+/// Looks for the node placed on the given path, with the given type.
+///
+/// When the node doesn't exist (or it's of another type) logs which one it's missing and returns None, so a
+/// restructured scene degrades the behaviour of the caller instead of crashing the whole game
+pub fn try_get_node_as<'a, T>(owner: &Node, path: &str) -> Option<TRef<'a, T>>
+where
+    T: GodotObject + SubClass<Node>,
+{
+    let node = unsafe { owner.get_node_as::<T>(path) };
+    if node.is_none() {
+        godot_error!("Node `{}` of type {} not found from `{}`", path, T::class_name(), owner.name());
+    }
+    node
+}
+
+/// Why the game couldn't move to another scene
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneChangeError {
+    // There's no scene on the given path, like a typo on the path of a warp
+    NotFound(String),
+    // Godot refused to change the scene
+    ChangeFailed(String)
 }
 
-/// Convenient function to change scene just passing the `owner` and a `path` as a String
-pub fn change_scene(_owner: &Node, next_scene_path: String) -> () {
+impl std::fmt::Display for SceneChangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SceneChangeError::NotFound(path) => write!(f, "The scene `{}` doesn't exist", path),
+            SceneChangeError::ChangeFailed(reason) => write!(f, "Can't change the scene: {}", reason)
+        }
+    }
+}
+
+// The extensions of the text and the binary scenes of Godot
+const SCENE_EXTENSIONS: [&str; 2] = [".tscn", ".scn"];
+
+/// The paths that can't be a scene at all, so Godot doesn't even need to be asked: out of the project
+/// resources, or with another extension (like a typo on it)
+fn check_scene_path_format(scene_path: &str) -> Result<(), SceneChangeError> {
+    let file_name = scene_path.strip_prefix("res://").unwrap_or("");
+    if file_name.is_empty() || !SCENE_EXTENSIONS.iter().any(|extension| file_name.ends_with(extension)) {
+        return Err(SceneChangeError::NotFound(scene_path.to_string()));
+    }
+    Ok(())
+}
+
+/// Checks that there's a scene on the given resource path, before trying to go there
+pub fn validate_scene_path(scene_path: &str) -> Result<(), SceneChangeError> {
+    check_scene_path_format(scene_path)?;
+    if ResourceLoader::godot_singleton().exists(scene_path, "PackedScene") {
+        Ok(())
+    } else {
+        Err(SceneChangeError::NotFound(scene_path.to_string()))
+    }
+}
+
+/// Convenient function to change scene just passing the `owner` and a `path` as a String.
+///
+/// When the scene doesn't exist, the error gets logged and the game stays on the current scene
+pub fn change_scene(_owner: &Node, next_scene_path: String) -> Result<(), SceneChangeError> {
+    if let Err(err) = validate_scene_path(&next_scene_path) {
+        godot_error!("{}", err);
+        return Err(err);
+    }
+
+    // Lets the music manager play the track of the new scene
+    notify_music_manager(_owner, "on_scene_changed", &[next_scene_path.to_variant()]);
+
+    // The scene gets swapped behind a fade to black
+    if let Some(transition_overlay) = unsafe { _owner.get_node("/root/TransitionOverlay") } {
+        unsafe { transition_overlay.assume_safe().call("change_scene", &[next_scene_path.to_variant()]) };
+        return Ok(());
+    }
     
     let scene_tree_ref = 
         unsafe { Node::get_tree(_owner)
         .unwrap().assume_safe() };
     
-    let new_scene = SceneTree::change_scene(
-        &scene_tree_ref, next_scene_path);
-        
-    match new_scene {
-        Ok(()) => (),
-        Err(err) => println!("{}", err)
+    SceneTree::change_scene(&scene_tree_ref, next_scene_path)
+        .map_err(|err| SceneChangeError::ChangeFailed(err.to_string()))
+}
+
+/// Calls a method of the MusicManager autoload, if it's loaded
+pub fn notify_music_manager(owner: &Node, method: &str, args: &[Variant]) {
+    if let Some(music_manager) = unsafe { owner.get_node("/root/MusicManager") } {
+        unsafe { music_manager.assume_safe().call(method, args) };
+    }
+}
+
+/// Gives access to a node reference cached before (usually on `_ready`), as long as the node hasn't been freed since then
+pub fn cached_node<'a>(node: Option<Ref<Node>>) -> Option<TRef<'a, Node>> {
+    node.and_then(|node| unsafe { node.assume_safe_if_sane() })
+}
+
+/// The name of the save file of the current save slot
+pub fn save_file_name() -> String {
+    save_slot_file_name(session::get_save_slot())
+}
+
+/// The name of the save file of the given slot for the account that's logged in, like `save_slot_2_root`. Without
+/// anyone logged in (like when a scene it's played straight from the editor) it's just `save_slot_2`
+pub fn save_slot_file_name(slot: u8) -> String {
+    match session::get_logged_in_username() {
+        Some(username) => format!("{}{}_{}", SAVE_SLOT_FILE_PREFIX, slot, session::sanitize_username(&username)),
+        None => format!("{}{}", SAVE_SLOT_FILE_PREFIX, slot)
     }
 }
 
-pub fn get_player_absolute_position() -> (f32, f32) {
-    let (file, json) = open_json_file(GodotString::from_str("gamestate"), File::READ);
+/// If nothing was saved yet on the given slot, so loading it starts a new game
+pub fn is_save_slot_empty(slot: u8) -> bool {
+    save_slot_file(slot).is_none()
+}
+
+/// The file of the given slot that has a save, if any. The saves of the accounts from before the slots existed
+/// (`gamestate_root`) are the first slot
+fn save_slot_file(slot: u8) -> Option<String> {
+    let save_file = save_slot_file_name(slot);
+    if File::new().file_exists(json_file_path(&save_file)) {
+        return Some(save_file);
+    }
+    session::get_logged_in_username()
+        .map(|username| format!("{}_{}", STARTING_SAVE_FILE, session::sanitize_username(&username)))
+        .filter(|legacy_save_file| slot == 1 && File::new().file_exists(json_file_path(legacy_save_file)))
+}
+
+/// The save file to load. The empty slots start from the shared `gamestate`, the starting progress of the game
+fn save_file_to_load() -> String {
+    save_slot_file(session::get_save_slot()).unwrap_or_else(|| STARTING_SAVE_FILE.to_string())
+}
+
+/// Reads the data of the player from the text of a save. None if the save it's truncated, it isn't JSON or it
+/// doesn't have any player data
+pub fn parse_saved_player_data(save: &str) -> Option<serde_json::Value> {
+    let mut save: serde_json::Value = serde_json::from_str(save).ok()?;
+    Some(save.get_mut("player_data")?.take())
+}
 
-    let my_data = json.parse(file.get_as_text()).expect("SI, error parseando el JSON");
-    let my_json = unsafe { &my_data.assume_safe().result().to_dictionary() }; 
-    let player_position = my_json.get("player_data").to_dictionary()
-        .get("player_position").to_dictionary();
+/// The position stored on the data of a player. None if it's missing, or if any of his coordinates isn't a number
+pub fn parse_player_position(player_data: &serde_json::Value) -> Option<(f32, f32)> {
+    let player_position = player_data.get("player_position")?;
+    Some((player_position.get("x")?.as_f64()? as f32, player_position.get("y")?.as_f64()? as f32))
+}
 
-    let player_x = player_position.get("x").to_f64() as f32;
-    let player_y = player_position.get("y").to_f64() as f32;
+/// The data of the player on the save to load, or None if the save it's missing or can't be read
+fn read_saved_player_data() -> Option<serde_json::Value> {
+    parse_saved_player_data(&read_trusted_save(&save_file_to_load()).ok()?)
+}
 
-    //*! REMEBER TO CLOSE THE OPENED FILE HERE
+/// The whole text of a file, or None if it can't be opened
+fn read_text_file(path: &str) -> Option<String> {
+    let file = File::new();
+    file.open(path, File::READ).ok()?;
+    let text = file.get_as_text().to_string();
     file.close();
-    (player_x, player_y)
+    Some(text)
 }
 
-pub fn get_player_direction() -> PlayerDirection {
-    let (file, json) = open_json_file(GodotString::from_str("gamestate"), File::READ);
+/// Logs a warning about the saves, unless it was already logged before
+fn warn_once(warning: String) {
+    if let Ok(mut logged_warnings) = LOGGED_SAVE_WARNINGS.lock() {
+        if !logged_warnings.contains(&warning) {
+            godot_warn!("{}", warning);
+            logged_warnings.push(warning);
+        }
+    }
+}
 
-    let my_data = json.parse(file.get_as_text()).expect("SI, error parseando el JSON");
-    let my_json = unsafe { &my_data.assume_safe().result().to_dictionary() }; 
-    
-    let player_direction = my_json.get("player_data").to_dictionary().get("player_direction").to_string();
+/// The data of the given save, once it's checked against his checksum. When the save it's corrupted (or missing),
+/// the backup of the last good save it's used instead
+fn read_trusted_save(save_file: &str) -> Result<String, SaveReadError> {
+    let save_path = json_file_path(save_file);
+    let save = read_text_file(&save_path);
+    let backup = read_text_file(&(save_path + BACKUP_SAVE_EXTENSION));
+
+    match save_integrity::pick_trusted_save(save.as_deref(), backup.as_deref()) {
+        Ok((save_data, SaveSource::Save)) => Ok(save_data.to_string()),
+        Ok((backup_data, SaveSource::Backup)) => {
+            let problem = if save.is_some() { "is corrupted" } else { "is missing" };
+            warn_once(format!("The save {} {}, so its backup it's loaded instead", save_file, problem));
+            Ok(backup_data.to_string())
+        },
+        Err(SaveReadError::Corrupted) => {
+            warn_once(format!("The save {} is corrupted, and there's no good backup of it", save_file));
+            Err(SaveReadError::Corrupted)
+        },
+        Err(SaveReadError::Missing) => Err(SaveReadError::Missing)
+    }
+}
 
-    //*! REMEBER TO CLOSE THE OPENED FILE HERE
+/// Where the player was when the game got saved. None if the save can't be read
+pub fn get_player_absolute_position() -> Option<(f32, f32)> {
+    read_saved_player_data().as_ref().and_then(parse_player_position)
+}
+
+/// Where the player was looking at when the game got saved. None if the save can't be read
+pub fn get_player_direction() -> Option<PlayerDirection> {
+    let player_data = read_saved_player_data()?;
+    Some(PlayerDirection::from_string(player_data.get("player_direction")?.as_str()?))
+}
+
+/// What the player was doing when the game got saved. The saves without it load as Idle, and None it's only
+/// given when the save can't be read
+pub fn get_player_status() -> Option<PlayerStatus> {
+    let player_data = read_saved_player_data()?;
+    Some(player_data.get("player_status")
+        .and_then(serde_json::Value::as_str)
+        .map(PlayerStatus::from_status_str)
+        .unwrap_or_default())
+}
+
+/// Loads the game saved on the current slot. An empty slot, or a save that can't be read, starts from the
+/// starting progress of the game.
+///
+/// The saves of the older builds get upgraded to the current shape of the player data before being read
+pub fn retrieve_game_data() -> Game {
+    let read_game_data = |save_file: &str| {
+        let json_game_data = read_trusted_save(save_file).map_err(|err| format!("{:?}", err))?;
+
+        let mut save: serde_json::Value = serde_json::from_str(json_game_data.as_str()).map_err(|err| err.to_string())?;
+        match save_migration::migrate_save(&mut save) {
+            Ok(version) if version < save_migration::CURRENT_SAVE_VERSION =>
+                warn_once(format!("The save {} was upgraded from the version {}", save_file, version)),
+            Ok(_) => (),
+            // A newer save it's read anyway, ignoring whatever this build doesn't know about
+            Err(err) => warn_once(format!("The save {} can't be upgraded: {:?}", save_file, err))
+        }
+        serde_json::from_value::<Game>(save).map_err(|err| err.to_string())
+    };
+
+    let save_file = save_file_to_load();
+    match read_game_data(&save_file) {
+        Ok(game_data) => game_data,
+        Err(err) => {
+            warn_once(format!("The save {} can't be loaded ({}). Starting from the starting progress", save_file, err));
+            read_game_data(STARTING_SAVE_FILE).unwrap_or_else(|err| {
+                godot_error!("The starting progress of the game can't be read ({}). Starting from an empty game", err);
+                Game::default()
+            })
+        }
+    }
+}
+
+/// Writes the game on the save of the current slot, sealed with his checksum.
+///
+/// The new save it's written on a temporary file first, and only once it's complete it replaces the old one, so a
+/// write cut in the middle never clobbers the previous save. That previous one it's kept as the backup
+pub fn save_game_data(player_data: &Game) {
+    let save = match serde_json::to_string_pretty(&player_data) {
+        Ok(save) => save,
+        Err(err) => {
+            godot_print!("Error serializing the game data: {:?}", err);
+            return;
+        }
+    };
+
+    let save_path = json_file_path(&save_file_name());
+    let temp_path = save_path.clone() + TEMP_SAVE_EXTENSION;
+    let backup_path = save_path.clone() + BACKUP_SAVE_EXTENSION;
+
+    let file = File::new();
+    if let Err(err) = file.open(temp_path.as_str(), File::WRITE) {
+        godot_print!("Error opening the save {}: {:?}", temp_path, err);
+        return;
+    }
+    file.store_string(save_integrity::seal(&save));
     file.close();
 
-    if player_direction == "Upwards".to_string() {
-        PlayerDirection::Upwards
-    } else if player_direction == "Downwards".to_string() {
-        PlayerDirection::Downwards
-    } else if player_direction == "Left".to_string() {
-        PlayerDirection::Left
-    } else if player_direction == "Right".to_string() {
-        PlayerDirection::Right
+    let directory = Directory::new();
+    // A corrupted save never replaces the last good backup
+    let previous_save_trusted = read_text_file(&save_path).as_deref()
+        .and_then(save_integrity::trusted_save_data)
+        .is_some();
+    if previous_save_trusted {
+        if let Err(err) = directory.copy(save_path.as_str(), backup_path.as_str()) {
+            godot_print!("Error backing up the save {}: {:?}", save_path, err);
+        }
+    }
+    if let Err(err) = directory.rename(temp_path.as_str(), save_path.as_str()) {
+        godot_print!("Error replacing the save {}: {:?}", save_path, err);
+    }
+}
+
+/// Takes a small screenshot of the game for the given save slot, removing the one of the previous save of the slot.
+///
+/// Returns the path of the new thumbnail, or the default image when the screenshot can't be taken
+pub fn capture_save_thumbnail(owner: &Node, slot: &str, previous_thumbnail: &str) -> String {
+    // An account that never saved comes with the thumbnail of the starting save, that isn't his to remove
+    if previous_thumbnail.starts_with(&format!("{}/{}_", save_thumbnails::SAVE_THUMBNAILS_DIR, slot)) {
+        delete_save_thumbnail(previous_thumbnail);
+    }
+
+    let image = owner.get_viewport()
+        .and_then(|viewport| unsafe { viewport.assume_safe() }.get_texture())
+        .and_then(|texture| unsafe { texture.assume_safe() }.get_data());
+    let image = match image {
+        Some(image) => image,
+        None => {
+            godot_print!("Error capturing the thumbnail of the save slot {}", slot);
+            return save_thumbnails::DEFAULT_SAVE_THUMBNAIL.to_string();
+        }
+    };
+    // The viewport textures are upside down
+    image.flip_y();
+    image.resize(save_thumbnails::SAVE_THUMBNAIL_WIDTH, save_thumbnails::SAVE_THUMBNAIL_HEIGHT, Image::INTERPOLATE_BILINEAR);
+
+    // Every save gets a new name, so the load screen never shows an old one cached by Godot
+    let thumbnail_path = format!("{}/{}_{}.png",
+        save_thumbnails::SAVE_THUMBNAILS_DIR, slot, Utc::now().timestamp_millis());
+    let saved = Directory::new().make_dir_recursive(save_thumbnails::SAVE_THUMBNAILS_DIR)
+        .and_then(|_| image.save_png(thumbnail_path.as_str()));
+
+    match saved {
+        Ok(()) => thumbnail_path,
+        Err(err) => {
+            godot_print!("Error saving the thumbnail of the save slot {}: {:?}", slot, err);
+            save_thumbnails::DEFAULT_SAVE_THUMBNAIL.to_string()
+        }
+    }
+}
+
+/// Removes the thumbnail of a save slot, when the slot it's overwritten or deleted. The default image is never removed
+pub fn delete_save_thumbnail(thumbnail_path: &str) {
+    if !thumbnail_path.starts_with(save_thumbnails::SAVE_THUMBNAILS_DIR) {
+        return;
+    }
+    if File::new().file_exists(thumbnail_path) {
+        if let Err(err) = Directory::new().remove(thumbnail_path) {
+            godot_print!("Error removing the old save thumbnail {}: {:?}", thumbnail_path, err);
+        }
+    }
+}
+
+/// The thumbnail that the load screen should show for a save slot, falling back to the default image if it's gone
+pub fn get_save_thumbnail_or_default(thumbnail_path: &str) -> String {
+    if !thumbnail_path.is_empty() && File::new().file_exists(thumbnail_path) {
+        thumbnail_path.to_string()
     } else {
-        PlayerDirection::default()
+        save_thumbnails::DEFAULT_SAVE_THUMBNAIL.to_string()
     }
 }
 
-pub fn retrieve_game_data() -> Game {
-    let (file, _) = open_json_file(GodotString::from_str("gamestate"), File::READ);
+/// Loads the wild Pokémon encounter zones of the given map. Maps without wild Pokémon just gets an empty table
+pub fn retrieve_encounter_table(map_name: &str) -> EncounterTable {
+    let file_name: String = "encounters/".to_string() + map_name;
 
-    let json_game_data = file.get_as_text().to_string();
-    let my_str = json_game_data.as_str();
+    if !File::new().file_exists("res://godot/".to_string() + &file_name + &".json".to_string()) {
+        return EncounterTable::default();
+    }
 
-    let game_data: Game = serde_json::from_str(my_str).unwrap();
-    
-    return game_data;
+    let (file, _) = open_json_file(GodotString::from_str(&file_name), File::READ);
+    let json_encounter_table = file.get_as_text().to_string();
+    file.close();
 
+    match serde_json::from_str(json_encounter_table.as_str()) {
+        Ok(encounter_table) => encounter_table,
+        Err(err) => {
+            godot_print!("Error parsing the encounter table of {}: {:?}", map_name, err);
+            EncounterTable::default()
+        }
+    }
 }
 
-pub fn save_game_data(player_data: &Game) {
-    let (file, _) = open_json_file(GodotString::from_str("gamestate"), File::WRITE);
+/// Loads the settings chosen by the player. If there's no settings file yet, the defaults are used
+pub fn retrieve_settings() -> Settings {
+    if !File::new().file_exists("res://godot/settings.json") {
+        return Settings::default();
+    }
 
-    let j = serde_json::to_string_pretty(&player_data).unwrap();
+    let (file, _) = open_json_file(GodotString::from_str("settings"), File::READ);
+    let json_settings = file.get_as_text().to_string();
+    file.close();
+
+    serde_json::from_str(json_settings.as_str()).unwrap_or_default()
+}
+
+pub fn save_settings(settings: &Settings) {
+    let (file, _) = open_json_file(GodotString::from_str("settings"), File::WRITE);
+
+    let j = serde_json::to_string_pretty(settings).unwrap();
 
     file.store_string(&j);
     file.close();
 }
 
+/// Loads the scene -> track mapping of the background music
+pub fn retrieve_music_config() -> MusicConfig {
+    let (file, _) = open_json_file(GodotString::from_str("music"), File::READ);
+    let json_music_config = file.get_as_text().to_string();
+    file.close();
+
+    match serde_json::from_str(json_music_config.as_str()) {
+        Ok(music_config) => music_config,
+        Err(err) => {
+            godot_print!("Error parsing the music config: {:?}", err);
+            MusicConfig::default()
+        }
+    }
+}
+
+/// Loads the items that sells every shop of the game
+pub fn retrieve_shop_config() -> ShopConfig {
+    let (file, _) = open_json_file(GodotString::from_str("shops"), File::READ);
+    let json_shop_config = file.get_as_text().to_string();
+    file.close();
+
+    match serde_json::from_str(json_shop_config.as_str()) {
+        Ok(shop_config) => shop_config,
+        Err(err) => {
+            godot_print!("Error parsing the shops config: {:?}", err);
+            ShopConfig::default()
+        }
+    }
+}
+
+/// Loads the gameplay tuning values. Anything wrong on the config falls back to the compiled-in defaults
+pub fn retrieve_balance_config() -> BalanceConfig {
+    if !File::new().file_exists("res://godot/balance.json") {
+        return BalanceConfig::default();
+    }
+
+    let (file, _) = open_json_file(GodotString::from_str("balance"), File::READ);
+    let json_balance_config = file.get_as_text().to_string();
+    file.close();
+
+    let mut balance_config: BalanceConfig = match serde_json::from_str(json_balance_config.as_str()) {
+        Ok(balance_config) => balance_config,
+        Err(err) => {
+            godot_print!("Error parsing the balance config: {:?}", err);
+            BalanceConfig::default()
+        }
+    };
+    for invalid_value in balance_config.validate() {
+        godot_print!("The balance value {} it's out of range. Using the default one", invalid_value);
+    }
+    balance_config
+}
+
+/// Loads the texts of the signs and the rest of the dialogues of the world: the main dialogues file first, and then
+/// every JSON file of the dialogues folder, by name. A file, or a dialogue, that can't be read it's skipped
+pub fn retrieve_dialogue_registry() -> DialogueRegistry {
+    let mut dialogue_files = vec![DIALOGUES_FILE.to_string()];
+    let directory = Directory::new();
+    if directory.dir_exists(DIALOGUES_DIR) && directory.open(DIALOGUES_DIR).is_ok() {
+        let mut file_names = Vec::new();
+        if directory.list_dir_begin(true, true).is_ok() {
+            loop {
+                let file_name = directory.get_next().to_string();
+                if file_name.is_empty() {
+                    break;
+                }
+                if !directory.current_is_dir() && file_name.ends_with(".json") {
+                    file_names.push(file_name);
+                }
+            }
+            directory.list_dir_end();
+        }
+        file_names.sort();
+        dialogue_files.extend(file_names.into_iter().map(|file_name| format!("{}/{}", DIALOGUES_DIR, file_name)));
+    }
+
+    let mut dialogue_registry = DialogueRegistry::new();
+    for dialogue_file in dialogue_files {
+        let file = File::new();
+        if let Err(err) = file.open(dialogue_file.as_str(), File::READ) {
+            godot_error!("Error opening the dialogues file {}: {:?}", dialogue_file, err);
+            continue;
+        }
+        let json_dialogues = file.get_as_text().to_string();
+        file.close();
+
+        for error in dialogue_registry.load_json(&dialogue_file, &json_dialogues) {
+            godot_error!("Skipping a wrong dialogue. {}", error);
+        }
+    }
+    dialogue_registry
+}
+
+/// Loads the steps of every cutscene of the game
+pub fn retrieve_cutscene_table() -> CutsceneTable {
+    let (file, _) = open_json_file(GodotString::from_str("cutscenes"), File::READ);
+    let json_cutscene_table = file.get_as_text().to_string();
+    file.close();
+
+    match serde_json::from_str(json_cutscene_table.as_str()) {
+        Ok(cutscene_table) => cutscene_table,
+        Err(err) => {
+            godot_print!("Error parsing the cutscenes config: {:?}", err);
+            CutsceneTable::default()
+        }
+    }
+}
+
+/// Loads the teams and the prizes of every trainer of the game
+pub fn retrieve_trainer_table() -> TrainerTable {
+    let (file, _) = open_json_file(GodotString::from_str("trainers"), File::READ);
+    let json_trainer_table = file.get_as_text().to_string();
+    file.close();
+
+    match serde_json::from_str(json_trainer_table.as_str()) {
+        Ok(trainer_table) => trainer_table,
+        Err(err) => {
+            godot_print!("Error parsing the trainers config: {:?}", err);
+            TrainerTable::default()
+        }
+    }
+}
+
+/// Loads every quest of the game, with his objectives
+pub fn retrieve_quest_table() -> QuestTable {
+    if !File::new().file_exists(json_file_path("quests")) {
+        return QuestTable::default();
+    }
+
+    let (file, _) = open_json_file(GodotString::from_str("quests"), File::READ);
+    let json_quest_table = file.get_as_text().to_string();
+    file.close();
+
+    match serde_json::from_str(json_quest_table.as_str()) {
+        Ok(quest_table) => quest_table,
+        Err(err) => {
+            godot_print!("Error parsing the quests config: {:?}", err);
+            QuestTable::default()
+        }
+    }
+}
+
+/// Loads the base stats, the learnset, the evolutions and the rest of the data of every Pokémon specie.
+/// A specie that it's malformed it's reported and skipped
+pub fn retrieve_species_table() -> SpeciesTable {
+    let (file, _) = open_json_file(GodotString::from_str("species"), File::READ);
+    let json_species_table = file.get_as_text().to_string();
+    file.close();
+
+    let mut species_table = SpeciesTable::new();
+    for error in species_table.load_json(&json_file_path("species"), &json_species_table) {
+        godot_error!("Skipping a wrong specie. {}", error);
+    }
+    species_table
+}
+
+/// Where the JSON file with the given name lives
+fn json_file_path(file_name: &str) -> String {
+    "res://godot/".to_string() + file_name + ".json"
+}
+
 pub fn open_json_file(file_name: GodotString, mode: i64) -> (Ref<File, Unique>, &'static JSON ){
     let file = File::new();
     let json = JSON::godot_singleton();
 
-    let file_name: String = "res://godot/".to_string() + &file_name.to_string() + &".json".to_string();
+    let file_name: String = json_file_path(&file_name.to_string());
 
     let gamestate = file.open(file_name, mode);
     match gamestate {
@@ -212,3 +712,61 @@ pub fn open_json_file(file_name: GodotString, mode: i64) -> (Ref<File, Unique>,
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::consts::{in_game_constant::DEFAULT_SPAWN_POSITION, scenes};
+
+    /// Where the player appears with the given save, like the PlayerCharacter does when it's ready
+    fn spawn_position(save: &str) -> (f32, f32) {
+        parse_saved_player_data(save).as_ref()
+            .and_then(parse_player_position)
+            .unwrap_or(DEFAULT_SPAWN_POSITION)
+    }
+
+    #[test]
+    fn the_saved_position_is_read() {
+        let save = r#"{ "player_data": { "player_position": { "x": 32.0, "y": -16.5 } } }"#;
+        assert_eq!(spawn_position(save), (32.0, -16.5));
+    }
+
+    #[test]
+    fn an_empty_save_falls_back_to_the_default_spawn() {
+        assert_eq!(parse_saved_player_data(""), None);
+        assert_eq!(spawn_position(""), DEFAULT_SPAWN_POSITION);
+    }
+
+    #[test]
+    fn a_garbage_save_falls_back_to_the_default_spawn() {
+        assert_eq!(parse_saved_player_data("\u{0}not json{"), None);
+        // Truncated in the middle of the position
+        assert_eq!(spawn_position(r#"{ "player_data": { "player_position": { "x": 32.0, "#), DEFAULT_SPAWN_POSITION);
+    }
+
+    #[test]
+    fn a_save_with_missing_fields_falls_back_to_the_default_spawn() {
+        assert_eq!(parse_saved_player_data(r#"{ "money": 10 }"#), None);
+        assert_eq!(spawn_position(r#"{ "player_data": { "name": "Teo" } }"#), DEFAULT_SPAWN_POSITION);
+        assert_eq!(spawn_position(r#"{ "player_data": { "player_position": { "x": 32.0 } } }"#),
+            DEFAULT_SPAWN_POSITION);
+        assert_eq!(spawn_position(r#"{ "player_data": { "player_position": { "x": "32", "y": 1.0 } } }"#),
+            DEFAULT_SPAWN_POSITION);
+    }
+
+    #[test]
+    fn a_path_that_cant_be_a_scene_is_rejected() {
+        // Out of the project, a typo on the extension, without extension, and no scene at all
+        for scene_path in &["godot/Game/Game.tscn", "res://godot/Game/Game.tcsn", "res://godot/Game/Game", "res://", ""] {
+            assert_eq!(check_scene_path_format(scene_path), Err(SceneChangeError::NotFound(scene_path.to_string())));
+        }
+        assert_eq!(SceneChangeError::NotFound("res://godot/Game/Game.tcsn".to_string()).to_string(),
+            "The scene `res://godot/Game/Game.tcsn` doesn't exist");
+    }
+
+    #[test]
+    fn a_scene_path_goes_on_to_godot() {
+        assert_eq!(check_scene_path_format(scenes::GAME), Ok(()));
+        assert_eq!(check_scene_path_format("res://godot/Game/Map.scn"), Ok(()));
+    }
+}